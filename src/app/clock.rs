@@ -0,0 +1,126 @@
+//! Games played against the clock, with a time bank for each side or a limit on each move.
+
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use super::{App, GameState};
+use crate::{
+    bot,
+    game::board::{Outcome, Player},
+};
+
+/// The per-move time limits the player can cycle through, in seconds.
+const MOVE_TIME_LIMITS: [u64; 3] = [5, 10, 30];
+
+/// The time banks each side can be given for a whole game, in seconds.
+const CLOCK_TIMES: [u64; 3] = [60, 180, 300];
+
+/// The time each side has left in a game played on the clock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Clocks {
+    human: Duration,
+    bot: Duration,
+}
+
+impl Clocks {
+    /// Give both sides the same time bank.
+    pub const fn new(bank: Duration) -> Self {
+        Self {
+            human: bank,
+            bot: bank,
+        }
+    }
+
+    /// The time bank of `player`.
+    const fn bank(&mut self, player: Player) -> &mut Duration {
+        match player {
+            Player::Human => &mut self.human,
+            Player::Bot => &mut self.bot,
+        }
+    }
+}
+
+impl App {
+    /// Deduct the time spent on the current turn from the mover's clock.
+    pub fn charge_clock(&mut self) {
+        if let Some(clocks) = &mut self.clocks
+            && self.game_state == GameState::Playing
+        {
+            let bank = clocks.bank(self.turn);
+            *bank = bank.saturating_sub(self.turn_started.elapsed());
+        }
+    }
+
+    /// Time `player` has left on their clock, if the game is played on the clock.
+    pub fn clock_left(&self, player: Player) -> Option<Duration> {
+        let mut clocks = self.clocks?;
+        let bank = *clocks.bank(player);
+        if self.game_state == GameState::Playing && self.turn == player {
+            Some(bank.saturating_sub(self.turn_started.elapsed()))
+        } else {
+            Some(bank)
+        }
+    }
+
+    /// Lose the game for whoever is to move once their clock has run out.
+    pub fn check_flag_fall(&mut self) {
+        if self.game_state == GameState::Playing
+            && self.clock_left(self.turn) == Some(Duration::ZERO)
+        {
+            self.charge_clock();
+            self.finish_game(Outcome::Winner(self.turn.other()));
+        }
+    }
+
+    /// Switch between untimed games and each of [`CLOCK_TIMES`], starting a new game.
+    pub fn cycle_clock_time(&mut self) {
+        let current = self.clock_time.map(|bank| bank.as_secs());
+        self.clock_time = match CLOCK_TIMES.iter().position(|&secs| Some(secs) == current) {
+            None => CLOCK_TIMES.first(),
+            Some(i) => CLOCK_TIMES.get(i + 1),
+        }
+        .map(|&secs| Duration::from_secs(secs));
+        self.rematch();
+    }
+
+    /// Switch between no per-move time limit and each of [`MOVE_TIME_LIMITS`].
+    pub fn cycle_move_time_limit(&mut self) {
+        let current = self.move_time_limit.map(|limit| limit.as_secs());
+        self.move_time_limit = match MOVE_TIME_LIMITS
+            .iter()
+            .position(|&secs| Some(secs) == current)
+        {
+            None => MOVE_TIME_LIMITS.first(),
+            Some(i) => MOVE_TIME_LIMITS.get(i + 1),
+        }
+        .map(|&secs| Duration::from_secs(secs));
+        self.turn_started = Instant::now();
+    }
+
+    /// Time the human has left for the current move, if a limit is set.
+    pub fn move_time_left(&self) -> Option<Duration> {
+        self.move_time_limit
+            .map(|limit| limit.saturating_sub(self.turn_started.elapsed()))
+    }
+
+    /// Play a random move for the human once their time for this move has run out.
+    pub fn check_move_timeout(&mut self) {
+        let timed_out = self.game_state == GameState::Playing
+            && self.turn == Player::Human
+            && !self.confirm_restart
+            && self.move_time_left() == Some(Duration::ZERO);
+        if timed_out && self.quantum.is_some() {
+            self.quantum_turn(bot::Opponent::Random);
+            return;
+        }
+        if timed_out
+            && let Some(placement) =
+                bot::random_move(&self.items, self.rules, Player::Human, self.last_move())
+        {
+            let [row, col] = placement.cell;
+            self.apply_mark(row, col, placement.mark);
+            self.redo_stack.clear();
+        }
+    }
+}
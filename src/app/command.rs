@@ -0,0 +1,225 @@
+//! The changes made to a game of marks in a row, kept in the order made so they can be taken
+//! back and made again.
+
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use super::{ANIMATION_FRAME, App, Effect, EffectKind, GameState, PLACE_FRAMES};
+use crate::game::board::{CellState, Outcome, Player, check_winner};
+
+/// A mark placed on the board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Move {
+    pub row: usize,
+    pub col: usize,
+    pub player: Player,
+    /// Whose mark was written, which only differs from `player` in wild play.
+    pub mark: Player,
+    /// How long the player took over the move.
+    pub took: Duration,
+}
+
+/// A change to a game of marks in a row, kept in its history in the order made so it can be
+/// taken back and made again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Command {
+    Place(Move),
+    /// The human took over the opening move, given as the bot played it, under the pie rule.
+    Swap(Move),
+    /// The human gave up the game.
+    Resign,
+}
+
+impl Command {
+    /// Who made the change.
+    pub const fn player(self) -> Player {
+        match self {
+            Self::Place(mv) => mv.player,
+            Self::Swap(_) | Self::Resign => Player::Human,
+        }
+    }
+
+    /// The mark placed, if the change placed one.
+    pub const fn placed(&self) -> Option<&Move> {
+        match self {
+            Self::Place(mv) => Some(mv),
+            Self::Swap(_) | Self::Resign => None,
+        }
+    }
+
+    /// Make the change to `app` and add it to the history, passing the turn on.
+    pub fn apply(self, app: &mut App) {
+        match self {
+            Self::Place(mv) => {
+                app.items[mv.row][mv.col] = mv.mark.into();
+                app.effects.push(Effect::new(
+                    EffectKind::Placed([mv.row, mv.col]),
+                    ANIMATION_FRAME * PLACE_FRAMES,
+                ));
+                if mv.player == Player::Human {
+                    app.stats.move_times.push(mv.took);
+                }
+                app.charge_clock();
+                app.hint = None;
+                app.turn = mv.player.other();
+                app.turn_started = Instant::now();
+                app.history.push(self);
+                // under the pie rule only the opening move can be taken over
+                app.swap_pending = app.rules.swap && app.history.len() == 1;
+                if let Some(outcome) = check_winner(&app.items, app.rules, mv.player) {
+                    app.finish_game(outcome);
+                }
+            }
+            Self::Swap(opening) => {
+                let owner = opening.player.other();
+                app.set_opening_owner(owner);
+                app.swap_pending = false;
+                app.charge_clock();
+                app.turn = opening.player;
+                app.turn_started = Instant::now();
+                app.history.push(self);
+            }
+            Self::Resign => {
+                app.history.push(self);
+                app.finish_game(Outcome::Winner(Player::Bot));
+            }
+        }
+    }
+
+    /// Take the change back from `app`, once it has left the history, handing the turn back
+    /// to whoever made it and the result of the game it decided back to the score.
+    pub fn revert(self, app: &mut App) {
+        match self {
+            Self::Place(mv) => {
+                app.items[mv.row][mv.col] = CellState::Empty;
+                if mv.player == Player::Human {
+                    app.stats.move_times.pop();
+                }
+            }
+            Self::Swap(opening) => app.set_opening_owner(opening.player),
+            Self::Resign => {}
+        }
+        app.turn = self.player();
+        // a result taken back no longer counts towards the score
+        if let GameState::GameOver(outcome) = app.game_state {
+            *app.score.tally(outcome) -= 1;
+            app.stats.unrecord();
+            if let Some(series) = &mut app.series {
+                series.results.pop();
+            }
+            app.game_state = GameState::Playing;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{app::Reply, engine, game::board::Pos};
+
+    /// A game of classic tic-tac-toe where the human has taken the centre and the bot has
+    /// answered in a corner.
+    fn after_one_round() -> App {
+        let mut app = App::new();
+        app.placement = Pos::new(1, 1);
+        app.place_mark();
+        app.bot_move(Reply::Place(Some(engine::Move {
+            cell: [0, 0],
+            mark: Player::Bot,
+        })));
+        app
+    }
+
+    #[test]
+    fn undo_takes_back_the_round_and_redo_makes_it_again() {
+        let mut app = after_one_round();
+        let played = app.items.clone();
+        let history = app.history.clone();
+        assert_eq!(history.len(), 2);
+
+        app.undo();
+        assert!(app.items.iter().flatten().all(|cell| cell.is_empty()));
+        assert!(app.history.is_empty());
+        assert_eq!(app.turn, Player::Human);
+
+        app.redo();
+        assert_eq!(app.items, played);
+        assert_eq!(app.history, history);
+        assert_eq!(app.turn, Player::Human);
+        assert!(app.redo_stack.is_empty());
+    }
+
+    #[test]
+    fn undo_reopens_a_resigned_game_and_takes_the_loss_off_the_score() {
+        let mut app = after_one_round();
+        app.resign();
+        assert_eq!(
+            app.game_state,
+            GameState::GameOver(Outcome::Winner(Player::Bot))
+        );
+        assert_eq!(app.score.losses, 1);
+
+        app.undo();
+        assert_eq!(app.game_state, GameState::Playing);
+        assert_eq!(app.score.losses, 0);
+        assert_eq!(app.history.len(), 2);
+
+        app.redo();
+        assert_eq!(app.score.losses, 1);
+    }
+
+    #[test]
+    fn a_game_taken_back_after_it_ended_stays_reopened_until_the_rematch() {
+        let mut app = after_one_round();
+        app.undo();
+        assert!(!app.reopened);
+
+        app.resign();
+        app.undo();
+        assert!(app.reopened);
+        app.resign();
+        assert!(app.reopened);
+
+        app.rematch();
+        assert!(!app.reopened);
+    }
+
+    #[test]
+    fn undo_takes_back_a_win_and_redo_wins_again() {
+        let mut app = App::new();
+        let x = CellState::X;
+        let o = CellState::O;
+        app.items = vec![
+            vec![x, x, CellState::Empty],
+            vec![o, o, CellState::Empty],
+            vec![CellState::Empty; 3],
+        ];
+        app.placement = Pos::new(0, 2);
+        app.place_mark();
+        assert_eq!(
+            app.game_state,
+            GameState::GameOver(Outcome::Winner(Player::Human))
+        );
+        assert_eq!(app.score.wins, 1);
+        let won = app.stats.clone();
+        assert_eq!(won.streak, Some((Outcome::Winner(Player::Human), 1)));
+        assert_eq!(won.move_times.len(), 1);
+
+        app.undo();
+        assert_eq!(app.game_state, GameState::Playing);
+        assert_eq!(app.items[0][2], CellState::Empty);
+        assert_eq!(app.score.wins, 0);
+        assert_eq!(app.stats.streak, None);
+        assert_eq!(app.stats.best_streak, 0);
+        assert!(app.stats.move_times.is_empty());
+
+        app.redo();
+        assert_eq!(
+            app.game_state,
+            GameState::GameOver(Outcome::Winner(Player::Human))
+        );
+        assert_eq!(app.score.wins, 1);
+        assert_eq!(app.stats, won);
+    }
+}
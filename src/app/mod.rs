@@ -0,0 +1,1542 @@
+//! Tic-tac-toe and the other games of placing marks in a row, as played in the terminal: the
+//! board and its cursor, the bot's turns, the score kept over a session and the screens drawn
+//! for them.
+
+mod clock;
+mod command;
+mod render;
+mod results;
+mod saved;
+mod score;
+
+use std::{
+    str::FromStr,
+    time::{Duration, Instant},
+    vec,
+};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style, palette::tailwind},
+    widgets::{BorderType, TableState},
+};
+use serde::{Deserialize, Serialize};
+use unicode_width::UnicodeWidthStr;
+
+use crate::{
+    bot, engine,
+    error::Error,
+    game::{
+        Game, Help, Status,
+        board::{
+            CellState, Outcome, Player, Pos, Rules, dead_boards, landing_row, legal_moves,
+            parse_cell, winning_line, winning_lines,
+        },
+        quantum, ultimate,
+    },
+    grid::{self, Viewport},
+    options::GameMode,
+    settings::Settings,
+    ui::{
+        banner,
+        layout::{calculate_layout, cell_size, table_extent},
+        palette::{PALETTES, TableColors},
+    },
+};
+
+pub use saved::SavedBoard;
+
+use clock::Clocks;
+use command::{Command, Move};
+use render::{MIN_VISIBLE_CELLS, STATS_WIDTH, Scale, ZOOMED_OUT_CELL, mark_art};
+use results::{ResultButton, Results};
+use score::{Score, Series, SessionStats};
+
+/// How long a suggested move stays highlighted.
+const HINT_DURATION: Duration = Duration::from_secs(2);
+
+/// How long a rejected move keeps flashing.
+const INVALID_MOVE_DURATION: Duration = Duration::from_millis(900);
+
+/// How long the bot's latest move stays accented.
+const LAST_MOVE_DURATION: Duration = Duration::from_secs(2);
+
+/// How long each on or off phase of a flashing cell lasts.
+const FLASH_INTERVAL: Duration = Duration::from_millis(150);
+
+/// How long the cursor takes to pulse through its shades and back.
+const PULSE_PERIOD: Duration = Duration::from_millis(1200);
+
+/// How many lines the title in large letters takes above the line telling the state of the
+/// game, including the blank one between them.
+const BANNER_HEIGHT: u16 = 3;
+
+/// How fast the color wave runs through the title in large letters, in radians a second.
+const WAVE_SPEED: f32 = 3.0;
+
+/// How far apart in the color wave neighbouring columns of the title are, in radians.
+const WAVE_STRETCH: f32 = 0.25;
+
+/// The longest name of a cell that can be typed, such as `aa27`.
+const MAX_CELL_NAME: usize = 5;
+
+/// The glyph filling the cell under the cursor when patterns tell it apart.
+const HATCH: &str = "░";
+
+/// The glyphs a newly placed mark grows through before it shows, first faint and then bold.
+const PLACE_GLYPHS: [&str; 2] = ["·", "•"];
+
+/// How many frames a newly placed mark takes to appear.
+const PLACE_FRAMES: u32 = PLACE_GLYPHS.len() as u32 + 2;
+
+/// How long each frame of an animation lasts.
+const ANIMATION_FRAME: Duration = Duration::from_millis(60);
+
+/// How long the human's win is celebrated before the rematch prompt shows.
+const CELEBRATION_DURATION: Duration = Duration::from_millis(1500);
+
+/// The glyphs of the confetti scattered over the board to celebrate a win.
+const CONFETTI: [&str; 6] = ["✦", "✧", "*", "+", "•", "·"];
+
+/// The colors of the confetti.
+const CONFETTI_COLORS: [Color; 5] = [
+    tailwind::AMBER.c300,
+    tailwind::PINK.c400,
+    tailwind::SKY.c300,
+    tailwind::LIME.c300,
+    tailwind::VIOLET.c300,
+];
+
+/// One screen cell in this many of the board shows confetti on each frame.
+const CONFETTI_SPARSENESS: u32 = 10;
+
+/// How long a finished game of a series stays on screen before the next one starts.
+const NEXT_GAME_DELAY: Duration = Duration::from_millis(1500);
+
+/// How long the coin spins before deciding who opens.
+const COIN_FLIP_DURATION: Duration = Duration::from_millis(1500);
+
+/// How long each face of the spinning coin shows.
+const COIN_FRAME: Duration = Duration::from_millis(100);
+
+/// The faces shown in turn while the coin spins.
+const COIN_FRAMES: [&str; 4] = ["◐", "◓", "◑", "◒"];
+
+/// Who opens each game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FirstMove {
+    /// The human, every game.
+    Me,
+    /// The bot, every game.
+    Bot,
+    /// Decided by a coin flip before every game.
+    Random,
+    /// The human opens the first game and the sides swap on every rematch.
+    Alternate,
+}
+
+impl FromStr for FirstMove {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        match s {
+            "me" => Ok(Self::Me),
+            "bot" => Ok(Self::Bot),
+            "random" => Ok(Self::Random),
+            _ => Err(Error::invalid("first player", s, "`me`, `bot` or `random`")),
+        }
+    }
+}
+
+/// The marks shown on the board for each player.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Symbols {
+    pub human: String,
+    pub bot: String,
+}
+
+impl Default for Symbols {
+    fn default() -> Self {
+        Self {
+            human: Player::Human.symbol().into(),
+            bot: Player::Bot.symbol().into(),
+        }
+    }
+}
+
+impl Symbols {
+    /// The mark shown for a board cell holding `cell`.
+    fn display(&self, cell: CellState) -> &str {
+        match cell.owner() {
+            Some(Player::Human) => &self.human,
+            Some(Player::Bot) => &self.bot,
+            None => "",
+        }
+    }
+
+    /// The mark shown for a board cell holding `cell` in a cell `width` columns wide. Marks
+    /// too wide for it, such as emoji in the smallest cells, give way to X and O.
+    fn display_within(&self, cell: CellState, width: u16) -> &str {
+        let mark = self.display(cell);
+        match mark.width() > usize::from(width) {
+            true => cell.symbol(),
+            false => mark,
+        }
+    }
+}
+
+/// What the bot settled on while it thought its turn over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reply {
+    /// Take over the human's opening move under the pie rule.
+    Swap,
+    /// Place a mark, unless the board was full.
+    Place(Option<engine::Move>),
+    /// Play a turn of quantum tic-tac-toe, which is quick enough to choose as it is played.
+    Quantum,
+}
+
+/// Everything that changes a game of marks in a row, whether the player pressed a key or
+/// clicked, or time passed for the bot to answer; each goes through [`App::update`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Message {
+    NextRow,
+    PreviousRow,
+    NextColumn,
+    PreviousColumn,
+    NextLayer,
+    PreviousLayer,
+    /// Select the given cell, or the column of a board with gravity, placing a mark there when
+    /// it was already selected.
+    Click([usize; 2]),
+    PlaceMark,
+    Hint,
+    Undo,
+    Redo,
+    /// Give up the game under way.
+    Resign,
+    /// Ask whether to start the game over.
+    RequestRestart,
+    /// Start the next game, keeping the score.
+    Rematch,
+    /// Take back a question asked, such as whether to start over.
+    Dismiss,
+    /// Take over the bot's opening move under the pie rule, or decline to.
+    AnswerSwap(bool),
+    CycleOpponent,
+    CycleMatchMode,
+    CycleMoveTimeLimit,
+    CycleClockTime,
+    ResetScore,
+    ToggleStats,
+    ToggleZoom,
+    /// Switch the mark the human places in wild play.
+    SwitchWildMark,
+    /// Start typing the name of a cell after `:`.
+    StartEntry,
+    /// A key typed into the name of a cell.
+    Entry(KeyCode),
+    /// A key pressed on the screen shown once the game is over.
+    Results(KeyCode),
+    /// The bot has thought its turn over and answers.
+    BotMove(Reply),
+    /// The coin deciding who opens has landed.
+    CoinLanded,
+    /// A game of a series is over and the next one starts.
+    NextGame,
+    /// Time passed, which may run out the move time limit or a clock.
+    Tick,
+}
+
+/// Whether moves are still being played or the game has been decided.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum GameState {
+    /// A coin is being flipped to decide who opens; it lands on the given player.
+    CoinFlip {
+        started: Instant,
+        winner: Player,
+    },
+    Playing,
+    GameOver(Outcome),
+}
+
+/// What a timed effect shows while it is active.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EffectKind {
+    /// The human tried to play on an occupied cell or outside the sub-board they were sent to.
+    InvalidMove([usize; 2]),
+    /// The bot has just played on this cell.
+    LastMove([usize; 2]),
+    /// A mark has just been placed on this cell, by either side, and is appearing.
+    Placed([usize; 2]),
+    /// The human has just won, with confetti over the board and the winning line pulsing.
+    Celebration,
+}
+
+/// A short-lived visual effect drawn on top of the board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Effect {
+    kind: EffectKind,
+    started: Instant,
+    duration: Duration,
+}
+
+impl Effect {
+    fn new(kind: EffectKind, duration: Duration) -> Self {
+        Self {
+            kind,
+            started: Instant::now(),
+            duration,
+        }
+    }
+
+    /// Whether the effect should still be drawn.
+    fn is_active(&self) -> bool {
+        self.started.elapsed() < self.duration
+    }
+
+    /// How far along the effect is, as one of `frames` steps of equal length.
+    fn frame(&self, frames: u32) -> u32 {
+        let step = self.duration / frames;
+        let frame = self.started.elapsed().as_millis() / step.as_millis().max(1);
+        (frame as u32).min(frames - 1)
+    }
+
+    /// Whether a flashing effect is in its visible phase.
+    fn flash_on(&self) -> bool {
+        (self.started.elapsed().as_millis() / FLASH_INTERVAL.as_millis()).is_multiple_of(2)
+    }
+}
+
+/// The main application which holds the state and logic of the application.
+pub struct App {
+    state: TableState,
+    rows: usize,
+    cols: usize,
+    rules: Rules,
+    items: Vec<Vec<CellState>>,
+    colors: TableColors,
+    placement: Pos,
+    turn: Player,
+    game_state: GameState,
+    opponent: bot::Opponent,
+    starter: Player,
+    confirm_restart: bool,
+    history: Vec<Command>,
+    redo_stack: Vec<Vec<Command>>,
+    /// The game was decided and then taken back with undo.
+    reopened: bool,
+    score: Score,
+    stats: SessionStats,
+    /// Show the stats panel beside the board, when there is room for it.
+    show_stats: bool,
+    series: Option<Series>,
+    /// When the next game of an undecided series starts, once the last one is over.
+    next_game_at: Option<Instant>,
+    move_time_limit: Option<Duration>,
+    turn_started: Instant,
+    /// When the game was paused, to push back the start of the turn by as long on resuming.
+    paused: Option<Instant>,
+    /// Messages for the player not yet shown.
+    toasts: Vec<String>,
+    /// What the screen shown once the game is over holds.
+    results: Results,
+    /// The player chose to go back to the menu.
+    leaving: bool,
+    /// The name of the cell being typed after `:`, to place a mark there.
+    entry: Option<String>,
+    clock_time: Option<Duration>,
+    clocks: Option<Clocks>,
+    first_move: FirstMove,
+    symbols: Symbols,
+    hint: Option<([usize; 2], Instant)>,
+    /// The mark the human places in wild play.
+    wild_mark: Player,
+    /// The spooky and classical marks of a game of quantum tic-tac-toe.
+    quantum: Option<quantum::Board>,
+    /// The first cell of the quantum move the human is placing.
+    quantum_pick: Option<[usize; 2]>,
+    /// The second player has yet to decide whether to take over the opening move.
+    swap_pending: bool,
+    /// The bot thinking its turn over.
+    thinking: Option<bot::Thinking<Reply>>,
+    effects: Vec<Effect>,
+    viewport: Viewport,
+    zoomed_out: bool,
+    /// Where the board was last drawn, while it is shown.
+    hitbox: Option<grid::Hitbox>,
+    /// The scale the board was last drawn at, which the marks are drawn to fit.
+    scale: Option<Scale>,
+    /// Hatch the cursor and draw the player's marks bold and the bot's dim, besides coloring
+    /// them.
+    patterns: bool,
+    /// The lines the board is framed with, if it is.
+    border: Option<BorderType>,
+    /// The name of the game, shown on the board's frame.
+    name: &'static str,
+    /// When the game was set up, which the color wave through the title runs from.
+    opened: Instant,
+}
+
+impl Default for App {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl App {
+    /// Construct a new instance of [`App`].
+    pub fn new() -> Self {
+        Self {
+            state: TableState::default().with_selected(0),
+            rows: 3,
+            cols: 3,
+            rules: Rules::new(3),
+            items: vec![vec![CellState::Empty; 3]; 3],
+            colors: TableColors::new(&PALETTES[0]),
+            placement: Pos::new(1, 1),
+            turn: Player::Human,
+            game_state: GameState::Playing,
+            opponent: bot::Opponent::Random,
+            starter: Player::Human,
+            confirm_restart: false,
+            history: Vec::new(),
+            redo_stack: Vec::new(),
+            reopened: false,
+            score: Score::default(),
+            stats: SessionStats::default(),
+            show_stats: false,
+            series: None,
+            next_game_at: None,
+            move_time_limit: None,
+            turn_started: Instant::now(),
+            paused: None,
+            toasts: Vec::new(),
+            results: Results::Summary(ResultButton::Rematch),
+            leaving: false,
+            entry: None,
+            clock_time: None,
+            clocks: None,
+            first_move: FirstMove::Alternate,
+            symbols: Symbols::default(),
+            hint: None,
+            wild_mark: Player::Human,
+            quantum: None,
+            quantum_pick: None,
+            swap_pending: false,
+            thinking: None,
+            effects: Vec::new(),
+            viewport: Viewport::default(),
+            zoomed_out: false,
+            hitbox: None,
+            scale: None,
+            patterns: false,
+            border: Some(BorderType::Rounded),
+            name: GameMode::TicTacToe.name(),
+            opened: Instant::now(),
+        }
+    }
+
+    /// Show `name` as the name of the game, for the variants played by other rules.
+    pub fn with_name(mut self, name: &'static str) -> Self {
+        self.name = name;
+        self
+    }
+
+    /// Show the players' marks as `symbols` instead of X and O.
+    pub fn with_symbols(mut self, symbols: Symbols) -> Self {
+        self.symbols = symbols;
+        self
+    }
+
+    /// Play on a board of `rows`×`cols` cells, each up to the largest of the [`BOARD_SIZES`].
+    pub fn with_board_size(mut self, rows: usize, cols: usize) -> Self {
+        self.rows = rows;
+        self.cols = cols;
+        self.rules = Rules::new(rows);
+        self.items = vec![vec![CellState::Empty; cols]; rows];
+        self.placement = Pos::center(rows, cols);
+        self
+    }
+
+    /// Play by `rules` instead of the classic ones for the board size.
+    pub fn with_rules(mut self, rules: Rules) -> Self {
+        self.rules = rules;
+        self.quantum = rules.quantum.then(quantum::Board::new);
+        self
+    }
+
+    /// Decide who opens each game, restarting the first game accordingly.
+    pub fn with_first_move(mut self, first_move: FirstMove) -> Self {
+        self.first_move = first_move;
+        if first_move != FirstMove::Alternate {
+            self.rematch();
+        }
+        self
+    }
+
+    /// The message a key press stands for, if it does anything at this point of the game.
+    fn message(&self, key: KeyEvent) -> Option<Message> {
+        let message = match (key.modifiers, key.code) {
+            (_, code) if self.entry.is_some() => Message::Entry(code),
+            (_, KeyCode::Char('y')) if self.confirm_restart => Message::Rematch,
+            _ if self.confirm_restart => Message::Dismiss,
+            (_, KeyCode::Char('y')) if self.swap_offered() => Message::AnswerSwap(true),
+            (_, KeyCode::Char('n')) if self.swap_offered() => Message::AnswerSwap(false),
+            (
+                _,
+                code @ (KeyCode::Char('w' | 'a' | 's' | 'd' | ' ')
+                | KeyCode::Up
+                | KeyCode::Down
+                | KeyCode::Left
+                | KeyCode::Right
+                | KeyCode::Enter
+                | KeyCode::Backspace),
+            ) if self.showing_results() => Message::Results(code),
+            (_, KeyCode::Char('b')) => Message::CycleOpponent,
+            (_, KeyCode::Char('x')) => Message::ResetScore,
+            (_, KeyCode::Tab) => Message::ToggleStats,
+            (_, KeyCode::Char('m')) => Message::CycleMatchMode,
+            (_, KeyCode::Char('t')) => Message::CycleMoveTimeLimit,
+            (_, KeyCode::Char('c')) => Message::CycleClockTime,
+            (KeyModifiers::CONTROL, KeyCode::Char('r')) => Message::Redo,
+            (_, KeyCode::Char('u')) => Message::Undo,
+            (_, KeyCode::Char('r')) => Message::RequestRestart,
+            // the board is frozen once the game is decided
+            _ if self.game_state != GameState::Playing => return None,
+            // only the column is chosen when marks drop into place
+            (_, KeyCode::Char('s' | 'w') | KeyCode::Down | KeyCode::Up) if self.rules.gravity => {
+                return None;
+            }
+            (_, KeyCode::Char('s') | KeyCode::Down) => Message::NextRow,
+            (_, KeyCode::Char('w') | KeyCode::Up) => Message::PreviousRow,
+            (_, KeyCode::Char('d') | KeyCode::Right) => Message::NextColumn,
+            (_, KeyCode::Char('a') | KeyCode::Left) => Message::PreviousColumn,
+            (_, KeyCode::Char(']')) => Message::NextLayer,
+            (_, KeyCode::Char('[')) => Message::PreviousLayer,
+            (_, KeyCode::Char(' ') | KeyCode::Enter) => Message::PlaceMark,
+            (_, KeyCode::Char('h')) => Message::Hint,
+            (_, KeyCode::Char('g')) => Message::Resign,
+            (_, KeyCode::Char('f')) if self.rules.wild => Message::SwitchWildMark,
+            (_, KeyCode::Char('z')) => Message::ToggleZoom,
+            (_, KeyCode::Char(':')) => Message::StartEntry,
+            _ => return None,
+        };
+        Some(message)
+    }
+
+    /// Carry out `message`, the one way the game changes, whatever it came from.
+    pub fn update(&mut self, message: Message) {
+        match message {
+            Message::NextRow => self.next_row(),
+            Message::PreviousRow => self.previous_row(),
+            Message::NextColumn => self.next_column(),
+            Message::PreviousColumn => self.previous_column(),
+            Message::NextLayer => self.next_layer(),
+            Message::PreviousLayer => self.previous_layer(),
+            Message::Click([row, col]) => {
+                let selected = match self.rules.gravity {
+                    true => col == self.placement.col,
+                    false => [row, col] == self.placement.cell(),
+                };
+                self.placement.col = col;
+                if !self.rules.gravity {
+                    self.placement.row = row;
+                }
+                if selected {
+                    self.place_mark();
+                }
+            }
+            Message::PlaceMark => self.place_mark(),
+            Message::Hint => self.show_hint(),
+            Message::Undo => self.undo(),
+            Message::Redo => self.redo(),
+            Message::Resign => self.resign(),
+            Message::RequestRestart => self.request_restart(),
+            Message::Rematch | Message::NextGame => self.rematch(),
+            Message::Dismiss => self.confirm_restart = false,
+            Message::AnswerSwap(true) => self.swap_sides(),
+            Message::AnswerSwap(false) => self.swap_pending = false,
+            Message::CycleOpponent => self.opponent = self.opponent.next(),
+            Message::CycleMatchMode => self.cycle_match_mode(),
+            Message::CycleMoveTimeLimit => self.cycle_move_time_limit(),
+            Message::CycleClockTime => self.cycle_clock_time(),
+            Message::ResetScore => {
+                self.score = Score::default();
+                self.stats = SessionStats::default();
+            }
+            Message::ToggleStats => self.show_stats = !self.show_stats,
+            Message::ToggleZoom => self.zoomed_out = !self.zoomed_out,
+            Message::SwitchWildMark => self.wild_mark = self.wild_mark.other(),
+            Message::StartEntry => self.entry = Some(String::new()),
+            Message::Entry(code) => self.handle_entry_key(code),
+            Message::Results(code) => self.handle_results_key(code),
+            Message::BotMove(reply) => {
+                self.bot_move(reply);
+                if self.game_state == GameState::Playing && self.turn == Player::Human {
+                    self.toasts.push("Your turn".into());
+                }
+            }
+            Message::CoinLanded => {
+                self.game_state = GameState::Playing;
+                self.turn_started = Instant::now();
+            }
+            Message::Tick => {
+                self.check_move_timeout();
+                self.check_flag_fall();
+            }
+        }
+    }
+
+    pub fn next_row(&mut self) {
+        // the table state only knows the row within the visible part of the board
+        self.placement = self.placement.down(self.rows);
+    }
+
+    pub fn previous_row(&mut self) {
+        self.placement = self.placement.up(self.rows);
+    }
+
+    pub fn next_column(&mut self) {
+        self.placement = self.placement.right(1, self.cols);
+    }
+
+    pub fn previous_column(&mut self) {
+        self.placement = self.placement.left(1, self.cols);
+    }
+
+    /// Move the cursor to the same cell of the next layer, wrapping around after the last.
+    pub fn next_layer(&mut self) {
+        let width = self.cols / self.rules.layers;
+        self.placement = self.placement.right(width, self.cols);
+    }
+
+    /// Move the cursor to the same cell of the previous layer, wrapping around before the first.
+    pub fn previous_layer(&mut self) {
+        let width = self.cols / self.rules.layers;
+        self.placement = self.placement.left(width, self.cols);
+    }
+
+    /// The cell a mark placed now would end up in, if any.
+    ///
+    /// With gravity this is where a mark dropped into the selected column lands.
+    fn target_cell(&self) -> Option<[usize; 2]> {
+        let Pos { row, col } = self.placement;
+        if self.rules.gravity {
+            landing_row(&self.items, col).map(|row| [row, col])
+        } else {
+            Some([row, col])
+        }
+    }
+
+    /// Place the human's mark into the selected cell, or drop it into the selected column.
+    ///
+    /// Occupied cells, full columns and cells outside the sub-board the human was sent to are
+    /// left untouched and flash to show the move was rejected.
+    pub fn place_mark(&mut self) {
+        if self.turn != Player::Human {
+            return;
+        }
+        let cell = match self.quantum {
+            Some(_) => self.placement.cell(),
+            None => self.target_cell().unwrap_or([0, self.placement.col]),
+        };
+        if !self.apply(cell) {
+            self.effects.push(Effect::new(
+                EffectKind::InvalidMove(cell),
+                INVALID_MOVE_DURATION,
+            ));
+            self.toasts.push("Invalid move".into());
+        }
+    }
+
+    /// Pick `cell` for the human's quantum move, or as the cell a pending cycle collapses into.
+    ///
+    /// The first pick is remembered until a second, different cell completes the move; picking
+    /// the same cell again drops it.
+    fn pick_quantum_cell(&mut self, cell: [usize; 2]) {
+        let Some(board) = &self.quantum else {
+            return;
+        };
+        if board.collapse_options().is_some() {
+            self.collapse_quantum(cell);
+        } else if board.free_cells().len() == 1 {
+            self.play_quantum([cell, cell]);
+        } else {
+            match self.quantum_pick.take() {
+                None => self.quantum_pick = Some(cell),
+                Some(first) if first == cell => {}
+                Some(first) => self.play_quantum([first, cell]),
+            }
+        }
+    }
+
+    /// Let `opponent` take the current player's quantum turn: settle a pending collapse, then
+    /// place a move.
+    fn quantum_turn(&mut self, opponent: bot::Opponent) {
+        let player = self.turn;
+        if let Some(cell) = self
+            .quantum
+            .as_ref()
+            .and_then(|board| opponent.choose_collapse(board, player))
+        {
+            self.collapse_quantum(cell);
+        }
+        if self.game_state != GameState::Playing {
+            return;
+        }
+        if let Some(cells) = self
+            .quantum
+            .as_ref()
+            .and_then(|board| opponent.choose_quantum_move(board, player))
+        {
+            self.play_quantum(cells);
+            if player == Player::Bot {
+                self.effects.push(Effect::new(
+                    EffectKind::LastMove(cells[1]),
+                    LAST_MOVE_DURATION,
+                ));
+            }
+        }
+    }
+
+    /// Superpose the current player's quantum move over `cells` and pass the turn.
+    fn play_quantum(&mut self, cells: [[usize; 2]; 2]) {
+        let Some(board) = &mut self.quantum else {
+            return;
+        };
+        board.play(self.turn, cells);
+        let [row, col] = cells[0];
+        self.history.push(Command::Place(Move {
+            row,
+            col,
+            player: self.turn,
+            mark: self.turn,
+            took: self.turn_started.elapsed(),
+        }));
+        self.charge_clock();
+        self.quantum_pick = None;
+        self.turn = self.turn.other();
+        self.turn_started = Instant::now();
+        self.sync_quantum();
+    }
+
+    /// Collapse the pending cycle of the quantum game into `cell`.
+    fn collapse_quantum(&mut self, cell: [usize; 2]) {
+        if let Some(board) = &mut self.quantum {
+            board.collapse(cell);
+            self.sync_quantum();
+        }
+    }
+
+    /// Copy the classical marks of the quantum game onto the board and check for a result.
+    fn sync_quantum(&mut self) {
+        let Some(board) = &self.quantum else {
+            return;
+        };
+        for (row, cells) in self.items.iter_mut().enumerate() {
+            for (col, cell) in cells.iter_mut().enumerate() {
+                *cell = match board.classical([row, col]) {
+                    Some((player, _)) => player.into(),
+                    None => CellState::Empty,
+                };
+            }
+        }
+        if let Some(outcome) = board.outcome() {
+            self.finish_game(outcome);
+        }
+    }
+
+    /// The cell the human last tried to play on without success, while its effect is active.
+    fn invalid_move(&self) -> Option<(&Effect, [usize; 2])> {
+        self.effects
+            .iter()
+            .rev()
+            .find_map(|effect| match effect.kind {
+                EffectKind::InvalidMove(cell) => Some((effect, cell)),
+                _ => None,
+            })
+    }
+
+    /// The cell the previous move was played on, by either side.
+    fn last_move(&self) -> Option<[usize; 2]> {
+        self.moves().last().map(|mv| [mv.row, mv.col])
+    }
+
+    /// The cell the bot last played on, while its accent is active.
+    fn last_bot_move(&self) -> Option<[usize; 2]> {
+        self.effects
+            .iter()
+            .rev()
+            .find_map(|effect| match effect.kind {
+                EffectKind::LastMove(cell) => Some(cell),
+                _ => None,
+            })
+    }
+
+    /// The frame the mark on `cell` shows while it is appearing, out of [`PLACE_FRAMES`].
+    fn placing_frame(&self, cell: [usize; 2]) -> Option<u32> {
+        self.effects
+            .iter()
+            .rev()
+            .find_map(|effect| match effect.kind {
+                EffectKind::Placed(placed) if placed == cell && effect.is_active() => {
+                    Some(effect.frame(PLACE_FRAMES))
+                }
+                _ => None,
+            })
+    }
+
+    /// The celebration of the human's win, while it plays.
+    fn celebration(&self) -> Option<&Effect> {
+        self.effects
+            .iter()
+            .find(|effect| effect.kind == EffectKind::Celebration && effect.is_active())
+    }
+
+    /// Whether a mark is still appearing or a win is being celebrated.
+    fn animating(&self) -> bool {
+        self.effects.iter().any(|effect| {
+            matches!(effect.kind, EffectKind::Placed(_) | EffectKind::Celebration)
+                && effect.is_active()
+        })
+    }
+
+    /// Highlight the move the minimax engine recommends for the human.
+    ///
+    /// In wild play the recommended mark is selected too.
+    pub fn show_hint(&mut self) {
+        if self.turn == Player::Human
+            && self.quantum.is_none()
+            && let Some(placement) =
+                bot::minimax(&self.items, self.rules, Player::Human, self.last_move())
+        {
+            self.hint = Some((placement.cell, Instant::now()));
+            self.wild_mark = placement.mark;
+        }
+    }
+
+    /// The suggested cell, while its highlight has not yet expired.
+    fn active_hint(&self) -> Option<[usize; 2]> {
+        self.hint
+            .filter(|(_, shown)| shown.elapsed() < HINT_DURATION)
+            .map(|(cell, _)| cell)
+    }
+
+    /// Take back the last human move together with the bot's reply to it.
+    ///
+    /// Collapses cannot be taken back, so quantum moves are final.
+    pub fn undo(&mut self) {
+        if self.quantum.is_some() {
+            return;
+        }
+        let Some(index) = self
+            .history
+            .iter()
+            .rposition(|command| command.player() == Player::Human)
+        else {
+            return;
+        };
+        self.charge_clock();
+        self.effects.clear();
+        self.thinking = None;
+        self.reopened |= matches!(self.game_state, GameState::GameOver(_));
+        let undone = self.history.split_off(index);
+        for &command in undone.iter().rev() {
+            command.revert(self);
+        }
+        self.redo_stack.push(undone);
+        self.turn_started = Instant::now();
+        self.swap_pending = self.rules.swap && self.history.len() == 1;
+    }
+
+    /// Make again the changes most recently taken back with [`App::undo`].
+    pub fn redo(&mut self) {
+        let Some(commands) = self.redo_stack.pop() else {
+            return;
+        };
+        for command in commands {
+            command.apply(self);
+        }
+    }
+
+    /// Give up the game under way, which [`App::undo`] takes back like a move.
+    pub fn resign(&mut self) {
+        if self.game_state == GameState::Playing {
+            Command::Resign.apply(self);
+            self.redo_stack.clear();
+        }
+    }
+
+    /// The marks placed so far, in the order they were.
+    fn moves(&self) -> impl Iterator<Item = &Move> {
+        self.history.iter().filter_map(Command::placed)
+    }
+
+    /// Start the selected opponent thinking over the bot's turn on a thread of its own,
+    /// whether to take over the opening move as well as where to place its mark.
+    fn think(&self) -> bot::Thinking<Reply> {
+        if self.quantum.is_some() {
+            return bot::Thinking::start(|| Reply::Quantum);
+        }
+        let (items, rules, last) = (self.items.clone(), self.rules, self.last_move());
+        let (opponent, swap) = (self.opponent, self.swap_pending);
+        bot::Thinking::start(move || {
+            if swap && last.is_some() && opponent.choose_swap(&items, rules, Player::Bot, last) {
+                return Reply::Swap;
+            }
+            let board = engine::Board::from_position(items, rules, Player::Bot, last);
+            Reply::Place(opponent.bot().choose_move(&board))
+        })
+    }
+
+    /// Play the bot's turn as it settled on while it thought.
+    fn bot_move(&mut self, reply: Reply) {
+        if reply == Reply::Quantum {
+            self.quantum_turn(self.opponent);
+            return;
+        }
+        self.swap_pending = false;
+        if reply == Reply::Swap
+            && let Some(cell) = self.last_move()
+        {
+            self.swap_sides();
+            self.effects
+                .push(Effect::new(EffectKind::LastMove(cell), LAST_MOVE_DURATION));
+            return;
+        }
+        if let Reply::Place(Some(engine::Move {
+            cell: [row, col],
+            mark,
+        })) = reply
+        {
+            self.apply_mark(row, col, mark);
+            self.effects.push(Effect::new(
+                EffectKind::LastMove([row, col]),
+                LAST_MOVE_DURATION,
+            ));
+        }
+    }
+
+    /// Write `mark` at `row`/`col` for the current player, pass the turn and check for a result.
+    ///
+    /// Returns `false` if the cell was already occupied.
+    fn apply_mark(&mut self, row: usize, col: usize, mark: Player) -> bool {
+        if !self.items[row][col].is_empty() {
+            return false;
+        }
+        Command::Place(Move {
+            row,
+            col,
+            player: self.turn,
+            mark,
+            took: self.turn_started.elapsed(),
+        })
+        .apply(self);
+        true
+    }
+
+    /// Type the name of a cell, such as `b2`, and place the human's mark there with `Enter`;
+    /// with gravity it drops into the named column. `Esc`, or `Backspace` once nothing is left
+    /// to erase, gives up.
+    fn handle_entry_key(&mut self, code: KeyCode) {
+        let Some(entry) = &mut self.entry else {
+            return;
+        };
+        match code {
+            KeyCode::Char(c) if c.is_ascii_alphanumeric() && entry.len() < MAX_CELL_NAME => {
+                entry.push(c.to_ascii_lowercase());
+            }
+            KeyCode::Backspace if !entry.is_empty() => {
+                entry.pop();
+            }
+            KeyCode::Backspace | KeyCode::Esc => self.entry = None,
+            KeyCode::Enter => {
+                let name = self.entry.take().unwrap_or_default();
+                match parse_cell(&name).filter(|&[row, col]| row < self.rows && col < self.cols) {
+                    Some([row, col]) => {
+                        self.placement = Pos::new(row, col);
+                        self.place_mark();
+                    }
+                    None => self.toasts.push(format!("No cell {name}")),
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Whether the human may take over the bot's opening move under the pie rule.
+    fn swap_offered(&self) -> bool {
+        self.swap_pending && self.turn == Player::Human && self.game_state == GameState::Playing
+    }
+
+    /// Let the player to move take over the opening move as their own, passing the turn back
+    /// to the player who made it.
+    fn swap_sides(&mut self) {
+        if let Some(&Command::Place(opening)) = self.history.first() {
+            Command::Swap(opening).apply(self);
+        }
+    }
+
+    /// Hand the opening move, and the mark it placed, over to `owner`.
+    fn set_opening_owner(&mut self, owner: Player) {
+        if let Some(Command::Place(opening)) = self.history.first_mut() {
+            opening.player = owner;
+            opening.mark = owner;
+            self.items[opening.row][opening.col] = owner.into();
+        }
+    }
+
+    /// End the game with `outcome` and record it in the score.
+    fn finish_game(&mut self, outcome: Outcome) {
+        self.game_state = GameState::GameOver(outcome);
+        self.results = Results::Summary(ResultButton::Rematch);
+        self.entry = None;
+        if outcome == Outcome::Winner(Player::Human) {
+            self.effects
+                .push(Effect::new(EffectKind::Celebration, CELEBRATION_DURATION));
+        }
+        *self.score.tally(outcome) += 1;
+        self.stats.record(outcome);
+        if let Some(series) = &mut self.series {
+            series.results.push(outcome);
+        }
+    }
+
+    /// Restart the game, asking for confirmation first if a game is still under way.
+    pub fn request_restart(&mut self) {
+        let in_progress = self.game_state == GameState::Playing && !self.history.is_empty();
+        if in_progress {
+            self.confirm_restart = true;
+        } else {
+            self.rematch();
+        }
+    }
+
+    /// Switch between casual play and best-of-3, 5 and 7 series, starting a new game.
+    pub fn cycle_match_mode(&mut self) {
+        let best_of = match self.series.as_ref().map(|series| series.best_of) {
+            None => Some(3),
+            Some(3) => Some(5),
+            Some(5) => Some(7),
+            Some(_) => None,
+        };
+        self.series = best_of.map(Series::new);
+        self.rematch();
+    }
+
+    /// Clear the board and start a fresh game, letting the other side open.
+    ///
+    /// A decided series is replaced by a new one of the same length.
+    pub fn rematch(&mut self) {
+        if let Some(series) = &mut self.series
+            && series.winner().is_some()
+        {
+            *series = Series::new(series.best_of);
+        }
+        self.items = vec![vec![CellState::Empty; self.cols]; self.rows];
+        self.placement = Pos::center(self.rows, self.cols);
+        self.game_state = GameState::Playing;
+        self.starter = match self.first_move {
+            FirstMove::Me => Player::Human,
+            FirstMove::Bot => Player::Bot,
+            FirstMove::Alternate => self.starter.other(),
+            FirstMove::Random => {
+                let winner = if rand::random() {
+                    Player::Human
+                } else {
+                    Player::Bot
+                };
+                self.game_state = GameState::CoinFlip {
+                    started: Instant::now(),
+                    winner,
+                };
+                winner
+            }
+        };
+        self.turn = self.starter;
+        self.turn_started = Instant::now();
+        self.clocks = self.clock_time.map(Clocks::new);
+        self.confirm_restart = false;
+        self.history.clear();
+        self.redo_stack.clear();
+        self.reopened = false;
+        self.effects.clear();
+        self.thinking = None;
+        self.quantum = self.rules.quantum.then(quantum::Board::new);
+        self.quantum_pick = None;
+        self.swap_pending = false;
+        self.entry = None;
+    }
+}
+
+impl Game for App {
+    type Move = [usize; 2];
+
+    fn dimensions(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    /// The cells the human may pick; in quantum tic-tac-toe either half of a move, or where a
+    /// pending cycle collapses to.
+    fn legal_moves(&self) -> Vec<[usize; 2]> {
+        if self.game_state != GameState::Playing || self.turn != Player::Human {
+            return Vec::new();
+        }
+        match &self.quantum {
+            Some(board) => match board.collapse_options() {
+                Some(options) => options.to_vec(),
+                None => board.free_cells(),
+            },
+            None => legal_moves(&self.items, self.rules, self.last_move()),
+        }
+    }
+
+    fn apply(&mut self, cell: [usize; 2]) -> bool {
+        if !self.legal_moves().contains(&cell) {
+            return false;
+        }
+        if self.quantum.is_some() {
+            self.pick_quantum_cell(cell);
+            return true;
+        }
+        let mark = match self.rules.wild {
+            true => self.wild_mark,
+            false => self.rules.marks(Player::Human)[0],
+        };
+        if !self.apply_mark(cell[0], cell[1], mark) {
+            return false;
+        }
+        self.redo_stack.clear();
+        true
+    }
+
+    fn status(&self) -> Status {
+        match self.game_state {
+            GameState::GameOver(Outcome::Winner(Player::Human)) => Status::Won,
+            GameState::GameOver(Outcome::Winner(Player::Bot)) => Status::Lost,
+            GameState::GameOver(Outcome::Draw) => Status::Draw,
+            GameState::CoinFlip { .. } | GameState::Playing => Status::Playing,
+        }
+    }
+
+    fn opponent(&self) -> Option<bot::Opponent> {
+        Some(self.opponent)
+    }
+
+    fn render_cell(&self, cell: [usize; 2]) -> (String, Style) {
+        let [row, col] = cell;
+        let content = self.items[row][col];
+        let text = match &self.quantum {
+            Some(board) if content.is_empty() => self.spooky_text(board, cell),
+            _ => {
+                let width = self.scale.map_or(u16::MAX, |scale| scale.cell_width);
+                self.symbols.display_within(content, width).to_string()
+            }
+        };
+        // cells with room for it show the mark drawn large
+        let text = match self.scale.and_then(|scale| mark_art(&text, scale)) {
+            Some(art) => art,
+            None => text,
+        };
+
+        let base = if self.rules.ultimate {
+            // the sub-boards the human may play in, highlighted while it is their turn
+            let open_boards: Vec<_> = match (self.game_state, self.turn) {
+                (GameState::Playing, Player::Human) => {
+                    legal_moves(&self.items, self.rules, self.last_move())
+                        .into_iter()
+                        .map(ultimate::sub_board_of)
+                        .collect()
+                }
+                _ => Vec::new(),
+            };
+            self.sub_board_style(cell, &open_boards)
+        } else if self.rules.layers > 1 || self.rules.boards > 1 {
+            // tell the layers and boards apart by shading every other one
+            let section = col / (self.cols / (self.rules.layers * self.rules.boards));
+            let style = match section % 2 {
+                0 => Style::default().bg(self.colors.normal_row_color),
+                _ => Style::default().bg(self.colors.alt_row_color),
+            };
+            // dead Notakto boards are dimmed
+            let dead = match self.rules.notakto {
+                true => {
+                    let lines = winning_lines(self.rows, self.cols, self.rules);
+                    dead_boards(&self.items, &lines, self.rules)
+                }
+                false => Vec::new(),
+            };
+            match dead.get(col / (self.cols / self.rules.boards)) {
+                Some(true) => style.add_modifier(Modifier::DIM),
+                _ => style,
+            }
+        } else {
+            Style::default()
+        };
+
+        // once someone has won, show the winning line instead of the cursor
+        let winner = match self.game_state {
+            GameState::GameOver(Outcome::Winner(_)) => winning_line(&self.items, self.rules),
+            _ => None,
+        };
+        // the cells a pending collapse chooses between, or the first half of a quantum move
+        let quantum_cells = match &self.quantum {
+            Some(board) => match board.collapse_options() {
+                Some(options) => options.to_vec(),
+                None => self.quantum_pick.into_iter().collect(),
+            },
+            None => Vec::new(),
+        };
+        let hint_cell_style = Style::default()
+            .bg(self.colors.hint_cell_style_bg)
+            .add_modifier(Modifier::BOLD);
+        let style = match &winner {
+            // the winning line pulses while the win is celebrated
+            Some((_, line)) if line.contains(&cell) => match self.celebration() {
+                Some(celebration) if !celebration.flash_on() => Style::default()
+                    .add_modifier(Modifier::BOLD)
+                    .fg(self.colors.selected_cell_style_fg),
+                _ => Style::default()
+                    .add_modifier(Modifier::REVERSED)
+                    .fg(self.colors.selected_cell_style_fg),
+            },
+            Some(_) => Style::default().add_modifier(Modifier::DIM),
+            None if self.flashing_invalid_cell() == Some(cell) => Style::default().bg(Color::Red),
+            None if self.active_hint() == Some(cell) => hint_cell_style,
+            None if quantum_cells.contains(&cell) => hint_cell_style,
+            None if self.last_bot_move() == Some(cell) => Style::default()
+                .fg(self.colors.last_move_style_fg)
+                .add_modifier(Modifier::BOLD),
+            None => Style::default(),
+        };
+        let (text, style) = match self.patterns {
+            true => self.patterned(cell, text, style),
+            false => (text, style),
+        };
+        // a new mark grows through the placing glyphs, then fades in and shows bold
+        let (text, style) = match self.placing_frame(cell) {
+            Some(frame) => match PLACE_GLYPHS.get(frame as usize) {
+                Some(glyph) => (glyph.to_string(), style.add_modifier(Modifier::DIM)),
+                None if frame as usize == PLACE_GLYPHS.len() => {
+                    (text, style.add_modifier(Modifier::DIM))
+                }
+                None => (text, style.add_modifier(Modifier::BOLD)),
+            },
+            None => (text, style),
+        };
+        (text, base.patch(style))
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        if let Some(message) = self.message(key) {
+            self.update(message);
+        }
+    }
+
+    fn wants_mouse(&self) -> bool {
+        true
+    }
+
+    /// A click selects the cell under the mouse, or the column of a board with gravity, and a
+    /// click on the cell already selected places a mark there.
+    fn handle_mouse(&mut self, event: MouseEvent) {
+        if event.kind != MouseEventKind::Down(MouseButton::Left) {
+            return;
+        }
+        if self.confirm_restart {
+            self.update(Message::Dismiss);
+            return;
+        }
+        if let Some(cell) = self
+            .hitbox
+            .and_then(|hitbox| hitbox.cell_at(event.column, event.row))
+            && self.game_state == GameState::Playing
+        {
+            self.update(Message::Click(cell));
+        }
+    }
+
+    fn tick(&mut self) -> bool {
+        // Let a new mark finish appearing, and a win its celebration, before the bot answers
+        // or the next game starts.
+        if self.animating() {
+            return false;
+        }
+
+        // Move on to the next game of an undecided series, once the last one has been seen.
+        if matches!(self.game_state, GameState::GameOver(_))
+            && self
+                .series
+                .as_ref()
+                .is_some_and(|series| series.winner().is_none())
+        {
+            let next_game_at = *self
+                .next_game_at
+                .get_or_insert_with(|| Instant::now() + NEXT_GAME_DELAY);
+            if Instant::now() < next_game_at {
+                return false;
+            }
+            self.next_game_at = None;
+            self.update(Message::NextGame);
+            return true;
+        }
+        self.next_game_at = None;
+
+        // Let the bot think its turn over while the board is drawn, and play it once it has.
+        if self.game_state == GameState::Playing && self.turn == Player::Bot {
+            if self.thinking.is_none() {
+                self.thinking = Some(self.think());
+            }
+            let answer = self
+                .thinking
+                .as_ref()
+                .map_or(bot::Answer::Pending, bot::Thinking::answer);
+            let reply = match answer {
+                bot::Answer::Pending => None,
+                bot::Answer::Ready(reply) => Some(reply),
+                // a search that died gives way to a random move rather than a bot stuck thinking
+                bot::Answer::Failed if self.quantum.is_some() => Some(Reply::Quantum),
+                bot::Answer::Failed => Some(Reply::Place(bot::random_move(
+                    &self.items,
+                    self.rules,
+                    Player::Bot,
+                    self.last_move(),
+                ))),
+            };
+            if let Some(reply) = reply {
+                self.thinking = None;
+                self.update(Message::BotMove(reply));
+                return true;
+            }
+        } else {
+            self.thinking = None;
+        }
+
+        // Start the game once the coin has landed.
+        if let GameState::CoinFlip { started, .. } = self.game_state
+            && started.elapsed() >= COIN_FLIP_DURATION
+        {
+            self.update(Message::CoinLanded);
+        }
+
+        self.update(Message::Tick);
+        false
+    }
+
+    /// `h` asks for a hint rather than moving the cursor, and `Esc` stops typing a cell.
+    fn uses_key(&self, code: KeyCode) -> bool {
+        code == KeyCode::Char('h') || (code == KeyCode::Esc && self.entry.is_some())
+    }
+
+    /// Every letter goes into the name of a cell while one is typed.
+    fn takes_letters(&self) -> bool {
+        self.entry.is_some()
+    }
+
+    fn apply_settings(&mut self, settings: &Settings) {
+        self.colors = settings.colors();
+        self.opponent = settings.opponent(self.opponent);
+        self.symbols = settings.marks().clone();
+        self.patterns = settings.patterns;
+        self.border = settings.border.border_type();
+    }
+
+    fn pause(&mut self) {
+        self.paused = Some(Instant::now());
+    }
+
+    fn resume(&mut self) {
+        if let Some(paused) = self.paused.take() {
+            self.turn_started += paused.elapsed();
+        }
+    }
+
+    fn toasts(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.toasts)
+    }
+
+    fn reopened(&self) -> bool {
+        self.reopened
+    }
+
+    fn wants_to_leave(&mut self) -> bool {
+        std::mem::take(&mut self.leaving)
+    }
+
+    fn restart(&mut self) {
+        self.rematch();
+    }
+
+    fn help(&self) -> Help {
+        let rules = &self.rules;
+        let line = format!("{} in a row across, down or diagonally", rules.win_length);
+        let mut text = vec![match (rules.quantum, rules.notakto, rules.order) {
+            (true, ..) => "Every move places two entangled marks in different cells. Once the \
+                           entanglements form a cycle they collapse into real marks, the other \
+                           side choosing where the closing move ends up."
+                .to_string(),
+            (_, true, _) => format!(
+                "Both sides place X. A board holding {line} is dead, and whoever completes a \
+                 line on the last board left loses."
+            ),
+            (_, _, Some(order)) => {
+                let side = match order {
+                    Player::Human => "You are Order",
+                    Player::Bot => "You are Chaos",
+                };
+                format!(
+                    "Either side places either mark. Order wins by making {line} of the same \
+                     mark, Chaos by filling the board without one. {side}."
+                )
+            }
+            _ if rules.ultimate => "Nine small boards make up a big one. The cell of every move \
+                                   picks the small board the next one is played in, and three \
+                                   small boards won in a row win the game."
+                .to_string(),
+            _ => format!("Take turns placing marks; the first to make {line} wins."),
+        }];
+        if rules.gravity {
+            text.push("Marks drop to the lowest empty cell of the chosen column.".into());
+        }
+        if rules.layers > 1 {
+            text.push("Lines run through the stacked layers as well as across each one.".into());
+        }
+        if rules.boards > 1 {
+            text.push("Lines never cross from one board to another.".into());
+        }
+        if rules.wild {
+            text.push("Either side may place either mark, and a line of either wins.".into());
+        }
+        if rules.misere && !rules.notakto {
+            text.push("Completing a line loses the game instead of winning it.".into());
+        }
+        if rules.wrap {
+            text.push("Lines may run off one edge and go on from the opposite one.".into());
+        }
+        if rules.swap {
+            text.push("The second player may take over the opening move as their own.".into());
+        }
+
+        let mut keys = vec![match rules.gravity {
+            true => ("←/→/a/d", "choose a column"),
+            false => ("←↑↓→/wasd", "move the cursor"),
+        }];
+        if rules.layers > 1 {
+            keys.push(("[ / ]", "previous or next layer"));
+        }
+        keys.push(("Space/Enter", "place a mark"));
+        keys.push(("click", "select a cell, and click it again to place a mark"));
+        if rules.wild {
+            keys.push(("f", "switch the mark to place"));
+        }
+        if rules.swap {
+            keys.push(("y / n", "take over the opening move or not"));
+        }
+        keys.extend([
+            (":", "type a cell such as b2, then Enter to place there"),
+            ("h", "hint"),
+            ("u / Ctrl+R", "undo or redo"),
+            ("g", "give up, which u takes back"),
+            ("Ctrl+S", "save, to continue from the menu"),
+            ("z", "zoom out"),
+            ("m", "casual play or a best-of series"),
+            ("t", "time limit per move"),
+            ("c", "chess clock"),
+            ("x", "reset the score"),
+            ("Tab", "show or hide the stats"),
+            ("b", "switch bot"),
+            ("r", "restart"),
+            ("←/→ Enter", "pick a button once the game is over"),
+        ]);
+        Help {
+            rules: text.join(" "),
+            keys,
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        self.effects.retain(Effect::is_active);
+        self.hitbox = None;
+        let [area, footer_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(frame.area());
+        self.render_footer(frame, footer_area);
+        let (rows, cols) = self.dimensions();
+        let preferred = match self.zoomed_out {
+            true => ZOOMED_OUT_CELL,
+            false => cell_size(rows.max(cols)),
+        };
+        if self.showing_results() {
+            self.render_results(frame, area);
+            return;
+        }
+        let header_height = 1 + u16::from(self.clocks.is_some());
+        let (frame_width, frame_height) = self.frame_size();
+        let board_width = area.width.saturating_sub(frame_width);
+        let board_height = area.height.saturating_sub(header_height + frame_height);
+
+        if let Some(scale) = Scale::fitting(board_width, board_height, (rows, cols), preferred) {
+            let (table_width, table_height) = self.fit_viewport(scale, board_width, board_height);
+            let layout = calculate_layout(
+                area,
+                self.clocks.is_some(),
+                table_width + frame_width,
+                table_height + frame_height,
+            );
+
+            // render ui elements, with the title in large letters above where there is room
+            let banner = banner::lines("you vs bot").filter(|rows| {
+                rows[0].width() <= usize::from(area.width)
+                    && layout.title.y >= area.y + BANNER_HEIGHT
+            });
+            self.render_title(frame, layout.title, banner.is_none());
+            if let Some(rows) = banner {
+                let banner_area = Rect {
+                    y: layout.title.y - BANNER_HEIGHT,
+                    height: BANNER_HEIGHT - 1,
+                    ..area
+                };
+                self.render_banner(frame, banner_area, rows);
+            }
+            match self.game_state {
+                GameState::CoinFlip { started, winner } => {
+                    self.render_coin_flip(frame, layout.table, started, winner)
+                }
+                _ => {
+                    let table = self.render_board(frame, layout.table, scale);
+                    if self.celebration().is_some() {
+                        self.render_confetti(frame, table);
+                    }
+                }
+            }
+            if let Some(clocks_area) = layout.clocks {
+                self.render_clocks(frame, clocks_area);
+            }
+            if let Some(mut score_area) = layout.score {
+                if self.series.is_some() {
+                    score_area.height += 2;
+                }
+                self.render_score(frame, score_area);
+            }
+            // the stats go on the table's left, when asked for and there is room
+            let table = layout.table;
+            if self.show_stats && area.x + STATS_WIDTH < table.x {
+                let stats_area = Rect {
+                    x: table.x - 1 - STATS_WIDTH,
+                    y: table.y,
+                    width: STATS_WIDTH,
+                    height: 6.min(area.bottom() - table.y),
+                };
+                self.render_stats(frame, stats_area);
+            }
+        } else {
+            // the smallest cells, set side by side
+            let min_width = table_extent(cols.min(MIN_VISIBLE_CELLS), 1, 0) + frame_width;
+            let min_height =
+                table_extent(rows.min(MIN_VISIBLE_CELLS), 1, 0) + header_height + frame_height;
+            grid::render_too_small(frame, area, min_width, min_height);
+        }
+    }
+}
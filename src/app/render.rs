@@ -0,0 +1,659 @@
+//! How a game of marks in a row is drawn: the board at a scale that fits, and the title,
+//! the panels and the effects around it.
+
+use std::{time::Instant, vec};
+
+use rand::{Rng, seq::IndexedRandom};
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span, Text},
+    widgets::{Block, Paragraph},
+};
+use unicode_width::UnicodeWidthStr;
+
+use super::{
+    App, COIN_FLIP_DURATION, COIN_FRAME, COIN_FRAMES, CONFETTI, CONFETTI_COLORS,
+    CONFETTI_SPARSENESS, GameState, HATCH, PULSE_PERIOD, WAVE_SPEED, WAVE_STRETCH,
+    results::Results,
+};
+use crate::{
+    game::{
+        Game,
+        board::{CellState, Outcome, Player, column_name, winning_line},
+        quantum, ultimate,
+    },
+    grid::{self, Viewport},
+    ui::layout::{cells_fitting, table_extent},
+};
+
+/// The color `amount` of the way from `from` to `to`; colors not given as red, green and blue
+/// switch over halfway.
+fn blend(from: Color, to: Color, amount: f32) -> Color {
+    match (from, to) {
+        (Color::Rgb(r1, g1, b1), Color::Rgb(r2, g2, b2)) => {
+            let mix = |a: u8, b: u8| (f32::from(a) + (f32::from(b) - f32::from(a)) * amount) as u8;
+            Color::Rgb(mix(r1, r2), mix(g1, g2), mix(b1, b2))
+        }
+        _ if amount < 0.5 => from,
+        _ => to,
+    }
+}
+
+impl App {
+    /// Show as much of the board at `scale` as fits in `width`×`height` and scroll over the
+    /// rest, keeping the cursor in view; returns the size the visible part is drawn at.
+    pub fn fit_viewport(&mut self, scale: Scale, width: u16, height: u16) -> (u16, u16) {
+        let (rows, cols) = self.dimensions();
+        let (view_rows, view_cols) = scale.cells_fitting(width, height);
+        let view_rows = rows.min(view_rows);
+        let view_cols = cols.min(view_cols);
+
+        // handle the cell placements, showing where a dropped mark would land
+        let row = match self.rules.gravity {
+            true => self.target_cell().map(|[row, _]| row),
+            false => Some(self.placement.row),
+        };
+        let viewport = &mut self.viewport;
+        viewport.rows = view_rows;
+        viewport.cols = view_cols;
+        viewport.row = Viewport::follow(
+            viewport.row,
+            view_rows,
+            rows,
+            row.unwrap_or(self.placement.row),
+        );
+        viewport.col = Viewport::follow(viewport.col, view_cols, cols, self.placement.col);
+        self.state.select(row.map(|row| row - viewport.row));
+        self.state
+            .select_column(Some(self.placement.col - viewport.col));
+        scale.extent(view_rows, view_cols)
+    }
+
+    /// The width of the row numbers left of the board, with the space after them, and the
+    /// height of the column letters above it.
+    fn label_size(&self) -> (u16, u16) {
+        (self.rows.to_string().len() as u16 + 1, 1)
+    }
+
+    /// How much wider and taller than the board itself it is drawn, with its labels and
+    /// border.
+    pub fn frame_size(&self) -> (u16, u16) {
+        let (label_width, label_height) = self.label_size();
+        let border = 2 * u16::from(self.border.is_some());
+        (label_width + border, label_height + border)
+    }
+
+    /// Draw the board at `scale` into `area`, inside its border titled with the name of the
+    /// game, below the letters of its columns and right of the numbers of its rows, those of
+    /// the cursor standing out; returns where the board itself went.
+    pub fn render_board(&mut self, frame: &mut Frame, area: Rect, scale: Scale) -> Rect {
+        let area = match self.border {
+            Some(border_type) => {
+                let mut title = self.name.to_string();
+                if let Some(series) = &self.series {
+                    title.push_str(&format!(" · best of {}", series.best_of));
+                }
+                let block = Block::bordered()
+                    .border_type(border_type)
+                    .title(Line::from(title).centered())
+                    .border_style(Style::default().fg(self.colors.selected_column_style_fg))
+                    .bg(self.colors.buffer_bg);
+                let inner = block.inner(area);
+                frame.render_widget(block, area);
+                inner
+            }
+            None => area,
+        };
+        let (label_width, label_height) = self.label_size();
+        let table = Rect {
+            x: area.x + label_width,
+            y: area.y + label_height,
+            width: area.width.saturating_sub(label_width),
+            height: area.height.saturating_sub(label_height),
+        };
+        let style = |current: bool| match current {
+            true => Style::new().fg(self.colors.selected_column_style_fg),
+            false => Style::new().dark_gray(),
+        };
+        let viewport = self.viewport;
+        let buffer = frame.buffer_mut();
+        for i in 0..viewport.cols {
+            let col = viewport.col + i;
+            let x = table.x + i as u16 * (scale.cell_width + scale.spacing);
+            let name = column_name(col);
+            let padding = scale.cell_width.saturating_sub(name.len() as u16) / 2;
+            if x + padding < table.right() {
+                let current = col == self.placement.col;
+                let width = usize::from(scale.cell_width);
+                buffer.set_stringn(x + padding, area.y, name, width, style(current));
+            }
+        }
+        for i in 0..viewport.rows {
+            let row = viewport.row + i;
+            let y = table.y + i as u16 * scale.cell_height + scale.cell_height / 2;
+            if y < table.bottom() {
+                // with gravity only a column is chosen
+                let current = row == self.placement.row && !self.rules.gravity;
+                let number = format!("{:>1$}", row + 1, usize::from(label_width - 1));
+                buffer.set_string(area.x, y, number, style(current));
+            }
+        }
+        self.render_table(frame, table, scale);
+        table
+    }
+
+    pub fn render_coin_flip(
+        &mut self,
+        frame: &mut Frame,
+        area: Rect,
+        started: Instant,
+        winner: Player,
+    ) {
+        let elapsed = started.elapsed();
+        let (coin, caption) = if elapsed + COIN_FRAME * 3 >= COIN_FLIP_DURATION {
+            // hold the final face for a moment so the result can be read
+            match winner {
+                Player::Human => ("●", "You go first!"),
+                Player::Bot => ("○", "Bot goes first!"),
+            }
+        } else {
+            let frame_index = (elapsed.as_millis() / COIN_FRAME.as_millis()) as usize;
+            (
+                COIN_FRAMES[frame_index % COIN_FRAMES.len()],
+                "Flipping a coin…",
+            )
+        };
+        let text = Text::from(vec![
+            Line::from(""),
+            Line::from(""),
+            Line::from(coin)
+                .bold()
+                .fg(self.colors.selected_cell_style_fg),
+            Line::from(""),
+            Line::from(caption).fg(self.colors.row_fg),
+        ])
+        .centered();
+
+        frame.render_widget(Paragraph::new(text).bg(self.colors.buffer_bg), area);
+    }
+
+    /// The `rows` of the title in large letters, with a wave of the cursor's color running
+    /// through them.
+    pub fn render_banner(&self, frame: &mut Frame, area: Rect, rows: [String; 2]) {
+        let phase = self.opened.elapsed().as_secs_f32() * WAVE_SPEED;
+        let lines: Vec<Line> = rows
+            .iter()
+            .map(|row| {
+                let spans: Vec<Span> = row
+                    .chars()
+                    .enumerate()
+                    .map(|(x, c)| {
+                        let amount = ((phase - x as f32 * WAVE_STRETCH).sin() + 1.0) / 2.0;
+                        let color = blend(
+                            self.colors.row_fg,
+                            self.colors.selected_cell_style_fg,
+                            amount,
+                        );
+                        c.to_string().fg(color)
+                    })
+                    .collect();
+                Line::from(spans)
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(lines).centered(), area);
+    }
+
+    pub fn render_clocks(&mut self, frame: &mut Frame, area: Rect) {
+        let clock = |player: Player, label: &str| {
+            let left = self.clock_left(player).unwrap_or_default().as_secs();
+            let text = format!("{label} {}:{:02}", left / 60, left % 60);
+            if self.game_state == GameState::Playing && self.turn == player {
+                text.fg(self.colors.selected_cell_style_fg).bold()
+            } else {
+                text.fg(self.colors.row_fg)
+            }
+        };
+        let clocks = Line::from(vec![
+            clock(Player::Human, "You"),
+            "   ".into(),
+            clock(Player::Bot, "Bot"),
+        ]);
+
+        frame.render_widget(Paragraph::new(clocks).centered(), area);
+    }
+
+    pub fn render_score(&mut self, frame: &mut Frame, area: Rect) {
+        let lines = [
+            ("Wins", self.score.wins),
+            ("Losses", self.score.losses),
+            ("Draws", self.score.draws),
+        ]
+        .map(|(label, count)| Line::from(format!("{label:<7}{count:>3}")));
+        let mut lines = lines.to_vec();
+        if let Some(series) = &self.series {
+            let games = series
+                .results
+                .iter()
+                .map(|outcome| outcome.letter())
+                .collect::<String>();
+            lines.push(Line::from(format!(
+                "Bo{} {:>2}-{}",
+                series.best_of,
+                series.wins(Player::Human),
+                series.wins(Player::Bot)
+            )));
+            lines.push(Line::from(games).dark_gray());
+        }
+        let score = Paragraph::new(lines)
+            .block(
+                Block::bordered()
+                    .title("Score")
+                    .border_style(Style::default().fg(self.colors.selected_column_style_fg)),
+            )
+            .style(
+                Style::default()
+                    .fg(self.colors.row_fg)
+                    .bg(self.colors.buffer_bg),
+            );
+
+        frame.render_widget(score, area);
+    }
+
+    /// The series score, how long the human's moves take on average and the streaks of the
+    /// session.
+    pub fn render_stats(&self, frame: &mut Frame, area: Rect) {
+        let series = match &self.series {
+            Some(series) => format!(
+                "{}-{}",
+                series.wins(Player::Human),
+                series.wins(Player::Bot)
+            ),
+            None => "–".into(),
+        };
+        let average = self.stats.average_move_time().map_or_else(
+            || "–".into(),
+            |average| format!("{:.1}s", average.as_secs_f64()),
+        );
+        let streak = self.stats.streak.map_or_else(
+            || "–".into(),
+            |(outcome, length)| format!("{}{length}", outcome.letter()),
+        );
+        let lines = [
+            ("Series", series),
+            ("Avg move", average),
+            ("Streak", streak),
+            ("Best", format!("W{}", self.stats.best_streak)),
+        ]
+        .map(|(label, value)| Line::from(format!("{label:<9}{value:>6}")));
+        let stats = Paragraph::new(lines.to_vec())
+            .block(
+                Block::bordered()
+                    .title("Stats")
+                    .border_style(Style::default().fg(self.colors.selected_column_style_fg)),
+            )
+            .style(
+                Style::default()
+                    .fg(self.colors.row_fg)
+                    .bg(self.colors.buffer_bg),
+            );
+        frame.render_widget(stats, area);
+    }
+
+    /// The state of the game in a line; `versus` starts it with "You VS Bot", unless the
+    /// banner above says so already.
+    pub fn render_title(&mut self, frame: &mut Frame, area: Rect, versus: bool) {
+        let matchup = match versus {
+            true => format!("You VS Bot [{}]", self.opponent.label()),
+            false => format!("[{}]", self.opponent.label()),
+        };
+        let title = match self.game_state {
+            _ if self.confirm_restart => Line::from("Restart this game? (y/n)"),
+            _ if self.swap_offered() => Line::from("Take over the bot's opening move? (y/n)"),
+            GameState::CoinFlip { .. } => Line::from(matchup),
+            GameState::Playing => {
+                let rejected = self.invalid_move().map(|(_, [row, col])| {
+                    match self.items[row][col].is_empty() {
+                        true if self.rules.notakto => "That board is dead!",
+                        true if self.quantum.is_some() => "Pick a highlighted cell!",
+                        true => "Play in a highlighted board!",
+                        false => "That cell is taken!",
+                    }
+                });
+                let turn = match (self.turn, self.move_time_left()) {
+                    (Player::Human, _) if let Some(reason) = rejected => reason.fg(Color::Red),
+                    (Player::Human, Some(left)) => {
+                        // round up so the countdown reaches 0 exactly when time runs out
+                        let secs = left.as_millis().div_ceil(1000);
+                        format!("Your turn ({secs}s)").fg(self.colors.selected_cell_style_fg)
+                    }
+                    (Player::Human, None) => "Your turn".fg(self.colors.selected_cell_style_fg),
+                    (Player::Bot, _) => "Bot thinking…".fg(self.colors.row_fg),
+                };
+                let mut spans = vec![matchup.into(), " · ".dark_gray(), turn];
+                if self.rules.wrap {
+                    spans.push(" · edges wrap".dark_gray());
+                }
+                if let Some(order) = self.rules.order {
+                    let role = match order {
+                        Player::Human => " · you are Order",
+                        Player::Bot => " · you are Chaos",
+                    };
+                    spans.push(role.dark_gray());
+                }
+                if self.rules.wild && self.turn == Player::Human {
+                    let mark = self.symbols.display(self.wild_mark.into());
+                    spans.push(format!(" · placing {mark} (f)").dark_gray());
+                }
+                if let Some(board) = &self.quantum
+                    && self.turn == Player::Human
+                {
+                    let step = match (board.collapse_options(), self.quantum_pick) {
+                        (Some(_), _) => " · pick where the cycle collapses",
+                        (None, Some(_)) => " · pick a second cell",
+                        (None, None) => " · pick two cells",
+                    };
+                    spans.push(step.dark_gray());
+                }
+                Line::from(spans)
+            }
+            // the rematch prompt waits for the celebration to end
+            GameState::GameOver(outcome) if self.celebration().is_some() => {
+                Line::from(outcome.message().bold())
+            }
+            // decided games move on to the results screen, and those of a series to the next
+            GameState::GameOver(outcome) => Line::from(format!("{} Next game…", outcome.message())),
+        };
+        let title = Paragraph::new(title).centered();
+
+        frame.render_widget(title, area);
+    }
+
+    /// The keys that do something at this point of the game.
+    pub fn render_footer(&self, frame: &mut Frame, area: Rect) {
+        let hints = match self.game_state {
+            _ if let Some(entry) = &self.entry => {
+                format!("Cell: {entry}▏ · Enter place · Backspace erase · Esc cancel")
+            }
+            _ if self.confirm_restart => "y restart · any other key keeps playing".into(),
+            _ if self.swap_offered() => "y take over the move · n play on · q quit".into(),
+            _ if self.showing_results() => match self.results {
+                Results::Summary(_) => {
+                    "←/→ choose · Enter confirm · x reset score · ? help · q quit".into()
+                }
+                Results::Analysis { .. } => "↑/↓ scroll · Enter back · ? help · q quit".into(),
+            },
+            GameState::CoinFlip { .. } => "Flipping for the first move… · q quit".into(),
+            GameState::Playing => {
+                let mut hints = vec![match self.rules.gravity {
+                    true => "←/→/a/d column · Space drop",
+                    false => "wasd/←↑↓→ move · Space place",
+                }];
+                if self.rules.layers > 1 {
+                    hints.push("[/] layer");
+                }
+                if self.rules.wild {
+                    hints.push("f mark");
+                }
+                hints.push(": type a cell · h hint · u undo · r restart · ? help · q quit");
+                hints.join(" · ")
+            }
+            GameState::GameOver(_) if self.celebration().is_some() => "? help · q quit".into(),
+            GameState::GameOver(_) => "q quit".into(),
+        };
+        frame.render_widget(Paragraph::new(hints).centered().dark_gray(), area);
+    }
+
+    fn render_table(&mut self, frame: &mut Frame, area: Rect, scale: Scale) {
+        // with gravity only a column is chosen, so the row is not highlighted
+        let selected_row_style = match self.rules.gravity {
+            true => Style::default(),
+            false => Style::default()
+                .add_modifier(Modifier::REVERSED)
+                .fg(self.colors.selected_row_style_fg),
+        };
+        let selected_col_style = Style::default().fg(self.colors.selected_column_style_fg);
+        // the cursor pulses between two shades of the theme, both set apart from the row's, to
+        // be found at a glance on a large board
+        let beat = self.opened.elapsed().as_secs_f32() / PULSE_PERIOD.as_secs_f32();
+        let amount = ((beat * std::f32::consts::TAU).sin() + 1.0) / 2.0;
+        let selected_cell_style = Style::default().fg(self.colors.row_fg).bg(blend(
+            self.colors.selected_cell_style_fg,
+            self.colors.hint_cell_style_bg,
+            amount,
+        ));
+
+        // once someone has won, show the winning line instead of the cursor
+        let winner = match self.game_state {
+            GameState::GameOver(Outcome::Winner(_)) => winning_line(&self.items, self.rules),
+            _ => None,
+        };
+        let selected_cell_style = match self.flashing_invalid_cell() {
+            Some(cell) if cell == self.placement.cell() => selected_cell_style.bg(Color::Red),
+            _ => selected_cell_style,
+        };
+        let (selected_row_style, selected_col_style, selected_cell_style) = match winner {
+            Some(_) => Default::default(),
+            None => (selected_row_style, selected_col_style, selected_cell_style),
+        };
+
+        self.scale = Some(scale);
+        let t = grid::table(
+            self,
+            self.viewport,
+            scale.cell_width,
+            scale.cell_height,
+            &self.colors,
+        )
+        .column_spacing(scale.spacing)
+        .row_highlight_style(selected_row_style)
+        .column_highlight_style(selected_col_style)
+        .cell_highlight_style(selected_cell_style);
+
+        frame.render_stateful_widget(t, area, &mut self.state);
+        self.hitbox = Some(grid::Hitbox {
+            area,
+            viewport: self.viewport,
+            cell_width: scale.cell_width,
+            cell_height: scale.cell_height,
+            spacing: scale.spacing,
+        });
+    }
+
+    /// The `text` and `style` of `cell` told apart by more than color: an empty cell under the
+    /// cursor is hatched, and the player's marks are bold and the bot's dim.
+    pub fn patterned(&self, cell: [usize; 2], text: String, style: Style) -> (String, Style) {
+        let content = self.items[cell[0]][cell[1]];
+        if content == CellState::X {
+            (text, style.add_modifier(Modifier::BOLD))
+        } else if content == CellState::O {
+            (text, style.add_modifier(Modifier::DIM))
+        } else if text.is_empty()
+            && self.game_state == GameState::Playing
+            && self.target_cell() == Some(cell)
+        {
+            let (width, height) = self
+                .scale
+                .map_or((1, 1), |scale| (scale.cell_width, scale.cell_height));
+            let line = HATCH.repeat(usize::from(width));
+            (vec![line; usize::from(height)].join("\n"), style)
+        } else {
+            (text, style)
+        }
+    }
+
+    /// Scatter confetti over the board in `area`, anew on every frame, leaving the winning
+    /// line uncovered.
+    pub fn render_confetti(&self, frame: &mut Frame, area: Rect) {
+        if area.is_empty() {
+            return;
+        }
+        let line = winning_line(&self.items, self.rules).map_or_else(Vec::new, |(_, line)| line);
+        let on_line = |x, y| {
+            self.hitbox
+                .and_then(|hitbox| hitbox.cell_at(x, y))
+                .is_some_and(|cell| line.contains(&cell))
+        };
+        let mut rng = rand::rng();
+        let buffer = frame.buffer_mut();
+        for _ in 0..area.area() / CONFETTI_SPARSENESS {
+            let x = rng.random_range(area.left()..area.right());
+            let y = rng.random_range(area.top()..area.bottom());
+            if on_line(x, y) {
+                continue;
+            }
+            let glyph = CONFETTI.choose(&mut rng).unwrap_or(&"*");
+            let color = CONFETTI_COLORS.choose(&mut rng).unwrap_or(&Color::White);
+            buffer[(x, y)].set_symbol(glyph).set_fg(*color);
+        }
+    }
+
+    /// The cell of a rejected move while its flash is showing.
+    pub fn flashing_invalid_cell(&self) -> Option<[usize; 2]> {
+        self.invalid_move()
+            .filter(|(effect, _)| effect.flash_on())
+            .map(|(_, cell)| cell)
+    }
+
+    /// The spooky marks superposed on `cell` of a quantum `board`, three to a line.
+    pub fn spooky_text(&self, board: &quantum::Board, cell: [usize; 2]) -> String {
+        let marks: Vec<_> = board
+            .spooky(cell)
+            .into_iter()
+            .map(|(player, subscript)| {
+                format!("{}{subscript}", self.symbols.display(player.into()))
+            })
+            .collect();
+        let lines: Vec<_> = marks.chunks(3).map(|line| line.join(" ")).collect();
+        lines.join("\n")
+    }
+
+    /// The background of `cell` in ultimate tic-tac-toe, telling its sub-board apart from the
+    /// neighbouring ones.
+    ///
+    /// Won sub-boards take the winner's color, decided ones are dimmed and `open_boards` stand
+    /// out from the rest.
+    pub fn sub_board_style(&self, cell: [usize; 2], open_boards: &[[usize; 2]]) -> Style {
+        let board = ultimate::sub_board_of(cell);
+        let outcome = ultimate::sub_board_outcome(&self.items, board);
+        let bg = match outcome {
+            Some(Outcome::Winner(Player::Human)) => self.colors.human_board_bg,
+            Some(Outcome::Winner(Player::Bot)) => self.colors.bot_board_bg,
+            _ if open_boards.contains(&board) => self.colors.open_board_bg,
+            _ if (board[0] + board[1]).is_multiple_of(2) => self.colors.normal_row_color,
+            _ => self.colors.alt_row_color,
+        };
+        let style = Style::default().bg(bg);
+        match outcome {
+            Some(_) => style.add_modifier(Modifier::DIM),
+            None => style,
+        }
+    }
+}
+
+/// Width of the stats panel, including its border.
+pub const STATS_WIDTH: u16 = 17;
+
+/// The cell size used when zoomed out to see more of a large board.
+pub const ZOOMED_OUT_CELL: (u16, u16) = (2, 1);
+
+/// How many cells along each axis must fit on screen before the board is shown at all.
+pub const MIN_VISIBLE_CELLS: usize = 3;
+
+/// How many cells along each axis the board shrinks to keep in view before it scrolls
+/// instead.
+const SCALED_VISIBLE_CELLS: usize = 9;
+
+/// The cell sizes a board shrinks through when the screen is too small for it, largest first.
+const CELL_SIZES: [(u16, u16); 7] = [(9, 3), (7, 3), (6, 3), (4, 1), (3, 1), (2, 1), (1, 1)];
+
+/// Large drawings of the marks, each its lines of equal width, largest first.
+const MARK_ART: [(&str, [&str; 3]); 4] = [
+    ("X", ["╲   ╱", "  ╳  ", "╱   ╲"]),
+    ("O", ["╭───╮", "│   │", "╰───╯"]),
+    ("X", ["╲ ╱", " ╳ ", "╱ ╲"]),
+    ("O", ["╭─╮", "│ │", "╰─╯"]),
+];
+
+/// The largest drawing of `mark` that fits in a cell of `scale`, if there is one.
+pub fn mark_art(mark: &str, scale: Scale) -> Option<String> {
+    MARK_ART
+        .iter()
+        .find(|(art_mark, lines)| {
+            *art_mark == mark
+                && lines.len() <= usize::from(scale.cell_height)
+                && lines[0].width() <= usize::from(scale.cell_width)
+        })
+        .map(|(_, lines)| lines.join("\n"))
+}
+
+/// How large the cells of a board are drawn, and how far apart its columns are set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Scale {
+    pub cell_width: u16,
+    cell_height: u16,
+    spacing: u16,
+}
+
+impl Scale {
+    /// The largest scale, no larger than `preferred`, showing up to [`SCALED_VISIBLE_CELLS`]
+    /// of a `rows`×`cols` board along each axis in `width`×`height`. Below that the columns
+    /// lose the space between them, and the board is left to scroll as long as at least
+    /// [`MIN_VISIBLE_CELLS`] still fit; `None` if not even those do.
+    pub fn fitting(
+        width: u16,
+        height: u16,
+        (rows, cols): (usize, usize),
+        preferred: (u16, u16),
+    ) -> Option<Self> {
+        let wanted = (
+            rows.min(SCALED_VISIBLE_CELLS),
+            cols.min(SCALED_VISIBLE_CELLS),
+        );
+        let needed = (rows.min(MIN_VISIBLE_CELLS), cols.min(MIN_VISIBLE_CELLS));
+        let scales = CELL_SIZES
+            .into_iter()
+            .filter(|&(cell_width, cell_height)| {
+                cell_width <= preferred.0 && cell_height <= preferred.1
+            })
+            .map(|(cell_width, cell_height)| Self {
+                cell_width,
+                cell_height,
+                spacing: 1,
+            })
+            .chain([Self {
+                cell_width: 1,
+                cell_height: 1,
+                spacing: 0,
+            }]);
+        let mut smallest = None;
+        for scale in scales {
+            let (view_rows, view_cols) = scale.cells_fitting(width, height);
+            if view_rows >= wanted.0 && view_cols >= wanted.1 {
+                return Some(scale);
+            }
+            smallest = Some(scale);
+        }
+        smallest.filter(|scale| {
+            let (view_rows, view_cols) = scale.cells_fitting(width, height);
+            view_rows >= needed.0 && view_cols >= needed.1
+        })
+    }
+
+    /// How many rows and columns of cells fit in `width`×`height`.
+    const fn cells_fitting(self, width: u16, height: u16) -> (usize, usize) {
+        (
+            cells_fitting(height, self.cell_height, 0),
+            cells_fitting(width, self.cell_width, self.spacing),
+        )
+    }
+
+    /// The width and height of `rows`×`cols` cells.
+    const fn extent(self, rows: usize, cols: usize) -> (u16, u16) {
+        (
+            table_extent(cols, self.cell_width, self.spacing),
+            table_extent(rows, self.cell_height, 0),
+        )
+    }
+}
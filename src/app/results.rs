@@ -0,0 +1,308 @@
+//! The screen shown once a game of marks in a row is over: the outcome, how long the moves
+//! took and how each compared with the best one.
+
+use std::{time::Duration, vec};
+
+use crossterm::event::KeyCode;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Flex, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Paragraph},
+};
+use unicode_width::UnicodeWidthStr;
+
+use super::{App, GameState, render::Scale, score::Series};
+use crate::{
+    bot,
+    game::{
+        Game,
+        board::{CellState, Outcome, Player, cell_name},
+    },
+    ui::{banner, layout::cell_size},
+};
+
+/// Width of the move timing panel of the results screen, including its border.
+const TIMING_WIDTH: u16 = 20;
+
+/// Width of the move analysis panel of the results screen, including its border.
+const ANALYSIS_WIDTH: u16 = 30;
+
+/// The buttons of the screen shown once a game is over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResultButton {
+    Rematch,
+    Analysis,
+    Menu,
+}
+
+impl ResultButton {
+    const ALL: [Self; 3] = [Self::Rematch, Self::Analysis, Self::Menu];
+
+    const fn label(self) -> &'static str {
+        match self {
+            Self::Rematch => "Rematch",
+            Self::Analysis => "Analysis",
+            Self::Menu => "Menu",
+        }
+    }
+}
+
+/// What the screen shown once a game is over holds beside the final board.
+#[derive(Debug, Clone)]
+pub enum Results {
+    /// How long the moves took, above the buttons with the given one selected.
+    Summary(ResultButton),
+    /// How each move compares with the best one, one line per move, scrolled down by `offset`
+    /// lines.
+    Analysis {
+        lines: Vec<Line<'static>>,
+        offset: usize,
+    },
+}
+
+impl App {
+    /// Whether the screen shown once the game is over is up, which waits for the celebration
+    /// to end and is skipped between the games of a series.
+    pub fn showing_results(&self) -> bool {
+        matches!(self.game_state, GameState::GameOver(_))
+            && self.celebration().is_none()
+            && self
+                .series
+                .as_ref()
+                .is_none_or(|series| series.winner().is_some())
+    }
+
+    /// Choose among the buttons of the results screen and press them, or scroll the analysis
+    /// and go back from it.
+    pub fn handle_results_key(&mut self, code: KeyCode) {
+        match &mut self.results {
+            Results::Summary(selected) => {
+                let index = ResultButton::ALL
+                    .iter()
+                    .position(|button| button == selected)
+                    .unwrap_or(0);
+                match code {
+                    KeyCode::Char('a') | KeyCode::Left => {
+                        *selected = ResultButton::ALL[index.saturating_sub(1)];
+                    }
+                    KeyCode::Char('d') | KeyCode::Right => {
+                        *selected = ResultButton::ALL[(index + 1).min(ResultButton::ALL.len() - 1)];
+                    }
+                    KeyCode::Char(' ') | KeyCode::Enter => match *selected {
+                        ResultButton::Rematch => self.rematch(),
+                        ResultButton::Analysis => {
+                            self.results = Results::Analysis {
+                                lines: self.analysis(),
+                                offset: 0,
+                            };
+                        }
+                        ResultButton::Menu => self.leaving = true,
+                    },
+                    _ => {}
+                }
+            }
+            Results::Analysis { lines, offset } => match code {
+                KeyCode::Char('w') | KeyCode::Up => *offset = offset.saturating_sub(1),
+                KeyCode::Char('s') | KeyCode::Down => {
+                    *offset = (*offset + 1).min(lines.len().saturating_sub(1));
+                }
+                KeyCode::Char(' ') | KeyCode::Enter | KeyCode::Backspace => {
+                    self.results = Results::Summary(ResultButton::Analysis);
+                }
+                _ => {}
+            },
+        }
+    }
+
+    /// Every move of the finished game, with how each of the human's compares with the best
+    /// one the minimax engine finds from the same position: the best, as good an outcome a
+    /// little slower or faster, or a mistake giving away a better outcome.
+    fn analysis(&self) -> Vec<Line<'static>> {
+        if self.quantum.is_some() {
+            return vec![Line::from("Quantum games can't be analysed.")];
+        }
+        let mut items = vec![vec![CellState::Empty; self.cols]; self.rows];
+        let mut last = None;
+        let mut lines = Vec::new();
+        for (i, mv) in self.moves().enumerate() {
+            let who = match mv.player {
+                Player::Human => "You",
+                Player::Bot => "Bot",
+            };
+            let mut line = Line::from(format!(
+                "{:>2}. {who} {:<4}{:>6.1}s ",
+                i + 1,
+                cell_name([mv.row, mv.col]),
+                mv.took.as_secs_f64()
+            ));
+            // an opening move taken over under the pie rule wasn't chosen by its new owner
+            let taken_over = self.rules.swap && i == 0;
+            if mv.player == Player::Human && !taken_over {
+                let scores = bot::evaluate_moves(&items, self.rules, Player::Human, last);
+                let best = scores.iter().map(|&(_, score)| score).max();
+                let played = scores
+                    .iter()
+                    .find(|(placement, _)| {
+                        placement.cell == [mv.row, mv.col] && placement.mark == mv.mark
+                    })
+                    .map(|&(_, score)| score);
+                line.push_span(match played.zip(best) {
+                    Some((played, best)) if played == best => {
+                        "best".fg(self.colors.selected_cell_style_fg)
+                    }
+                    Some((played, best)) if played.signum() == best.signum() => "good".into(),
+                    Some(_) => "mistake".fg(Color::Red),
+                    None => "–".into(),
+                });
+            }
+            lines.push(line);
+            items[mv.row][mv.col] = mv.mark.into();
+            last = Some([mv.row, mv.col]);
+        }
+        lines
+    }
+
+    /// How long the moves of the game took: all of them together, and the human's on average,
+    /// at the fastest and at the slowest.
+    fn timing(&self) -> Vec<Line<'static>> {
+        let seconds = |time: Option<Duration>| {
+            time.map_or_else(|| "–".into(), |time| format!("{:.1}s", time.as_secs_f64()))
+        };
+        let yours: Vec<Duration> = self
+            .moves()
+            .filter(|mv| mv.player == Player::Human)
+            .map(|mv| mv.took)
+            .collect();
+        let average =
+            (!yours.is_empty()).then(|| yours.iter().sum::<Duration>() / yours.len() as u32);
+        let total = self.moves().map(|mv| mv.took).sum();
+        [
+            ("Moves", self.moves().count().to_string()),
+            ("Yours", yours.len().to_string()),
+            ("Average", seconds(average)),
+            ("Fastest", seconds(yours.iter().min().copied())),
+            ("Slowest", seconds(yours.iter().max().copied())),
+            ("Game time", seconds(Some(total))),
+        ]
+        .map(|(label, value)| Line::from(format!("{label:<10}{value:>8}")))
+        .to_vec()
+    }
+
+    /// The screen shown once the game is over: the outcome in large letters, the final board
+    /// beside how long the moves took or how good they were, and the buttons below.
+    pub fn render_results(&mut self, frame: &mut Frame, area: Rect) {
+        let GameState::GameOver(outcome) = self.game_state else {
+            return;
+        };
+        let [banner_area, middle, buttons_area] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+        ])
+        .spacing(1)
+        .areas(area);
+        self.render_outcome(frame, banner_area, outcome);
+
+        let (title, lines, offset, panel_width, selected) = match &self.results {
+            Results::Summary(selected) => ("Timing", self.timing(), 0, TIMING_WIDTH, *selected),
+            Results::Analysis { lines, offset } => (
+                "Analysis",
+                lines.clone(),
+                *offset,
+                ANALYSIS_WIDTH,
+                ResultButton::Analysis,
+            ),
+        };
+        let panel_height = (lines.len() as u16 + 2).min(middle.height);
+        let panel = Paragraph::new(lines)
+            .scroll((offset as u16, 0))
+            .block(
+                Block::bordered()
+                    .title(title)
+                    .border_style(Style::default().fg(self.colors.selected_column_style_fg)),
+            )
+            .style(
+                Style::default()
+                    .fg(self.colors.row_fg)
+                    .bg(self.colors.buffer_bg),
+            );
+
+        // the board goes on the panel's left, as large as fits
+        let (rows, cols) = self.dimensions();
+        let (frame_width, frame_height) = self.frame_size();
+        let board_width = middle.width.saturating_sub(panel_width + 2 + frame_width);
+        let board_height = middle.height.saturating_sub(frame_height);
+        let board = Scale::fitting(
+            board_width,
+            board_height,
+            (rows, cols),
+            cell_size(rows.max(cols)),
+        )
+        .map(|scale| (scale, self.fit_viewport(scale, board_width, board_height)));
+        let (table_width, table_height) = board.map_or((0, 0), |(_, (width, height))| {
+            (width + frame_width, height + frame_height)
+        });
+        let gap = if board.is_some() { 2 } else { 0 };
+        let [block] = Layout::horizontal([Constraint::Length(table_width + gap + panel_width)])
+            .flex(Flex::Center)
+            .areas(middle);
+        let [table_area, _, panel_area] = Layout::horizontal([
+            Constraint::Length(table_width),
+            Constraint::Length(gap),
+            Constraint::Length(panel_width),
+        ])
+        .areas(block);
+        if let Some((scale, _)) = board {
+            let [table_area] = Layout::vertical([Constraint::Length(table_height)])
+                .flex(Flex::Center)
+                .areas(table_area);
+            self.render_board(frame, table_area, scale);
+        }
+        let [panel_area] = Layout::vertical([Constraint::Length(panel_height)])
+            .flex(Flex::Center)
+            .areas(panel_area);
+        frame.render_widget(panel, panel_area);
+
+        self.render_buttons(frame, buttons_area, selected);
+    }
+
+    /// The outcome of the game in large letters when they fit, above the result in words.
+    fn render_outcome(&self, frame: &mut Frame, area: Rect, outcome: Outcome) {
+        let (big, color) = match outcome {
+            Outcome::Winner(Player::Human) => ("you win", self.colors.selected_cell_style_fg),
+            Outcome::Winner(Player::Bot) => ("you lose", Color::Red),
+            Outcome::Draw => ("draw", self.colors.row_fg),
+        };
+        let caption = match self.series.as_ref().and_then(Series::winner) {
+            Some(Player::Human) => "You win the series!",
+            Some(Player::Bot) => "Bot wins the series!",
+            None => outcome.message(),
+        };
+        let mut lines = match banner::lines(big) {
+            Some(rows) if rows[0].width() <= usize::from(area.width) => {
+                rows.map(|row| Line::from(row).fg(color)).to_vec()
+            }
+            _ => Vec::new(),
+        };
+        lines.push(Line::from(caption).bold());
+        frame.render_widget(Paragraph::new(lines).centered(), area);
+    }
+
+    /// The buttons of the results screen side by side, with `selected` standing out.
+    fn render_buttons(&self, frame: &mut Frame, area: Rect, selected: ResultButton) {
+        let mut spans: Vec<Span> = Vec::new();
+        for button in ResultButton::ALL {
+            if !spans.is_empty() {
+                spans.push("  ".into());
+            }
+            let label = format!("[ {} ]", button.label());
+            spans.push(match button == selected {
+                true => label.fg(self.colors.selected_cell_style_fg).reversed(),
+                false => label.fg(self.colors.row_fg),
+            });
+        }
+        frame.render_widget(Paragraph::new(Line::from(spans)).centered(), area);
+    }
+}
@@ -0,0 +1,113 @@
+//! Games of marks in a row kept in the save file, to be played on in a later run.
+
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    App, FirstMove, GameState,
+    clock::Clocks,
+    command::Command,
+    score::{Score, Series, SessionStats},
+};
+use crate::{
+    bot,
+    game::board::{CellState, Outcome, Player, Rules},
+    options::{GameMode, Session},
+};
+
+/// A game of marks in a row as the save file holds it: the board, whose turn it is, the clocks
+/// and the score.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedBoard {
+    pub game: GameMode,
+    rows: usize,
+    cols: usize,
+    rules: Rules,
+    cells: Vec<Vec<CellState>>,
+    turn: Player,
+    starter: Player,
+    /// How the game ended, if it has.
+    outcome: Option<Outcome>,
+    opponent: bot::Opponent,
+    first_move: FirstMove,
+    history: Vec<Command>,
+    score: Score,
+    stats: SessionStats,
+    series: Option<Series>,
+    move_time_limit: Option<Duration>,
+    /// How long the player to move had been thinking.
+    turn_time: Duration,
+    clock_time: Option<Duration>,
+    clocks: Option<Clocks>,
+    swap_pending: bool,
+    wild_mark: Player,
+}
+
+impl SavedBoard {
+    /// The game saved, set up to be played on from where it was left.
+    pub fn restore(self) -> (GameMode, Session) {
+        let game = self.game;
+        let mut app = App::new()
+            .with_board_size(self.rows, self.cols)
+            .with_rules(self.rules)
+            .with_name(game.name());
+        app.items = self.cells;
+        app.turn = self.turn;
+        app.starter = self.starter;
+        app.game_state = self.outcome.map_or(GameState::Playing, GameState::GameOver);
+        app.opponent = self.opponent;
+        app.first_move = self.first_move;
+        app.history = self.history;
+        app.score = self.score;
+        app.stats = self.stats;
+        app.series = self.series;
+        app.move_time_limit = self.move_time_limit;
+        let now = Instant::now();
+        app.turn_started = now.checked_sub(self.turn_time).unwrap_or(now);
+        app.clock_time = self.clock_time;
+        app.clocks = self.clocks;
+        app.swap_pending = self.swap_pending;
+        app.wild_mark = self.wild_mark;
+        (game, Session::Board(Box::new(app)))
+    }
+}
+
+impl App {
+    /// The game as the save file holds it, as the `game` it is; quantum games can't be saved.
+    pub fn snapshot(&self, game: GameMode) -> Option<SavedBoard> {
+        if self.quantum.is_some() {
+            return None;
+        }
+        // a coin still in the air has already picked who opens
+        let turn = match self.game_state {
+            GameState::CoinFlip { winner, .. } => winner,
+            _ => self.turn,
+        };
+        Some(SavedBoard {
+            game,
+            rows: self.rows,
+            cols: self.cols,
+            rules: self.rules,
+            cells: self.items.clone(),
+            turn,
+            starter: self.starter,
+            outcome: match self.game_state {
+                GameState::GameOver(outcome) => Some(outcome),
+                _ => None,
+            },
+            opponent: self.opponent,
+            first_move: self.first_move,
+            history: self.history.clone(),
+            score: self.score,
+            stats: self.stats.clone(),
+            series: self.series.clone(),
+            move_time_limit: self.move_time_limit,
+            turn_time: self.paused.unwrap_or_else(Instant::now) - self.turn_started,
+            clock_time: self.clock_time,
+            clocks: self.clocks,
+            swap_pending: self.swap_pending,
+            wild_mark: self.wild_mark,
+        })
+    }
+}
@@ -0,0 +1,106 @@
+//! The score kept over a session: the games won, lost and drawn, the streaks and the series.
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::game::board::{Outcome, Player};
+
+/// Results of the games played since the session started.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Score {
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+}
+
+impl Score {
+    /// The counter tracking games that ended with `outcome`.
+    pub const fn tally(&mut self, outcome: Outcome) -> &mut u32 {
+        match outcome {
+            Outcome::Winner(Player::Human) => &mut self.wins,
+            Outcome::Winner(Player::Bot) => &mut self.losses,
+            Outcome::Draw => &mut self.draws,
+        }
+    }
+}
+
+/// How the human has been playing over the games of the session, for the stats panel.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionStats {
+    /// How long the human took over each of their moves.
+    pub move_times: Vec<Duration>,
+    /// The outcome the latest games ended with, and how many of them in a row did.
+    pub streak: Option<(Outcome, u32)>,
+    /// The most games the human won in a row.
+    pub best_streak: u32,
+    /// The streaks as they were before each game was counted, for taking it back.
+    #[serde(default)]
+    earlier: Vec<(Option<(Outcome, u32)>, u32)>,
+}
+
+impl SessionStats {
+    /// Count a game that ended with `outcome` towards the streaks.
+    pub fn record(&mut self, outcome: Outcome) {
+        self.earlier.push((self.streak, self.best_streak));
+        let length = match self.streak {
+            Some((last, length)) if last == outcome => length + 1,
+            _ => 1,
+        };
+        self.streak = Some((outcome, length));
+        if outcome == Outcome::Winner(Player::Human) {
+            self.best_streak = self.best_streak.max(length);
+        }
+    }
+
+    /// Take back the game counted last, leaving the streaks as they were before it.
+    pub fn unrecord(&mut self) {
+        if let Some((streak, best_streak)) = self.earlier.pop() {
+            self.streak = streak;
+            self.best_streak = best_streak;
+        }
+    }
+
+    /// How long the human's moves took on average, once they have made one.
+    pub fn average_move_time(&self) -> Option<Duration> {
+        let total: Duration = self.move_times.iter().sum();
+        Some(
+            total
+                / u32::try_from(self.move_times.len())
+                    .ok()
+                    .filter(|&n| n > 0)?,
+        )
+    }
+}
+
+/// A best-of-N match played as a sequence of games.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Series {
+    pub best_of: u32,
+    pub results: Vec<Outcome>,
+}
+
+impl Series {
+    pub const fn new(best_of: u32) -> Self {
+        Self {
+            best_of,
+            results: Vec::new(),
+        }
+    }
+
+    /// Number of games `player` has won in this series.
+    pub fn wins(&self, player: Player) -> u32 {
+        self.results
+            .iter()
+            .filter(|&&outcome| outcome == Outcome::Winner(player))
+            .count() as u32
+    }
+
+    /// The player who has won a majority of the `best_of` games, if any.
+    pub fn winner(&self) -> Option<Player> {
+        let needed = self.best_of / 2 + 1;
+        [Player::Human, Player::Bot]
+            .into_iter()
+            .find(|&player| self.wins(player) >= needed)
+    }
+}
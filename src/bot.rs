@@ -0,0 +1,676 @@
+//! Opponents that choose the bot's moves.
+
+use std::{
+    sync::mpsc::{self, Receiver, TryRecvError},
+    thread,
+    time::{Duration, Instant},
+};
+
+use rand::seq::IteratorRandom;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    engine::{Board, Move},
+    game::{
+        board::{CellState, Outcome, Player, Rules, dead_boards, legal_moves, winning_lines},
+        quantum, ultimate,
+    },
+};
+
+/// How long the bot appears to think before placing its mark.
+pub const BOT_DELAY: Duration = Duration::from_millis(400);
+
+/// Roughly how many board cells a search may scan before it stops looking further ahead.
+///
+/// Visiting a position costs about one scan of the board, so larger boards are searched less
+/// deeply.
+const WORK_BUDGET: usize = 4_500_000;
+
+/// Boards with more cells than this only search moves next to existing marks.
+const LARGE_BOARD_CELLS: usize = 81;
+
+/// How many plies the heuristic bot looks ahead: its own move and the reply to it.
+const HEURISTIC_DEPTH: usize = 2;
+
+/// An opponent for games of marks in a row.
+pub trait Bot {
+    /// Choose a move for the side to move on `board`.
+    ///
+    /// Returns `None` when the board is full or the game is over.
+    fn choose_move(&mut self, board: &Board) -> Option<Move>;
+}
+
+/// Plays any legal move.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RandomBot;
+
+impl Bot for RandomBot {
+    fn choose_move(&mut self, board: &Board) -> Option<Move> {
+        let player = board.to_move()?;
+        random_move(board.cells(), board.rules(), player, board.last())
+    }
+}
+
+/// Looks one move ahead: takes a win when it can, and otherwise picks at random among the moves
+/// that don't hand a win to the reply.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HeuristicBot;
+
+impl Bot for HeuristicBot {
+    fn choose_move(&mut self, board: &Board) -> Option<Move> {
+        let player = board.to_move()?;
+        let mut search = Search::new(board.cells(), board.rules(), board.last());
+        search.max_depth = HEURISTIC_DEPTH;
+        let win = (search.empty + 1) as i32 + search.evaluation_bound();
+        let scored: Vec<_> = search
+            .placements(player)
+            .into_iter()
+            .map(|placement| {
+                let score = search.score_move(placement, player, 1, win);
+                (placement, score)
+            })
+            .collect();
+        let best = scored.iter().map(|&(_, score)| score).max()?;
+        scored
+            .into_iter()
+            .filter(|&(_, score)| score == best)
+            .map(|(placement, _)| placement)
+            .choose(&mut rand::rng())
+    }
+}
+
+/// Searches the game tree with [`minimax`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MinimaxBot;
+
+impl Bot for MinimaxBot {
+    fn choose_move(&mut self, board: &Board) -> Option<Move> {
+        let player = board.to_move()?;
+        minimax(board.cells(), board.rules(), player, board.last())
+    }
+}
+
+/// The strategies the bot can play with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Opponent {
+    /// Plays any empty cell.
+    Random,
+    /// Plays the [`HeuristicBot`]. Games other than marks in a row have no medium bot of their
+    /// own and play their hard one.
+    Heuristic,
+    /// Searches the game tree; on classic 3x3 the whole tree is searched and the bot never loses.
+    Minimax,
+}
+
+impl Opponent {
+    /// Every opponent, from the weakest to the strongest.
+    pub const ALL: [Self; 3] = [Self::Random, Self::Heuristic, Self::Minimax];
+
+    /// Short label shown next to the title.
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Random => "easy",
+            Self::Heuristic => "medium",
+            Self::Minimax => "hard",
+        }
+    }
+
+    /// The opponent selected after this one.
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Random => Self::Heuristic,
+            Self::Heuristic => Self::Minimax,
+            Self::Minimax => Self::Random,
+        }
+    }
+
+    /// The bot that plays games of marks in a row at this difficulty.
+    pub fn bot(self) -> Box<dyn Bot> {
+        match self {
+            Self::Random => Box::new(RandomBot),
+            Self::Heuristic => Box::new(HeuristicBot),
+            Self::Minimax => Box::new(MinimaxBot),
+        }
+    }
+
+    /// Decide whether `player`, second to move under the pie rule, takes over the opening move.
+    ///
+    /// The minimax opponent swaps when the search rates the opening side above the other one;
+    /// the others toss a coin.
+    pub fn choose_swap(
+        self,
+        items: &[Vec<CellState>],
+        rules: Rules,
+        player: Player,
+        last: Option<[usize; 2]>,
+    ) -> bool {
+        match self {
+            Self::Random | Self::Heuristic => rand::random(),
+            Self::Minimax => {
+                let best = |items: &[Vec<CellState>], player| {
+                    evaluate_moves(items, rules, player, last)
+                        .into_iter()
+                        .map(|(_, score)| score)
+                        .max()
+                        .unwrap_or(0)
+                };
+                let mut swapped = items.to_vec();
+                for cell in swapped.iter_mut().flatten() {
+                    if cell.owner() == Some(player.other()) {
+                        *cell = player.into();
+                    }
+                }
+                // after a swap the other side moves, so their best outcome is our worst
+                -best(&swapped, player.other()) > best(items, player)
+            }
+        }
+    }
+
+    /// Choose the cell the pending cycle of a quantum `board` collapses into, as `player`.
+    ///
+    /// The heuristic and minimax opponents take a collapse that wins outright if there is one,
+    /// and otherwise avoid one that loses.
+    pub fn choose_collapse(self, board: &quantum::Board, player: Player) -> Option<[usize; 2]> {
+        let options = board.collapse_options()?;
+        match self {
+            Self::Random => options.into_iter().choose(&mut rand::rng()),
+            Self::Heuristic | Self::Minimax => options.into_iter().max_by_key(|&cell| {
+                let mut board = board.clone();
+                board.collapse(cell);
+                quantum_score(&board, player)
+            }),
+        }
+    }
+
+    /// Choose the pair of cells `player` superposes their next quantum move over.
+    ///
+    /// The heuristic and minimax opponents look one move ahead: they close a cycle when every collapse the
+    /// other side may pick wins, and never close one they could collapse into a loss.
+    pub fn choose_quantum_move(
+        self,
+        board: &quantum::Board,
+        player: Player,
+    ) -> Option<[[usize; 2]; 2]> {
+        let free = board.free_cells();
+        if let [cell] = free[..] {
+            return Some([cell, cell]);
+        }
+        let moves = free
+            .iter()
+            .enumerate()
+            .flat_map(|(i, &first)| free[i + 1..].iter().map(move |&second| [first, second]));
+        match self {
+            Self::Random => moves.choose(&mut rand::rng()),
+            Self::Heuristic | Self::Minimax => {
+                let scored: Vec<_> = moves
+                    .map(|cells| {
+                        let mut board = board.clone();
+                        board.play(player, cells);
+                        // the other side picks the collapse that suits them best
+                        let score = match board.collapse_options() {
+                            Some(options) => options
+                                .into_iter()
+                                .map(|cell| {
+                                    let mut board = board.clone();
+                                    board.collapse(cell);
+                                    quantum_score(&board, player)
+                                })
+                                .min()
+                                .unwrap_or(0),
+                            None => quantum_score(&board, player),
+                        };
+                        (cells, score)
+                    })
+                    .collect();
+                let best = scored.iter().map(|&(_, score)| score).max()?;
+                scored
+                    .into_iter()
+                    .filter(|&(_, score)| score == best)
+                    .map(|(cells, _)| cells)
+                    .choose(&mut rand::rng())
+            }
+        }
+    }
+}
+
+/// Score a quantum `board` for `player`: 1 for a win, -1 for a loss and 0 otherwise.
+fn quantum_score(board: &quantum::Board, player: Player) -> i32 {
+    match board.outcome() {
+        Some(Outcome::Winner(winner)) if winner == player => 1,
+        Some(Outcome::Winner(_)) => -1,
+        _ => 0,
+    }
+}
+
+/// Where the search of a [`Thinking`] bot has got to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Answer<T> {
+    /// Still searching, or waiting out the delay.
+    Pending,
+    Ready(T),
+    /// The search stopped without an answer, as when it panics; the game plays on without it
+    /// rather than wait for it forever.
+    Failed,
+}
+
+/// A move the bot is still working out on a thread of its own, so the screen goes on drawing
+/// and taking keys while it searches.
+#[derive(Debug)]
+pub struct Thinking<T> {
+    /// When the move may be played, so the one before it can be seen first.
+    ready_at: Instant,
+    answer: Receiver<T>,
+}
+
+impl<T: Send + 'static> Thinking<T> {
+    /// Start `search`, whose answer is played no sooner than [`BOT_DELAY`] from now.
+    pub fn start(search: impl FnOnce() -> T + Send + 'static) -> Self {
+        let (sender, answer) = mpsc::channel();
+        thread::spawn(move || {
+            // nobody is waiting any more once the game has started over
+            let _ = sender.send(search());
+        });
+        Self {
+            ready_at: Instant::now() + BOT_DELAY,
+            answer,
+        }
+    }
+
+    /// The answer, once the search has found it and the delay has passed.
+    pub fn answer(&self) -> Answer<T> {
+        if Instant::now() < self.ready_at {
+            return Answer::Pending;
+        }
+        match self.answer.try_recv() {
+            Ok(answer) => Answer::Ready(answer),
+            Err(TryRecvError::Empty) => Answer::Pending,
+            Err(TryRecvError::Disconnected) => Answer::Failed,
+        }
+    }
+}
+
+/// Pick a uniformly random legal move for `player`, with a random mark where they may choose.
+///
+/// Returns `None` when the board is full.
+pub fn random_move(
+    items: &[Vec<CellState>],
+    rules: Rules,
+    player: Player,
+    last: Option<[usize; 2]>,
+) -> Option<Move> {
+    let cell = legal_moves(items, rules, last)
+        .into_iter()
+        .choose(&mut rand::rng())?;
+    let mark = rules.marks(player).into_iter().choose(&mut rand::rng())?;
+    Some(Move { cell, mark })
+}
+
+/// Pick the best move for `player` by searching the game tree.
+///
+/// Faster wins and slower losses are preferred. Returns `None` when the board is full.
+pub fn minimax(
+    items: &[Vec<CellState>],
+    rules: Rules,
+    player: Player,
+    last: Option<[usize; 2]>,
+) -> Option<Move> {
+    evaluate_moves(items, rules, player, last)
+        .into_iter()
+        .max_by_key(|&(_, score)| score)
+        .map(|(placement, _)| placement)
+}
+
+/// Score every legal move for `player` under perfect play from both sides.
+///
+/// Positive scores win, negative scores lose and zero draws; the further from zero, the sooner
+/// the game ends. Boards too large to search to the end are searched as deep as
+/// `WORK_BUDGET` allows, treating undecided positions as draws, or on stacked boards judging
+/// them with `Search::evaluate`.
+pub fn evaluate_moves(
+    items: &[Vec<CellState>],
+    rules: Rules,
+    player: Player,
+    last: Option<[usize; 2]>,
+) -> Vec<(Move, i32)> {
+    let mut search = Search::new(items, rules, last);
+    let moves = search.placements(player);
+    let cells = items.len() * items[0].len();
+    let growth = if rules.gravity || rules.ultimate {
+        Branching::Constant
+    } else if cells > LARGE_BOARD_CELLS {
+        Branching::Growing
+    } else {
+        Branching::Shrinking
+    };
+    search.max_depth = search_depth(moves.len(), search.empty, growth, WORK_BUDGET / cells);
+    let win = (search.empty + 1) as i32 + search.evaluation_bound();
+    moves
+        .into_iter()
+        .map(|placement| (placement, search.score_move(placement, player, 1, win)))
+        .collect()
+}
+
+/// How the number of candidate moves changes as a game goes on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Branching {
+    /// Every move uses up one of the choices.
+    Shrinking,
+    /// Columns keep accepting marks until they fill up, as with gravity, or play is sent to a
+    /// sub-board of about the same size each time, as in ultimate tic-tac-toe.
+    Constant,
+    /// Every mark opens up the cells around it, as on large boards.
+    Growing,
+}
+
+/// How many plies can be searched from a position with `moves` candidate moves and `empty`
+/// free cells without visiting more than `budget` positions.
+fn search_depth(moves: usize, empty: usize, growth: Branching, budget: usize) -> usize {
+    let mut depth = 0;
+    let mut nodes = 1;
+    while depth < empty {
+        let branching = match growth {
+            Branching::Shrinking => moves.saturating_sub(depth).max(1),
+            Branching::Constant => moves,
+            Branching::Growing => moves + 2 * depth,
+        };
+        if nodes * branching > budget {
+            break;
+        }
+        nodes *= branching;
+        depth += 1;
+    }
+    depth.max(1)
+}
+
+/// The state of a game-tree search.
+struct Search {
+    board: Vec<Vec<CellState>>,
+    lines: Vec<Vec<[usize; 2]>>,
+    /// Indices into `lines` of the lines passing through each cell.
+    lines_through: Vec<Vec<Vec<usize>>>,
+    rules: Rules,
+    /// The move played just before the position being searched.
+    last: Option<[usize; 2]>,
+    empty: usize,
+    max_depth: usize,
+}
+
+impl Search {
+    fn new(items: &[Vec<CellState>], rules: Rules, last: Option<[usize; 2]>) -> Self {
+        let (rows, cols) = (items.len(), items[0].len());
+        let lines = winning_lines(rows, cols, rules);
+        let mut lines_through = vec![vec![Vec::new(); cols]; rows];
+        for (i, line) in lines.iter().enumerate() {
+            for &[row, col] in line {
+                lines_through[row][col].push(i);
+            }
+        }
+        Self {
+            board: items.to_vec(),
+            lines,
+            lines_through,
+            rules,
+            last,
+            empty: items
+                .iter()
+                .flatten()
+                .filter(|cell| cell.is_empty())
+                .count(),
+            max_depth: 0,
+        }
+    }
+
+    /// The moves worth searching.
+    ///
+    /// On large boards only cells next to existing marks are considered, since far-away moves
+    /// rarely matter and would make the search far too wide.
+    fn candidates(&self) -> Vec<[usize; 2]> {
+        let moves = legal_moves(&self.board, self.rules, self.last);
+        let (rows, cols) = (self.board.len(), self.board[0].len());
+        if rows * cols <= LARGE_BOARD_CELLS {
+            return moves;
+        }
+        let marked = |row: usize, col: usize| !self.board[row][col].is_empty();
+        let near_mark = |&[row, col]: &[usize; 2]| {
+            (row.saturating_sub(1)..=(row + 1).min(rows - 1))
+                .any(|r| (col.saturating_sub(1)..=(col + 1).min(cols - 1)).any(|c| marked(r, c)))
+        };
+        let near: Vec<_> = moves.iter().copied().filter(near_mark).collect();
+        if near.is_empty() {
+            // nothing has been played yet, so open in the middle
+            vec![[rows / 2, cols / 2]]
+        } else {
+            near
+        }
+    }
+
+    /// The moves worth searching for `player`, with every mark they may place.
+    fn placements(&self, player: Player) -> Vec<Move> {
+        let marks = self.rules.marks(player);
+        self.candidates()
+            .into_iter()
+            .flat_map(|cell| marks.iter().map(move |&mark| Move { cell, mark }))
+            .collect()
+    }
+
+    /// Judge an undecided position for `player` once the search can look no further ahead.
+    ///
+    /// Stacked boards are far too deep to search to the end, so every line still open to only
+    /// one side counts for that side, the more so the more of it is filled. In Order and Chaos
+    /// every line still made of a single mark counts for Order instead. Other boards count
+    /// such positions as draws.
+    fn evaluate(&self, player: Player) -> i32 {
+        if let Some(order) = self.rules.order {
+            let score: i32 = self
+                .lines
+                .iter()
+                .map(|line| {
+                    let count = |mark: Player| {
+                        line.iter()
+                            .filter(|&&[row, col]| self.board[row][col].owner() == Some(mark))
+                            .count() as i32
+                    };
+                    match (count(Player::Human), count(Player::Bot)) {
+                        (marks, 0) | (0, marks) => marks * marks,
+                        _ => 0,
+                    }
+                })
+                .sum();
+            return if player == order { score } else { -score };
+        }
+        if self.rules.layers == 1 {
+            return 0;
+        }
+        let score: i32 = self
+            .lines
+            .iter()
+            .map(|line| {
+                let count = |mark: Player| {
+                    line.iter()
+                        .filter(|&&[row, col]| self.board[row][col].owner() == Some(mark))
+                        .count() as i32
+                };
+                match (count(player), count(player.other())) {
+                    (mine, 0) => mine * mine,
+                    (0, theirs) => -theirs * theirs,
+                    _ => 0,
+                }
+            })
+            .sum();
+        // in misère play the fuller lines are the ones to stay away from
+        if self.rules.misere { -score } else { score }
+    }
+
+    /// The largest score [`Search::evaluate`] can give, which wins and losses must stay beyond.
+    fn evaluation_bound(&self) -> i32 {
+        if self.rules.layers == 1 && self.rules.order.is_none() {
+            return 0;
+        }
+        let open = self.rules.win_length as i32 - 1;
+        self.lines.len() as i32 * open * open
+    }
+
+    /// Whether the mark on `[row, col]` completes one of the lines through it.
+    fn completes_line(&self, [row, col]: [usize; 2]) -> bool {
+        let mark = &self.board[row][col];
+        self.lines_through[row][col].iter().any(|&i| {
+            self.lines[i]
+                .iter()
+                .all(|&[r, c]| self.board[r][c] == *mark)
+        })
+    }
+
+    /// Whether the mark just placed on `cell` completes a line, or three sub-boards in a row,
+    /// or in Notakto kills the last board.
+    fn wins(&self, cell: [usize; 2]) -> bool {
+        if self.rules.notakto {
+            return dead_boards(&self.board, &self.lines, self.rules)
+                .iter()
+                .all(|&dead| dead);
+        }
+        if self.rules.ultimate {
+            return matches!(
+                ultimate::check_winner(&self.board),
+                Some(Outcome::Winner(_))
+            );
+        }
+        self.completes_line(cell)
+    }
+
+    /// Score `player` making the move `placement`, `depth` plies into the search, from their
+    /// own point of view.
+    ///
+    /// Winning right now scores `win - depth`, so `win` must exceed the deepest possible ply. In
+    /// misère play completing a line scores `depth - win` instead, as it loses, and in Order
+    /// and Chaos a completed line or a full board decides the game for one of the roles.
+    fn score_move(&mut self, placement: Move, player: Player, depth: usize, win: i32) -> i32 {
+        let [row, col] = placement.cell;
+        self.board[row][col] = placement.mark.into();
+        self.empty -= 1;
+        let last = self.last.replace([row, col]);
+        let (won, lost) = (win - depth as i32, depth as i32 - win);
+        let score = if self.wins([row, col]) {
+            match self.rules.order {
+                Some(order) if order == player => won,
+                Some(_) => lost,
+                None if self.rules.misere => lost,
+                None => won,
+            }
+        } else if self.empty == 0 {
+            match self.rules.order {
+                Some(order) if order == player => lost,
+                Some(_) => won,
+                None => 0,
+            }
+        } else {
+            -self.negamax(player.other(), depth, win)
+        };
+        self.last = last;
+        self.empty += 1;
+        self.board[row][col] = CellState::Empty;
+        score
+    }
+
+    /// Score the position from the point of view of `to_move`, who has already seen `depth`
+    /// plies played.
+    fn negamax(&mut self, to_move: Player, depth: usize, win: i32) -> i32 {
+        if depth >= self.max_depth {
+            return self.evaluate(to_move);
+        }
+        self.placements(to_move)
+            .into_iter()
+            .map(|placement| self.score_move(placement, to_move, depth + 1, win))
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::GameStatus;
+
+    /// Play every line the human can choose against the minimax bot from `board`, failing on
+    /// any the bot loses.
+    fn never_loses(board: &Board) {
+        match board.status() {
+            GameStatus::Over(outcome) => {
+                assert_ne!(
+                    outcome,
+                    Outcome::Winner(Player::Human),
+                    "{:?}",
+                    board.cells()
+                );
+            }
+            GameStatus::InProgress {
+                to_move: Player::Bot,
+            } => {
+                let mv = MinimaxBot.choose_move(board).expect("the bot has a move");
+                let mut next = board.clone();
+                assert!(next.play(mv));
+                never_loses(&next);
+            }
+            GameStatus::InProgress {
+                to_move: Player::Human,
+            } => {
+                for mv in board.legal_moves() {
+                    let mut next = board.clone();
+                    assert!(next.play(mv));
+                    never_loses(&next);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn minimax_never_loses_classic_going_second() {
+        never_loses(&Board::new(3, 3, Rules::new(3), Player::Human));
+    }
+
+    #[test]
+    fn minimax_never_loses_classic_going_first() {
+        never_loses(&Board::new(3, 3, Rules::new(3), Player::Bot));
+    }
+
+    #[test]
+    fn minimax_takes_a_win_and_blocks_a_loss() {
+        let (x, o, empty) = (CellState::X, CellState::O, CellState::Empty);
+        // O to move wins on the right of the middle row rather than blocking X's top row
+        let cells = vec![vec![x, x, empty], vec![o, o, empty], vec![x, empty, empty]];
+        let board = Board::from_position(cells, Rules::new(3), Player::Bot, Some([2, 0]));
+        let mv = MinimaxBot.choose_move(&board).unwrap();
+        assert_eq!(mv.cell, [1, 2]);
+        // with no win of its own, O blocks X's top row
+        let cells = vec![vec![x, x, empty], vec![o, empty, empty], vec![empty; 3]];
+        let board = Board::from_position(cells, Rules::new(3), Player::Bot, Some([0, 1]));
+        let mv = MinimaxBot.choose_move(&board).unwrap();
+        assert_eq!(mv.cell, [0, 2]);
+    }
+
+    #[test]
+    fn thinking_answers_once_the_search_is_done_and_the_delay_has_passed() {
+        let started = Instant::now();
+        let thinking = Thinking::start(|| 42);
+        assert_eq!(thinking.answer(), Answer::Pending);
+        let answer = loop {
+            if let Answer::Ready(answer) = thinking.answer() {
+                break answer;
+            }
+            thread::sleep(crate::game::TICK_RATE);
+        };
+        assert_eq!(answer, 42);
+        assert!(started.elapsed() >= BOT_DELAY);
+    }
+
+    #[test]
+    fn thinking_fails_rather_than_waits_forever_when_the_search_panics() {
+        let thinking = Thinking::<u32>::start(|| panic!("the search went wrong"));
+        let answer = loop {
+            match thinking.answer() {
+                Answer::Pending => thread::sleep(crate::game::TICK_RATE),
+                answer => break answer,
+            }
+        };
+        assert_eq!(answer, Answer::Failed);
+    }
+}
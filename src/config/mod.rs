@@ -0,0 +1,18 @@
+//! What is kept between runs: the player's settings and themes, the records of the games
+//! played and the game saved to be resumed.
+
+pub mod records;
+pub mod save;
+pub mod settings;
+pub mod stats;
+pub mod theme;
+
+use std::{env, path::PathBuf};
+
+/// Where the file `name`, one the player may edit, is kept in the user's config directory.
+pub fn path(name: &str) -> Option<PathBuf> {
+    let config = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(config.join("rustgame").join(name))
+}
@@ -0,0 +1,31 @@
+//! Records kept between runs, such as best scores and times, each in a small file under the
+//! user's data directory.
+//!
+//! Records are only a nicety, so failing to read or write one is ignored.
+
+use std::{env, fmt::Display, fs, path::PathBuf, str::FromStr};
+
+/// Where the record `name` is kept.
+pub fn path(name: &str) -> Option<PathBuf> {
+    let data = env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))?;
+    Some(data.join("rustgame").join(name))
+}
+
+/// The record `name` saved by an earlier run, if there is one.
+pub fn load<T: FromStr>(name: &str) -> Option<T> {
+    path(name)
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|record| record.trim().parse().ok())
+}
+
+/// Save `record` as `name` for later runs.
+pub fn save(name: &str, record: impl Display) {
+    if let Some(path) = path(name) {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).ok();
+        }
+        fs::write(path, record.to_string()).ok();
+    }
+}
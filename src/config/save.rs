@@ -0,0 +1,59 @@
+//! The game saved with `Ctrl+S` to be resumed later, kept as TOML beside the records.
+//!
+//! Every save file names the version of its layout, so a later release can still read the
+//! games saved by an earlier one. Only one game is kept; saving another replaces it.
+
+use std::fs;
+
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+use crate::{
+    error::{Error, Result},
+    records,
+};
+
+/// The name of the save file among the records.
+const RECORD: &str = "save.toml";
+
+/// A save file in each of the layouts it has had, told apart by its `version` field.
+///
+/// A change to the layout of the game saved adds a version here, which older ones are
+/// converted from when read.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "version")]
+enum SaveFile<T> {
+    #[serde(rename = "1")]
+    V1(T),
+}
+
+/// Save `game`, replacing the game saved before.
+pub fn write<T: Serialize>(game: T) -> Result<()> {
+    let path = records::path(RECORD).ok_or_else(|| Error::SaveFile {
+        path: RECORD.into(),
+        reason: "there is no home directory to keep it in".into(),
+    })?;
+    let failed = |reason: String| Error::SaveFile {
+        path: path.clone(),
+        reason,
+    };
+    let text = toml::to_string(&SaveFile::V1(game)).map_err(|err| failed(err.to_string()))?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|err| failed(err.to_string()))?;
+    }
+    fs::write(&path, text).map_err(|err| failed(err.to_string()))
+}
+
+/// The game saved by this run or an earlier one, or `None` if there is none.
+pub fn read<T: DeserializeOwned>() -> Result<Option<T>> {
+    let Some(path) = records::path(RECORD).filter(|path| path.exists()) else {
+        return Ok(None);
+    };
+    let failed = |reason: String| Error::SaveFile {
+        path: path.clone(),
+        reason,
+    };
+    let text = fs::read_to_string(&path).map_err(|err| failed(err.to_string()))?;
+    match toml::from_str(&text).map_err(|err| failed(err.message().to_string()))? {
+        SaveFile::V1(game) => Ok(Some(game)),
+    }
+}
@@ -0,0 +1,722 @@
+//! The settings the player can change from the menu, or with `o` during a game: the colors,
+//! the marks, how strong the bots play, the size of a tic-tac-toe board and the frame around
+//! it, and the keys moving the cursor. Changes take effect at once and are kept between runs
+//! in `rustgame/config.toml` under the user's config directory, which is written with the
+//! defaults on the first run and can be edited by hand:
+//!
+//! ```toml
+//! theme = "Blue"
+//! background = "auto"
+//! symbols = ["X", "O"]
+//! difficulty = "default"
+//! board_size = 3
+//! border = "rounded"
+//! keys = "wasd"
+//! patterns = false
+//! ```
+//!
+//! The `difficulty` is `easy`, `medium`, `hard` or `default` to leave it to each game, and
+//! `keys` is `wasd` or `hjkl`. A value that can't be used keeps the default and is reported,
+//! and the file is then left as it is rather than saved over until it is mended.
+
+use std::{convert::Infallible, fs, str::FromStr};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style, Stylize, palette::tailwind},
+    text::Line,
+    widgets::{Block, BorderType, HighlightSpacing, List, ListItem, ListState, Paragraph},
+};
+
+use serde::{Deserialize, Serialize};
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{
+    app::Symbols,
+    bot, color, config,
+    error::Error,
+    input::Keys,
+    options::BOARD_SIZES,
+    records,
+    theme::{self, Theme},
+    ui::{layout::center, palette::TableColors},
+};
+
+/// The file the settings are kept in, in the user's config directory.
+const CONFIG_FILE: &str = "config.toml";
+
+/// The record earlier versions kept the settings in, which the config file is first written
+/// from.
+const RECORD: &str = "settings";
+
+/// The marks the player can switch between, the human's first.
+const SYMBOL_SETS: [(&str, &str); 6] = [
+    ("X", "O"),
+    ("O", "X"),
+    ("✕", "◯"),
+    ("❌", "⭕"),
+    ("▣", "▢"),
+    ("●", "○"),
+];
+
+/// Width of the settings panel, including its border.
+const PANEL_WIDTH: u16 = 56;
+
+/// Whether the games are drawn for a dark or a light terminal background.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Background {
+    /// Whichever the terminal has, when it says so, or else dark.
+    Auto,
+    Dark,
+    Light,
+}
+
+impl Background {
+    const fn label(self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::Dark => "dark",
+            Self::Light => "light",
+        }
+    }
+}
+
+/// The frame drawn around the board of the games of placing marks in a row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Border {
+    None,
+    Rounded,
+    Thick,
+    Double,
+}
+
+impl Border {
+    const ALL: [Self; 4] = [Self::None, Self::Rounded, Self::Thick, Self::Double];
+
+    const fn label(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Rounded => "rounded",
+            Self::Thick => "thick",
+            Self::Double => "double",
+        }
+    }
+
+    /// The lines the frame is drawn with, or `None` to leave the board unframed.
+    pub const fn border_type(self) -> Option<BorderType> {
+        match self {
+            Self::None => None,
+            Self::Rounded => Some(BorderType::Rounded),
+            Self::Thick => Some(BorderType::Thick),
+            Self::Double => Some(BorderType::Double),
+        }
+    }
+}
+
+/// What the player has chosen to play with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Settings {
+    /// The name of the theme the games are drawn in.
+    pub theme: String,
+    /// The themes to choose from: the built-in ones, then the player's own.
+    pub themes: Vec<Theme>,
+    /// The marks of the games of placing marks in a row.
+    pub symbols: Symbols,
+    /// The marks given with `--me` or `--bot`, played with in place of the chosen ones for
+    /// this run only and never saved.
+    pub given_symbols: Option<Symbols>,
+    /// How strong the bots play, or `None` to leave it to each game.
+    pub difficulty: Option<bot::Opponent>,
+    /// The rows and columns of a tic-tac-toe board started without `--size`.
+    pub board_size: usize,
+    pub border: Border,
+    pub keys: Keys,
+    /// Tell the cursor and the two sides' marks apart by more than color: the cursor is
+    /// hatched, the player's marks bold and the bot's dim.
+    pub patterns: bool,
+    pub background: Background,
+    /// The colors the terminal can show, found out anew on every run.
+    pub color: color::Mode,
+    /// Whether the terminal's background is light, found out anew on every run.
+    pub light_terminal: bool,
+    /// The config file couldn't be read, and is left as it is to be mended rather than
+    /// written over; changes hold for this run only.
+    unsaved: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        let themes = theme::built_in();
+        Self {
+            theme: themes[0].name.clone(),
+            themes,
+            symbols: Symbols::default(),
+            given_symbols: None,
+            difficulty: None,
+            board_size: *BOARD_SIZES.start(),
+            border: Border::Rounded,
+            keys: Keys::Wasd,
+            patterns: false,
+            background: Background::Auto,
+            color: color::Mode::default(),
+            light_terminal: false,
+            unsaved: false,
+        }
+    }
+}
+
+impl Settings {
+    /// The settings of the config file, choosing among `themes`, together with why it couldn't
+    /// be read if it couldn't.
+    ///
+    /// On the first run the config file is written with the settings an earlier version kept,
+    /// or else the defaults. A config file that can't be read leaves the defaults in place,
+    /// and one holding values that can't be used the defaults in their place; either is left
+    /// as it is rather than saved over.
+    pub fn load(themes: Vec<Theme>) -> (Self, Option<Error>) {
+        let Some(path) = config::path(CONFIG_FILE) else {
+            return (Self::default().with_themes(themes), None);
+        };
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(_) if !path.exists() => {
+                let settings = records::load::<Self>(RECORD)
+                    .unwrap_or_default()
+                    .with_themes(themes);
+                settings.save();
+                return (settings, None);
+            }
+            Err(err) => {
+                let reason = err.to_string();
+                return (
+                    Self::default().unsaved(themes),
+                    Some(Error::Config { path, reason }),
+                );
+            }
+        };
+        let file = match toml::from_str::<ConfigFile>(&text) {
+            Ok(file) => file,
+            Err(err) => {
+                let reason = format!("invalid settings: {}", err.message());
+                return (
+                    Self::default().unsaved(themes),
+                    Some(Error::Config { path, reason }),
+                );
+            }
+        };
+        let (settings, mut mistakes) = file.settings();
+        if !themes.iter().any(|theme| theme.name == settings.theme) {
+            mistakes.push(format!("no theme is called `{}`", settings.theme));
+        }
+        if mistakes.is_empty() {
+            return (settings.with_themes(themes), None);
+        }
+        let reason = mistakes.join("; ");
+        (
+            settings.unsaved(themes),
+            Some(Error::Config { path, reason }),
+        )
+    }
+
+    fn with_themes(mut self, themes: Vec<Theme>) -> Self {
+        self.themes = themes;
+        self
+    }
+
+    /// The settings choosing among `themes`, standing in for those of a config file that
+    /// can't be used and never saved over it.
+    fn unsaved(self, themes: Vec<Theme>) -> Self {
+        Self {
+            unsaved: true,
+            ..self.with_themes(themes)
+        }
+    }
+
+    /// Whether changes to the settings are kept between runs, which they aren't while the
+    /// config file can't be used.
+    pub const fn saved(&self) -> bool {
+        !self.unsaved
+    }
+
+    /// Write the settings to the config file. Failing to is ignored, as the settings still
+    /// hold for this run.
+    fn save(&self) {
+        if !self.unsaved
+            && let Some(path) = config::path(CONFIG_FILE)
+            && let Ok(text) = toml::to_string(&ConfigFile::from(self))
+        {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir).ok();
+            }
+            fs::write(path, text).ok();
+        }
+    }
+
+    /// Where the chosen theme is among the [themes](Settings::themes); a theme no longer
+    /// there, such as one taken out of the theme file, gives way to the first.
+    fn theme_index(&self) -> usize {
+        self.themes
+            .iter()
+            .position(|theme| theme.name == self.theme)
+            .unwrap_or(0)
+    }
+
+    /// The marks the games are played with: those given on the command line, or else the
+    /// chosen ones.
+    pub fn marks(&self) -> &Symbols {
+        self.given_symbols.as_ref().unwrap_or(&self.symbols)
+    }
+
+    /// Whether the games are drawn for a light background, as chosen or as the terminal has.
+    pub fn light(&self) -> bool {
+        match self.background {
+            Background::Auto => self.light_terminal,
+            Background::Dark => false,
+            Background::Light => true,
+        }
+    }
+
+    /// The colors of the chosen theme, in its light version on a light background if it has
+    /// one.
+    pub fn colors(&self) -> TableColors {
+        let theme = &self.themes[self.theme_index()];
+        match theme.light {
+            Some(light) if self.light() => light,
+            _ => theme.colors,
+        }
+    }
+
+    /// Switch from a dark to a light background or back, overriding the terminal's, and save
+    /// the change.
+    pub fn toggle_background(&mut self) {
+        self.background = match self.light() {
+            true => Background::Dark,
+            false => Background::Light,
+        };
+        self.save();
+    }
+
+    /// Switch to the next theme, or the previous one when `back` is set, and save the change.
+    pub fn cycle_theme(&mut self, back: bool) {
+        Field::Theme.cycle(self, back);
+        self.save();
+    }
+
+    /// The opponent a game with a bot plays against: the chosen one, or `default`.
+    pub fn opponent(&self, default: bot::Opponent) -> bot::Opponent {
+        self.difficulty.unwrap_or(default)
+    }
+}
+
+/// The settings as the config file holds them, each by the name it is shown with.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+struct ConfigFile {
+    theme: String,
+    background: String,
+    /// The human's mark, then the bot's.
+    symbols: [String; 2],
+    difficulty: String,
+    board_size: usize,
+    border: String,
+    keys: String,
+    patterns: bool,
+}
+
+impl Default for ConfigFile {
+    fn default() -> Self {
+        Self::from(&Settings::default())
+    }
+}
+
+impl From<&Settings> for ConfigFile {
+    fn from(settings: &Settings) -> Self {
+        Self {
+            theme: settings.theme.clone(),
+            background: settings.background.label().into(),
+            symbols: [settings.symbols.human.clone(), settings.symbols.bot.clone()],
+            difficulty: settings
+                .difficulty
+                .map_or("default", bot::Opponent::label)
+                .into(),
+            board_size: settings.board_size,
+            border: settings.border.label().into(),
+            keys: match settings.keys {
+                Keys::Wasd => "wasd",
+                Keys::Hjkl => "hjkl",
+            }
+            .into(),
+            patterns: settings.patterns,
+        }
+    }
+}
+
+impl ConfigFile {
+    /// The settings the file holds, leaving the default in place of any value that can't be
+    /// used, together with why each of those can't.
+    fn settings(&self) -> (Settings, Vec<String>) {
+        let mut settings = Settings::default();
+        let [human, bot] = &self.symbols;
+        let patterns = if self.patterns { "on" } else { "off" };
+        let mistakes = [
+            ("theme", self.theme.as_str()),
+            ("background", &self.background),
+            ("symbols", &format!("{human} {bot}")),
+            ("difficulty", &self.difficulty),
+            ("board", &self.board_size.to_string()),
+            ("border", &self.border),
+            ("keys", &self.keys),
+            ("patterns", patterns),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| settings.set(name, value).err())
+        .collect();
+        (settings, mistakes)
+    }
+}
+
+/// Read from the record of earlier versions, one `name=value` line per setting. Lines that
+/// can't be read keep the default.
+impl FromStr for Settings {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Infallible> {
+        let mut settings = Self::default();
+        for (name, value) in s.lines().filter_map(|line| line.split_once('=')) {
+            settings.set(name.trim(), value.trim()).ok();
+        }
+        Ok(settings)
+    }
+}
+
+impl Settings {
+    /// Set the setting `name` to the `value` it is shown or saved as, or say why it can't
+    /// take it. Marks can't be more than one character each or the same for both players.
+    fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+        let invalid = |expected: &str| format!("invalid {name} `{value}`, expected {expected}");
+        match (name, value) {
+            // earlier runs saved the place of a built-in theme rather than its name
+            ("theme", value) => match value.parse() {
+                Ok(index) => {
+                    if let Some(name) = theme::built_in_name(index) {
+                        self.theme = name.into();
+                    }
+                }
+                Err(_) if !value.is_empty() => self.theme = value.into(),
+                Err(_) => return Err(invalid("the name of a theme")),
+            },
+            ("symbols", value) => {
+                let Some((human, bot)) = value.split_once(' ') else {
+                    return Err(format!("invalid marks `{value}`, expected two marks"));
+                };
+                if let Some(mark) = [human, bot]
+                    .into_iter()
+                    .find(|mark| mark.graphemes(true).count() != 1)
+                {
+                    return Err(format!(
+                        "invalid mark `{mark}`, expected a single character or emoji"
+                    ));
+                }
+                if human == bot {
+                    return Err(format!("both players cannot use the mark `{human}`"));
+                }
+                self.symbols = Symbols {
+                    human: human.into(),
+                    bot: bot.into(),
+                };
+            }
+            ("difficulty", "default") => self.difficulty = None,
+            ("difficulty", "easy") => self.difficulty = Some(bot::Opponent::Random),
+            ("difficulty", "medium") => self.difficulty = Some(bot::Opponent::Heuristic),
+            ("difficulty", "hard") => self.difficulty = Some(bot::Opponent::Minimax),
+            ("difficulty", _) => return Err(invalid("`easy`, `medium`, `hard` or `default`")),
+            ("board", value) => match value.parse() {
+                Ok(size) if BOARD_SIZES.contains(&size) => self.board_size = size,
+                _ => {
+                    let (min, max) = (BOARD_SIZES.start(), BOARD_SIZES.end());
+                    return Err(invalid(&format!("a size from {min} to {max}")));
+                }
+            },
+            ("border", value) => match Border::ALL.iter().find(|b| b.label() == value) {
+                Some(&border) => self.border = border,
+                None => return Err(invalid("`none`, `rounded`, `thick` or `double`")),
+            },
+            ("keys", "wasd") => self.keys = Keys::Wasd,
+            ("keys", "hjkl") => self.keys = Keys::Hjkl,
+            ("keys", _) => return Err(invalid("`wasd` or `hjkl`")),
+            ("patterns", "on") => self.patterns = true,
+            ("patterns", "off") => self.patterns = false,
+            ("patterns", _) => return Err(invalid("`on` or `off`")),
+            ("background", "auto") => self.background = Background::Auto,
+            ("background", "dark") => self.background = Background::Dark,
+            ("background", "light") => self.background = Background::Light,
+            ("background", _) => return Err(invalid("`auto`, `dark` or `light`")),
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+/// The settings listed on the panel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Theme,
+    Symbols,
+    Difficulty,
+    BoardSize,
+    Border,
+    Keys,
+    Patterns,
+    Background,
+}
+
+impl Field {
+    const ALL: [Self; 8] = [
+        Self::Theme,
+        Self::Background,
+        Self::Symbols,
+        Self::Difficulty,
+        Self::BoardSize,
+        Self::Border,
+        Self::Keys,
+        Self::Patterns,
+    ];
+
+    const fn label(self) -> &'static str {
+        match self {
+            Self::Theme => "Theme",
+            Self::Symbols => "Marks",
+            Self::Difficulty => "Difficulty",
+            Self::BoardSize => "Tic-tac-toe board",
+            Self::Border => "Board border",
+            Self::Keys => "Movement keys",
+            Self::Patterns => "Patterns",
+            Self::Background => "Background",
+        }
+    }
+
+    fn value(self, settings: &Settings) -> String {
+        match self {
+            Self::Theme => settings.themes[settings.theme_index()].name.clone(),
+            Self::Symbols => {
+                let marks = settings.marks();
+                format!("{} / {}", marks.human, marks.bot)
+            }
+            Self::Difficulty => match settings.difficulty {
+                Some(opponent) => opponent.label().into(),
+                None => "each game's own".into(),
+            },
+            Self::BoardSize => format!("{0}x{0}", settings.board_size),
+            Self::Border => settings.border.label().into(),
+            Self::Keys => settings.keys.label().into(),
+            Self::Patterns => match settings.patterns {
+                true => "on".into(),
+                false => "off".into(),
+            },
+            Self::Background => match (settings.background, settings.light_terminal) {
+                (Background::Auto, true) => "auto (light)".into(),
+                (Background::Auto, false) => "auto (dark)".into(),
+                (background, _) => background.label().into(),
+            },
+        }
+    }
+
+    /// Step the setting to its next value, or its previous one when `back` is set.
+    fn cycle(self, settings: &mut Settings, back: bool) {
+        // a step forward through `len` choices, or back by going forward all but one
+        let step = |index: usize, len: usize| match back {
+            true => (index + len - 1) % len,
+            false => (index + 1) % len,
+        };
+        match self {
+            Self::Theme => {
+                let index = step(settings.theme_index(), settings.themes.len());
+                settings.theme = settings.themes[index].name.clone();
+            }
+            Self::Symbols => {
+                // marks given with `--me` or `--bot` are not among the sets, and start them over
+                let marks = settings.marks();
+                let current = SYMBOL_SETS
+                    .iter()
+                    .position(|&(human, bot)| marks.human == human && marks.bot == bot);
+                let index = current.map_or(0, |index| step(index, SYMBOL_SETS.len()));
+                let (human, bot) = SYMBOL_SETS[index];
+                settings.symbols = Symbols {
+                    human: human.into(),
+                    bot: bot.into(),
+                };
+                settings.given_symbols = None;
+            }
+            Self::Difficulty => {
+                let choices = [
+                    None,
+                    Some(bot::Opponent::Random),
+                    Some(bot::Opponent::Heuristic),
+                    Some(bot::Opponent::Minimax),
+                ];
+                let current = choices.iter().position(|&d| d == settings.difficulty);
+                settings.difficulty = choices[step(current.unwrap_or(0), choices.len())];
+            }
+            Self::BoardSize => {
+                let first = *BOARD_SIZES.start();
+                let index = step(settings.board_size - first, BOARD_SIZES.count());
+                settings.board_size = first + index;
+            }
+            Self::Border => {
+                let current = Border::ALL.iter().position(|&b| b == settings.border);
+                settings.border = Border::ALL[step(current.unwrap_or(0), Border::ALL.len())];
+            }
+            Self::Keys => {
+                settings.keys = match settings.keys {
+                    Keys::Wasd => Keys::Hjkl,
+                    Keys::Hjkl => Keys::Wasd,
+                };
+            }
+            Self::Patterns => settings.patterns = !settings.patterns,
+            Self::Background => {
+                let choices = [Background::Auto, Background::Dark, Background::Light];
+                let current = choices.iter().position(|&b| b == settings.background);
+                settings.background = choices[step(current.unwrap_or(0), choices.len())];
+            }
+        }
+    }
+}
+
+/// The panel listing the settings, with the one being changed selected.
+#[derive(Debug, Default)]
+pub struct Panel {
+    state: ListState,
+}
+
+impl Panel {
+    /// The height of the panel, including its border.
+    pub const HEIGHT: u16 = Field::ALL.len() as u16 + 2;
+
+    /// The help line shown below the panel.
+    pub const HELP: &str = "↑/↓ choose · ←/→ change · q back";
+
+    /// React to `code`, changing and saving the selected setting when it asks to.
+    pub fn handle_key(&mut self, code: KeyCode, settings: &mut Settings) {
+        let selected = self.state.selected().unwrap_or(0).min(Field::ALL.len() - 1);
+        let field = Field::ALL[selected];
+        match code {
+            KeyCode::Char('s') | KeyCode::Down => self.state.select_next(),
+            KeyCode::Char('w') | KeyCode::Up => self.state.select_previous(),
+            KeyCode::Char('d' | ' ') | KeyCode::Right | KeyCode::Enter => {
+                field.cycle(settings, false);
+                settings.save();
+            }
+            KeyCode::Char('a') | KeyCode::Left => {
+                field.cycle(settings, true);
+                settings.save();
+            }
+            _ => {}
+        }
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, settings: &Settings) {
+        if self.state.selected().is_none() {
+            self.state.select(Some(0));
+        }
+        let colors = settings.colors();
+        let items = Field::ALL.iter().map(|&field| {
+            ListItem::new(Line::from(vec![
+                format!("{:<20}", field.label()).bold(),
+                format!("◀ {} ▶", field.value(settings)).fg(colors.last_move_style_fg),
+            ]))
+        });
+        let list = List::new(items)
+            .block(Block::bordered().title(Line::from(" Settings ").centered()))
+            .highlight_style(
+                Style::default()
+                    .bg(colors.human_board_bg)
+                    .add_modifier(Modifier::REVERSED),
+            )
+            .highlight_symbol("▶ ")
+            .highlight_spacing(HighlightSpacing::Always);
+        frame.render_stateful_widget(list, area, &mut self.state);
+    }
+}
+
+/// Draw the settings in the middle of the screen, the help line below them.
+pub fn draw(frame: &mut Frame, panel: &mut Panel, settings: &Settings) {
+    let area = center(
+        frame.area(),
+        Constraint::Length(PANEL_WIDTH),
+        Constraint::Length(Panel::HEIGHT + 2),
+    );
+    let [panel_area, _, help_area] = Layout::vertical([
+        Constraint::Length(Panel::HEIGHT),
+        Constraint::Length(1),
+        Constraint::Length(1),
+    ])
+    .areas(area);
+    panel.render(frame, panel_area, settings);
+    let help = Paragraph::new(Panel::HELP)
+        .centered()
+        .fg(tailwind::SLATE.c400);
+    frame.render_widget(help, help_area);
+}
+
+/// React to `key` pressed on the settings, returning whether the player goes back from them
+/// with `q`, `o`, `Esc` or `Ctrl+C`. `Shift+←/→` change the theme and `Ctrl+L` switches
+/// between a dark and a light background whichever setting is selected.
+pub fn handle_key(panel: &mut Panel, key: KeyEvent, settings: &mut Settings) -> bool {
+    let key = settings.keys.translate(key);
+    match (key.modifiers, key.code) {
+        (_, KeyCode::Char('q' | 'o') | KeyCode::Esc)
+        | (KeyModifiers::CONTROL, KeyCode::Char('c')) => return true,
+        (KeyModifiers::SHIFT, code @ (KeyCode::Left | KeyCode::Right)) => {
+            settings.cycle_theme(code == KeyCode::Left);
+        }
+        (KeyModifiers::CONTROL, KeyCode::Char('l')) => settings.toggle_background(),
+        (_, code) => panel.handle_key(code, settings),
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn marks_that_cant_be_played_with_are_turned_down() {
+        let mut settings = Settings::default();
+        for marks in ["XX O", "X X", "X"] {
+            assert!(settings.set("symbols", marks).is_err(), "took `{marks}`");
+        }
+        assert_eq!(settings.symbols, Symbols::default());
+        assert!(settings.set("symbols", "🔥 💧").is_ok());
+        assert_eq!(settings.marks().human, "🔥");
+    }
+
+    #[test]
+    fn every_value_of_the_config_file_that_cant_be_used_is_reported() {
+        let file = ConfigFile {
+            difficulty: "brutal".into(),
+            board_size: 12,
+            keys: "arrows".into(),
+            ..ConfigFile::default()
+        };
+        let (settings, mistakes) = file.settings();
+        assert_eq!(mistakes.len(), 3, "{mistakes:?}");
+        assert!(mistakes[0].contains("`brutal`"));
+        assert_eq!(settings.board_size, Settings::default().board_size);
+        assert!(ConfigFile::default().settings().1.is_empty());
+    }
+
+    #[test]
+    fn marks_given_on_the_command_line_are_not_saved() {
+        let settings = Settings {
+            given_symbols: Some(Symbols {
+                human: "A".into(),
+                bot: "B".into(),
+            }),
+            ..Settings::default()
+        };
+        assert_eq!(settings.marks().human, "A");
+        assert_eq!(ConfigFile::from(&settings).symbols, ["X", "O"]);
+    }
+}
@@ -0,0 +1,170 @@
+//! The lifetime stats: how many games of each kind and against each bot were won, lost and
+//! drawn, and how long was spent playing, kept between runs in `stats.toml` among the records
+//! and updated as every game ends.
+
+use std::{collections::BTreeMap, fs, str::FromStr, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{GameMode, bot, error::Error, game::Status, records};
+
+/// The name of the stats file among the records.
+const RECORD: &str = "stats.toml";
+
+/// The games of one kind finished so far.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Tally {
+    pub won: usize,
+    pub lost: usize,
+    pub drawn: usize,
+}
+
+impl Tally {
+    pub const fn played(&self) -> usize {
+        self.won + self.lost + self.drawn
+    }
+
+    /// Count a game ended as `status`; games left unfinished are not counted.
+    fn count(&mut self, status: Status) {
+        match status {
+            Status::Playing => {}
+            Status::Won => self.won += 1,
+            Status::Lost => self.lost += 1,
+            Status::Draw => self.drawn += 1,
+        }
+    }
+}
+
+/// The three counts in a row, won, lost and drawn, as earlier versions kept the tally of each
+/// game in a record of its own.
+impl FromStr for Tally {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        let counts: Vec<usize> = s
+            .split_whitespace()
+            .map(str::parse)
+            .collect::<Result<_, _>>()
+            .map_err(|_| ())?;
+        let [won, lost, drawn] = counts[..] else {
+            return Err(());
+        };
+        Ok(Self { won, lost, drawn })
+    }
+}
+
+/// Everything played since the stats were first kept.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Stats {
+    /// The seconds spent in games, finished or not.
+    play_time: u64,
+    /// The tally of each kind of game, by its [key](GameMode::key).
+    games: BTreeMap<String, Tally>,
+    /// The tally of the games against each bot, by its [label](bot::Opponent::label).
+    difficulties: BTreeMap<String, Tally>,
+    /// The stats file couldn't be read, and is left as it is to be mended rather than
+    /// written over.
+    #[serde(skip)]
+    unsaved: bool,
+}
+
+impl Stats {
+    /// The stats saved by earlier runs, taking over the tallies an earlier version kept when
+    /// there are none yet, together with why they couldn't be read if they couldn't.
+    ///
+    /// A stats file that can't be read leaves empty stats in its place for this run, which
+    /// aren't saved over it.
+    pub fn load() -> (Self, Option<Error>) {
+        let Some(path) = records::path(RECORD) else {
+            return (Self::default(), None);
+        };
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(_) if !path.exists() => {
+                let games = GameMode::ALL
+                    .iter()
+                    .filter_map(|game| {
+                        let key = game.key();
+                        let tally = records::load(&format!("stats-{key}"))?;
+                        Some((key.to_string(), tally))
+                    })
+                    .collect();
+                let stats = Self {
+                    games,
+                    ..Self::default()
+                };
+                return (stats, None);
+            }
+            Err(err) => {
+                let reason = err.to_string();
+                return (Self::unreadable(), Some(Error::Config { path, reason }));
+            }
+        };
+        match toml::from_str(&text) {
+            Ok(stats) => (stats, None),
+            Err(err) => {
+                let reason = format!("invalid stats: {}", err.message());
+                (Self::unreadable(), Some(Error::Config { path, reason }))
+            }
+        }
+    }
+
+    /// Empty stats that are never saved, standing in for those of a file that can't be read.
+    pub(crate) fn unreadable() -> Self {
+        Self {
+            unsaved: true,
+            ..Self::default()
+        }
+    }
+
+    /// The games of `game` finished so far.
+    pub fn game(&self, game: GameMode) -> Tally {
+        self.games.get(game.key()).copied().unwrap_or_default()
+    }
+
+    /// The games finished so far against `opponent`.
+    pub fn difficulty(&self, opponent: bot::Opponent) -> Tally {
+        self.difficulties
+            .get(opponent.label())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// How long was spent in games altogether.
+    pub const fn play_time(&self) -> Duration {
+        Duration::from_secs(self.play_time)
+    }
+
+    /// Count a game of `game` ended as `status` against `opponent`, if it had a bot, after
+    /// `played` more of it, and save the stats.
+    pub fn record(
+        &mut self,
+        game: GameMode,
+        status: Status,
+        opponent: Option<bot::Opponent>,
+        played: Duration,
+    ) {
+        self.games
+            .entry(game.key().into())
+            .or_default()
+            .count(status);
+        if let Some(opponent) = opponent {
+            let tally = self.difficulties.entry(opponent.label().into());
+            tally.or_default().count(status);
+        }
+        self.add_play_time(played);
+    }
+
+    /// Count `played` more of a game, and save the stats.
+    pub fn add_play_time(&mut self, played: Duration) {
+        // the stats file keeps whole seconds
+        self.play_time += (played + Duration::from_millis(500)).as_secs();
+        if !self.unsaved
+            && let Ok(text) = toml::to_string(self)
+        {
+            records::save(RECORD, text);
+        }
+    }
+}
@@ -0,0 +1,223 @@
+//! The themes the games are drawn in: one for each of the `PALETTES`, in dark and light
+//! versions, two kept apart for colorblind players, and any the player defines in a theme
+//! file of their own.
+//!
+//! The theme file is `rustgame/themes.toml` under the user's config directory, or the file
+//! given with `--themes`. Each theme is a `[[theme]]` table with a `name`, optionally the
+//! built-in theme it starts from as `base`, and the colors it changes, as hex codes or color
+//! names:
+//!
+//! ```toml
+//! [[theme]]
+//! name = "Solarized"
+//! base = "Blue"
+//! background = "#002b36"
+//! rows = "#073642"
+//! text = "#93a1a1"
+//! highlight = "#268bd2"
+//! cursor = "#2aa198"
+//! ```
+//!
+//! The colors are `background`, `rows` (every other row), `text`, `highlight` (the selected
+//! row and column), `cursor`, `hint`, `last_move`, `board` (a board still open), and
+//! `human_board` and `bot_board` (boards won by either side).
+
+use std::{fs, path::Path};
+
+use ratatui::style::Color;
+use toml::{Table, Value};
+
+use crate::{
+    config,
+    error::Error,
+    ui::palette::{PALETTES, TableColors},
+};
+
+/// The names of the [`PALETTES`], in the same order.
+const BUILT_IN_NAMES: [&str; PALETTES.len()] = ["Blue", "Emerald", "Indigo", "Red"];
+
+/// Built-in themes besides the [`PALETTES`], keeping the cursor, the marks and the boards
+/// apart for players who can't tell red from green or blue from yellow. The first uses the
+/// Okabe-Ito colors, the second only black, white and strong yellow and cyan.
+const COLORBLIND: [(&str, TableColors); 2] = [
+    (
+        "Okabe-Ito",
+        TableColors {
+            buffer_bg: Color::Rgb(0x10, 0x10, 0x10),
+            row_fg: Color::Rgb(0xf0, 0xf0, 0xf0),
+            selected_row_style_fg: Color::Rgb(0x56, 0xb4, 0xe9),
+            selected_column_style_fg: Color::Rgb(0x56, 0xb4, 0xe9),
+            selected_cell_style_fg: Color::Rgb(0xe6, 0x9f, 0x00),
+            hint_cell_style_bg: Color::Rgb(0x00, 0x72, 0xb2),
+            last_move_style_fg: Color::Rgb(0xf0, 0xe4, 0x42),
+            normal_row_color: Color::Rgb(0x10, 0x10, 0x10),
+            alt_row_color: Color::Rgb(0x26, 0x26, 0x26),
+            open_board_bg: Color::Rgb(0x0b, 0x2a, 0x40),
+            human_board_bg: Color::Rgb(0x00, 0x72, 0xb2),
+            bot_board_bg: Color::Rgb(0x8a, 0x3d, 0x00),
+        },
+    ),
+    (
+        "High contrast",
+        TableColors {
+            buffer_bg: Color::Rgb(0x00, 0x00, 0x00),
+            row_fg: Color::Rgb(0xff, 0xff, 0xff),
+            selected_row_style_fg: Color::Rgb(0x00, 0xff, 0xff),
+            selected_column_style_fg: Color::Rgb(0x00, 0xff, 0xff),
+            selected_cell_style_fg: Color::Rgb(0xff, 0xff, 0x00),
+            hint_cell_style_bg: Color::Rgb(0x55, 0x55, 0x55),
+            last_move_style_fg: Color::Rgb(0xff, 0xff, 0x00),
+            normal_row_color: Color::Rgb(0x00, 0x00, 0x00),
+            alt_row_color: Color::Rgb(0x1c, 0x1c, 0x1c),
+            open_board_bg: Color::Rgb(0x33, 0x33, 0x33),
+            human_board_bg: Color::Rgb(0x00, 0x5f, 0x87),
+            bot_board_bg: Color::Rgb(0x87, 0x87, 0x87),
+        },
+    ),
+];
+
+/// A named set of colors to draw the games in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Theme {
+    pub name: String,
+    pub colors: TableColors,
+    /// The colors on a light terminal background, for the themes that have them.
+    pub light: Option<TableColors>,
+}
+
+/// The built-in themes: one for each of the `PALETTES`, then the `COLORBLIND` ones.
+pub fn built_in() -> Vec<Theme> {
+    let palettes = BUILT_IN_NAMES.iter().zip(&PALETTES).map(|(name, palette)| {
+        let light = Some(TableColors::light(palette));
+        (*name, TableColors::new(palette), light)
+    });
+    let colorblind = COLORBLIND.map(|(name, colors)| (name, colors, None));
+    palettes
+        .chain(colorblind)
+        .map(|(name, colors, light)| Theme {
+            name: name.into(),
+            colors,
+            light,
+        })
+        .collect()
+}
+
+/// The name of the built-in theme saved as `index` by earlier runs, which kept the theme by
+/// its place among the `PALETTES`.
+pub fn built_in_name(index: usize) -> Option<&'static str> {
+    BUILT_IN_NAMES.get(index).copied()
+}
+
+/// The built-in themes followed by those of the theme file at `path`, or of the one in the
+/// config directory if no path is given.
+///
+/// A theme file that can't be read or holds a mistake is left out as a whole, and the reason
+/// why is returned alongside the built-in themes. The config directory having no theme file
+/// is not a mistake.
+pub fn load(path: Option<&Path>) -> (Vec<Theme>, Option<Error>) {
+    let mut themes = built_in();
+    let (path, given) = match path {
+        Some(path) => (path.to_path_buf(), true),
+        None => match config::path("themes.toml") {
+            Some(path) => (path, false),
+            None => return (themes, None),
+        },
+    };
+    let text = match fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(_) if !given && !path.exists() => return (themes, None),
+        Err(err) => {
+            let reason = err.to_string();
+            return (themes, Some(Error::Config { path, reason }));
+        }
+    };
+    match parse(&text, &themes) {
+        Ok(custom) => {
+            themes.extend(custom);
+            (themes, None)
+        }
+        Err(err) => {
+            let reason = format!("invalid themes: {err}");
+            (themes, Some(Error::Config { path, reason }))
+        }
+    }
+}
+
+/// The themes of a theme file, starting from the `built_in` ones.
+fn parse(text: &str, built_in: &[Theme]) -> Result<Vec<Theme>, String> {
+    let table: Table = toml::from_str(text).map_err(|err| err.message().to_string())?;
+    let themes = match table.get("theme") {
+        Some(Value::Array(themes)) => themes.as_slice(),
+        Some(_) => return Err("`theme` should be a list of tables, written `[[theme]]`".into()),
+        None => &[],
+    };
+    let mut parsed: Vec<Theme> = Vec::new();
+    for (i, theme) in themes.iter().enumerate() {
+        let Value::Table(theme) = theme else {
+            return Err(format!("theme {}: should be a table", i + 1));
+        };
+        let theme =
+            parse_theme(theme, built_in).map_err(|err| format!("theme {}: {err}", i + 1))?;
+        if built_in
+            .iter()
+            .chain(&parsed)
+            .any(|other| other.name == theme.name)
+        {
+            return Err(format!("there is already a theme named `{}`", theme.name));
+        }
+        parsed.push(theme);
+    }
+    Ok(parsed)
+}
+
+fn parse_theme(table: &Table, built_in: &[Theme]) -> Result<Theme, String> {
+    let string = |key: &str| match table.get(key) {
+        Some(Value::String(text)) if !text.trim().is_empty() => Ok(Some(text.trim())),
+        Some(Value::String(_)) => Err(format!("`{key}` is empty")),
+        Some(_) => Err(format!("`{key}` should be a string")),
+        None => Ok(None),
+    };
+    let name = string("name")?.ok_or("missing `name`")?;
+    let mut colors = match string("base")? {
+        Some(base) => {
+            let Some(base) = built_in
+                .iter()
+                .find(|theme| theme.name.eq_ignore_ascii_case(base))
+            else {
+                return Err(format!("no built-in theme named `{base}`"));
+            };
+            base.colors
+        }
+        None => built_in[0].colors,
+    };
+    for key in table.keys().filter(|&key| key != "name" && key != "base") {
+        let fields = match key.as_str() {
+            "background" => vec![&mut colors.buffer_bg, &mut colors.normal_row_color],
+            "rows" => vec![&mut colors.alt_row_color],
+            "text" => vec![&mut colors.row_fg],
+            "highlight" => vec![
+                &mut colors.selected_row_style_fg,
+                &mut colors.selected_column_style_fg,
+            ],
+            "cursor" => vec![&mut colors.selected_cell_style_fg],
+            "hint" => vec![&mut colors.hint_cell_style_bg],
+            "last_move" => vec![&mut colors.last_move_style_fg],
+            "board" => vec![&mut colors.open_board_bg],
+            "human_board" => vec![&mut colors.human_board_bg],
+            "bot_board" => vec![&mut colors.bot_board_bg],
+            _ => return Err(format!("unknown color `{key}`")),
+        };
+        let value = string(key)?.unwrap_or_default();
+        let color: Color = value
+            .parse()
+            .map_err(|_| format!("`{key}`: `{value}` is not a color"))?;
+        for field in fields {
+            *field = color;
+        }
+    }
+    Ok(Theme {
+        name: name.into(),
+        colors,
+        light: None,
+    })
+}
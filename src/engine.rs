@@ -0,0 +1,124 @@
+//! Games of marks in a row with no terminal attached: the board, the moves made on it, how the
+//! game stands and the bots that play it, for driving from tests, benchmarks or another
+//! frontend.
+//!
+//! Nothing here draws or reads keys. Quantum play keeps its own board in
+//! [`quantum`](crate::game::quantum).
+
+pub use crate::bot::{Bot, HeuristicBot, MinimaxBot, RandomBot, evaluate_moves};
+pub use crate::game::board::{CellState, Outcome, Player, Rules};
+
+use crate::game::board::{check_winner, legal_moves};
+
+/// A move to play: the cell and whose mark goes into it.
+///
+/// The mark is the mover's own except in wild play, where either may be placed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Move {
+    pub cell: [usize; 2],
+    pub mark: Player,
+}
+
+/// Whether a game is still under way, and how it ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameStatus {
+    InProgress { to_move: Player },
+    Over(Outcome),
+}
+
+/// A game of marks in a row: the marks on the board, the rules it is played by and whose turn
+/// it is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Board {
+    cells: Vec<Vec<CellState>>,
+    rules: Rules,
+    status: GameStatus,
+    /// The move played just before, which in ultimate tic-tac-toe decides where play goes.
+    last: Option<[usize; 2]>,
+}
+
+impl Board {
+    /// An empty `rows`×`cols` board played by `rules`, with `first` to move.
+    pub fn new(rows: usize, cols: usize, rules: Rules, first: Player) -> Self {
+        Self::from_position(vec![vec![CellState::Empty; cols]; rows], rules, first, None)
+    }
+
+    /// The board holding `cells`, with `to_move` to play after the `last` move.
+    pub fn from_position(
+        cells: Vec<Vec<CellState>>,
+        rules: Rules,
+        to_move: Player,
+        last: Option<[usize; 2]>,
+    ) -> Self {
+        let status = match last.and_then(|_| check_winner(&cells, rules, to_move.other())) {
+            Some(outcome) => GameStatus::Over(outcome),
+            None => GameStatus::InProgress { to_move },
+        };
+        Self {
+            cells,
+            rules,
+            status,
+            last,
+        }
+    }
+
+    /// What every cell holds, row by row.
+    pub fn cells(&self) -> &[Vec<CellState>] {
+        &self.cells
+    }
+
+    pub const fn rules(&self) -> Rules {
+        self.rules
+    }
+
+    pub const fn status(&self) -> GameStatus {
+        self.status
+    }
+
+    /// The cell of the move played just before.
+    pub const fn last(&self) -> Option<[usize; 2]> {
+        self.last
+    }
+
+    /// The side to move, or `None` once the game is over.
+    pub const fn to_move(&self) -> Option<Player> {
+        match self.status {
+            GameStatus::InProgress { to_move } => Some(to_move),
+            GameStatus::Over(_) => None,
+        }
+    }
+
+    /// Every move the side to move may play, with each mark they may place.
+    pub fn legal_moves(&self) -> Vec<Move> {
+        let Some(player) = self.to_move() else {
+            return Vec::new();
+        };
+        let marks = self.rules.marks(player);
+        legal_moves(&self.cells, self.rules, self.last)
+            .into_iter()
+            .flat_map(|cell| marks.iter().map(move |&mark| Move { cell, mark }))
+            .collect()
+    }
+
+    /// Play `mv` for the side to move, pass the turn and check for a result.
+    ///
+    /// Returns `false`, leaving the board as it was, if the move isn't legal.
+    pub fn play(&mut self, mv: Move) -> bool {
+        let Some(player) = self.to_move() else {
+            return false;
+        };
+        if !self.legal_moves().contains(&mv) {
+            return false;
+        }
+        let [row, col] = mv.cell;
+        self.cells[row][col] = mv.mark.into();
+        self.last = Some(mv.cell);
+        self.status = match check_winner(&self.cells, self.rules, player) {
+            Some(outcome) => GameStatus::Over(outcome),
+            None => GameStatus::InProgress {
+                to_move: player.other(),
+            },
+        };
+        true
+    }
+}
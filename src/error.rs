@@ -0,0 +1,96 @@
+//! What can go wrong outside the rules of a game, told to the player in words rather than as a
+//! trace.
+
+use std::{io, path::PathBuf};
+
+use thiserror::Error;
+
+use crate::GameMode;
+
+/// A result whose error is one of ours.
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Something that kept the program from doing what the player asked.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// An argument the command line doesn't take.
+    #[error(
+        "unknown argument `{0}`, expected `--game={games}`, `--size=<3-9>`, `--win=<k>`, `--boards=<1-3>`, `--role=order|chaos`, `--grid=<rows>x<cols>`, `--mines=<percent>`, `--difficulty=easy|medium|hard`, `--words=<file>`, `--pattern=<file.rle>`, `--levels=<file.xsb>`, `--puzzles=<file>`, `--pack=<file.toml|file.json>`, `--themes=<file.toml>`, `--color=24bit|16|none`, `--heaps=<n>,<n>,…`, `--misere`, `--wild`, `--wrap`, `--swap`, `--first=me|bot|random`, `--me=<mark>` or `--bot=<mark>`",
+        games = GameMode::keys("|")
+    )]
+    UnknownArgument(String),
+    /// An option given a value it doesn't take, such as a board size out of range.
+    #[error("invalid {what} `{value}`, expected {expected}")]
+    InvalidValue {
+        what: &'static str,
+        value: String,
+        expected: String,
+    },
+    /// An option given for a game that doesn't take it.
+    #[error("`{option}` can only be used with {games}")]
+    OnlyFor {
+        option: &'static str,
+        games: &'static str,
+    },
+    /// Options that can't be played together, such as a rule and a game without it.
+    #[error("{options} cannot be used with {with}")]
+    Conflict { options: &'static str, with: String },
+    /// Both players were given the same mark.
+    #[error("both players cannot use the mark `{0}`")]
+    SameMarks(String),
+    /// A file named on the command line, such as a word list, can't be read.
+    #[error("cannot read the {what} `{}`: {source}", path.display())]
+    ReadFile {
+        what: &'static str,
+        path: PathBuf,
+        source: io::Error,
+    },
+    /// A file named on the command line holds something that can't be played.
+    #[error("invalid {what} `{}`: {reason}", path.display())]
+    InvalidFile {
+        what: &'static str,
+        path: PathBuf,
+        reason: String,
+    },
+    /// The terminal couldn't be drawn to, read from or set up.
+    #[error("the terminal stopped responding: {0}")]
+    Terminal(#[from] io::Error),
+    /// A config file, such as the themes, or the stats kept between runs can't be read or
+    /// holds a mistake.
+    #[error("cannot use `{}`: {reason}", path.display())]
+    Config { path: PathBuf, reason: String },
+    /// A game couldn't be saved, or a saved one read back.
+    #[error("cannot use the save file `{}`: {reason}", path.display())]
+    SaveFile { path: PathBuf, reason: String },
+}
+
+impl Error {
+    /// The value `value` given for `what`, which takes `expected`.
+    pub fn invalid(
+        what: &'static str,
+        value: impl Into<String>,
+        expected: impl Into<String>,
+    ) -> Self {
+        Self::InvalidValue {
+            what,
+            value: value.into(),
+            expected: expected.into(),
+        }
+    }
+
+    /// The title of the dialog telling the player about it.
+    pub const fn title(&self) -> &'static str {
+        match self {
+            Self::UnknownArgument(_)
+            | Self::InvalidValue { .. }
+            | Self::OnlyFor { .. }
+            | Self::Conflict { .. }
+            | Self::SameMarks(_)
+            | Self::ReadFile { .. }
+            | Self::InvalidFile { .. } => "Can't play that",
+            Self::Terminal(_) => "Terminal trouble",
+            Self::Config { .. } => "Config problem",
+            Self::SaveFile { .. } => "Save problem",
+        }
+    }
+}
@@ -0,0 +1,560 @@
+//! Battleship against the bot: place a fleet, then take turns firing at each other's waters
+//! until one fleet is sunk.
+//!
+//! The hard bot hunts by probability: it counts every way the ships still afloat could lie
+//! given the shots so far, and fires where a ship is most likely to be, chasing down any ship
+//! it has hit but not yet sunk.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use rand::seq::IteratorRandom;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style, Stylize, palette::tailwind},
+    text::Line,
+    widgets::{Paragraph, TableState},
+};
+
+use crate::{
+    Outcome, Player, bot,
+    game::{Game, Help, Status},
+    grid::{self, Cursor, Viewport},
+    settings::Settings,
+    ui::{
+        layout::{calculate_layout, table_extent},
+        palette::{PALETTES, TableColors},
+    },
+};
+
+/// Cells along one side of each board.
+const SIDE: usize = 10;
+
+/// The ships of each fleet, with their lengths, in the order they are placed.
+const FLEET: [(&str, usize); 5] = [
+    ("Carrier", 5),
+    ("Battleship", 4),
+    ("Cruiser", 3),
+    ("Submarine", 3),
+    ("Destroyer", 2),
+];
+
+/// How much likelier the hard bot takes a ship to lie across a hit it has not sunk yet.
+const HIT_WEIGHT: u32 = 50;
+
+/// What became of a shot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Shot {
+    Miss,
+    Hit,
+    /// A hit on a ship that has gone down.
+    Sunk,
+}
+
+/// The shots fired at one side's waters.
+type Shots = [[Option<Shot>; SIDE]; SIDE];
+
+/// The cells of a ship of `length` with its bow at `bow`, running right or down, if it stays
+/// on the board.
+fn ship_cells([row, col]: [usize; 2], length: usize, horizontal: bool) -> Option<Vec<[usize; 2]>> {
+    let (end_row, end_col) = match horizontal {
+        true => (row, col + length - 1),
+        false => (row + length - 1, col),
+    };
+    (end_row < SIDE && end_col < SIDE).then(|| {
+        (0..length)
+            .map(|i| match horizontal {
+                true => [row, col + i],
+                false => [row + i, col],
+            })
+            .collect()
+    })
+}
+
+/// Every cell of a board, row by row.
+fn cells() -> impl Iterator<Item = [usize; 2]> + Clone {
+    (0..SIDE).flat_map(|row| (0..SIDE).map(move |col| [row, col]))
+}
+
+/// One side's fleet and the shots fired at it.
+struct Waters {
+    /// The cells of each ship placed so far, in the order of [`FLEET`].
+    ships: Vec<Vec<[usize; 2]>>,
+    shots: Shots,
+}
+
+impl Waters {
+    const fn new() -> Self {
+        Self {
+            ships: Vec::new(),
+            shots: [[None; SIDE]; SIDE],
+        }
+    }
+
+    /// The ship at `cell`, as its index into [`FLEET`].
+    fn ship_at(&self, cell: [usize; 2]) -> Option<usize> {
+        self.ships.iter().position(|ship| ship.contains(&cell))
+    }
+
+    /// The next ship to be placed, with its bow at `bow`, if it fits without crossing another.
+    fn next_ship(&self, bow: [usize; 2], horizontal: bool) -> Option<Vec<[usize; 2]>> {
+        let (_, length) = FLEET.get(self.ships.len())?;
+        ship_cells(bow, *length, horizontal)
+            .filter(|ship| ship.iter().all(|&cell| self.ship_at(cell).is_none()))
+    }
+
+    /// Place the ships still missing anywhere they fit.
+    fn place_rest(&mut self) {
+        let mut rng = rand::rng();
+        while self.ships.len() < FLEET.len() {
+            let ship = cells()
+                .flat_map(|bow| [true, false].map(|horizontal| self.next_ship(bow, horizontal)))
+                .flatten()
+                .choose(&mut rng);
+            match ship {
+                Some(ship) => self.ships.push(ship),
+                // the ships placed by hand left no room; start the fleet over
+                None => self.ships.clear(),
+            }
+        }
+    }
+
+    fn sunk(&self, ship: usize) -> bool {
+        self.ships[ship]
+            .iter()
+            .all(|&[row, col]| self.shots[row][col].is_some())
+    }
+
+    fn defeated(&self) -> bool {
+        (0..self.ships.len()).all(|ship| self.sunk(ship))
+    }
+
+    /// The lengths of the ships still afloat.
+    fn afloat(&self) -> Vec<usize> {
+        (0..self.ships.len())
+            .filter(|&ship| !self.sunk(ship))
+            .map(|ship| FLEET[ship].1)
+            .collect()
+    }
+
+    /// Fire at `cell`, returning what the shot did, or `None` if the cell was already shot.
+    ///
+    /// A shot sinking a ship marks every cell of it as [`Shot::Sunk`].
+    fn fire(&mut self, cell: [usize; 2]) -> Option<Shot> {
+        let [row, col] = cell;
+        if self.shots[row][col].is_some() {
+            return None;
+        }
+        let Some(ship) = self.ship_at(cell) else {
+            self.shots[row][col] = Some(Shot::Miss);
+            return Some(Shot::Miss);
+        };
+        self.shots[row][col] = Some(Shot::Hit);
+        if !self.sunk(ship) {
+            return Some(Shot::Hit);
+        }
+        for &[row, col] in &self.ships[ship] {
+            self.shots[row][col] = Some(Shot::Sunk);
+        }
+        Some(Shot::Sunk)
+    }
+}
+
+/// The cell `opponent` fires at, knowing only the `shots` fired so far and the lengths of
+/// the ships still `afloat`.
+fn aim(shots: &Shots, afloat: &[usize], opponent: bot::Opponent) -> Option<[usize; 2]> {
+    let mut rng = rand::rng();
+    let open = cells().filter(|&[row, col]| shots[row][col].is_none());
+    match opponent {
+        bot::Opponent::Random => open.choose(&mut rng),
+        // fire where the most ways of laying out the remaining ships cross
+        bot::Opponent::Heuristic | bot::Opponent::Minimax => {
+            let mut density = [[0; SIDE]; SIDE];
+            for &length in afloat {
+                for bow in cells() {
+                    for horizontal in [true, false] {
+                        let Some(ship) = ship_cells(bow, length, horizontal) else {
+                            continue;
+                        };
+                        let shot = |&[row, col]: &[usize; 2]| shots[row][col];
+                        if ship
+                            .iter()
+                            .any(|cell| matches!(shot(cell), Some(Shot::Miss | Shot::Sunk)))
+                        {
+                            continue;
+                        }
+                        let hits = ship.iter().filter(|cell| shot(cell).is_some()).count();
+                        let weight = 1 + HIT_WEIGHT * hits as u32;
+                        for &[row, col] in &ship {
+                            if shots[row][col].is_none() {
+                                density[row][col] += weight;
+                            }
+                        }
+                    }
+                }
+            }
+            let best = open.clone().map(|[row, col]| density[row][col]).max()?;
+            open.filter(|&[row, col]| density[row][col] == best)
+                .choose(&mut rng)
+        }
+    }
+}
+
+/// A game of Battleship against the bot.
+pub struct Battleship {
+    /// The human's fleet, fired at by the bot.
+    home: Waters,
+    /// The bot's fleet, fired at by the human.
+    target: Waters,
+    /// Whether the next ship is placed running right rather than down.
+    horizontal: bool,
+    turn: Player,
+    /// Who fires first this game; the sides take turns opening.
+    starter: Player,
+    opponent: bot::Opponent,
+    outcome: Option<Outcome>,
+    last_bot_shot: Option<[usize; 2]>,
+    /// The bot's search for where it fires next.
+    thinking: Option<bot::Thinking<Option<[usize; 2]>>>,
+    /// What the last shot did.
+    report: Option<(String, Color)>,
+    /// Why the last placement or shot was rejected.
+    notice: Option<&'static str>,
+    cursor: Cursor,
+    state: TableState,
+    colors: TableColors,
+}
+
+impl Battleship {
+    pub fn new() -> Self {
+        let mut target = Waters::new();
+        target.place_rest();
+        Self {
+            home: Waters::new(),
+            target,
+            horizontal: true,
+            turn: Player::Human,
+            starter: Player::Human,
+            opponent: bot::Opponent::Minimax,
+            outcome: None,
+            last_bot_shot: None,
+            thinking: None,
+            report: None,
+            notice: None,
+            cursor: Cursor::new(SIDE, SIDE),
+            state: TableState::default(),
+            colors: TableColors::new(&PALETTES[0]),
+        }
+    }
+
+    /// Start a new game with a fresh fleet to place, with the other side opening.
+    fn rematch(&mut self) {
+        let starter = self.starter.other();
+        *self = Self {
+            colors: self.colors,
+            turn: starter,
+            starter,
+            opponent: self.opponent,
+            ..Self::new()
+        };
+    }
+
+    /// Whether the human is still placing ships.
+    fn placing(&self) -> bool {
+        self.home.ships.len() < FLEET.len()
+    }
+
+    /// Fire the shot the bot settled on.
+    fn bot_move(&mut self, cell: [usize; 2]) {
+        let shot = self.home.fire(cell);
+        self.last_bot_shot = Some(cell);
+        self.report = match (shot, self.home.ship_at(cell)) {
+            (Some(Shot::Sunk), Some(ship)) => {
+                Some((format!("The bot sank your {}!", FLEET[ship].0), Color::Red))
+            }
+            (Some(Shot::Hit), Some(ship)) => {
+                Some((format!("The bot hit your {}", FLEET[ship].0), Color::Red))
+            }
+            _ => Some(("The bot missed".into(), self.colors.row_fg)),
+        };
+        self.end_turn();
+    }
+
+    /// Hand the turn over, or end the game if the fleet fired at is gone.
+    fn end_turn(&mut self) {
+        if self.target.defeated() {
+            self.outcome = Some(Outcome::Winner(Player::Human));
+        } else if self.home.defeated() {
+            self.outcome = Some(Outcome::Winner(Player::Bot));
+        }
+        self.turn = self.turn.other();
+    }
+
+    fn render_title(&self) -> Line<'static> {
+        let versus = format!("Battleship · You VS Bot [{}]", self.opponent.label());
+        let mut spans = vec![versus.into(), " · ".dark_gray()];
+        if self.outcome.is_none()
+            && !self.placing()
+            && let Some((report, color)) = &self.report
+        {
+            spans.extend([report.clone().fg(*color), " · ".dark_gray()]);
+        }
+        spans.push(match self.outcome {
+            Some(outcome) => format!("{} (r: rematch)", outcome.message()).into(),
+            None if let Some(notice) = self.notice => notice.fg(Color::Red),
+            None if let Some((name, length)) = FLEET.get(self.home.ships.len()) => {
+                format!("Place your {name} ({length}) · t: turn · p: place the rest")
+                    .fg(self.colors.selected_cell_style_fg)
+            }
+            None if self.turn == Player::Bot => "Bot aiming…".fg(self.colors.row_fg),
+            None => "Your turn · fire away".fg(self.colors.selected_cell_style_fg),
+        });
+        Line::from(spans)
+    }
+
+    /// How a cell of the human's own waters looks: the fleet, and the bot's shots at it.
+    fn render_home(&self, cell: [usize; 2]) -> (String, Style) {
+        let [row, col] = cell;
+        let water = Style::default().bg(tailwind::BLUE.c950);
+        let ghost = match self.placing() {
+            true => self.home.next_ship(self.cursor.cell(), self.horizontal),
+            false => None,
+        };
+        if ghost.is_some_and(|ghost| ghost.contains(&cell)) {
+            return ("■".into(), water.bg(self.colors.hint_cell_style_bg));
+        }
+        let style = match self.last_bot_shot == Some(cell) {
+            true => water.bg(tailwind::BLUE.c800),
+            false => water,
+        };
+        match (self.home.shots[row][col], self.home.ship_at(cell)) {
+            (Some(Shot::Miss), _) => ("·".into(), style.fg(tailwind::SLATE.c400)),
+            (Some(Shot::Hit), _) => ("✱".into(), style.fg(Color::Red)),
+            (Some(Shot::Sunk), _) => ("✱".into(), style.bg(tailwind::RED.c900).fg(Color::Red)),
+            (None, Some(_)) => ("■".into(), style.fg(tailwind::SLATE.c400)),
+            (None, None) => (String::new(), style),
+        }
+    }
+
+    /// How a cell of the bot's waters looks: the human's shots, and once the game is over,
+    /// the ships they missed.
+    fn render_target(&self, cell: [usize; 2]) -> (String, Style) {
+        let [row, col] = cell;
+        let water = Style::default().bg(tailwind::BLUE.c950);
+        match (self.target.shots[row][col], self.target.ship_at(cell)) {
+            (Some(Shot::Miss), _) => ("·".into(), water.fg(tailwind::SLATE.c400)),
+            (Some(Shot::Hit), _) => ("✱".into(), water.fg(tailwind::AMBER.c400)),
+            (Some(Shot::Sunk), _) => ("✱".into(), water.bg(tailwind::RED.c900).fg(Color::Red)),
+            (None, Some(_)) if self.outcome.is_some() => {
+                ("■".into(), water.fg(tailwind::SLATE.c500))
+            }
+            (None, _) => (String::new(), water),
+        }
+    }
+}
+
+impl Game for Battleship {
+    /// Where to place the next ship's bow while placing the fleet, then where to fire.
+    type Move = [usize; 2];
+
+    /// Both boards side by side, a column apart.
+    fn dimensions(&self) -> (usize, usize) {
+        (SIDE, 2 * SIDE + 1)
+    }
+
+    fn legal_moves(&self) -> Vec<[usize; 2]> {
+        match self.placing() {
+            true => cells()
+                .filter(|&bow| self.home.next_ship(bow, self.horizontal).is_some())
+                .collect(),
+            false if self.outcome.is_some() || self.turn != Player::Human => Vec::new(),
+            false => cells()
+                .filter(|&[row, col]| self.target.shots[row][col].is_none())
+                .collect(),
+        }
+    }
+
+    fn apply(&mut self, cell: [usize; 2]) -> bool {
+        if !self.legal_moves().contains(&cell) {
+            return false;
+        }
+        if let Some(ship) = self.home.next_ship(cell, self.horizontal) {
+            self.home.ships.push(ship);
+            return true;
+        }
+        let shot = self.target.fire(cell);
+        self.report = match (shot, self.target.ship_at(cell)) {
+            (Some(Shot::Sunk), Some(ship)) => Some((
+                format!("You sank the bot's {}!", FLEET[ship].0),
+                self.colors.selected_cell_style_fg,
+            )),
+            (Some(Shot::Hit), _) => Some(("Hit!".into(), self.colors.selected_cell_style_fg)),
+            _ => Some(("Miss".into(), self.colors.row_fg)),
+        };
+        self.end_turn();
+        true
+    }
+
+    fn status(&self) -> Status {
+        match self.outcome {
+            None => Status::Playing,
+            Some(Outcome::Winner(Player::Human)) => Status::Won,
+            Some(Outcome::Winner(Player::Bot)) => Status::Lost,
+            Some(Outcome::Draw) => Status::Draw,
+        }
+    }
+
+    fn opponent(&self) -> Option<bot::Opponent> {
+        Some(self.opponent)
+    }
+
+    fn render_cell(&self, [row, col]: [usize; 2]) -> (String, Style) {
+        match col {
+            ..SIDE => self.render_home([row, col]),
+            SIDE => (String::new(), Style::default().bg(self.colors.buffer_bg)),
+            _ => self.render_target([row, col - SIDE - 1]),
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        self.notice = None;
+        match key.code {
+            KeyCode::Char('r') => self.rematch(),
+            KeyCode::Char('b') => self.opponent = self.opponent.next(),
+            KeyCode::Char('t') if self.placing() => self.horizontal = !self.horizontal,
+            KeyCode::Char('p') if self.placing() => self.home.place_rest(),
+            // the boards are frozen once the game is decided
+            _ if self.outcome.is_some() || (!self.placing() && self.turn != Player::Human) => {}
+            KeyCode::Char(' ') | KeyCode::Enter => {
+                if !self.apply(self.cursor.cell()) {
+                    self.notice = Some(match self.placing() {
+                        true => "It doesn't fit there!",
+                        false => "You already fired there!",
+                    });
+                }
+            }
+            code => {
+                self.cursor.move_with(code);
+            }
+        }
+    }
+
+    /// Aim the bot's shot, from what its earlier shots found, once the human's splash has
+    /// been seen, and fire it.
+    fn tick(&mut self) -> bool {
+        if self.outcome.is_some() || self.placing() || self.turn != Player::Bot {
+            return false;
+        }
+        let (shots, afloat, opponent) = (self.home.shots, self.home.afloat(), self.opponent);
+        let thinking = self
+            .thinking
+            .get_or_insert_with(|| bot::Thinking::start(move || aim(&shots, &afloat, opponent)));
+        let answer = match thinking.answer() {
+            bot::Answer::Pending => return false,
+            bot::Answer::Ready(answer) => answer,
+            bot::Answer::Failed => {
+                aim(&self.home.shots, &self.home.afloat(), bot::Opponent::Random)
+            }
+        };
+        self.thinking = None;
+        if let Some(cell) = answer {
+            self.bot_move(cell);
+        }
+        true
+    }
+
+    /// `p` places the rest of the fleet rather than opening the pause menu.
+    fn uses_key(&self, code: KeyCode) -> bool {
+        code == KeyCode::Char('p')
+    }
+
+    fn apply_settings(&mut self, settings: &Settings) {
+        self.colors = settings.colors();
+        self.opponent = settings.opponent(self.opponent);
+    }
+
+    fn restart(&mut self) {
+        self.rematch();
+    }
+
+    fn help(&self) -> Help {
+        Help {
+            rules: "Place your fleet on the left board, then take turns with the bot firing at \
+                    each other's waters. The first to sink every ship of the other side wins."
+                .into(),
+            keys: vec![
+                ("←↑↓→/wasd", "move the cursor"),
+                ("Space/Enter", "place a ship, or fire"),
+                ("t", "turn the ship being placed"),
+                ("p", "place the rest of the fleet at random"),
+                ("b", "switch bot"),
+                ("r", "rematch"),
+            ],
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [area, help_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(frame.area());
+        let help = match (self.outcome, self.placing()) {
+            (Some(_), _) => "r rematch · b switch bot",
+            (None, true) => "←↑↓→/wasd move · Space place · t turn · p place the rest · r restart",
+            (None, false) => "←↑↓→/wasd move · Space fire · b switch bot · r rematch",
+        };
+        frame.render_widget(Paragraph::new(help).centered().dark_gray(), help_area);
+        let (rows, cols) = self.dimensions();
+        // three columns to a cell when both boards fit that wide, otherwise two
+        let cell_width = match area.width >= table_extent(cols, 3, 1) {
+            true => 3,
+            false => 2,
+        };
+        let table_width = table_extent(cols, cell_width, 1);
+        let table_height = table_extent(rows, 1, 0);
+        // a line above the boards names them
+        let min_width = table_width.max(60);
+        let min_height = table_height + 2;
+        if area.width < min_width || area.height < min_height {
+            grid::render_too_small(frame, area, min_width, min_height);
+            return;
+        }
+
+        let layout = calculate_layout(area, false, table_width, table_height + 1);
+        let [labels, board] =
+            Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(layout.table);
+        let [home_label, _, target_label] = Layout::horizontal([
+            Constraint::Fill(1),
+            Constraint::Length(cell_width),
+            Constraint::Fill(1),
+        ])
+        .areas(labels);
+        let viewport = Viewport {
+            row: 0,
+            col: 0,
+            rows,
+            cols,
+        };
+        // the cursor sits on the fleet while placing it, then on the bot's waters
+        let cursor_col = match self.placing() {
+            true => self.cursor.col,
+            false => self.cursor.col + SIDE + 1,
+        };
+        self.state.select(Some(self.cursor.row));
+        self.state.select_column(Some(cursor_col));
+
+        frame.render_widget(Paragraph::new(self.render_title()).centered(), layout.title);
+        frame.render_widget(
+            Paragraph::new("Your fleet".fg(self.colors.row_fg)).centered(),
+            home_label,
+        );
+        frame.render_widget(
+            Paragraph::new("Your shots".fg(self.colors.row_fg)).centered(),
+            target_label,
+        );
+        let cursor_style = match self.legal_moves().is_empty() {
+            false => Style::default().add_modifier(Modifier::REVERSED),
+            true => Style::default(),
+        };
+        let table = grid::table(self, viewport, cell_width, 1, &self.colors)
+            .cell_highlight_style(cursor_style);
+        frame.render_stateful_widget(table, board, &mut self.state);
+    }
+}
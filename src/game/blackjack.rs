@@ -0,0 +1,661 @@
+//! Blackjack against the dealer, played from a six-deck shoe.
+//!
+//! Each round starts with a bet from the bankroll, which carries over between runs. Hands
+//! can be hit, stood, doubled or split; the dealer then draws to 17 and stands on every 17,
+//! and a blackjack pays 3:2.
+
+use std::time::Instant;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use rand::seq::SliceRandom;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style, Stylize, palette::tailwind},
+    text::{Line, Span, Text},
+    widgets::Paragraph,
+};
+
+use crate::{
+    bot,
+    game::{Game, Help, Status},
+    grid, records,
+    settings::Settings,
+    ui::{
+        layout::center,
+        palette::{PALETTES, TableColors},
+    },
+};
+
+/// The record the bankroll is kept under.
+const BANKROLL_RECORD: &str = "blackjack-bankroll";
+
+/// The chips a new player starts with, and is given again once they can't cover a bet.
+const STARTING_BANKROLL: u64 = 1000;
+
+/// The smallest bet, and the step bets are raised and lowered by.
+const MIN_BET: u64 = 10;
+
+/// The step a bet is raised and lowered by with the up and down keys.
+const BIG_STEP: u64 = 100;
+
+/// How many decks the shoe holds.
+const DECKS: usize = 6;
+
+/// The shoe is shuffled again once fewer cards than this are left in it.
+const RESHUFFLE_AT: usize = 52;
+
+/// How many hands splitting can make.
+const MAX_HANDS: usize = 4;
+
+const RANKS: [&str; 13] = [
+    "A", "2", "3", "4", "5", "6", "7", "8", "9", "10", "J", "Q", "K",
+];
+const SUITS: [&str; 4] = ["♠", "♥", "♦", "♣"];
+
+/// A playing card, by its index into [`RANKS`] and [`SUITS`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Card {
+    rank: usize,
+    suit: usize,
+}
+
+impl Card {
+    /// What the card counts for, an ace as one.
+    const fn value(self) -> u32 {
+        match self.rank {
+            rank @ 0..9 => rank as u32 + 1,
+            _ => 10,
+        }
+    }
+
+    fn label(self) -> String {
+        format!("{}{}", RANKS[self.rank], SUITS[self.suit])
+    }
+
+    const fn color(self) -> Color {
+        match self.suit {
+            1 | 2 => tailwind::RED.c600,
+            _ => tailwind::SLATE.c950,
+        }
+    }
+}
+
+/// The best total of `cards`, counting an ace as eleven when that doesn't bust, and whether
+/// one is counted so.
+fn total(cards: &[Card]) -> (u32, bool) {
+    let hard: u32 = cards.iter().map(|card| card.value()).sum();
+    match cards.iter().any(|card| card.rank == 0) && hard + 10 <= 21 {
+        true => (hard + 10, true),
+        false => (hard, false),
+    }
+}
+
+fn is_blackjack(cards: &[Card]) -> bool {
+    cards.len() == 2 && total(cards).0 == 21
+}
+
+/// How a hand of the player's came out against the dealer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Verdict {
+    Blackjack,
+    Win,
+    Push,
+    Lose,
+    Bust,
+}
+
+impl Verdict {
+    /// The chips paid back for a hand that staked `bet`, the stake included.
+    const fn payout(self, bet: u64) -> u64 {
+        match self {
+            Self::Blackjack => bet + bet * 3 / 2,
+            Self::Win => bet * 2,
+            Self::Push => bet,
+            Self::Lose | Self::Bust => 0,
+        }
+    }
+
+    const fn label(self) -> &'static str {
+        match self {
+            Self::Blackjack => "Blackjack!",
+            Self::Win => "Win",
+            Self::Push => "Push",
+            Self::Lose => "Lose",
+            Self::Bust => "Bust",
+        }
+    }
+}
+
+/// One of the player's hands, more than one after a split.
+#[derive(Debug, Clone)]
+struct Hand {
+    cards: Vec<Card>,
+    bet: u64,
+    /// Whether the hand takes no more cards.
+    done: bool,
+    /// Whether the hand came from splitting aces, which take a single card each.
+    split_aces: bool,
+    verdict: Option<Verdict>,
+}
+
+impl Hand {
+    fn new(cards: Vec<Card>, bet: u64) -> Self {
+        Self {
+            cards,
+            bet,
+            done: false,
+            split_aces: false,
+            verdict: None,
+        }
+    }
+}
+
+/// A decision for the hand being played.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Hit,
+    Stand,
+    Double,
+    Split,
+}
+
+/// Where a round stands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Phase {
+    Betting,
+    /// The player decides on each hand in turn.
+    Playing,
+    /// The dealer turns the hole card over and draws.
+    Dealer,
+    /// Every hand is paid out, until the next bet.
+    Settled,
+}
+
+/// A table of Blackjack.
+pub struct Blackjack {
+    shoe: Vec<Card>,
+    bankroll: u64,
+    /// The bankroll this session started with, to tell whether it ended up or down.
+    starting: u64,
+    bet: u64,
+    dealer: Vec<Card>,
+    hole_shown: bool,
+    hands: Vec<Hand>,
+    /// The hand being played, as an index into `hands`.
+    active: usize,
+    phase: Phase,
+    /// When the dealer plays its next card, while it plays its hand.
+    dealer_step_at: Option<Instant>,
+    notice: Option<&'static str>,
+    colors: TableColors,
+}
+
+impl Blackjack {
+    pub fn new() -> Self {
+        let bankroll = records::load(BANKROLL_RECORD).unwrap_or(STARTING_BANKROLL);
+        let mut table = Self {
+            shoe: Vec::new(),
+            bankroll,
+            starting: bankroll,
+            bet: MIN_BET,
+            dealer: Vec::new(),
+            hole_shown: false,
+            hands: Vec::new(),
+            active: 0,
+            phase: Phase::Betting,
+            dealer_step_at: None,
+            notice: None,
+            colors: TableColors::new(&PALETTES[0]),
+        };
+        table.refill();
+        table.bet = (5 * MIN_BET).min(table.bankroll);
+        table
+    }
+
+    /// Give a player who can't cover the smallest bet a fresh bankroll.
+    fn refill(&mut self) {
+        if self.bankroll < MIN_BET {
+            self.bankroll = STARTING_BANKROLL;
+            records::save(BANKROLL_RECORD, self.bankroll);
+            self.notice = Some("Out of chips: the house stakes you again");
+        }
+    }
+
+    fn draw_card(&mut self) -> Card {
+        if self.shoe.is_empty() {
+            self.shuffle();
+        }
+        self.shoe.pop().unwrap_or(Card { rank: 0, suit: 0 })
+    }
+
+    fn shuffle(&mut self) {
+        self.shoe = (0..DECKS)
+            .flat_map(|_| (0..13).flat_map(|rank| (0..4).map(move |suit| Card { rank, suit })))
+            .collect();
+        self.shoe.shuffle(&mut rand::rng());
+    }
+
+    /// Raise or lower the bet by `step` chips, keeping it within the bankroll.
+    fn change_bet(&mut self, step: i64) {
+        let bet = self.bet.saturating_add_signed(step);
+        self.bet = bet.clamp(MIN_BET, self.bankroll.max(MIN_BET));
+    }
+
+    /// Stake the bet and deal the opening cards.
+    fn deal(&mut self) {
+        if self.shoe.len() < RESHUFFLE_AT {
+            self.shuffle();
+            self.notice = Some("The shoe is shuffled");
+        } else {
+            self.notice = None;
+        }
+        self.bankroll -= self.bet;
+        records::save(BANKROLL_RECORD, self.bankroll);
+        let cards = vec![self.draw_card(), self.draw_card()];
+        self.dealer = vec![self.draw_card(), self.draw_card()];
+        self.hole_shown = false;
+        self.hands = vec![Hand::new(cards, self.bet)];
+        self.active = 0;
+        self.phase = Phase::Playing;
+        // a blackjack on either side ends the round at once
+        if is_blackjack(&self.dealer) || is_blackjack(&self.hands[0].cards) {
+            self.hole_shown = true;
+            self.settle();
+        }
+    }
+
+    /// Whether the hand being played may take `action`.
+    fn allows(&self, action: Action) -> bool {
+        let Some(hand) = self.hands.get(self.active) else {
+            return false;
+        };
+        if self.phase != Phase::Playing || hand.done {
+            return false;
+        }
+        let opening = hand.cards.len() == 2;
+        match action {
+            Action::Hit | Action::Stand => true,
+            Action::Double => opening && self.bankroll >= hand.bet,
+            Action::Split => {
+                opening
+                    && hand.cards[0].value() == hand.cards[1].value()
+                    && self.hands.len() < MAX_HANDS
+                    && self.bankroll >= hand.bet
+            }
+        }
+    }
+
+    /// Mark the hand being played as done if it can't take another card, and move on to the
+    /// next one; once all are done the dealer plays.
+    fn advance(&mut self) {
+        let hand = &mut self.hands[self.active];
+        if total(&hand.cards).0 >= 21 || hand.split_aces {
+            hand.done = true;
+        }
+        if !hand.done {
+            return;
+        }
+        match self.hands.iter().position(|hand| !hand.done) {
+            Some(next) => self.active = next,
+            None => self.phase = Phase::Dealer,
+        }
+    }
+
+    /// Let the dealer turn the hole card over or draw one card, settling the round once it
+    /// stands; the dealer stands on every 17, and doesn't draw when every hand is bust.
+    fn dealer_step(&mut self) {
+        if !self.hole_shown {
+            self.hole_shown = true;
+            return;
+        }
+        let all_bust = self.hands.iter().all(|hand| total(&hand.cards).0 > 21);
+        if total(&self.dealer).0 < 17 && !all_bust {
+            let card = self.draw_card();
+            self.dealer.push(card);
+            return;
+        }
+        self.settle();
+    }
+
+    /// Pay out every hand against the dealer's.
+    fn settle(&mut self) {
+        let dealer = total(&self.dealer).0;
+        let dealer_blackjack = is_blackjack(&self.dealer);
+        // blackjack only counts on the opening hand, not on one made by splitting
+        let single = self.hands.len() == 1;
+        for hand in &mut self.hands {
+            let player = total(&hand.cards).0;
+            let verdict = if player > 21 {
+                Verdict::Bust
+            } else if single && is_blackjack(&hand.cards) {
+                match dealer_blackjack {
+                    true => Verdict::Push,
+                    false => Verdict::Blackjack,
+                }
+            } else if dealer_blackjack || (dealer <= 21 && dealer > player) {
+                Verdict::Lose
+            } else if dealer == player {
+                Verdict::Push
+            } else {
+                Verdict::Win
+            };
+            hand.verdict = Some(verdict);
+            hand.done = true;
+            self.bankroll += verdict.payout(hand.bet);
+        }
+        records::save(BANKROLL_RECORD, self.bankroll);
+        self.phase = Phase::Settled;
+    }
+
+    /// Start betting on the next round.
+    fn next_round(&mut self) {
+        self.phase = Phase::Betting;
+        self.notice = None;
+        self.refill();
+        self.bet = self.bet.clamp(MIN_BET, self.bankroll);
+    }
+
+    fn render_title(&self) -> Line<'static> {
+        let status = match self.phase {
+            Phase::Betting => "Place your bet".fg(self.colors.selected_cell_style_fg),
+            Phase::Playing => "Your move".fg(self.colors.selected_cell_style_fg),
+            Phase::Dealer => "Dealer plays…".fg(self.colors.row_fg),
+            Phase::Settled => {
+                let staked: u64 = self.hands.iter().map(|hand| hand.bet).sum();
+                let paid: u64 = self
+                    .hands
+                    .iter()
+                    .filter_map(|hand| Some(hand.verdict?.payout(hand.bet)))
+                    .sum();
+                let net = paid as i64 - staked as i64;
+                format!("Round over: {net:+} chips").fg(self.colors.selected_cell_style_fg)
+            }
+        };
+        let mut spans = vec![
+            format!("Blackjack · Bankroll {} · Bet {}", self.bankroll, self.bet).into(),
+            " · ".dark_gray(),
+            status,
+        ];
+        if let Some(notice) = self.notice {
+            spans.extend([" · ".dark_gray(), notice.fg(self.colors.row_fg)]);
+        }
+        Line::from(spans)
+    }
+
+    /// A row of cards three lines tall, the ones left out by [`Game::render_cell`] skipped.
+    fn render_cards(&self, row: usize) -> [Line<'static>; 3] {
+        let (_, cols) = self.dimensions();
+        let mut lines: [Vec<Span>; 3] = Default::default();
+        for col in 0..cols {
+            let (label, style) = self.render_cell([row, col]);
+            if label.is_empty() {
+                break;
+            }
+            let face = Style::default().bg(tailwind::SLATE.c50);
+            lines[0].push(Span::styled("┌───┐", face.fg(tailwind::SLATE.c400)));
+            lines[1].push(Span::styled(format!("│{label:<3}│"), style));
+            lines[2].push(Span::styled("└───┘", face.fg(tailwind::SLATE.c400)));
+            for line in &mut lines {
+                line.push(Span::raw(" "));
+            }
+        }
+        lines.map(Line::from)
+    }
+
+    /// The heading over a hand: its total, bet and how it came out.
+    fn render_heading(&self, row: usize) -> Line<'static> {
+        if row == 0 {
+            let shown = match (self.dealer.is_empty(), self.hole_shown) {
+                (true, _) => String::new(),
+                (false, true) => format!(" · {}", total(&self.dealer).0),
+                (false, false) => " · ?".into(),
+            };
+            return Line::from(format!("Dealer{shown}")).bold();
+        }
+        let index = row - 1;
+        let hand = &self.hands[index];
+        let (value, soft) = total(&hand.cards);
+        let soft = if soft && value < 21 { "soft " } else { "" };
+        let active = self.phase == Phase::Playing && index == self.active && !hand.done;
+        let marker = if active { "▸ " } else { "  " };
+        let mut spans = vec![Span::raw(format!(
+            "{marker}Hand {} · {soft}{value} · Bet {}",
+            index + 1,
+            hand.bet
+        ))];
+        if let Some(verdict) = hand.verdict {
+            let color = match verdict {
+                Verdict::Blackjack | Verdict::Win => tailwind::GREEN.c400,
+                Verdict::Push => self.colors.row_fg,
+                Verdict::Lose | Verdict::Bust => tailwind::RED.c400,
+            };
+            spans.push(" · ".dark_gray());
+            spans.push(verdict.label().fg(color).bold());
+        }
+        let line = Line::from(spans);
+        match active {
+            true => line.fg(self.colors.selected_cell_style_fg).bold(),
+            false => line,
+        }
+    }
+
+    fn help(&self) -> String {
+        match self.phase {
+            Phase::Betting => "←/→ bet ±10 · ↑/↓ bet ±100 · Enter deal".into(),
+            Phase::Playing => {
+                let mut keys = vec!["h hit", "s stand"];
+                if self.allows(Action::Double) {
+                    keys.push("d double");
+                }
+                if self.allows(Action::Split) {
+                    keys.push("p split");
+                }
+                keys.join(" · ")
+            }
+            Phase::Dealer => String::new(),
+            Phase::Settled => "Enter next round".into(),
+        }
+    }
+}
+
+impl Game for Blackjack {
+    type Move = Action;
+
+    /// The dealer's cards, then a row for each of the player's hands.
+    fn dimensions(&self) -> (usize, usize) {
+        let longest = self
+            .hands
+            .iter()
+            .map(|hand| hand.cards.len())
+            .chain([self.dealer.len()])
+            .max()
+            .unwrap_or(0);
+        (1 + self.hands.len(), longest)
+    }
+
+    fn legal_moves(&self) -> Vec<Action> {
+        [Action::Hit, Action::Stand, Action::Double, Action::Split]
+            .into_iter()
+            .filter(|&action| self.allows(action))
+            .collect()
+    }
+
+    fn apply(&mut self, action: Action) -> bool {
+        if !self.allows(action) {
+            return false;
+        }
+        match action {
+            Action::Hit => {
+                let card = self.draw_card();
+                self.hands[self.active].cards.push(card);
+            }
+            Action::Stand => self.hands[self.active].done = true,
+            Action::Double => {
+                let card = self.draw_card();
+                let hand = &mut self.hands[self.active];
+                self.bankroll -= hand.bet;
+                hand.bet *= 2;
+                hand.cards.push(card);
+                hand.done = true;
+            }
+            Action::Split => {
+                let hand = &mut self.hands[self.active];
+                let bet = hand.bet;
+                let aces = hand.cards[0].rank == 0;
+                let moved = hand.cards.pop().into_iter().collect();
+                self.bankroll -= bet;
+                self.hands.insert(self.active + 1, Hand::new(moved, bet));
+                for index in [self.active, self.active + 1] {
+                    let card = self.draw_card();
+                    let hand = &mut self.hands[index];
+                    hand.cards.push(card);
+                    hand.split_aces = aces;
+                    hand.done = aces;
+                }
+            }
+        }
+        records::save(BANKROLL_RECORD, self.bankroll);
+        self.advance();
+        true
+    }
+
+    /// Whether the session has left the player up or down on the bankroll it started with;
+    /// breaking even, or a round still being played, counts as unfinished.
+    fn status(&self) -> Status {
+        if matches!(self.phase, Phase::Playing | Phase::Dealer) {
+            return Status::Playing;
+        }
+        match self.bankroll.cmp(&self.starting) {
+            std::cmp::Ordering::Greater => Status::Won,
+            std::cmp::Ordering::Less => Status::Lost,
+            std::cmp::Ordering::Equal => Status::Playing,
+        }
+    }
+
+    /// A card of the dealer's, in the first row, or of one of the player's hands; the hole
+    /// card is face down until the dealer plays.
+    fn render_cell(&self, [row, col]: [usize; 2]) -> (String, Style) {
+        let cards = match row {
+            0 => &self.dealer,
+            row => &self.hands[row - 1].cards,
+        };
+        let Some(&card) = cards.get(col) else {
+            return (String::new(), Style::default());
+        };
+        let face = Style::default()
+            .bg(tailwind::SLATE.c50)
+            .add_modifier(Modifier::BOLD);
+        match row == 0 && col == 1 && !self.hole_shown {
+            true => ("░░░".into(), face.fg(tailwind::BLUE.c700)),
+            false => (card.label(), face.fg(card.color())),
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        match (self.phase, key.code) {
+            (Phase::Betting, KeyCode::Left | KeyCode::Char('a')) => {
+                self.change_bet(-(MIN_BET as i64))
+            }
+            (Phase::Betting, KeyCode::Right | KeyCode::Char('d')) => {
+                self.change_bet(MIN_BET as i64)
+            }
+            (Phase::Betting, KeyCode::Down | KeyCode::Char('s')) => {
+                self.change_bet(-(BIG_STEP as i64));
+            }
+            (Phase::Betting, KeyCode::Up | KeyCode::Char('w')) => self.change_bet(BIG_STEP as i64),
+            (Phase::Betting, KeyCode::Enter | KeyCode::Char(' ')) => self.deal(),
+            (Phase::Playing, KeyCode::Char('h')) => {
+                self.apply(Action::Hit);
+            }
+            (Phase::Playing, KeyCode::Char('s')) => {
+                self.apply(Action::Stand);
+            }
+            (Phase::Playing, KeyCode::Char('d')) => {
+                self.apply(Action::Double);
+            }
+            (Phase::Playing, KeyCode::Char('p')) => {
+                self.apply(Action::Split);
+            }
+            (Phase::Settled, KeyCode::Enter | KeyCode::Char(' ')) => self.next_round(),
+            _ => {}
+        }
+    }
+
+    /// Let the dealer play a card at a time once every hand is done.
+    fn tick(&mut self) -> bool {
+        if self.phase != Phase::Dealer {
+            self.dealer_step_at = None;
+            return false;
+        }
+        let step_at = *self
+            .dealer_step_at
+            .get_or_insert_with(|| Instant::now() + bot::BOT_DELAY);
+        if Instant::now() < step_at {
+            return false;
+        }
+        self.dealer_step_at = None;
+        self.dealer_step();
+        true
+    }
+
+    /// `h` hits rather than moving the cursor, and `p` splits rather than opening the pause
+    /// menu.
+    fn uses_key(&self, code: KeyCode) -> bool {
+        matches!(code, KeyCode::Char('h' | 'p'))
+    }
+
+    fn apply_settings(&mut self, settings: &Settings) {
+        self.colors = settings.colors();
+    }
+
+    fn restart(&mut self) {
+        *self = Self {
+            colors: self.colors,
+            ..Self::new()
+        };
+    }
+
+    fn help(&self) -> Help {
+        Help {
+            rules: "Bet from your bankroll, then draw cards to get closer to 21 than the dealer \
+                    without going over. Aces count 1 or 11 and faces 10. The dealer draws to 17, \
+                    and a blackjack pays 3:2."
+                .into(),
+            keys: vec![
+                ("←/→ ↑/↓", "change the bet"),
+                ("Space/Enter", "deal, or start the next round"),
+                ("h", "hit"),
+                ("s", "stand"),
+                ("d", "double"),
+                ("p", "split"),
+            ],
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let area = frame.area();
+        let (rows, _) = self.dimensions();
+        // a heading and a row of cards for the dealer and as many hands as splitting makes
+        let width = 80;
+        let height = 2 + 5 * (1 + MAX_HANDS as u16) + 1;
+        if area.width < width || area.height < height {
+            grid::render_too_small(frame, area, width, height);
+            return;
+        }
+
+        let area = center(area, Constraint::Length(width), Constraint::Percentage(100));
+        let mut constraints = vec![Constraint::Length(2)];
+        constraints.extend((0..rows).map(|_| Constraint::Length(5)));
+        constraints.extend([Constraint::Fill(1), Constraint::Length(1)]);
+        let areas = Layout::vertical(constraints).split(area);
+        frame.render_widget(Paragraph::new(self.render_title()).centered(), areas[0]);
+        for row in 0..rows {
+            let mut lines = vec![self.render_heading(row)];
+            lines.extend(self.render_cards(row));
+            frame.render_widget(Paragraph::new(Text::from(lines)), areas[row + 1]);
+        }
+        let help = Paragraph::new(self.help()).centered().dark_gray();
+        frame.render_widget(help, areas[rows + 2]);
+    }
+}
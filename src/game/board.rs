@@ -0,0 +1,534 @@
+//! The rules of the games of placing marks in a row: the two sides, which moves are legal,
+//! which lines win and how a game ends.
+
+use serde::{Deserialize, Serialize};
+
+use crate::game::ultimate;
+
+/// One of the two sides taking turns on the board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Player {
+    Human,
+    Bot,
+}
+
+impl Player {
+    /// The mark this player writes into the board.
+    pub const fn symbol(self) -> &'static str {
+        match self {
+            Self::Human => "X",
+            Self::Bot => "O",
+        }
+    }
+
+    /// The player whose turn comes after this one.
+    pub const fn other(self) -> Self {
+        match self {
+            Self::Human => Self::Bot,
+            Self::Bot => Self::Human,
+        }
+    }
+}
+
+/// A cell of the board, counting rows from the top and columns from the left.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Pos {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Pos {
+    pub const fn new(row: usize, col: usize) -> Self {
+        Self { row, col }
+    }
+
+    /// The middle cell of a `rows`×`cols` board.
+    pub const fn center(rows: usize, cols: usize) -> Self {
+        Self::new(rows / 2, cols / 2)
+    }
+
+    /// The cell as a `[row, column]` pair.
+    pub const fn cell(self) -> [usize; 2] {
+        [self.row, self.col]
+    }
+
+    /// The cell below on a board `rows` high, wrapping round to the top row after the last.
+    pub const fn down(self, rows: usize) -> Self {
+        Self::new((self.row + 1) % rows, self.col)
+    }
+
+    /// The cell above on a board `rows` high, wrapping round to the bottom row from the top.
+    pub const fn up(self, rows: usize) -> Self {
+        Self::new((self.row + rows - 1) % rows, self.col)
+    }
+
+    /// The cell `step` columns to the right on a board `cols` wide, wrapping round to the left
+    /// edge.
+    pub const fn right(self, step: usize, cols: usize) -> Self {
+        Self::new(self.row, (self.col + step % cols) % cols)
+    }
+
+    /// The cell `step` columns to the left on a board `cols` wide, wrapping round to the right
+    /// edge.
+    pub const fn left(self, step: usize, cols: usize) -> Self {
+        Self::new(self.row, (self.col + cols - step % cols) % cols)
+    }
+}
+
+/// What a cell of the board holds: nothing yet, or one of the two marks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum CellState {
+    #[default]
+    Empty,
+    X,
+    O,
+}
+
+impl CellState {
+    pub const fn is_empty(self) -> bool {
+        matches!(self, Self::Empty)
+    }
+
+    /// The player whose mark fills the cell, if any.
+    pub const fn owner(self) -> Option<Player> {
+        match self {
+            Self::Empty => None,
+            Self::X => Some(Player::Human),
+            Self::O => Some(Player::Bot),
+        }
+    }
+
+    /// The mark as written into the board, or nothing for an empty cell.
+    pub const fn symbol(self) -> &'static str {
+        match self.owner() {
+            Some(player) => player.symbol(),
+            None => "",
+        }
+    }
+}
+
+/// The cell holding `player`'s mark.
+impl From<Player> for CellState {
+    fn from(player: Player) -> Self {
+        match player {
+            Player::Human => Self::X,
+            Player::Bot => Self::O,
+        }
+    }
+}
+
+/// How a finished game ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Outcome {
+    Winner(Player),
+    Draw,
+}
+
+impl Outcome {
+    /// The banner shown once the game is over.
+    pub const fn message(self) -> &'static str {
+        match self {
+            Self::Winner(Player::Human) => "You win!",
+            Self::Winner(Player::Bot) => "Bot wins!",
+            Self::Draw => "It's a draw!",
+        }
+    }
+
+    /// The letter standing for the outcome in a list of results, from the human's side.
+    pub const fn letter(self) -> char {
+        match self {
+            Self::Winner(Player::Human) => 'W',
+            Self::Winner(Player::Bot) => 'L',
+            Self::Draw => 'D',
+        }
+    }
+}
+
+/// The rules a game is played by, beyond the size of its board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Rules {
+    /// How many marks in a row win the game.
+    pub win_length: usize,
+    /// Marks drop to the lowest empty cell of the chosen column.
+    pub gravity: bool,
+    /// The board is a 3x3 grid of sub-boards and every move picks the sub-board the next one
+    /// must be played in.
+    pub ultimate: bool,
+    /// Completing a line loses the game instead of winning it.
+    pub misere: bool,
+    /// How many layers the board is stacked from; the layers are stored and shown side by side.
+    pub layers: usize,
+    /// Either player may place either mark, and whoever completes a line takes it.
+    pub wild: bool,
+    /// Both players place X and a board holding a full line is dead; whoever completes the
+    /// line on the last board takes the game, which [`Rules::misere`] turns into a loss.
+    pub notakto: bool,
+    /// How many separate boards are played side by side; lines never cross between them.
+    pub boards: usize,
+    /// The player out to complete a line of either mark, as Order; the other side, Chaos, wins
+    /// by filling the board without one.
+    pub order: Option<Player>,
+    /// Every move places two entangled marks that only become real once they collapse, as in
+    /// quantum tic-tac-toe.
+    pub quantum: bool,
+    /// Lines may run off one edge of the board and continue from the opposite one.
+    pub wrap: bool,
+    /// After the opening move the second player may take it over as their own, leaving the
+    /// first player to move again with the other mark.
+    pub swap: bool,
+}
+
+impl Rules {
+    /// Classic rules on a `size`×`size` board: a full row, column or diagonal wins.
+    pub const fn new(size: usize) -> Self {
+        Self {
+            win_length: size,
+            gravity: false,
+            ultimate: false,
+            misere: false,
+            layers: 1,
+            wild: false,
+            notakto: false,
+            boards: 1,
+            order: None,
+            quantum: false,
+            wrap: false,
+            swap: false,
+        }
+    }
+
+    /// The marks `player` may place.
+    pub fn marks(self, player: Player) -> Vec<Player> {
+        if self.notakto {
+            vec![Player::Human]
+        } else if self.wild {
+            vec![player, player.other()]
+        } else {
+            vec![player]
+        }
+    }
+}
+
+/// The name of `cell` as chess names squares, and as the labels around the board show it: its
+/// column as a letter, or letters past `z`, and its row as a number counting from 1.
+pub fn cell_name([row, col]: [usize; 2]) -> String {
+    column_name(col) + &(row + 1).to_string()
+}
+
+/// The letter naming column `col`, `a` for the first, going on with `aa` after `z`.
+pub fn column_name(col: usize) -> String {
+    let mut letters = Vec::new();
+    let mut col = col + 1;
+    while col > 0 {
+        col -= 1;
+        letters.push(char::from(b'a' + (col % 26) as u8));
+        col /= 26;
+    }
+    letters.iter().rev().collect()
+}
+
+/// The cell named `name` in the notation of [`cell_name`], such as `b2`, whether or not it
+/// lies on the board.
+pub fn parse_cell(name: &str) -> Option<[usize; 2]> {
+    let digits = name.find(|c: char| c.is_ascii_digit())?;
+    let (letters, number) = name.split_at(digits);
+    if letters.is_empty() || !letters.chars().all(|c| c.is_ascii_lowercase()) {
+        return None;
+    }
+    let col = letters.bytes().try_fold(0usize, |col, letter| {
+        col.checked_mul(26)?
+            .checked_add(usize::from(letter - b'a') + 1)
+    })?;
+    let row: usize = number.parse().ok()?;
+    Some([row.checked_sub(1)?, col - 1])
+}
+
+/// The lowest empty row of column `col`, where a mark dropped into it lands.
+pub fn landing_row(items: &[Vec<CellState>], col: usize) -> Option<usize> {
+    (0..items.len())
+        .rev()
+        .find(|&row| items[row][col].is_empty())
+}
+
+/// Every cell the player to move may play on after the `last` move, as `[row, column]` pairs.
+pub fn legal_moves(
+    items: &[Vec<CellState>],
+    rules: Rules,
+    last: Option<[usize; 2]>,
+) -> Vec<[usize; 2]> {
+    if rules.ultimate {
+        return ultimate::legal_moves(items, last);
+    }
+    if rules.gravity {
+        let cols = items.first().map_or(0, Vec::len);
+        return (0..cols)
+            .filter_map(|col| landing_row(items, col).map(|row| [row, col]))
+            .collect();
+    }
+    let dead = match rules.notakto {
+        true => dead_boards(
+            items,
+            &winning_lines(items.len(), items[0].len(), rules),
+            rules,
+        ),
+        false => vec![false; rules.boards],
+    };
+    let width = items[0].len() / rules.boards;
+    items
+        .iter()
+        .enumerate()
+        .flat_map(|(row, cells)| {
+            cells
+                .iter()
+                .enumerate()
+                .filter(|(_, cell)| cell.is_empty())
+                .map(move |(col, _)| [row, col])
+        })
+        .filter(|&[_, col]| !dead[col / width])
+        .collect()
+}
+
+/// The directions a line can run in, as steps in layer, row and column.
+const LINE_DIRECTIONS: [(isize, isize, isize); 13] = [
+    (0, 0, 1),
+    (0, 1, 0),
+    (0, 1, 1),
+    (0, 1, -1),
+    (1, 0, 0),
+    (1, 0, 1),
+    (1, 0, -1),
+    (1, 1, 0),
+    (1, -1, 0),
+    (1, 1, 1),
+    (1, 1, -1),
+    (1, -1, 1),
+    (1, -1, -1),
+];
+
+/// Every line of cells that wins the game on a `rows`×`cols` board, as `[row, column]` pairs.
+///
+/// These are all runs of [`Rules::win_length`] cells along a row, a column or a diagonal. When
+/// the board is stacked from several [`Rules::layers`], runs may also pass through the layers,
+/// but never from one of several [`Rules::boards`] into another. With [`Rules::wrap`] runs
+/// continue across the edges, as long as they do not come back round to their own cells.
+pub fn winning_lines(rows: usize, cols: usize, rules: Rules) -> Vec<Vec<[usize; 2]>> {
+    let length = rules.win_length as isize;
+    let (boards, layers) = (rules.boards as isize, rules.layers as isize);
+    let (rows, cols) = (rows as isize, cols as isize / (boards * layers));
+    let within = |start: isize, step: isize, size: isize| match rules.wrap {
+        true => step == 0 || length <= size,
+        false => (0..size).contains(&(start + step * (length - 1))),
+    };
+    let inside = |[layer, row, col]: [isize; 3], [d_layer, d_row, d_col]: [isize; 3]| {
+        within(layer, d_layer, layers) && within(row, d_row, rows) && within(col, d_col, cols)
+    };
+    let mut lines: Vec<Vec<[usize; 2]>> = Vec::new();
+    for board in 0..boards {
+        for layer in 0..layers {
+            for row in 0..rows {
+                for col in 0..cols {
+                    for (d_layer, d_row, d_col) in LINE_DIRECTIONS {
+                        if inside([layer, row, col], [d_layer, d_row, d_col]) {
+                            let line = (0..length)
+                                .map(|i| {
+                                    let layer = board * layers + layer + d_layer * i;
+                                    let row = (row + d_row * i).rem_euclid(rows);
+                                    let col = layer * cols + (col + d_col * i).rem_euclid(cols);
+                                    [row as usize, col as usize]
+                                })
+                                .collect();
+                            lines.push(line);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    if rules.wrap {
+        // a line as long as the board is found again from each of its cells
+        for line in &mut lines {
+            line.sort();
+        }
+        lines.sort();
+        lines.dedup();
+    }
+    lines
+}
+
+/// Which of the [`Rules::boards`] hold a full line of marks, whoever placed them.
+///
+/// In Notakto such a board is dead and takes no more marks.
+pub fn dead_boards(items: &[Vec<CellState>], lines: &[Vec<[usize; 2]>], rules: Rules) -> Vec<bool> {
+    let width = items[0].len() / rules.boards;
+    let mut dead = vec![false; rules.boards];
+    for line in lines {
+        if line.iter().all(|&[row, col]| !items[row][col].is_empty()) {
+            dead[line[0][1] / width] = true;
+        }
+    }
+    dead
+}
+
+/// Find one of `lines` fully owned by one player, together with that player.
+fn find_winning_line<'a>(
+    items: &[Vec<CellState>],
+    lines: &'a [Vec<[usize; 2]>],
+) -> Option<(Player, &'a [[usize; 2]])> {
+    [Player::Human, Player::Bot].into_iter().find_map(|player| {
+        lines
+            .iter()
+            .find(|line| {
+                line.iter()
+                    .all(|&[row, col]| items[row][col].owner() == Some(player))
+            })
+            .map(|line| (player, line.as_slice()))
+    })
+}
+
+/// Find a line fully owned by one player, together with that player.
+pub fn winning_line(items: &[Vec<CellState>], rules: Rules) -> Option<(Player, Vec<[usize; 2]>)> {
+    if rules.ultimate {
+        return ultimate::winning_line(items);
+    }
+    let lines = winning_lines(items.len(), items[0].len(), rules);
+    if rules.notakto {
+        // show the lines that killed each board
+        let cells: Vec<_> = lines
+            .iter()
+            .filter(|line| line.iter().all(|&[row, col]| !items[row][col].is_empty()))
+            .flatten()
+            .copied()
+            .collect();
+        return (!cells.is_empty()).then_some((Player::Human, cells));
+    }
+    find_winning_line(items, &lines).map(|(player, line)| (player, line.to_vec()))
+}
+
+/// Determine whether the board has been decided, `mover` having played the last move.
+///
+/// Returns `None` while the game is still open.
+pub fn check_winner(items: &[Vec<CellState>], rules: Rules, mover: Player) -> Option<Outcome> {
+    let outcome = if rules.ultimate {
+        ultimate::check_winner(items)
+    } else if rules.notakto {
+        // the game only ends with the move that kills the last board
+        let lines = winning_lines(items.len(), items[0].len(), rules);
+        let dead = dead_boards(items, &lines, rules);
+        dead.iter()
+            .all(|&dead| dead)
+            .then_some(Outcome::Winner(mover))
+    } else {
+        let lines = winning_lines(items.len(), items[0].len(), rules);
+        let full = items.iter().flatten().all(|cell| !cell.is_empty());
+        match (find_winning_line(items, &lines), rules.order) {
+            // a line of either mark wins for Order, and a full board without one for Chaos
+            (Some(_), Some(order)) => return Some(Outcome::Winner(order)),
+            (None, Some(order)) => return full.then_some(Outcome::Winner(order.other())),
+            (Some((player, _)), None) => Some(Outcome::Winner(player)),
+            (None, None) => full.then_some(Outcome::Draw),
+        }
+    };
+    // in wild play and Notakto a line goes to whoever completed it, whichever marks it is made
+    // of, and in misère play completing it hands the game to the other side
+    match outcome {
+        Some(Outcome::Winner(owner)) => {
+            let player = if rules.wild || rules.notakto {
+                mover
+            } else {
+                owner
+            };
+            Some(Outcome::Winner(if rules.misere {
+                player.other()
+            } else {
+                player
+            }))
+        }
+        outcome => outcome,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A board written a row at a time, with `X`, `O` and `.` for an empty cell.
+    fn board(rows: &[&str]) -> Vec<Vec<CellState>> {
+        rows.iter()
+            .map(|row| {
+                row.chars()
+                    .map(|c| match c {
+                        'X' => CellState::X,
+                        'O' => CellState::O,
+                        _ => CellState::Empty,
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn rows_columns_and_diagonals_win() {
+        let rules = Rules::new(3);
+        for rows in [
+            ["XXX", "OO.", "..."],
+            ["XO.", "XO.", "X.."],
+            ["XO.", "OX.", "..X"],
+            ["O.X", "OX.", "X.."],
+        ] {
+            let items = board(&rows);
+            assert_eq!(
+                check_winner(&items, rules, Player::Human),
+                Some(Outcome::Winner(Player::Human)),
+                "{rows:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn unfinished_board_has_no_winner() {
+        let items = board(&["XO.", ".X.", "O.."]);
+        assert_eq!(check_winner(&items, Rules::new(3), Player::Human), None);
+    }
+
+    #[test]
+    fn full_board_without_a_line_is_a_draw() {
+        let items = board(&["XOX", "XOO", "OXX"]);
+        assert_eq!(
+            check_winner(&items, Rules::new(3), Player::Human),
+            Some(Outcome::Draw)
+        );
+    }
+
+    #[test]
+    fn misere_line_loses() {
+        let rules = Rules {
+            misere: true,
+            ..Rules::new(3)
+        };
+        let items = board(&["XXX", "OO.", "..."]);
+        assert_eq!(
+            check_winner(&items, rules, Player::Human),
+            Some(Outcome::Winner(Player::Bot))
+        );
+    }
+
+    #[test]
+    fn shorter_win_length_on_a_larger_board() {
+        let rules = Rules {
+            win_length: 4,
+            ..Rules::new(6)
+        };
+        let items = board(&["......", "......", "......", ".OOOO.", "......", "......"]);
+        assert_eq!(
+            check_winner(&items, rules, Player::Bot),
+            Some(Outcome::Winner(Player::Bot))
+        );
+    }
+
+    #[test]
+    fn marks_drop_to_the_lowest_empty_row() {
+        let items = board(&["...", "...", "X.."]);
+        assert_eq!(landing_row(&items, 0), Some(1));
+        assert_eq!(landing_row(&items, 1), Some(2));
+    }
+}
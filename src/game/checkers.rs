@@ -0,0 +1,673 @@
+//! Checkers on an 8x8 board against the bot.
+//!
+//! Pieces move diagonally on the dark squares; captures are forced and a capturing piece keeps
+//! jumping for as long as it can. Men reaching the far row are crowned kings, which move both
+//! ways, and crowning ends the move.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use rand::seq::IndexedRandom;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style, Stylize, palette::tailwind},
+    text::Line,
+    widgets::{Paragraph, TableState},
+};
+
+use crate::{
+    Outcome, Player, bot,
+    game::{Game, Help, Status},
+    grid::{self, Cursor, Viewport},
+    settings::Settings,
+    ui::{
+        layout::{calculate_layout, cell_size, table_extent},
+        palette::{PALETTES, TableColors},
+    },
+};
+
+/// Cells along one side of the board.
+const SIDE: usize = 8;
+
+/// How many moves in a row without a capture or crowning end the game in a draw.
+const QUIET_MOVE_LIMIT: usize = 80;
+
+/// How many moves ahead the hard bot searches.
+const SEARCH_DEPTH: usize = 6;
+
+/// A score beyond any material count, for positions where a side has no move left.
+const WIN: i32 = 100_000;
+
+/// A man or king on the board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Piece {
+    owner: Player,
+    king: bool,
+}
+
+type Board = [[Option<Piece>; SIDE]; SIDE];
+
+/// A single diagonal hop of a piece, either a step or a jump over an enemy piece.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    pub from: [usize; 2],
+    pub to: [usize; 2],
+}
+
+impl Step {
+    const fn is_capture(self) -> bool {
+        self.from[0].abs_diff(self.to[0]) == 2
+    }
+
+    /// The cell jumped over by a capture.
+    const fn captured(self) -> [usize; 2] {
+        [
+            (self.from[0] + self.to[0]) / 2,
+            (self.from[1] + self.to[1]) / 2,
+        ]
+    }
+}
+
+/// The board at the start of a game: three rows of men for each side on the dark squares,
+/// with the human's at the bottom.
+fn initial_board() -> Board {
+    std::array::from_fn(|row| {
+        std::array::from_fn(|col| {
+            let owner = match row {
+                _ if (row + col) % 2 == 0 => return None,
+                0..3 => Player::Bot,
+                5.. => Player::Human,
+                _ => return None,
+            };
+            Some(Piece { owner, king: false })
+        })
+    })
+}
+
+/// The row a man of `owner` is crowned on.
+const fn crowning_row(owner: Player) -> usize {
+    match owner {
+        Player::Human => 0,
+        Player::Bot => SIDE - 1,
+    }
+}
+
+/// Every hop the piece on `from` can make, captures and plain steps alike.
+fn steps_from(board: &Board, from: [usize; 2]) -> Vec<Step> {
+    let Some(piece) = board[from[0]][from[1]] else {
+        return Vec::new();
+    };
+    let forward: isize = match piece.owner {
+        Player::Human => -1,
+        Player::Bot => 1,
+    };
+    let rows: &[isize] = match piece.king {
+        true => &[-1, 1],
+        false => &[forward],
+    };
+    let cell = |row: isize, col: isize| {
+        let range = 0..SIDE as isize;
+        (range.contains(&row) && range.contains(&col)).then_some([row as usize, col as usize])
+    };
+    let mut steps = Vec::new();
+    for &dr in rows {
+        for dc in [-1, 1] {
+            let [row, col] = [from[0] as isize, from[1] as isize];
+            let Some(next) = cell(row + dr, col + dc) else {
+                continue;
+            };
+            match board[next[0]][next[1]] {
+                None => steps.push(Step { from, to: next }),
+                Some(other) if other.owner != piece.owner => {
+                    if let Some(to) = cell(row + 2 * dr, col + 2 * dc)
+                        && board[to[0]][to[1]].is_none()
+                    {
+                        steps.push(Step { from, to });
+                    }
+                }
+                Some(_) => {}
+            }
+        }
+    }
+    steps
+}
+
+/// The hops `player` may make next: only captures when any is available, and only further
+/// captures by the piece on `continuing` in the middle of a multi-jump.
+fn turn_steps(board: &Board, player: Player, continuing: Option<[usize; 2]>) -> Vec<Step> {
+    if let Some(cell) = continuing {
+        return steps_from(board, cell)
+            .into_iter()
+            .filter(|step| step.is_capture())
+            .collect();
+    }
+    let steps: Vec<_> = (0..SIDE)
+        .flat_map(|row| (0..SIDE).map(move |col| [row, col]))
+        .filter(|&[row, col]| board[row][col].is_some_and(|piece| piece.owner == player))
+        .flat_map(|cell| steps_from(board, cell))
+        .collect();
+    match steps.iter().any(|step| step.is_capture()) {
+        true => steps.into_iter().filter(|step| step.is_capture()).collect(),
+        false => steps,
+    }
+}
+
+/// Make `step` on `board`, removing any captured piece and crowning a man reaching the far
+/// row. Returns whether the move goes on with another jump by the same piece.
+fn play_step(board: &mut Board, step: Step) -> bool {
+    let Some(mut piece) = board[step.from[0]][step.from[1]].take() else {
+        return false;
+    };
+    if step.is_capture() {
+        let [row, col] = step.captured();
+        board[row][col] = None;
+    }
+    let crowned = !piece.king && step.to[0] == crowning_row(piece.owner);
+    piece.king |= crowned;
+    board[step.to[0]][step.to[1]] = Some(piece);
+    step.is_capture()
+        && !crowned
+        && steps_from(board, step.to)
+            .iter()
+            .any(|next| next.is_capture())
+}
+
+/// Every complete move `player` can make, as the hops taken and the board they lead to.
+fn turns(board: &Board, player: Player) -> Vec<(Vec<Step>, Board)> {
+    fn extend(
+        board: &Board,
+        player: Player,
+        continuing: Option<[usize; 2]>,
+        path: &mut Vec<Step>,
+        turns: &mut Vec<(Vec<Step>, Board)>,
+    ) {
+        for step in turn_steps(board, player, continuing) {
+            let mut next = *board;
+            path.push(step);
+            match play_step(&mut next, step) {
+                true => extend(&next, player, Some(step.to), path, turns),
+                false => turns.push((path.clone(), next)),
+            }
+            path.pop();
+        }
+    }
+    let mut found = Vec::new();
+    extend(board, player, None, &mut Vec::new(), &mut found);
+    found
+}
+
+/// The material balance of `board` for `player`; kings are worth more than men, and men
+/// gain a little for every row they have advanced.
+fn evaluate(board: &Board, player: Player) -> i32 {
+    let mut score = 0;
+    for (row, pieces) in board.iter().enumerate() {
+        for piece in pieces.iter().flatten() {
+            let value = match piece.king {
+                true => 160,
+                false => 100 + 2 * crowning_row(piece.owner.other()).abs_diff(row) as i32,
+            };
+            score += match piece.owner == player {
+                true => value,
+                false => -value,
+            };
+        }
+    }
+    score
+}
+
+/// Negamax with alpha-beta pruning, scoring `board` for `player` to move `depth` moves deep.
+fn negamax(board: &Board, player: Player, depth: usize, mut alpha: i32, beta: i32) -> i32 {
+    let moves = turns(board, player);
+    if moves.is_empty() {
+        // losing later is better than losing sooner
+        return -WIN - depth as i32;
+    }
+    if depth == 0 {
+        return evaluate(board, player);
+    }
+    let mut best = -i32::MAX;
+    for (_, next) in moves {
+        let score = -negamax(&next, player.other(), depth - 1, -beta, -alpha);
+        best = best.max(score);
+        alpha = alpha.max(score);
+        if alpha >= beta {
+            break;
+        }
+    }
+    best
+}
+
+/// The move `opponent` picks for `player`, if any is left.
+fn choose_turn(board: &Board, player: Player, opponent: bot::Opponent) -> Option<Vec<Step>> {
+    let moves = turns(board, player);
+    match opponent {
+        bot::Opponent::Random => moves.choose(&mut rand::rng()).map(|(path, _)| path.clone()),
+        bot::Opponent::Heuristic | bot::Opponent::Minimax => {
+            let mut best = None;
+            let mut alpha = -i32::MAX;
+            for (path, next) in moves {
+                let score = -negamax(&next, player.other(), SEARCH_DEPTH - 1, -i32::MAX, -alpha);
+                if best.is_none() || score > alpha {
+                    alpha = score;
+                    best = Some(path);
+                }
+            }
+            best
+        }
+    }
+}
+
+/// A game of checkers against the bot.
+pub struct Checkers {
+    board: Board,
+    turn: Player,
+    /// Who moved first this game; the sides take turns opening.
+    starter: Player,
+    opponent: bot::Opponent,
+    /// The piece picked to move, and whether it is in the middle of a multi-jump.
+    selected: Option<[usize; 2]>,
+    continuing: bool,
+    /// Moves played since the last capture or crowning.
+    quiet_moves: usize,
+    outcome: Option<Outcome>,
+    /// The cells the bot's last move went through.
+    last_bot_move: Vec<[usize; 2]>,
+    /// The bot weighing up its next move, jumps and all.
+    thinking: Option<bot::Thinking<Option<Vec<Step>>>>,
+    /// Why the last pick was rejected.
+    notice: Option<&'static str>,
+    cursor: Cursor,
+    state: TableState,
+    colors: TableColors,
+}
+
+impl Checkers {
+    pub fn new() -> Self {
+        Self {
+            board: initial_board(),
+            turn: Player::Human,
+            starter: Player::Human,
+            opponent: bot::Opponent::Minimax,
+            selected: None,
+            continuing: false,
+            quiet_moves: 0,
+            outcome: None,
+            last_bot_move: Vec::new(),
+            thinking: None,
+            notice: None,
+            cursor: Cursor::new(SIDE, SIDE),
+            state: TableState::default(),
+            colors: TableColors::new(&PALETTES[0]),
+        }
+    }
+
+    /// Start a new game, with the other side opening.
+    fn rematch(&mut self) {
+        let starter = self.starter.other();
+        *self = Self {
+            colors: self.colors,
+            turn: starter,
+            starter,
+            opponent: self.opponent,
+            ..Self::new()
+        };
+    }
+
+    /// Pick the piece under the cursor to move, move the picked piece to the cursor, or drop
+    /// the pick.
+    fn select(&mut self) {
+        let cell = self.cursor.cell();
+        let own = self.board[cell[0]][cell[1]].is_some_and(|piece| piece.owner == Player::Human);
+        match self.selected {
+            Some(from) if !self.continuing && from == cell => self.selected = None,
+            Some(_) if !self.continuing && own => self.pick(cell),
+            Some(from) => {
+                if !self.apply(Step { from, to: cell }) {
+                    self.notice = Some(match self.continuing {
+                        true => "Keep jumping!",
+                        false => "It can't go there!",
+                    });
+                }
+            }
+            None if own => self.pick(cell),
+            None => self.notice = Some("Pick one of your pieces!"),
+        }
+    }
+
+    /// Pick the human's piece on `cell`, if it has a move.
+    fn pick(&mut self, cell: [usize; 2]) {
+        let legal = self.legal_moves();
+        if legal.iter().any(|step| step.from == cell) {
+            self.selected = Some(cell);
+        } else if legal.iter().any(|step| step.is_capture()) {
+            self.notice = Some("A capture is forced!");
+        } else {
+            self.notice = Some("That piece can't move!");
+        }
+    }
+
+    /// Pass the turn once a move is complete, ending the game if the next side is stuck.
+    fn end_turn(&mut self, quiet: bool) {
+        self.selected = None;
+        self.continuing = false;
+        self.quiet_moves = match quiet {
+            true => self.quiet_moves + 1,
+            false => 0,
+        };
+        self.turn = self.turn.other();
+        if turn_steps(&self.board, self.turn, None).is_empty() {
+            self.outcome = Some(Outcome::Winner(self.turn.other()));
+        } else if self.quiet_moves >= QUIET_MOVE_LIMIT {
+            self.outcome = Some(Outcome::Draw);
+        }
+    }
+
+    /// Play the whole move the bot settled on, jumps and all.
+    fn bot_move(&mut self, path: Vec<Step>) {
+        let mut quiet = true;
+        for &step in &path {
+            let king = self.board[step.from[0]][step.from[1]].is_some_and(|piece| piece.king);
+            play_step(&mut self.board, step);
+            let crowned = !king && self.board[step.to[0]][step.to[1]].is_some_and(|p| p.king);
+            quiet &= !step.is_capture() && !crowned;
+        }
+        self.last_bot_move = path
+            .iter()
+            .map(|step| step.from)
+            .chain(path.last().map(|step| step.to))
+            .collect();
+        self.end_turn(quiet);
+    }
+
+    fn render_title(&self) -> Line<'static> {
+        let versus = format!("Checkers · You VS Bot [{}]", self.opponent.label());
+        let status = match (self.outcome, self.turn) {
+            (Some(outcome), _) => format!("{} (r: rematch)", outcome.message()).into(),
+            (None, Player::Bot) => "Bot thinking…".fg(self.colors.row_fg),
+            (None, Player::Human) if let Some(notice) = self.notice => notice.fg(Color::Red),
+            (None, Player::Human) => match (self.selected, self.continuing) {
+                (_, true) => "Keep jumping",
+                (Some(_), false) => "Pick where it goes",
+                (None, false) => "Your turn · pick a piece",
+            }
+            .fg(self.colors.selected_cell_style_fg),
+        };
+        Line::from(vec![versus.into(), " · ".dark_gray(), status])
+    }
+}
+
+impl Game for Checkers {
+    type Move = Step;
+
+    fn dimensions(&self) -> (usize, usize) {
+        (SIDE, SIDE)
+    }
+
+    fn legal_moves(&self) -> Vec<Step> {
+        if self.outcome.is_some() || self.turn != Player::Human {
+            return Vec::new();
+        }
+        let continuing = self.selected.filter(|_| self.continuing);
+        turn_steps(&self.board, Player::Human, continuing)
+    }
+
+    /// Make one hop for the human; after a capture the same piece must keep jumping while it
+    /// can, before the turn passes to the bot.
+    fn apply(&mut self, step: Step) -> bool {
+        if !self.legal_moves().contains(&step) {
+            return false;
+        }
+        let king = self.board[step.from[0]][step.from[1]].is_some_and(|piece| piece.king);
+        let jumping = play_step(&mut self.board, step);
+        self.last_bot_move.clear();
+        if jumping {
+            self.selected = Some(step.to);
+            self.continuing = true;
+        } else {
+            let crowned = !king && self.board[step.to[0]][step.to[1]].is_some_and(|p| p.king);
+            self.end_turn(!step.is_capture() && !crowned);
+        }
+        true
+    }
+
+    fn status(&self) -> Status {
+        match self.outcome {
+            None => Status::Playing,
+            Some(Outcome::Winner(Player::Human)) => Status::Won,
+            Some(Outcome::Winner(Player::Bot)) => Status::Lost,
+            Some(Outcome::Draw) => Status::Draw,
+        }
+    }
+
+    fn opponent(&self) -> Option<bot::Opponent> {
+        Some(self.opponent)
+    }
+
+    fn render_cell(&self, cell: [usize; 2]) -> (String, Style) {
+        let [row, col] = cell;
+        let bg = match (row + col) % 2 {
+            0 => tailwind::SLATE.c300,
+            _ => tailwind::SLATE.c800,
+        };
+        let targets: Vec<_> = match self.selected {
+            Some(from) => self
+                .legal_moves()
+                .into_iter()
+                .filter(|step| step.from == from)
+                .map(|step| step.to)
+                .collect(),
+            None => Vec::new(),
+        };
+        let bg = if self.selected == Some(cell) || targets.contains(&cell) {
+            self.colors.hint_cell_style_bg
+        } else if self.last_bot_move.contains(&cell) {
+            tailwind::SLATE.c600
+        } else {
+            bg
+        };
+        let style = Style::default().bg(bg).add_modifier(Modifier::BOLD);
+        match self.board[row][col] {
+            Some(piece) => {
+                let fg = match piece.owner {
+                    Player::Human => tailwind::SLATE.c50,
+                    Player::Bot => tailwind::RED.c500,
+                };
+                let text = match piece.king {
+                    true => "♛",
+                    false => "●",
+                };
+                (text.into(), style.fg(fg))
+            }
+            None if targets.contains(&cell) => ("·".into(), style),
+            None => (String::new(), style),
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        self.notice = None;
+        match key.code {
+            KeyCode::Char('r') => self.rematch(),
+            KeyCode::Char('b') => self.opponent = self.opponent.next(),
+            // the board is frozen once the game is decided
+            _ if self.outcome.is_some() || self.turn != Player::Human => {}
+            KeyCode::Char(' ') | KeyCode::Enter => self.select(),
+            code => {
+                self.cursor.move_with(code);
+            }
+        }
+    }
+
+    /// Search for the bot's move once the human's last jump has landed, and play it when
+    /// found; the bot also opens every other game.
+    fn tick(&mut self) -> bool {
+        if self.outcome.is_some() || self.turn != Player::Bot {
+            return false;
+        }
+        let (board, opponent) = (self.board, self.opponent);
+        let thinking = self.thinking.get_or_insert_with(|| {
+            bot::Thinking::start(move || choose_turn(&board, Player::Bot, opponent))
+        });
+        let answer = match thinking.answer() {
+            bot::Answer::Pending => return false,
+            bot::Answer::Ready(answer) => answer,
+            bot::Answer::Failed => choose_turn(&self.board, Player::Bot, bot::Opponent::Random),
+        };
+        self.thinking = None;
+        if let Some(path) = answer {
+            self.bot_move(path);
+        }
+        true
+    }
+
+    fn apply_settings(&mut self, settings: &Settings) {
+        self.colors = settings.colors();
+        self.opponent = settings.opponent(self.opponent);
+    }
+
+    fn restart(&mut self) {
+        self.rematch();
+    }
+
+    fn help(&self) -> Help {
+        Help {
+            rules: "Move diagonally on the dark squares and capture by jumping; captures are \
+                    forced, and a capturing piece keeps jumping while it can. Men reaching the \
+                    far row become kings, which move both ways. Take every piece of the bot's, or \
+                    leave it without a move, to win."
+                .into(),
+            keys: vec![
+                ("←↑↓→/wasd", "move the cursor"),
+                ("Space/Enter", "pick a piece, then where it goes"),
+                ("b", "switch bot"),
+                ("r", "rematch"),
+            ],
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [area, help_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(frame.area());
+        let help = match self.outcome {
+            Some(_) => "r rematch · b switch bot",
+            None => "←↑↓→/wasd move · Space pick, then move · b switch bot · r rematch",
+        };
+        frame.render_widget(Paragraph::new(help).centered().dark_gray(), help_area);
+        // tall cells when the whole board fits, otherwise the compact ones of a large board
+        let (cell_width, cell_height) = match area.height > table_extent(SIDE, 3, 0) {
+            true => cell_size(5),
+            false => cell_size(SIDE),
+        };
+        let table_width = table_extent(SIDE, cell_width, 1);
+        let table_height = table_extent(SIDE, cell_height, 0);
+        let min_width = table_width.max(50);
+        if area.width < min_width || area.height < table_height + 1 {
+            grid::render_too_small(frame, area, min_width, table_height + 1);
+            return;
+        }
+
+        let layout = calculate_layout(area, false, table_width, table_height);
+        let viewport = Viewport {
+            row: 0,
+            col: 0,
+            rows: SIDE,
+            cols: SIDE,
+        };
+        self.state.select(Some(self.cursor.row));
+        self.state.select_column(Some(self.cursor.col));
+        frame.render_widget(Paragraph::new(self.render_title()).centered(), layout.title);
+        let cursor_style = match (self.outcome, self.turn) {
+            (None, Player::Human) => Style::default().add_modifier(Modifier::REVERSED),
+            _ => Style::default(),
+        };
+        let table = grid::table(self, viewport, cell_width, cell_height, &self.colors)
+            .cell_highlight_style(cursor_style);
+        frame.render_stateful_widget(table, layout.table, &mut self.state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The board drawn in `rows`, the bot's side first: `M` and `K` for the human's men and
+    /// kings, lowercase for the bot's and `.` for an empty cell.
+    fn board(rows: [&str; SIDE]) -> Board {
+        std::array::from_fn(|row| {
+            let cells: Vec<char> = rows[row].chars().collect();
+            std::array::from_fn(|col| {
+                let owner = match cells[col] {
+                    'M' | 'K' => Player::Human,
+                    'm' | 'k' => Player::Bot,
+                    _ => return None,
+                };
+                let king = cells[col].eq_ignore_ascii_case(&'k');
+                Some(Piece { owner, king })
+            })
+        })
+    }
+
+    #[test]
+    fn a_capture_must_be_taken_when_there_is_one() {
+        let board = board([
+            ".m......", "........", "........", "........", "...m....", "..M...M.", "........",
+            "........",
+        ]);
+        let steps = turn_steps(&board, Player::Human, None);
+        assert_eq!(
+            steps,
+            [Step {
+                from: [5, 2],
+                to: [3, 4]
+            }]
+        );
+    }
+
+    #[test]
+    fn a_jump_goes_on_with_the_same_piece_while_it_can_capture() {
+        let mut checkers = Checkers::new();
+        checkers.board = board([
+            ".m......", "........", "........", "....m...", "........", "..m.....", ".M......",
+            "........",
+        ]);
+        assert!(checkers.apply(Step {
+            from: [6, 1],
+            to: [4, 3]
+        }));
+        assert!(checkers.continuing);
+        assert_eq!(checkers.turn, Player::Human);
+        assert_eq!(
+            checkers.legal_moves(),
+            [Step {
+                from: [4, 3],
+                to: [2, 5]
+            }]
+        );
+
+        assert!(checkers.apply(Step {
+            from: [4, 3],
+            to: [2, 5]
+        }));
+        assert_eq!(checkers.turn, Player::Bot);
+        assert_eq!(checkers.board[5][2], None);
+        assert_eq!(checkers.board[3][4], None);
+    }
+
+    #[test]
+    fn a_man_crowned_by_a_jump_ends_the_move_there() {
+        let board = board([
+            "........", "..m.m...", ".M......", "........", "........", "........", "........",
+            "......m.",
+        ]);
+        let turns = turns(&board, Player::Human);
+        assert_eq!(turns.len(), 1);
+        let (path, after) = &turns[0];
+        assert_eq!(path.len(), 1);
+        assert_eq!(
+            after[0][3],
+            Some(Piece {
+                owner: Player::Human,
+                king: true
+            })
+        );
+        assert!(after[1][4].is_some());
+    }
+}
@@ -0,0 +1,1077 @@
+//! Chess against the bot, which searches a few moves ahead with alpha-beta pruning.
+//!
+//! The human plays White from the bottom of the board. Every rule of movement is enforced,
+//! castling, en passant and promotion included; games are drawn by stalemate, the fifty-move
+//! rule, threefold repetition or too little material left to mate.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use rand::seq::IndexedRandom;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style, Stylize, palette::tailwind},
+    text::Line,
+    widgets::{Paragraph, TableState},
+};
+
+use crate::{
+    Outcome, Player, bot,
+    game::{Game, Help, Status},
+    grid::{self, Cursor, Viewport},
+    settings::Settings,
+    ui::{
+        layout::{calculate_layout, cell_size, table_extent},
+        palette::{PALETTES, TableColors},
+    },
+};
+
+/// Cells along one side of the board.
+const SIDE: usize = 8;
+
+/// How many moves ahead the hard bot searches before only following captures.
+const SEARCH_DEPTH: usize = 3;
+
+/// A score beyond any material count, for checkmate.
+const MATE: i32 = 100_000;
+
+/// How many moves by both sides without a capture or pawn move draw the game.
+const FIFTY_MOVE_LIMIT: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Pawn,
+    Knight,
+    Bishop,
+    Rook,
+    Queen,
+    King,
+}
+
+impl Kind {
+    /// What the piece is worth, in hundredths of a pawn.
+    const fn value(self) -> i32 {
+        match self {
+            Self::Pawn => 100,
+            Self::Knight => 320,
+            Self::Bishop => 330,
+            Self::Rook => 500,
+            Self::Queen => 900,
+            Self::King => 0,
+        }
+    }
+
+    const fn symbol(self) -> &'static str {
+        match self {
+            Self::Pawn => "♟",
+            Self::Knight => "♞",
+            Self::Bishop => "♝",
+            Self::Rook => "♜",
+            Self::Queen => "♛",
+            Self::King => "♚",
+        }
+    }
+}
+
+/// A piece of the human's White or the bot's Black.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Piece {
+    owner: Player,
+    kind: Kind,
+}
+
+/// A move of a piece, naming the piece a pawn reaching the last row becomes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChessMove {
+    pub from: [usize; 2],
+    pub to: [usize; 2],
+    pub promotion: Option<Kind>,
+}
+
+const KNIGHT_JUMPS: [(isize, isize); 8] = [
+    (-2, -1),
+    (-2, 1),
+    (-1, -2),
+    (-1, 2),
+    (1, -2),
+    (1, 2),
+    (2, -1),
+    (2, 1),
+];
+
+const KING_STEPS: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+const ROOK_LINES: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+const BISHOP_LINES: [(isize, isize); 4] = [(-1, -1), (-1, 1), (1, -1), (1, 1)];
+
+/// The cell `(dr, dc)` away from `[row, col]`, if it is on the board.
+fn offset([row, col]: [usize; 2], (dr, dc): (isize, isize)) -> Option<[usize; 2]> {
+    let row = row.checked_add_signed(dr).filter(|&row| row < SIDE)?;
+    let col = col.checked_add_signed(dc).filter(|&col| col < SIDE)?;
+    Some([row, col])
+}
+
+/// The row `player`'s pieces start on.
+const fn home_row(player: Player) -> usize {
+    match player {
+        Player::Human => SIDE - 1,
+        Player::Bot => 0,
+    }
+}
+
+/// The direction `player`'s pawns move in, along the rows.
+const fn forward(player: Player) -> isize {
+    match player {
+        Player::Human => -1,
+        Player::Bot => 1,
+    }
+}
+
+/// Everything deciding which moves can be played: the pieces, the side to move, the castling
+/// still allowed and the square a pawn may be taken on en passant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Position {
+    board: [[Option<Piece>; SIDE]; SIDE],
+    turn: Player,
+    /// Whether each side may still castle, kingside then queenside, human first.
+    castling: [[bool; 2]; 2],
+    en_passant: Option<[usize; 2]>,
+}
+
+impl Position {
+    fn initial() -> Self {
+        const BACK_ROW: [Kind; SIDE] = [
+            Kind::Rook,
+            Kind::Knight,
+            Kind::Bishop,
+            Kind::Queen,
+            Kind::King,
+            Kind::Bishop,
+            Kind::Knight,
+            Kind::Rook,
+        ];
+        let board = std::array::from_fn(|row| {
+            std::array::from_fn(|col| {
+                let (owner, kind) = match row {
+                    0 => (Player::Bot, BACK_ROW[col]),
+                    1 => (Player::Bot, Kind::Pawn),
+                    6 => (Player::Human, Kind::Pawn),
+                    7 => (Player::Human, BACK_ROW[col]),
+                    _ => return None,
+                };
+                Some(Piece { owner, kind })
+            })
+        });
+        Self {
+            board,
+            turn: Player::Human,
+            castling: [[true; 2]; 2],
+            en_passant: None,
+        }
+    }
+
+    fn piece(&self, [row, col]: [usize; 2]) -> Option<Piece> {
+        self.board[row][col]
+    }
+
+    fn king(&self, player: Player) -> Option<[usize; 2]> {
+        (0..SIDE)
+            .flat_map(|row| (0..SIDE).map(move |col| [row, col]))
+            .find(|&cell| {
+                self.piece(cell)
+                    == Some(Piece {
+                        owner: player,
+                        kind: Kind::King,
+                    })
+            })
+    }
+
+    /// Whether any piece of `by` attacks `cell`.
+    fn attacked(&self, cell: [usize; 2], by: Player) -> bool {
+        let holds = |cell: Option<[usize; 2]>, kinds: &[Kind]| {
+            cell.and_then(|cell| self.piece(cell))
+                .is_some_and(|piece| piece.owner == by && kinds.contains(&piece.kind))
+        };
+        // a pawn attacks from one row behind, as seen from its side
+        let pawn_row = -forward(by);
+        if [-1, 1]
+            .into_iter()
+            .any(|dc| holds(offset(cell, (pawn_row, dc)), &[Kind::Pawn]))
+        {
+            return true;
+        }
+        if KNIGHT_JUMPS
+            .iter()
+            .any(|&jump| holds(offset(cell, jump), &[Kind::Knight]))
+            || KING_STEPS
+                .iter()
+                .any(|&step| holds(offset(cell, step), &[Kind::King]))
+        {
+            return true;
+        }
+        let slides = |lines: &[(isize, isize)], kinds: &[Kind]| {
+            lines.iter().any(|&line| {
+                let mut next = offset(cell, line);
+                while let Some(at) = next {
+                    if self.piece(at).is_some() {
+                        return holds(Some(at), kinds);
+                    }
+                    next = offset(at, line);
+                }
+                false
+            })
+        };
+        slides(&ROOK_LINES, &[Kind::Rook, Kind::Queen])
+            || slides(&BISHOP_LINES, &[Kind::Bishop, Kind::Queen])
+    }
+
+    fn in_check(&self, player: Player) -> bool {
+        self.king(player)
+            .is_some_and(|king| self.attacked(king, player.other()))
+    }
+
+    /// The moves of the side to move that follow the movement rules, whether or not they leave
+    /// its king in check.
+    fn pseudo_moves(&self) -> Vec<ChessMove> {
+        let player = self.turn;
+        let mut moves = Vec::new();
+        let mut push = |from, to, pawn: bool| {
+            let [row, _] = to;
+            if pawn && row == home_row(player.other()) {
+                for kind in [Kind::Queen, Kind::Rook, Kind::Bishop, Kind::Knight] {
+                    moves.push(ChessMove {
+                        from,
+                        to,
+                        promotion: Some(kind),
+                    });
+                }
+            } else {
+                moves.push(ChessMove {
+                    from,
+                    to,
+                    promotion: None,
+                });
+            }
+        };
+        let free = |cell: [usize; 2]| self.piece(cell).is_none();
+        let enemy = |cell: [usize; 2]| self.piece(cell).is_some_and(|piece| piece.owner != player);
+
+        for from in (0..SIDE).flat_map(|row| (0..SIDE).map(move |col| [row, col])) {
+            let Some(piece) = self.piece(from).filter(|piece| piece.owner == player) else {
+                continue;
+            };
+            match piece.kind {
+                Kind::Pawn => {
+                    let ahead = offset(from, (forward(player), 0));
+                    if let Some(to) = ahead.filter(|&to| free(to)) {
+                        push(from, to, true);
+                        let start = home_row(player).saturating_add_signed(forward(player));
+                        if from[0] == start
+                            && let Some(to) =
+                                offset(to, (forward(player), 0)).filter(|&to| free(to))
+                        {
+                            push(from, to, true);
+                        }
+                    }
+                    for dc in [-1, 1] {
+                        if let Some(to) = offset(from, (forward(player), dc))
+                            && (enemy(to) || self.en_passant == Some(to))
+                        {
+                            push(from, to, true);
+                        }
+                    }
+                }
+                Kind::Knight | Kind::King => {
+                    let jumps = match piece.kind {
+                        Kind::Knight => &KNIGHT_JUMPS,
+                        _ => &KING_STEPS,
+                    };
+                    for &jump in jumps {
+                        if let Some(to) = offset(from, jump).filter(|&to| free(to) || enemy(to)) {
+                            push(from, to, false);
+                        }
+                    }
+                }
+                Kind::Bishop | Kind::Rook | Kind::Queen => {
+                    let lines: &[(isize, isize)] = match piece.kind {
+                        Kind::Bishop => &BISHOP_LINES,
+                        Kind::Rook => &ROOK_LINES,
+                        _ => &[ROOK_LINES, BISHOP_LINES].concat(),
+                    };
+                    for &line in lines {
+                        let mut next = offset(from, line);
+                        while let Some(to) = next {
+                            if free(to) {
+                                push(from, to, false);
+                            } else {
+                                if enemy(to) {
+                                    push(from, to, false);
+                                }
+                                break;
+                            }
+                            next = offset(to, line);
+                        }
+                    }
+                }
+            }
+        }
+        moves.extend(self.castling_moves());
+        moves
+    }
+
+    /// The castling moves open to the side to move: the king and rook are unmoved, the cells
+    /// between them empty, and the king neither starts in, passes through nor lands in check.
+    fn castling_moves(&self) -> Vec<ChessMove> {
+        let player = self.turn;
+        let row = home_row(player);
+        let side = match player {
+            Player::Human => 0,
+            Player::Bot => 1,
+        };
+        let king = [row, 4];
+        if self.in_check(player) {
+            return Vec::new();
+        }
+        // kingside, then queenside: the rook's column, the cells to clear and the king's path
+        let wings: [(usize, &[usize], [usize; 2]); 2] =
+            [(7, &[5, 6], [5, 6]), (0, &[1, 2, 3], [3, 2])];
+        wings
+            .iter()
+            .zip(self.castling[side])
+            .filter(|&(&(rook, between, path), allowed)| {
+                allowed
+                    && self.piece(king)
+                        == Some(Piece {
+                            owner: player,
+                            kind: Kind::King,
+                        })
+                    && self.piece([row, rook])
+                        == Some(Piece {
+                            owner: player,
+                            kind: Kind::Rook,
+                        })
+                    && between.iter().all(|&col| self.piece([row, col]).is_none())
+                    && path
+                        .iter()
+                        .all(|&col| !self.attacked([row, col], player.other()))
+            })
+            .map(|(&(_, _, path), _)| ChessMove {
+                from: king,
+                to: [row, path[1]],
+                promotion: None,
+            })
+            .collect()
+    }
+
+    /// The position after `mv`, which must follow the movement rules.
+    fn play(&self, mv: ChessMove) -> Self {
+        let mut next = *self;
+        let Some(mut piece) = next.board[mv.from[0]][mv.from[1]].take() else {
+            return next;
+        };
+        let player = piece.owner;
+        let side = match player {
+            Player::Human => 0,
+            Player::Bot => 1,
+        };
+        match piece.kind {
+            Kind::Pawn if Some(mv.to) == self.en_passant && mv.from[1] != mv.to[1] => {
+                next.board[mv.from[0]][mv.to[1]] = None;
+            }
+            Kind::King if mv.from[1].abs_diff(mv.to[1]) == 2 => {
+                // bring the rook over to the other side of the king
+                let (rook, col) = match mv.to[1] {
+                    6 => (7, 5),
+                    _ => (0, 3),
+                };
+                next.board[mv.from[0]][col] = next.board[mv.from[0]][rook].take();
+            }
+            _ => {}
+        }
+        if let Some(kind) = mv.promotion {
+            piece.kind = kind;
+        }
+        next.board[mv.to[0]][mv.to[1]] = Some(piece);
+
+        if piece.kind == Kind::King {
+            next.castling[side] = [false; 2];
+        }
+        // a rook leaving or taken on its corner can no longer castle
+        for (side, row) in [(0, SIDE - 1), (1, 0)] {
+            for (wing, col) in [(0, 7), (1, 0)] {
+                if mv.from == [row, col] || mv.to == [row, col] {
+                    next.castling[side][wing] = false;
+                }
+            }
+        }
+        next.en_passant = (piece.kind == Kind::Pawn && mv.from[0].abs_diff(mv.to[0]) == 2)
+            .then(|| [(mv.from[0] + mv.to[0]) / 2, mv.from[1]]);
+        next.turn = player.other();
+        next
+    }
+
+    /// The moves of the side to move that do not leave its own king in check.
+    fn legal_moves(&self) -> Vec<ChessMove> {
+        self.pseudo_moves()
+            .into_iter()
+            .filter(|&mv| !self.play(mv).in_check(self.turn))
+            .collect()
+    }
+
+    /// Whether neither side has the pieces left to ever checkmate: bare kings, or a king and a
+    /// single bishop or knight against a bare king.
+    fn insufficient_material(&self) -> bool {
+        let pieces: Vec<_> = self
+            .board
+            .iter()
+            .flatten()
+            .flatten()
+            .filter(|piece| piece.kind != Kind::King)
+            .collect();
+        match pieces.as_slice() {
+            [] => true,
+            [piece] => matches!(piece.kind, Kind::Bishop | Kind::Knight),
+            _ => false,
+        }
+    }
+
+    /// The material balance for `player`, with a small bonus for pieces near the centre and
+    /// pawns that have advanced.
+    fn evaluate(&self, player: Player) -> i32 {
+        let mut score = 0;
+        for (row, pieces) in self.board.iter().enumerate() {
+            for (col, piece) in pieces.iter().enumerate() {
+                let Some(piece) = piece else {
+                    continue;
+                };
+                let centre = 7 - (2 * row).abs_diff(7).max((2 * col).abs_diff(7)) as i32;
+                let bonus = match piece.kind {
+                    Kind::Pawn => 5 * home_row(piece.owner).abs_diff(row) as i32 + centre,
+                    Kind::Knight | Kind::Bishop => 4 * centre,
+                    Kind::Queen => centre,
+                    Kind::Rook | Kind::King => 0,
+                };
+                let value = piece.kind.value() + bonus;
+                score += match piece.owner == player {
+                    true => value,
+                    false => -value,
+                };
+            }
+        }
+        score
+    }
+
+    /// The value of the piece `mv` takes, for trying the most promising captures first.
+    fn capture_value(&self, mv: ChessMove) -> i32 {
+        self.piece(mv.to).map_or(0, |piece| piece.kind.value())
+            + mv.promotion.map_or(0, Kind::value)
+    }
+}
+
+/// Follow captures only until the position is quiet, so the search does not stop in the
+/// middle of an exchange.
+fn quiesce(position: &Position, mut alpha: i32, beta: i32) -> i32 {
+    let standing = position.evaluate(position.turn);
+    if standing >= beta {
+        return standing;
+    }
+    alpha = alpha.max(standing);
+    let mut captures: Vec<_> = position
+        .legal_moves()
+        .into_iter()
+        .filter(|&mv| position.capture_value(mv) > 0)
+        .collect();
+    captures.sort_by_key(|&mv| -position.capture_value(mv));
+    for mv in captures {
+        let score = -quiesce(&position.play(mv), -beta, -alpha);
+        if score >= beta {
+            return score;
+        }
+        alpha = alpha.max(score);
+    }
+    alpha
+}
+
+/// Negamax with alpha-beta pruning, scoring `position` for the side to move `depth` moves deep.
+fn negamax(position: &Position, depth: usize, mut alpha: i32, beta: i32) -> i32 {
+    let mut moves = position.legal_moves();
+    if moves.is_empty() {
+        // being mated later is better than sooner; stalemate is even
+        return match position.in_check(position.turn) {
+            true => -MATE - depth as i32,
+            false => 0,
+        };
+    }
+    if depth == 0 {
+        return quiesce(position, alpha, beta);
+    }
+    moves.sort_by_key(|&mv| -position.capture_value(mv));
+    let mut best = -i32::MAX;
+    for mv in moves {
+        let score = -negamax(&position.play(mv), depth - 1, -beta, -alpha);
+        best = best.max(score);
+        alpha = alpha.max(score);
+        if alpha >= beta {
+            break;
+        }
+    }
+    best
+}
+
+/// The move `opponent` picks in `position`, if any is left.
+fn choose_move(position: &Position, opponent: bot::Opponent) -> Option<ChessMove> {
+    let mut moves = position.legal_moves();
+    match opponent {
+        bot::Opponent::Random => moves.choose(&mut rand::rng()).copied(),
+        bot::Opponent::Heuristic | bot::Opponent::Minimax => {
+            moves.sort_by_key(|&mv| -position.capture_value(mv));
+            let mut best = None;
+            let mut alpha = -i32::MAX;
+            for mv in moves {
+                let score = -negamax(&position.play(mv), SEARCH_DEPTH - 1, -i32::MAX, -alpha);
+                if best.is_none() || score > alpha {
+                    alpha = score;
+                    best = Some(mv);
+                }
+            }
+            best
+        }
+    }
+}
+
+/// A game of chess against the bot.
+pub struct Chess {
+    position: Position,
+    /// Every position reached so far, for spotting threefold repetition.
+    history: Vec<Position>,
+    /// Moves by both sides since the last capture or pawn move.
+    quiet_moves: usize,
+    opponent: bot::Opponent,
+    outcome: Option<Outcome>,
+    /// The piece picked to move.
+    selected: Option<[usize; 2]>,
+    /// A pawn move onto the last row, waiting for the piece it promotes to.
+    promoting: Option<([usize; 2], [usize; 2])>,
+    last_bot_move: Option<ChessMove>,
+    /// The bot looking ahead for its reply.
+    thinking: Option<bot::Thinking<Option<ChessMove>>>,
+    /// Why the last pick was rejected.
+    notice: Option<&'static str>,
+    cursor: Cursor,
+    state: TableState,
+    colors: TableColors,
+}
+
+impl Chess {
+    pub fn new() -> Self {
+        let position = Position::initial();
+        Self {
+            position,
+            history: vec![position],
+            quiet_moves: 0,
+            opponent: bot::Opponent::Minimax,
+            outcome: None,
+            selected: None,
+            promoting: None,
+            last_bot_move: None,
+            thinking: None,
+            notice: None,
+            cursor: Cursor::new(SIDE, SIDE),
+            state: TableState::default(),
+            colors: TableColors::new(&PALETTES[0]),
+        }
+    }
+
+    /// Play `mv` for the side to move and settle whether the game is over.
+    fn play(&mut self, mv: ChessMove) {
+        let pawn = self
+            .position
+            .piece(mv.from)
+            .is_some_and(|piece| piece.kind == Kind::Pawn);
+        let capture = self.position.piece(mv.to).is_some()
+            || (pawn && Some(mv.to) == self.position.en_passant);
+        self.quiet_moves = match pawn || capture {
+            true => 0,
+            false => self.quiet_moves + 1,
+        };
+        self.position = self.position.play(mv);
+        self.history.push(self.position);
+        self.selected = None;
+
+        let repetitions = self
+            .history
+            .iter()
+            .filter(|&&position| position == self.position)
+            .count();
+        self.outcome = if self.position.legal_moves().is_empty() {
+            match self.position.in_check(self.position.turn) {
+                true => Some(Outcome::Winner(self.position.turn.other())),
+                false => Some(Outcome::Draw),
+            }
+        } else if self.quiet_moves >= FIFTY_MOVE_LIMIT
+            || repetitions >= 3
+            || self.position.insufficient_material()
+        {
+            Some(Outcome::Draw)
+        } else {
+            None
+        };
+    }
+
+    /// Pick the piece under the cursor to move, move the picked piece to the cursor, or drop
+    /// the pick.
+    fn select(&mut self) {
+        let cell = self.cursor.cell();
+        let own = self
+            .position
+            .piece(cell)
+            .is_some_and(|piece| piece.owner == Player::Human);
+        match self.selected {
+            Some(from) if from == cell => self.selected = None,
+            _ if own => {
+                if self.legal_moves().iter().any(|mv| mv.from == cell) {
+                    self.selected = Some(cell);
+                } else {
+                    self.notice = Some("That piece can't move!");
+                }
+            }
+            Some(from) => {
+                let promotes = self
+                    .legal_moves()
+                    .iter()
+                    .any(|mv| mv.from == from && mv.to == cell && mv.promotion.is_some());
+                if promotes {
+                    self.promoting = Some((from, cell));
+                } else if !self.apply(ChessMove {
+                    from,
+                    to: cell,
+                    promotion: None,
+                }) {
+                    self.notice = Some(match self.position.in_check(Player::Human) {
+                        true => "Get out of check!",
+                        false => "It can't go there!",
+                    });
+                }
+            }
+            None => self.notice = Some("Pick one of your pieces!"),
+        }
+    }
+
+    /// Start a new game against the same bot.
+    fn rematch(&mut self) {
+        *self = Self {
+            colors: self.colors,
+            opponent: self.opponent,
+            ..Self::new()
+        };
+    }
+
+    /// Play the move the bot settled on.
+    fn bot_move(&mut self, mv: ChessMove) {
+        self.play(mv);
+        self.last_bot_move = Some(mv);
+    }
+
+    fn render_title(&self) -> Line<'static> {
+        let versus = format!("Chess · You VS Bot [{}]", self.opponent.label());
+        let status = match (self.outcome, self.position.turn) {
+            (Some(outcome), _) => format!("{} (r: rematch)", outcome.message()).into(),
+            (None, _) if self.promoting.is_some() => {
+                "Promote · Enter: queen · r: rook · b: bishop · n: knight"
+                    .fg(self.colors.selected_cell_style_fg)
+            }
+            (None, Player::Bot) => "Bot thinking…".fg(self.colors.row_fg),
+            (None, Player::Human) if let Some(notice) = self.notice => notice.fg(Color::Red),
+            (None, Player::Human) => {
+                let step = match self.selected {
+                    Some(_) => "Pick where it goes",
+                    None => "Your turn · pick a piece",
+                };
+                match self.position.in_check(Player::Human) {
+                    true => format!("Check! {step}").fg(Color::Red),
+                    false => step.fg(self.colors.selected_cell_style_fg),
+                }
+            }
+        };
+        Line::from(vec![versus.into(), " · ".dark_gray(), status])
+    }
+}
+
+impl Game for Chess {
+    type Move = ChessMove;
+
+    fn dimensions(&self) -> (usize, usize) {
+        (SIDE, SIDE)
+    }
+
+    fn legal_moves(&self) -> Vec<ChessMove> {
+        if self.outcome.is_some() || self.position.turn != Player::Human {
+            return Vec::new();
+        }
+        self.position.legal_moves()
+    }
+
+    /// Play `mv` for the human; a pawn reaching the last row becomes a queen unless `mv` names
+    /// another piece.
+    fn apply(&mut self, mv: ChessMove) -> bool {
+        let legal = self.legal_moves();
+        let mv = match mv.promotion {
+            None if legal.contains(&ChessMove {
+                promotion: Some(Kind::Queen),
+                ..mv
+            }) =>
+            {
+                ChessMove {
+                    promotion: Some(Kind::Queen),
+                    ..mv
+                }
+            }
+            _ => mv,
+        };
+        if !legal.contains(&mv) {
+            return false;
+        }
+        self.last_bot_move = None;
+        self.play(mv);
+        true
+    }
+
+    fn status(&self) -> Status {
+        match self.outcome {
+            None => Status::Playing,
+            Some(Outcome::Winner(Player::Human)) => Status::Won,
+            Some(Outcome::Winner(Player::Bot)) => Status::Lost,
+            Some(Outcome::Draw) => Status::Draw,
+        }
+    }
+
+    fn opponent(&self) -> Option<bot::Opponent> {
+        Some(self.opponent)
+    }
+
+    fn render_cell(&self, cell: [usize; 2]) -> (String, Style) {
+        let [row, col] = cell;
+        let targets: Vec<_> = match self.selected {
+            Some(from) => self
+                .legal_moves()
+                .into_iter()
+                .filter(|mv| mv.from == from)
+                .map(|mv| mv.to)
+                .collect(),
+            None => Vec::new(),
+        };
+        let piece = self.position.piece(cell);
+        let checked = piece
+            .is_some_and(|piece| piece.kind == Kind::King && self.position.in_check(piece.owner));
+        let bg = if checked {
+            tailwind::RED.c600
+        } else if self.selected == Some(cell) || targets.contains(&cell) {
+            self.colors.hint_cell_style_bg
+        } else if self
+            .last_bot_move
+            .is_some_and(|mv| mv.from == cell || mv.to == cell)
+        {
+            tailwind::STONE.c500
+        } else {
+            match (row + col) % 2 {
+                0 => tailwind::STONE.c400,
+                _ => tailwind::STONE.c600,
+            }
+        };
+        let style = Style::default().bg(bg).add_modifier(Modifier::BOLD);
+        match piece {
+            Some(piece) => {
+                let fg = match piece.owner {
+                    Player::Human => tailwind::SLATE.c50,
+                    Player::Bot => tailwind::SLATE.c950,
+                };
+                (piece.kind.symbol().into(), style.fg(fg))
+            }
+            None if targets.contains(&cell) => ("·".into(), style.fg(tailwind::SLATE.c50)),
+            None => (String::new(), style),
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        self.notice = None;
+        if let Some((from, to)) = self.promoting {
+            let kind = match key.code {
+                // `q` would leave the game, so the queen takes the usual confirm keys
+                KeyCode::Char(' ') | KeyCode::Enter => Kind::Queen,
+                KeyCode::Char('r') => Kind::Rook,
+                KeyCode::Char('b') => Kind::Bishop,
+                KeyCode::Char('n') => Kind::Knight,
+                _ => return,
+            };
+            self.promoting = None;
+            self.apply(ChessMove {
+                from,
+                to,
+                promotion: Some(kind),
+            });
+            return;
+        }
+        match key.code {
+            KeyCode::Char('r') => self.rematch(),
+            KeyCode::Char('b') => self.opponent = self.opponent.next(),
+            // the board is frozen once the game is decided
+            _ if self.outcome.is_some() || self.position.turn != Player::Human => {}
+            KeyCode::Char(' ') | KeyCode::Enter => self.select(),
+            code => {
+                self.cursor.move_with(code);
+            }
+        }
+    }
+
+    /// Search for the bot's reply once the human has moved, and play it when found.
+    fn tick(&mut self) -> bool {
+        if self.outcome.is_some() || self.position.turn != Player::Bot {
+            return false;
+        }
+        let (position, opponent) = (self.position, self.opponent);
+        let thinking = self
+            .thinking
+            .get_or_insert_with(|| bot::Thinking::start(move || choose_move(&position, opponent)));
+        let answer = match thinking.answer() {
+            bot::Answer::Pending => return false,
+            bot::Answer::Ready(answer) => answer,
+            bot::Answer::Failed => choose_move(&self.position, bot::Opponent::Random),
+        };
+        self.thinking = None;
+        if let Some(mv) = answer {
+            self.bot_move(mv);
+        }
+        true
+    }
+
+    fn apply_settings(&mut self, settings: &Settings) {
+        self.colors = settings.colors();
+        self.opponent = settings.opponent(self.opponent);
+    }
+
+    fn restart(&mut self) {
+        self.rematch();
+    }
+
+    fn help(&self) -> Help {
+        Help {
+            rules: "You play White against the bot. Every rule of movement is enforced, castling, \
+                    en passant and promotion included. Checkmate wins; stalemate, the fifty-move \
+                    rule, threefold repetition and too little material draw."
+                .into(),
+            keys: vec![
+                ("←↑↓→/wasd", "move the cursor"),
+                ("Space/Enter", "pick a piece, then where it goes"),
+                ("Space r b n", "promote to a queen, rook, bishop or knight"),
+                ("b", "switch bot"),
+                ("r", "rematch"),
+            ],
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [area, help_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(frame.area());
+        let help = match (self.outcome, self.promoting) {
+            (Some(_), _) => "r rematch · b switch bot",
+            (None, Some(_)) => "Promote to: Space queen · r rook · b bishop · n knight",
+            (None, None) => "←↑↓→/wasd move · Space pick, then move · b switch bot · r rematch",
+        };
+        frame.render_widget(Paragraph::new(help).centered().dark_gray(), help_area);
+        // tall cells when the whole board fits, otherwise the compact ones of a large board
+        let (cell_width, cell_height) = match area.height > table_extent(SIDE, 3, 0) {
+            true => cell_size(5),
+            false => cell_size(SIDE),
+        };
+        let table_width = table_extent(SIDE, cell_width, 1);
+        let table_height = table_extent(SIDE, cell_height, 0);
+        let min_width = table_width.max(60);
+        if area.width < min_width || area.height < table_height + 1 {
+            grid::render_too_small(frame, area, min_width, table_height + 1);
+            return;
+        }
+
+        let layout = calculate_layout(area, false, table_width, table_height);
+        let viewport = Viewport {
+            row: 0,
+            col: 0,
+            rows: SIDE,
+            cols: SIDE,
+        };
+        self.state.select(Some(self.cursor.row));
+        self.state.select_column(Some(self.cursor.col));
+        frame.render_widget(Paragraph::new(self.render_title()).centered(), layout.title);
+        let cursor_style = match (self.outcome, self.position.turn) {
+            (None, Player::Human) => Style::default().add_modifier(Modifier::REVERSED),
+            _ => Style::default(),
+        };
+        let table = grid::table(self, viewport, cell_width, cell_height, &self.colors)
+            .cell_highlight_style(cursor_style);
+        frame.render_stateful_widget(table, layout.table, &mut self.state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The position drawn in `rows`, the bot's home row first: `KQRBNP` for the human's
+    /// pieces, lowercase for the bot's and `.` for an empty cell. No castling is allowed.
+    fn position(rows: [&str; SIDE], turn: Player) -> Position {
+        let board = std::array::from_fn(|row| {
+            let cells: Vec<char> = rows[row].chars().collect();
+            std::array::from_fn(|col| {
+                let kind = match cells[col].to_ascii_uppercase() {
+                    'P' => Kind::Pawn,
+                    'N' => Kind::Knight,
+                    'B' => Kind::Bishop,
+                    'R' => Kind::Rook,
+                    'Q' => Kind::Queen,
+                    'K' => Kind::King,
+                    _ => return None,
+                };
+                let owner = match cells[col].is_ascii_uppercase() {
+                    true => Player::Human,
+                    false => Player::Bot,
+                };
+                Some(Piece { owner, kind })
+            })
+        });
+        Position {
+            board,
+            turn,
+            castling: [[false; 2]; 2],
+            en_passant: None,
+        }
+    }
+
+    fn step(from: [usize; 2], to: [usize; 2]) -> ChessMove {
+        ChessMove {
+            from,
+            to,
+            promotion: None,
+        }
+    }
+
+    /// The game after the human plays `mv` in `position`.
+    fn after(position: Position, mv: ChessMove) -> Chess {
+        let mut chess = Chess::new();
+        chess.position = position;
+        chess.history = vec![position];
+        chess.play(mv);
+        chess
+    }
+
+    #[test]
+    fn the_king_castles_only_when_not_in_or_passing_through_check() {
+        let castle = step([7, 4], [7, 6]);
+        let rows = |attacker: &'static str| {
+            [
+                "k.......", attacker, "........", "........", "........", "........", "........",
+                "....K..R",
+            ]
+        };
+        let mut free = position(rows("........"), Player::Human);
+        free.castling[0][0] = true;
+        assert!(free.legal_moves().contains(&castle));
+        let castled = free.play(castle);
+        assert_eq!(
+            castled.piece([7, 5]).map(|piece| piece.kind),
+            Some(Kind::Rook)
+        );
+        assert_eq!(castled.piece([7, 7]), None);
+
+        for attacker in ["....r...", ".....r.."] {
+            let mut checked = position(rows(attacker), Player::Human);
+            checked.castling[0][0] = true;
+            assert!(
+                !checked.legal_moves().contains(&castle),
+                "castled past `{attacker}`"
+            );
+        }
+    }
+
+    #[test]
+    fn a_pawn_is_taken_en_passant_only_straight_after_its_double_step() {
+        let start = position(
+            [
+                "k.......", "...p....", "........", "....P...", "........", "........", "........",
+                "K.......",
+            ],
+            Player::Bot,
+        );
+        let stepped = start.play(step([1, 3], [3, 3]));
+        assert_eq!(stepped.en_passant, Some([2, 3]));
+        let take = step([3, 4], [2, 3]);
+        assert!(stepped.legal_moves().contains(&take));
+        let taken = stepped.play(take);
+        assert_eq!(taken.piece([3, 3]), None);
+        assert_eq!(
+            taken.piece([2, 3]).map(|piece| piece.owner),
+            Some(Player::Human)
+        );
+
+        let waited = stepped
+            .play(step([7, 0], [6, 0]))
+            .play(step([0, 0], [0, 1]));
+        assert!(!waited.legal_moves().contains(&take));
+    }
+
+    #[test]
+    fn a_pawn_reaching_the_last_row_promotes_to_the_piece_chosen() {
+        let start = position(
+            [
+                "....k...", "P.......", "........", "........", "........", "........", "........",
+                "....K...",
+            ],
+            Player::Human,
+        );
+        let promotions: Vec<_> = start
+            .legal_moves()
+            .into_iter()
+            .filter(|mv| mv.from == [1, 0])
+            .map(|mv| mv.promotion)
+            .collect();
+        assert_eq!(promotions.len(), 4);
+        assert!(promotions.iter().all(Option::is_some));
+        let promoted = start.play(ChessMove {
+            promotion: Some(Kind::Knight),
+            ..step([1, 0], [0, 0])
+        });
+        assert_eq!(
+            promoted.piece([0, 0]).map(|piece| piece.kind),
+            Some(Kind::Knight)
+        );
+    }
+
+    #[test]
+    fn no_moves_left_is_a_loss_in_check_and_a_draw_out_of_it() {
+        let back_rank = position(
+            [
+                ".......k", "......pp", "........", "........", "........", "........", "........",
+                "R.....K.",
+            ],
+            Player::Human,
+        );
+        let mated = after(back_rank, step([7, 0], [0, 0]));
+        assert_eq!(mated.outcome, Some(Outcome::Winner(Player::Human)));
+
+        let cornered = position(
+            [
+                "k.......", "........", ".K......", "..Q.....", "........", "........", "........",
+                "........",
+            ],
+            Player::Human,
+        );
+        let stalemated = after(cornered, step([3, 2], [1, 2]));
+        assert_eq!(stalemated.outcome, Some(Outcome::Draw));
+    }
+}
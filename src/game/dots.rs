@@ -0,0 +1,547 @@
+//! Dots and Boxes: take turns drawing a line between two neighbouring dots; closing the
+//! fourth side of a box claims it and earns another line, and whoever claims more boxes wins.
+//!
+//! The board is a lattice of dots, lines and boxes, and the cursor moves over the lines only.
+//! The hard bot avoids handing over boxes while it can, gives away the shortest chain once it
+//! can't, and turns down the last two boxes of a chain to keep control when that pays.
+
+use std::ops::RangeInclusive;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use rand::seq::IndexedRandom;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout},
+    style::{Modifier, Style, Stylize, palette::tailwind},
+    text::{Line, Span, Text},
+    widgets::Paragraph,
+};
+
+use crate::{
+    Outcome, Player, bot,
+    game::{Game, Help, Status},
+    grid,
+    settings::Settings,
+    ui::{
+        layout::center,
+        palette::{PALETTES, TableColors},
+    },
+};
+
+/// The smallest and largest number of rows or columns of boxes.
+pub const GRID_SIZES: RangeInclusive<usize> = 2..=9;
+
+/// The boxes played when no grid is given.
+pub const DEFAULT_GRID: (usize, usize) = (5, 5);
+
+/// Columns of the terminal a box, and the line above or below it, takes.
+const BOX_WIDTH: usize = 5;
+
+/// Rows of the terminal a box, and the line beside it, takes.
+const BOX_HEIGHT: usize = 2;
+
+/// The lines drawn on a lattice of dots.
+///
+/// Cells of the lattice with an even row and column are dots, those with both odd are boxes,
+/// and the rest are the lines between two dots: across on even rows, down on odd ones.
+#[derive(Debug, Clone)]
+struct Lattice {
+    drawn: Vec<Vec<bool>>,
+}
+
+impl Lattice {
+    /// A lattice around `rows`×`cols` boxes with no lines drawn.
+    fn new(rows: usize, cols: usize) -> Self {
+        Self {
+            drawn: vec![vec![false; 2 * cols + 1]; 2 * rows + 1],
+        }
+    }
+
+    fn rows(&self) -> usize {
+        self.drawn.len()
+    }
+
+    fn cols(&self) -> usize {
+        self.drawn[0].len()
+    }
+
+    /// The lines not drawn yet.
+    fn free_lines(&self) -> Vec<[usize; 2]> {
+        (0..self.rows())
+            .flat_map(|row| (0..self.cols()).map(move |col| [row, col]))
+            .filter(|&[row, col]| (row + col) % 2 == 1 && !self.drawn[row][col])
+            .collect()
+    }
+
+    /// The one or two boxes `line` is a side of.
+    fn boxes_beside(&self, [row, col]: [usize; 2]) -> Vec<[usize; 2]> {
+        let neighbours = match row % 2 {
+            0 => [[row.wrapping_sub(1), col], [row + 1, col]],
+            _ => [[row, col.wrapping_sub(1)], [row, col + 1]],
+        };
+        neighbours
+            .into_iter()
+            .filter(|&[row, col]| row < self.rows() && col < self.cols())
+            .collect()
+    }
+
+    /// The four sides of `cell`, a box.
+    const fn sides([row, col]: [usize; 2]) -> [[usize; 2]; 4] {
+        [
+            [row - 1, col],
+            [row + 1, col],
+            [row, col - 1],
+            [row, col + 1],
+        ]
+    }
+
+    /// How many sides of `cell`, a box, are drawn.
+    fn drawn_sides(&self, cell: [usize; 2]) -> usize {
+        Self::sides(cell)
+            .iter()
+            .filter(|&&[row, col]| self.drawn[row][col])
+            .count()
+    }
+
+    /// Draw `line`, returning the boxes it closes.
+    fn draw(&mut self, line: [usize; 2]) -> Vec<[usize; 2]> {
+        self.drawn[line[0]][line[1]] = true;
+        self.boxes_beside(line)
+            .into_iter()
+            .filter(|&cell| self.drawn_sides(cell) == 4)
+            .collect()
+    }
+
+    /// Whether drawing `line` would close a box.
+    fn closes_box(&self, line: [usize; 2]) -> bool {
+        self.boxes_beside(line)
+            .iter()
+            .any(|&cell| self.drawn_sides(cell) == 3)
+    }
+
+    /// Whether `line` can be drawn without leaving a box for the other side to close.
+    fn is_safe(&self, line: [usize; 2]) -> bool {
+        self.boxes_beside(line)
+            .iter()
+            .all(|&cell| self.drawn_sides(cell) < 2)
+    }
+
+    /// How many boxes the other side closes in a row after `line` is drawn.
+    fn giveaway(&self, line: [usize; 2]) -> usize {
+        let mut lattice = self.clone();
+        lattice.draw(line);
+        let mut closed = 0;
+        while let Some(line) = lattice
+            .free_lines()
+            .into_iter()
+            .find(|&line| lattice.closes_box(line))
+        {
+            closed += lattice.draw(line).len();
+        }
+        closed
+    }
+
+    /// The line that hands the last two boxes of a chain over instead of closing them, when
+    /// `line` closes the first of them; the other side has to move again after taking both.
+    fn double_deal(&self, line: [usize; 2]) -> Option<[usize; 2]> {
+        let [first, second] = self.boxes_beside(line)[..] else {
+            return None;
+        };
+        let (closing, next) = match self.drawn_sides(first) {
+            3 => (first, second),
+            _ => (second, first),
+        };
+        if self.drawn_sides(closing) != 3 || self.drawn_sides(next) != 2 {
+            return None;
+        }
+        // the far side of the second box must not lead the chain on into a third
+        let far = Lattice::sides(next)
+            .into_iter()
+            .find(|&[row, col]| [row, col] != line && !self.drawn[row][col])?;
+        let ends_chain = self
+            .boxes_beside(far)
+            .iter()
+            .all(|&cell| cell == next || self.drawn_sides(cell) < 2);
+        ends_chain.then_some(far)
+    }
+}
+
+/// The line `opponent` draws on `lattice`, if any are left.
+fn choose_line(lattice: &Lattice, opponent: bot::Opponent) -> Option<[usize; 2]> {
+    let free = lattice.free_lines();
+    let mut rng = rand::rng();
+    if opponent == bot::Opponent::Random {
+        return free.choose(&mut rng).copied();
+    }
+    let safe: Vec<_> = free
+        .iter()
+        .copied()
+        .filter(|&line| lattice.is_safe(line))
+        .collect();
+    let closing: Vec<_> = free
+        .iter()
+        .copied()
+        .filter(|&line| lattice.closes_box(line))
+        .collect();
+    // boxes that can be taken whole are taken first, leaving the end of a chain for last
+    if let Some(&line) = closing
+        .iter()
+        .find(|&&line| lattice.double_deal(line).is_none())
+        .or(closing.first())
+    {
+        // once every line left hands boxes over, giving up two keeps control: the other side
+        // then has to open the next chain, which pays when a long one is still to come
+        if safe.is_empty()
+            && closing.len() == 1
+            && let Some(far) = lattice.double_deal(line)
+        {
+            let mut rest = lattice.clone();
+            rest.draw(far);
+            rest.draw(line);
+            if rest
+                .free_lines()
+                .into_iter()
+                .any(|line| rest.giveaway(line) >= 3)
+            {
+                return Some(far);
+            }
+        }
+        return Some(line);
+    }
+    if let Some(&line) = safe.choose(&mut rng) {
+        return Some(line);
+    }
+    free.into_iter().min_by_key(|&line| lattice.giveaway(line))
+}
+
+/// A game of Dots and Boxes against the bot.
+pub struct Dots {
+    lattice: Lattice,
+    /// Who closed each box, by its cell in the lattice.
+    owners: Vec<Vec<Option<Player>>>,
+    turn: Player,
+    /// Who moved first this game; the sides take turns opening.
+    starter: Player,
+    opponent: bot::Opponent,
+    outcome: Option<Outcome>,
+    last_bot_line: Option<[usize; 2]>,
+    /// The bot's search for the line it draws next.
+    thinking: Option<bot::Thinking<Option<[usize; 2]>>>,
+    /// The line under the cursor, as a cell of the lattice.
+    cursor: [usize; 2],
+    colors: TableColors,
+}
+
+impl Dots {
+    /// A game on `rows`×`cols` boxes.
+    pub fn new(rows: usize, cols: usize) -> Self {
+        let lattice = Lattice::new(rows, cols);
+        Self {
+            owners: vec![vec![None; lattice.cols()]; lattice.rows()],
+            lattice,
+            turn: Player::Human,
+            starter: Player::Human,
+            opponent: bot::Opponent::Minimax,
+            outcome: None,
+            last_bot_line: None,
+            thinking: None,
+            cursor: [0, 1],
+            colors: TableColors::new(&PALETTES[0]),
+        }
+    }
+
+    /// Start a new game on a board of the same size, with the other side opening.
+    fn rematch(&mut self) {
+        let starter = self.starter.other();
+        let (rows, cols) = (self.lattice.rows() / 2, self.lattice.cols() / 2);
+        *self = Self {
+            colors: self.colors,
+            turn: starter,
+            starter,
+            opponent: self.opponent,
+            ..Self::new(rows, cols)
+        };
+    }
+
+    /// How many boxes each side holds, the human's first.
+    fn score(&self) -> (usize, usize) {
+        let owners = self.owners.iter().flatten().flatten();
+        let human = owners
+            .clone()
+            .filter(|&&owner| owner == Player::Human)
+            .count();
+        (human, owners.count() - human)
+    }
+
+    /// Draw `line` for the side to move, which keeps the turn when it closes a box.
+    fn play(&mut self, line: [usize; 2]) {
+        let closed = self.lattice.draw(line);
+        for &[row, col] in &closed {
+            self.owners[row][col] = Some(self.turn);
+        }
+        if self.lattice.free_lines().is_empty() {
+            let (human, bot) = self.score();
+            self.outcome = Some(match human.cmp(&bot) {
+                std::cmp::Ordering::Greater => Outcome::Winner(Player::Human),
+                std::cmp::Ordering::Less => Outcome::Winner(Player::Bot),
+                std::cmp::Ordering::Equal => Outcome::Draw,
+            });
+        } else if closed.is_empty() {
+            self.turn = self.turn.other();
+        }
+    }
+
+    /// Draw the line the bot settled on.
+    fn bot_move(&mut self, line: [usize; 2]) {
+        self.play(line);
+        self.last_bot_line = Some(line);
+    }
+
+    /// Move the cursor to the next line in the direction of `code`.
+    ///
+    /// Across, it steps over a dot or box to the line beyond; up or down, it moves between
+    /// lines across and lines down in turn, keeping to the same box.
+    fn move_cursor(&mut self, code: KeyCode) {
+        let [row, col] = self.cursor;
+        let (rows, cols) = (self.lattice.rows(), self.lattice.cols());
+        // the first and last line of the row: across on even rows, down on odd ones
+        let first = (row + 1) % 2;
+        let last = cols - 1 - first;
+        let row = match code {
+            KeyCode::Char('s') | KeyCode::Down => (row + 1) % rows,
+            KeyCode::Char('w') | KeyCode::Up => (row + rows - 1) % rows,
+            KeyCode::Char('d') | KeyCode::Right => {
+                self.cursor = [row, if col >= last { first } else { col + 2 }];
+                return;
+            }
+            KeyCode::Char('a') | KeyCode::Left => {
+                self.cursor = [row, if col <= first { last } else { col - 2 }];
+                return;
+            }
+            _ => return,
+        };
+        // lines across sit on odd columns and lines down on even ones; wrapping from the top
+        // row to the bottom one keeps to lines across
+        let col = match (row + col) % 2 {
+            1 => col,
+            _ if row % 2 == 0 && col + 1 < cols => col + 1,
+            _ => col - 1,
+        };
+        self.cursor = [row, col];
+    }
+
+    fn render_title(&self) -> Line<'static> {
+        let (human, bot) = self.score();
+        let versus = format!(
+            "Dots and Boxes · You {human} : {bot} Bot [{}]",
+            self.opponent.label()
+        );
+        let status = match (self.outcome, self.turn) {
+            (Some(outcome), _) => format!("{} (r: rematch)", outcome.message()).into(),
+            (None, Player::Bot) => "Bot thinking…".fg(self.colors.row_fg),
+            (None, Player::Human) => "Your turn".fg(self.colors.selected_cell_style_fg),
+        };
+        Line::from(vec![versus.into(), " · ".dark_gray(), status])
+    }
+
+    /// The lattice, each dot one column wide and each line or box [`BOX_WIDTH`] columns wide
+    /// and [`BOX_HEIGHT`] rows tall.
+    fn render_lattice(&self) -> Text<'static> {
+        let (rows, cols) = self.dimensions();
+        let mut lines = Vec::new();
+        for row in 0..rows {
+            let spans: Vec<Span> = (0..cols)
+                .map(|col| {
+                    let (text, style) = self.render_cell([row, col]);
+                    let width = match col % 2 {
+                        0 => 1,
+                        _ => BOX_WIDTH,
+                    };
+                    let text = match text.as_str() {
+                        "" => " ".repeat(width),
+                        text => text.repeat(width),
+                    };
+                    Span::styled(text, style)
+                })
+                .collect();
+            let height = match row % 2 {
+                0 => 1,
+                _ => BOX_HEIGHT,
+            };
+            lines.extend(std::iter::repeat_n(Line::from(spans), height));
+        }
+        Text::from(lines)
+    }
+}
+
+impl Game for Dots {
+    /// A line between two dots, as a cell of the lattice.
+    type Move = [usize; 2];
+
+    /// The rows and columns of the lattice of dots, lines and boxes.
+    fn dimensions(&self) -> (usize, usize) {
+        (self.lattice.rows(), self.lattice.cols())
+    }
+
+    fn legal_moves(&self) -> Vec<[usize; 2]> {
+        if self.outcome.is_some() || self.turn != Player::Human {
+            return Vec::new();
+        }
+        self.lattice.free_lines()
+    }
+
+    fn apply(&mut self, line: [usize; 2]) -> bool {
+        if !self.legal_moves().contains(&line) {
+            return false;
+        }
+        self.last_bot_line = None;
+        self.play(line);
+        true
+    }
+
+    fn status(&self) -> Status {
+        match self.outcome {
+            None => Status::Playing,
+            Some(Outcome::Winner(Player::Human)) => Status::Won,
+            Some(Outcome::Winner(Player::Bot)) => Status::Lost,
+            Some(Outcome::Draw) => Status::Draw,
+        }
+    }
+
+    fn opponent(&self) -> Option<bot::Opponent> {
+        Some(self.opponent)
+    }
+
+    /// A dot, a line drawn or still free, or a box shaded in the color of whoever closed it;
+    /// the text of a line is repeated across its width.
+    fn render_cell(&self, cell: [usize; 2]) -> (String, Style) {
+        let [row, col] = cell;
+        let style = Style::default();
+        match (row % 2, col % 2) {
+            (0, 0) => ("•".into(), style.fg(self.colors.row_fg)),
+            (1, 1) => match self.owners[row][col] {
+                Some(Player::Human) => (String::new(), style.bg(tailwind::BLUE.c700)),
+                Some(Player::Bot) => (String::new(), style.bg(tailwind::ROSE.c800)),
+                None => (String::new(), style),
+            },
+            (across, _) => {
+                let stroke = match across {
+                    0 => "─",
+                    _ => "│",
+                };
+                let drawn = self.lattice.drawn[row][col];
+                let playing = self.outcome.is_none() && self.turn == Player::Human;
+                if playing && self.cursor == cell {
+                    let stroke = match across {
+                        0 => "━",
+                        _ => "┃",
+                    };
+                    let color = match drawn {
+                        true => self.colors.row_fg,
+                        false => self.colors.selected_cell_style_fg,
+                    };
+                    (stroke.into(), style.fg(color).add_modifier(Modifier::BOLD))
+                } else if self.last_bot_line == Some(cell) {
+                    (stroke.into(), style.fg(self.colors.last_move_style_fg))
+                } else if drawn {
+                    (stroke.into(), style.fg(self.colors.row_fg))
+                } else {
+                    (String::new(), style)
+                }
+            }
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char('r') => self.rematch(),
+            KeyCode::Char('b') => self.opponent = self.opponent.next(),
+            // the board is frozen once the game is decided
+            _ if self.outcome.is_some() || self.turn != Player::Human => {}
+            KeyCode::Char(' ') | KeyCode::Enter => {
+                self.apply(self.cursor);
+            }
+            code => self.move_cursor(code),
+        }
+    }
+
+    /// Search for the bot's next line and draw it when found; a bot closing boxes goes on
+    /// a line at a time, each searched for anew, so every one it draws can be seen.
+    fn tick(&mut self) -> bool {
+        if self.outcome.is_some() || self.turn != Player::Bot {
+            return false;
+        }
+        let (lattice, opponent) = (self.lattice.clone(), self.opponent);
+        let thinking = self
+            .thinking
+            .get_or_insert_with(|| bot::Thinking::start(move || choose_line(&lattice, opponent)));
+        let answer = match thinking.answer() {
+            bot::Answer::Pending => return false,
+            bot::Answer::Ready(answer) => answer,
+            bot::Answer::Failed => choose_line(&self.lattice, bot::Opponent::Random),
+        };
+        self.thinking = None;
+        if let Some(line) = answer {
+            self.bot_move(line);
+        }
+        true
+    }
+
+    fn apply_settings(&mut self, settings: &Settings) {
+        self.colors = settings.colors();
+        self.opponent = settings.opponent(self.opponent);
+    }
+
+    fn restart(&mut self) {
+        self.rematch();
+    }
+
+    fn help(&self) -> Help {
+        Help {
+            rules: "Take turns drawing a line between two neighbouring dots. Closing the fourth \
+                    side of a box claims it and earns another line, and whoever claims more boxes \
+                    wins."
+                .into(),
+            keys: vec![
+                ("←↑↓→/wasd", "move the cursor"),
+                ("Space/Enter", "draw the line"),
+                ("b", "switch bot"),
+                ("r", "rematch"),
+            ],
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let area = frame.area();
+        let (rows, cols) = self.dimensions();
+        let board_width = (cols / 2 * (BOX_WIDTH + 1) + 1) as u16;
+        let board_height = (rows / 2 * (BOX_HEIGHT + 1) + 1) as u16;
+        let width = board_width.max(60);
+        let height = board_height + 4;
+        if area.width < width || area.height < height {
+            grid::render_too_small(frame, area, width, height);
+            return;
+        }
+
+        let area = center(
+            area,
+            Constraint::Percentage(100),
+            Constraint::Length(height),
+        );
+        let [title_area, board_area, help_area] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Length(board_height + 1),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+        frame.render_widget(Paragraph::new(self.render_title()).centered(), title_area);
+        let board_area = center(
+            board_area,
+            Constraint::Length(board_width),
+            Constraint::Percentage(100),
+        );
+        frame.render_widget(Paragraph::new(self.render_lattice()), board_area);
+        let help = "Arrows/WASD move · Space draw · b bot · r rematch";
+        frame.render_widget(Paragraph::new(help).centered().dark_gray(), help_area);
+    }
+}
@@ -0,0 +1,298 @@
+//! 2048: slide the numbered tiles of a 4x4 board and merge equal ones until a 2048 tile
+//! appears, or no move is left.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use rand::seq::IteratorRandom;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style, Stylize, palette::tailwind},
+    text::Line,
+    widgets::{Paragraph, TableState},
+};
+
+use crate::{
+    game::{Game, Help, Status},
+    grid::{self, Viewport},
+    records,
+    settings::Settings,
+    ui::{
+        layout::{calculate_layout, cell_size, table_extent},
+        palette::{PALETTES, TableColors},
+    },
+};
+
+/// Cells along one side of the board.
+const SIDE: usize = 4;
+
+/// The tile that wins the game.
+const GOAL: u32 = 2048;
+
+/// A direction all tiles slide in at once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    const ALL: [Self; 4] = [Self::Up, Self::Down, Self::Left, Self::Right];
+
+    /// The cells of line `i` across the slide, starting from the edge the tiles slide to.
+    fn line(self, i: usize) -> [[usize; 2]; SIDE] {
+        std::array::from_fn(|j| match self {
+            Self::Up => [j, i],
+            Self::Down => [SIDE - 1 - j, i],
+            Self::Left => [i, j],
+            Self::Right => [i, SIDE - 1 - j],
+        })
+    }
+}
+
+type Board = [[u32; SIDE]; SIDE];
+
+/// Slide every tile of `board` towards `direction`, merging each pair of equal tiles that
+/// meet once. Returns the board after the slide and the sum of the merged tiles.
+fn slide(board: &Board, direction: Direction) -> (Board, u32) {
+    let mut slid = [[0; SIDE]; SIDE];
+    let mut gained = 0;
+    for i in 0..SIDE {
+        let line = direction.line(i);
+        let mut tiles = line
+            .iter()
+            .map(|&[row, col]| board[row][col])
+            .filter(|&tile| tile != 0)
+            .peekable();
+        let mut cells = line.iter();
+        while let Some(tile) = tiles.next() {
+            let tile = match tiles.next_if_eq(&tile) {
+                Some(_) => {
+                    gained += tile * 2;
+                    tile * 2
+                }
+                None => tile,
+            };
+            if let Some(&[row, col]) = cells.next() {
+                slid[row][col] = tile;
+            }
+        }
+    }
+    (slid, gained)
+}
+
+/// The record the best score is kept under between runs.
+const BEST_SCORE_RECORD: &str = "2048-best";
+
+/// A game of 2048.
+pub struct Game2048 {
+    board: Board,
+    score: u32,
+    best: u32,
+    /// The boards and scores before each move, for undoing them.
+    history: Vec<(Board, u32)>,
+    /// The game was over and a move has been taken back since.
+    reopened: bool,
+    state: TableState,
+    colors: TableColors,
+}
+
+impl Game2048 {
+    /// A fresh board holding two tiles.
+    pub fn new() -> Self {
+        let mut game = Self {
+            board: [[0; SIDE]; SIDE],
+            score: 0,
+            best: records::load(BEST_SCORE_RECORD).unwrap_or(0),
+            history: Vec::new(),
+            reopened: false,
+            state: TableState::default(),
+            colors: TableColors::new(&PALETTES[0]),
+        };
+        game.spawn_tile();
+        game.spawn_tile();
+        game
+    }
+
+    /// Start over on a fresh board, keeping the best score.
+    fn restart(&mut self) {
+        *self = Self {
+            colors: self.colors,
+            ..Self::new()
+        };
+    }
+
+    /// Drop a 2, or now and then a 4, on a random empty cell.
+    fn spawn_tile(&mut self) {
+        let empty = (0..SIDE)
+            .flat_map(|row| (0..SIDE).map(move |col| [row, col]))
+            .filter(|&[row, col]| self.board[row][col] == 0);
+        if let Some([row, col]) = empty.choose(&mut rand::rng()) {
+            self.board[row][col] = if rand::random_ratio(1, 10) { 4 } else { 2 };
+        }
+    }
+
+    /// Take back the last move.
+    fn undo(&mut self) {
+        let over = self.status() != Status::Playing;
+        if let Some((board, score)) = self.history.pop() {
+            self.reopened |= over;
+            self.board = board;
+            self.score = score;
+        }
+    }
+
+    /// Whether a tile of at least [`GOAL`] is on the board.
+    fn reached_goal(&self) -> bool {
+        self.board.iter().flatten().any(|&tile| tile >= GOAL)
+    }
+
+    /// The colors of a tile: each palette shows three tiles in deepening shades before the
+    /// next palette takes over.
+    fn tile_style(tile: u32) -> Style {
+        let step = tile.trailing_zeros() as usize - 1;
+        let palette = &PALETTES[step / 3 % PALETTES.len()];
+        let bg = match step % 3 {
+            0 => palette.c900,
+            1 => palette.c700,
+            _ => palette.c500,
+        };
+        Style::default()
+            .bg(bg)
+            .fg(tailwind::SLATE.c50)
+            .add_modifier(Modifier::BOLD)
+    }
+
+    fn render_title(&self) -> Line<'static> {
+        let status = match self.status() {
+            Status::Playing if self.reached_goal() => {
+                "2048! Keep going".fg(self.colors.selected_cell_style_fg)
+            }
+            Status::Playing => "Arrows: slide · u: undo".fg(self.colors.selected_cell_style_fg),
+            Status::Won => "You win! (r: new game)".fg(self.colors.selected_cell_style_fg),
+            Status::Lost | Status::Draw => "No moves left! (r: new game)".fg(Color::Red),
+        };
+        Line::from(vec![
+            format!("2048 · Score {} · Best {}", self.score, self.best).into(),
+            " · ".dark_gray(),
+            status,
+        ])
+    }
+}
+
+impl Game for Game2048 {
+    type Move = Direction;
+
+    fn dimensions(&self) -> (usize, usize) {
+        (SIDE, SIDE)
+    }
+
+    /// The directions that move at least one tile.
+    fn legal_moves(&self) -> Vec<Direction> {
+        Direction::ALL
+            .into_iter()
+            .filter(|&direction| slide(&self.board, direction).0 != self.board)
+            .collect()
+    }
+
+    fn apply(&mut self, direction: Direction) -> bool {
+        let (board, gained) = slide(&self.board, direction);
+        if board == self.board {
+            return false;
+        }
+        self.history.push((self.board, self.score));
+        self.board = board;
+        self.score += gained;
+        self.spawn_tile();
+        if self.score > self.best {
+            self.best = self.score;
+            records::save(BEST_SCORE_RECORD, self.best);
+        }
+        true
+    }
+
+    /// The game goes on while any move is left; it is won if a 2048 tile was made by then.
+    fn status(&self) -> Status {
+        match (self.legal_moves().is_empty(), self.reached_goal()) {
+            (false, _) => Status::Playing,
+            (true, true) => Status::Won,
+            (true, false) => Status::Lost,
+        }
+    }
+
+    fn render_cell(&self, [row, col]: [usize; 2]) -> (String, Style) {
+        match self.board[row][col] {
+            0 => (String::new(), Style::default()),
+            tile => (tile.to_string(), Self::tile_style(tile)),
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        let direction = match key.code {
+            KeyCode::Char('r') => return self.restart(),
+            KeyCode::Char('u') => return self.undo(),
+            KeyCode::Char('w') | KeyCode::Up => Direction::Up,
+            KeyCode::Char('s') | KeyCode::Down => Direction::Down,
+            KeyCode::Char('a') | KeyCode::Left => Direction::Left,
+            KeyCode::Char('d') | KeyCode::Right => Direction::Right,
+            _ => return,
+        };
+        self.apply(direction);
+    }
+
+    fn apply_settings(&mut self, settings: &Settings) {
+        self.colors = settings.colors();
+    }
+
+    fn restart(&mut self) {
+        self.restart();
+    }
+
+    fn reopened(&self) -> bool {
+        self.reopened
+    }
+
+    fn help(&self) -> Help {
+        Help {
+            rules: "Slide every tile of the board one way; two equal tiles meeting merge into \
+                    their sum. A new tile appears after every move. Make a 2048 tile before no \
+                    move is left."
+                .into(),
+            keys: vec![
+                ("←↑↓→/wasd", "slide the tiles"),
+                ("u", "undo"),
+                ("r", "new game"),
+            ],
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [area, help_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(frame.area());
+        let help = match self.status() {
+            Status::Playing => "←↑↓→/wasd slide · u undo · r new game",
+            _ => "u undo · r new game",
+        };
+        frame.render_widget(Paragraph::new(help).centered().dark_gray(), help_area);
+        let (cell_width, cell_height) = cell_size(SIDE);
+        let table_width = table_extent(SIDE, cell_width, 1);
+        let table_height = table_extent(SIDE, cell_height, 0);
+        let min_width = table_width.max(40);
+        if area.width < min_width || area.height < table_height + 1 {
+            grid::render_too_small(frame, area, min_width, table_height + 1);
+            return;
+        }
+
+        let layout = calculate_layout(area, false, table_width, table_height);
+        let viewport = Viewport {
+            row: 0,
+            col: 0,
+            rows: SIDE,
+            cols: SIDE,
+        };
+        frame.render_widget(Paragraph::new(self.render_title()).centered(), layout.title);
+        let table = grid::table(self, viewport, cell_width, cell_height, &self.colors);
+        frame.render_stateful_widget(table, layout.table, &mut self.state);
+    }
+}
@@ -0,0 +1,1013 @@
+//! Go on a 9x9 board against the bot.
+//!
+//! Stones left without liberties are captured, a stone can't be played where it would have
+//! none itself, and a single stone can't be retaken straight back (ko). Once both sides pass
+//! in a row, the dead stones are marked and the board is scored by territory: the empty
+//! points each side surrounds, plus the stones it has captured, with 6.5 points of komi for
+//! White. Finished games can be saved as SGF in the `games` folder beside the records.
+
+use std::{
+    fmt::Write as _,
+    fs,
+    time::{Duration, Instant, SystemTime},
+};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use rand::seq::SliceRandom;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Flex, Layout},
+    style::{Color, Modifier, Style, Stylize, palette::tailwind},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+use crate::{
+    Outcome, Player, bot,
+    game::{Game, Help, Status},
+    grid::{self, Cursor},
+    records,
+    settings::Settings,
+    ui::{
+        layout::center,
+        palette::{PALETTES, TableColors},
+    },
+};
+
+/// Points along one side of the board.
+const SIZE: usize = 9;
+
+/// The points White is given for moving second.
+const KOMI: f32 = 6.5;
+
+/// How long the hard bot searches for each move.
+const THINK_TIME: Duration = Duration::from_millis(1500);
+
+/// How strongly the search tries moves it knows little about over those that did well.
+const EXPLORATION: f64 = 1.0;
+
+/// The most moves a game is played out for, in case it would go on capturing and refilling.
+const MAX_PLAYOUT_MOVES: usize = 3 * SIZE * SIZE;
+
+/// How many games are played out to guess which stones are dead once both sides pass, and
+/// the share of them a stone must be lost in to be marked dead.
+const DEAD_PLAYOUTS: usize = 300;
+const DEAD_SHARE: f64 = 0.7;
+
+/// The letters naming the columns, skipping `I` as Go boards do.
+const COLUMNS: &str = "ABCDEFGHJ";
+
+/// Columns of the terminal each point takes.
+const POINT_WIDTH: usize = 5;
+
+type Board = [[Option<Player>; SIZE]; SIZE];
+
+/// Where `player`'s count is kept in a pair of them, the human's first.
+const fn index(player: Player) -> usize {
+    match player {
+        Player::Human => 0,
+        Player::Bot => 1,
+    }
+}
+
+/// The points next to `[row, col]`.
+fn neighbours([row, col]: [usize; 2]) -> impl Iterator<Item = [usize; 2]> {
+    [(-1, 0), (1, 0), (0, -1), (0, 1)]
+        .into_iter()
+        .filter_map(move |(d_row, d_col)| {
+            let row = row.checked_add_signed(d_row).filter(|&row| row < SIZE)?;
+            let col = col.checked_add_signed(d_col).filter(|&col| col < SIZE)?;
+            Some([row, col])
+        })
+}
+
+/// The stones of the group at `cell` and how many liberties it has.
+fn group(board: &Board, cell: [usize; 2]) -> (Vec<[usize; 2]>, usize) {
+    let owner = board[cell[0]][cell[1]];
+    let mut seen = [[false; SIZE]; SIZE];
+    seen[cell[0]][cell[1]] = true;
+    let mut stones = vec![cell];
+    let mut liberties = 0;
+    let mut next = 0;
+    while let Some(&stone) = stones.get(next) {
+        next += 1;
+        for [row, col] in neighbours(stone) {
+            if seen[row][col] {
+                continue;
+            }
+            seen[row][col] = true;
+            match board[row][col] {
+                None => liberties += 1,
+                stone if stone == owner => stones.push([row, col]),
+                Some(_) => {}
+            }
+        }
+    }
+    (stones, liberties)
+}
+
+/// The points of the empty region at `cell`, and which sides' stones border it.
+fn region(board: &Board, cell: [usize; 2]) -> (Vec<[usize; 2]>, [bool; 2]) {
+    let mut seen = [[false; SIZE]; SIZE];
+    seen[cell[0]][cell[1]] = true;
+    let mut points = vec![cell];
+    let mut borders = [false; 2];
+    let mut next = 0;
+    while let Some(&point) = points.get(next) {
+        next += 1;
+        for [row, col] in neighbours(point) {
+            match board[row][col] {
+                Some(Player::Human) => borders[0] = true,
+                Some(Player::Bot) => borders[1] = true,
+                None if !seen[row][col] => {
+                    seen[row][col] = true;
+                    points.push([row, col]);
+                }
+                None => {}
+            }
+        }
+    }
+    (points, borders)
+}
+
+/// Who each point belongs to: the side whose stone is on it, or the only side bordering the
+/// empty region it is in.
+fn owners(board: &Board) -> [[Option<Player>; SIZE]; SIZE] {
+    let mut owners = *board;
+    let mut seen = [[false; SIZE]; SIZE];
+    for row in 0..SIZE {
+        for col in 0..SIZE {
+            if board[row][col].is_some() || seen[row][col] {
+                continue;
+            }
+            let (points, borders) = region(board, [row, col]);
+            let owner = match borders {
+                [true, false] => Some(Player::Human),
+                [false, true] => Some(Player::Bot),
+                _ => None,
+            };
+            for [r, c] in points {
+                seen[r][c] = true;
+                owners[r][c] = owner;
+            }
+        }
+    }
+    owners
+}
+
+/// The points each side scores by territory on `board`, the human's first: the empty points
+/// only it surrounds plus the `captures` it made, with komi for White.
+fn territory_score(board: &Board, captures: [usize; 2], black: Player) -> [f32; 2] {
+    let mut score = captures.map(|captures| captures as f32);
+    let owners = owners(board);
+    for (point, owner) in board.iter().flatten().zip(owners.iter().flatten()) {
+        if point.is_none()
+            && let Some(owner) = owner
+        {
+            score[index(*owner)] += 1.0;
+        }
+    }
+    score[index(black.other())] += KOMI;
+    score
+}
+
+/// The side with more points of `score`, which komi keeps from ever being even.
+fn leader([human, bot]: [f32; 2]) -> Player {
+    match human > bot {
+        true => Player::Human,
+        false => Player::Bot,
+    }
+}
+
+/// A board, the stones each side has captured on the way to it and the point a ko forbids
+/// retaking on the next move.
+#[derive(Debug, Clone, Copy)]
+struct Position {
+    board: Board,
+    /// The stones each side has captured, the human's first.
+    captures: [usize; 2],
+    ko: Option<[usize; 2]>,
+}
+
+impl Position {
+    fn new() -> Self {
+        Self {
+            board: [[None; SIZE]; SIZE],
+            captures: [0; 2],
+            ko: None,
+        }
+    }
+
+    /// Play `player`'s stone at `cell`, removing the stones it captures, and return whether
+    /// it could be played; it can't on a point taken or forbidden by ko, or where it would
+    /// have no liberties. The position is unchanged by a move it rejects.
+    fn play(&mut self, player: Player, cell: [usize; 2]) -> bool {
+        if self.board[cell[0]][cell[1]].is_some() || self.ko == Some(cell) {
+            return false;
+        }
+        let mut board = self.board;
+        board[cell[0]][cell[1]] = Some(player);
+        let mut captured = Vec::new();
+        for next in neighbours(cell) {
+            if board[next[0]][next[1]] == Some(player.other())
+                && let (stones, 0) = group(&board, next)
+            {
+                for [row, col] in stones {
+                    board[row][col] = None;
+                    captured.push([row, col]);
+                }
+            }
+        }
+        let (stones, liberties) = group(&board, cell);
+        if liberties == 0 {
+            return false;
+        }
+        // a lone stone taking a lone stone could be taken straight back, which is a ko
+        self.ko = match captured[..] {
+            [point] if stones.len() == 1 && liberties == 1 => Some(point),
+            _ => None,
+        };
+        self.board = board;
+        self.captures[index(player)] += captured.len();
+        true
+    }
+
+    /// Whether `player` may play at `cell`.
+    fn allows(&self, player: Player, cell: [usize; 2]) -> bool {
+        let mut next = *self;
+        next.play(player, cell)
+    }
+
+    /// Whether every empty point is surrounded by one side alone, so that playing on can
+    /// only fill in territory or give stones away in the other side's.
+    fn is_settled(&self) -> bool {
+        let owners = owners(&self.board);
+        self.board
+            .iter()
+            .flatten()
+            .zip(owners.iter().flatten())
+            .all(|(point, owner)| point.is_some() || owner.is_some())
+    }
+
+    /// Whether `cell` is an eye of `player`'s: every point next to it is their stone, and
+    /// the other side holds too few of the points diagonal to it to ever break it.
+    fn is_eye(&self, player: Player, [row, col]: [usize; 2]) -> bool {
+        if self.board[row][col].is_some()
+            || neighbours([row, col]).any(|[r, c]| self.board[r][c] != Some(player))
+        {
+            return false;
+        }
+        let diagonals: Vec<_> = [(-1, -1), (-1, 1), (1, -1), (1, 1)]
+            .into_iter()
+            .filter_map(|(d_row, d_col)| {
+                let r = row.checked_add_signed(d_row).filter(|&r| r < SIZE)?;
+                let c = col.checked_add_signed(d_col).filter(|&c| c < SIZE)?;
+                Some(self.board[r][c])
+            })
+            .collect();
+        let taken = diagonals
+            .iter()
+            .filter(|&&stone| stone == Some(player.other()))
+            .count();
+        match diagonals.len() {
+            4 => taken < 2,
+            _ => taken == 0,
+        }
+    }
+
+    /// The points `player` may play without filling an eye of their own, tried in a random
+    /// order.
+    fn sensible_moves(&self, player: Player) -> Vec<[usize; 2]> {
+        let mut moves: Vec<_> = (0..SIZE)
+            .flat_map(|row| (0..SIZE).map(move |col| [row, col]))
+            .filter(|&cell| self.board[cell[0]][cell[1]].is_none() && !self.is_eye(player, cell))
+            .collect();
+        moves.shuffle(&mut rand::rng());
+        moves
+    }
+
+    /// Play random sensible moves from here, `player` first, until both sides pass.
+    fn play_out(&mut self, mut player: Player) {
+        let mut passes = 0;
+        for _ in 0..MAX_PLAYOUT_MOVES {
+            let played = self
+                .sensible_moves(player)
+                .into_iter()
+                .any(|cell| self.play(player, cell));
+            passes = match played {
+                true => 0,
+                false => passes + 1,
+            };
+            if passes == 2 {
+                break;
+            }
+            player = player.other();
+        }
+    }
+
+    /// Who wins by territory and captures, as the game is scored, once the stones left on
+    /// the board are all alive.
+    fn winner(&self, black: Player) -> Player {
+        leader(territory_score(&self.board, self.captures, black))
+    }
+}
+
+/// A position in the search tree, reached by the move of the node above it.
+struct Node {
+    /// The point played to reach it, `None` for a pass, and who played it.
+    play: Option<[usize; 2]>,
+    mover: Player,
+    parent: Option<usize>,
+    children: Vec<usize>,
+    /// Moves from here with no node of their own yet, some of which may turn out illegal.
+    untried: Vec<Option<[usize; 2]>>,
+    visits: f64,
+    /// How many of the games played out through here `mover` won.
+    wins: f64,
+}
+
+impl Node {
+    /// A node for the position `player` is to move in after `play`, `passes` passes in a row
+    /// having ended the game once there are two.
+    fn new(
+        position: &Position,
+        player: Player,
+        play: Option<[usize; 2]>,
+        parent: Option<usize>,
+        passes: usize,
+    ) -> Self {
+        let mut untried: Vec<_> = match passes {
+            2 => Vec::new(),
+            _ => position
+                .sensible_moves(player)
+                .into_iter()
+                .map(Some)
+                .collect(),
+        };
+        // passing is only worth a look once there is nothing left to gain, or it ends the game
+        if passes == 1 || (passes == 0 && (untried.is_empty() || position.is_settled())) {
+            untried.insert(0, None);
+        }
+        Self {
+            play,
+            mover: player.other(),
+            parent,
+            children: Vec::new(),
+            untried,
+            visits: 0.0,
+            wins: 0.0,
+        }
+    }
+}
+
+/// The move Monte Carlo tree search picks for `player`, `None` for a pass, searching for
+/// `think` time; `passes` is how many passes were just made in a row.
+///
+/// Each round follows the tree down by the upper confidence bound, adds a node for a move not
+/// tried yet, plays the rest of the game out at random and counts who won on every node on
+/// the way back up. The move tried most often wins.
+fn search(
+    position: &Position,
+    player: Player,
+    passes: usize,
+    black: Player,
+    think: Duration,
+) -> Option<[usize; 2]> {
+    let mut nodes = vec![Node::new(position, player, None, None, passes)];
+    let deadline = Instant::now() + think;
+    while Instant::now() < deadline {
+        let mut position = *position;
+        let mut turn = player;
+        let mut passed = passes;
+        let mut node = 0;
+        loop {
+            if let Some(play) = nodes[node].untried.pop() {
+                let legal = match play {
+                    Some(cell) => position.play(turn, cell),
+                    None => true,
+                };
+                if !legal {
+                    continue;
+                }
+                passed = match play {
+                    Some(_) => 0,
+                    None => passed + 1,
+                };
+                turn = turn.other();
+                let child = Node::new(&position, turn, play, Some(node), passed);
+                nodes.push(child);
+                let child = nodes.len() - 1;
+                nodes[node].children.push(child);
+                node = child;
+                break;
+            }
+            if nodes[node].children.is_empty() {
+                break;
+            }
+            let log_visits = nodes[node].visits.ln();
+            let bound = |child: &Node| {
+                child.wins / child.visits + EXPLORATION * (log_visits / child.visits).sqrt()
+            };
+            node = nodes[node]
+                .children
+                .iter()
+                .copied()
+                .max_by(|&a, &b| bound(&nodes[a]).total_cmp(&bound(&nodes[b])))
+                .unwrap_or(node);
+            match nodes[node].play {
+                Some(cell) => {
+                    position.play(turn, cell);
+                    passed = 0;
+                }
+                None => passed += 1,
+            }
+            turn = turn.other();
+        }
+        if passed < 2 {
+            position.play_out(turn);
+        }
+        let winner = position.winner(black);
+        let mut next = Some(node);
+        while let Some(node) = next {
+            nodes[node].visits += 1.0;
+            if nodes[node].mover == winner {
+                nodes[node].wins += 1.0;
+            }
+            next = nodes[node].parent;
+        }
+    }
+    nodes[0]
+        .children
+        .iter()
+        .max_by(|&&a, &&b| nodes[a].visits.total_cmp(&nodes[b].visits))
+        .and_then(|&child| nodes[child].play)
+}
+
+/// The move `opponent` plays for `player`, `None` for a pass.
+fn choose_move(
+    position: &Position,
+    player: Player,
+    passes: usize,
+    black: Player,
+    opponent: bot::Opponent,
+) -> Option<[usize; 2]> {
+    match opponent {
+        bot::Opponent::Random => position
+            .sensible_moves(player)
+            .into_iter()
+            .find(|&cell| position.allows(player, cell)),
+        bot::Opponent::Heuristic | bot::Opponent::Minimax => {
+            search(position, player, passes, black, THINK_TIME)
+        }
+    }
+}
+
+/// The stones likely dead once both sides pass: those whose point ends up the other side's
+/// in most games played out from here.
+fn guess_dead(position: &Position, player: Player) -> Vec<Vec<bool>> {
+    let mut lost = [[0; SIZE]; SIZE];
+    for _ in 0..DEAD_PLAYOUTS {
+        let mut played = *position;
+        played.play_out(player);
+        let owners = owners(&played.board);
+        for row in 0..SIZE {
+            for col in 0..SIZE {
+                if let Some(stone) = position.board[row][col]
+                    && owners[row][col] == Some(stone.other())
+                {
+                    lost[row][col] += 1;
+                }
+            }
+        }
+    }
+    lost.iter()
+        .map(|row| {
+            row.iter()
+                .map(|&lost| lost as f64 > DEAD_SHARE * DEAD_PLAYOUTS as f64)
+                .collect()
+        })
+        .collect()
+}
+
+/// Where the game stands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Phase {
+    Playing,
+    /// Both sides passed, and the dead stones are being marked.
+    Scoring,
+    Over,
+}
+
+/// A game of Go against the bot.
+pub struct Go {
+    position: Position,
+    turn: Player,
+    /// Who plays Black, and so moves first; the sides take turns taking Black.
+    black: Player,
+    opponent: bot::Opponent,
+    phase: Phase,
+    /// How many passes were just made in a row.
+    passes: usize,
+    /// The stones marked dead while scoring.
+    dead: Vec<Vec<bool>>,
+    /// The guess at which stones are dead once both sides passed, until it is made.
+    marking: Option<bot::Thinking<Vec<Vec<bool>>>>,
+    /// Every move played, `None` for a pass, as saved to SGF.
+    moves: Vec<(Player, Option<[usize; 2]>)>,
+    outcome: Option<Outcome>,
+    /// The result as SGF writes it, such as `B+3.5` or `W+R`.
+    result: Option<String>,
+    last_bot_move: Option<[usize; 2]>,
+    /// The bot's search for its next stone, or pass.
+    thinking: Option<bot::Thinking<Option<[usize; 2]>>>,
+    notice: Option<String>,
+    cursor: Cursor,
+    colors: TableColors,
+}
+
+impl Go {
+    pub fn new() -> Self {
+        Self {
+            position: Position::new(),
+            turn: Player::Human,
+            black: Player::Human,
+            opponent: bot::Opponent::Minimax,
+            phase: Phase::Playing,
+            passes: 0,
+            dead: vec![vec![false; SIZE]; SIZE],
+            marking: None,
+            moves: Vec::new(),
+            outcome: None,
+            result: None,
+            last_bot_move: None,
+            thinking: None,
+            notice: None,
+            cursor: Cursor::new(SIZE, SIZE),
+            colors: TableColors::new(&PALETTES[0]),
+        }
+    }
+
+    /// Start a new game, with the other side taking Black.
+    fn rematch(&mut self) {
+        let black = self.black.other();
+        *self = Self {
+            colors: self.colors,
+            turn: black,
+            black,
+            opponent: self.opponent,
+            ..Self::new()
+        };
+    }
+
+    /// The SGF letter of `player`'s colour.
+    fn color_letter(&self, player: Player) -> char {
+        match player == self.black {
+            true => 'B',
+            false => 'W',
+        }
+    }
+
+    fn stone_color(&self, player: Player) -> Color {
+        match player == self.black {
+            true => tailwind::SLATE.c950,
+            false => tailwind::SLATE.c50,
+        }
+    }
+
+    /// Play a stone or pass for the side to move and hand the turn over; a second pass in a
+    /// row moves on to marking the dead stones, once a guess at them has been made.
+    fn play(&mut self, play: Option<[usize; 2]>) -> bool {
+        match play {
+            Some(cell) => {
+                if !self.position.play(self.turn, cell) {
+                    return false;
+                }
+                self.passes = 0;
+            }
+            None => {
+                self.position.ko = None;
+                self.passes += 1;
+            }
+        }
+        self.moves.push((self.turn, play));
+        self.turn = self.turn.other();
+        if self.passes == 2 {
+            self.phase = Phase::Scoring;
+            let (position, turn) = (self.position, self.turn);
+            self.marking = Some(bot::Thinking::start(move || guess_dead(&position, turn)));
+        }
+        true
+    }
+
+    /// Play the stone the bot settled on, or pass.
+    fn bot_move(&mut self, play: Option<[usize; 2]>) {
+        self.play(play);
+        self.last_bot_move = play;
+        if play.is_none() {
+            self.notice = Some("The bot passes".into());
+        }
+    }
+
+    /// The points each side scores by territory as the dead stones are marked: the empty
+    /// points it surrounds, and the stones of the other side it captured or holds dead.
+    fn score(&self) -> [f32; 2] {
+        let board = self.alive_stones();
+        let mut captures = self.position.captures;
+        for (stone, alive) in self
+            .position
+            .board
+            .iter()
+            .flatten()
+            .zip(board.iter().flatten())
+        {
+            if let (Some(stone), None) = (stone, alive) {
+                captures[index(stone.other())] += 1;
+            }
+        }
+        territory_score(&board, captures, self.black)
+    }
+
+    /// The board with the stones marked dead taken off.
+    fn alive_stones(&self) -> Board {
+        let mut board = self.position.board;
+        for (row, dead) in board.iter_mut().zip(&self.dead) {
+            for (point, &dead) in row.iter_mut().zip(dead) {
+                if dead {
+                    *point = None;
+                }
+            }
+        }
+        board
+    }
+
+    /// Mark the group at `cell` dead, or alive again.
+    fn toggle_dead(&mut self, cell: [usize; 2]) {
+        if self.position.board[cell[0]][cell[1]].is_none() {
+            return;
+        }
+        let dead = !self.dead[cell[0]][cell[1]];
+        for [row, col] in group(&self.position.board, cell).0 {
+            self.dead[row][col] = dead;
+        }
+    }
+
+    /// End the game with the score as marked.
+    fn accept_score(&mut self) {
+        let score = self.score();
+        let [human, bot] = score;
+        self.finish(leader(score), format!("{}", (human - bot).abs()));
+    }
+
+    fn resign(&mut self) {
+        self.finish(Player::Bot, "R".into());
+    }
+
+    fn finish(&mut self, winner: Player, margin: String) {
+        self.phase = Phase::Over;
+        self.outcome = Some(Outcome::Winner(winner));
+        self.result = Some(format!("{}+{margin}", self.color_letter(winner)));
+    }
+
+    /// The game as an SGF record.
+    fn to_sgf(&self) -> String {
+        let name = |player| match player {
+            Player::Human => "You",
+            Player::Bot => "Bot",
+        };
+        let mut sgf = format!(
+            "(;GM[1]FF[4]CA[UTF-8]AP[rustgame]SZ[{SIZE}]KM[{KOMI}]PB[{}]PW[{}]",
+            name(self.black),
+            name(self.black.other())
+        );
+        if let Some(result) = &self.result {
+            write!(sgf, "RE[{result}]").ok();
+        }
+        for &(player, play) in &self.moves {
+            let point = play.map_or(String::new(), |[row, col]| {
+                [col, row]
+                    .map(|i| char::from(b'a' + i as u8))
+                    .iter()
+                    .collect()
+            });
+            write!(sgf, ";{}[{point}]", self.color_letter(player)).ok();
+        }
+        sgf.push_str(")\n");
+        sgf
+    }
+
+    /// Save the game as SGF in the `games` folder beside the records, named after the time
+    /// it is saved, and tell the player where.
+    fn export(&mut self) {
+        let seconds = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map_or(0, |time| time.as_secs());
+        let Some(path) = records::path(&format!("games/go-{seconds}.sgf")) else {
+            self.notice = Some("Cannot save: there is no home directory to keep it in".into());
+            return;
+        };
+        let saved = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|()| fs::write(&path, self.to_sgf()));
+        self.notice = Some(match saved {
+            Ok(()) => format!("Saved {}", path.display()),
+            Err(err) => format!("Cannot save {}: {err}", path.display()),
+        });
+    }
+
+    fn render_title(&self) -> Line<'static> {
+        let [human, bot] = self.position.captures;
+        let stone = |player| match player == self.black {
+            true => "●",
+            false => "○",
+        };
+        let versus = format!(
+            "Go · You {} {human} : {bot} {} Bot [{}]",
+            stone(Player::Human),
+            stone(Player::Bot),
+            self.opponent.label()
+        );
+        let status = match (self.phase, self.turn) {
+            (Phase::Over, _) if let Some(notice) = &self.notice => {
+                notice.clone().fg(self.colors.selected_cell_style_fg)
+            }
+            (Phase::Over, _) => format!(
+                "{} {} (e: save SGF · r: rematch)",
+                self.outcome.map_or("", Outcome::message),
+                self.result.as_deref().unwrap_or_default()
+            )
+            .into(),
+            (Phase::Scoring, _) if self.marking.is_some() => {
+                "Finding the dead stones…".fg(self.colors.row_fg)
+            }
+            (Phase::Scoring, _) => {
+                let [human, bot] = self.score();
+                format!("Score {human} : {bot} · Space: mark dead · Enter: accept")
+                    .fg(self.colors.selected_cell_style_fg)
+            }
+            (Phase::Playing, Player::Bot) => "Bot thinking…".fg(self.colors.row_fg),
+            (Phase::Playing, Player::Human) if let Some(notice) = &self.notice => {
+                notice.clone().fg(self.colors.selected_cell_style_fg)
+            }
+            (Phase::Playing, Player::Human) => "Your turn".fg(self.colors.selected_cell_style_fg),
+        };
+        Line::from(vec![versus.into(), " · ".dark_gray(), status])
+    }
+
+    /// The board as lines of text: the grid lines between the points, with coordinates
+    /// around it.
+    fn render_board(&self) -> Vec<Line<'static>> {
+        let label = Style::default().fg(tailwind::SLATE.c400);
+        let wood = Style::default()
+            .bg(tailwind::AMBER.c600)
+            .fg(tailwind::AMBER.c950);
+        let letters: String = COLUMNS
+            .chars()
+            .map(|letter| format!("{letter:^POINT_WIDTH$}"))
+            .collect();
+        let mut lines = vec![Line::from(Span::styled(format!("   {letters}"), label))];
+        for row in 0..SIZE {
+            let number = format!("{:>2} ", SIZE - row);
+            let mut spans = vec![Span::styled(number.clone(), label)];
+            let mut spacer = vec!["   ".into()];
+            for col in 0..SIZE {
+                let (text, style) = self.render_cell([row, col]);
+                spans.push(Span::styled(text, style));
+                let line = match row + 1 < SIZE {
+                    true => "  │  ",
+                    false => "     ",
+                };
+                spacer.push(Span::styled(line, wood));
+            }
+            spans.push(Span::styled(format!(" {number}"), label));
+            lines.push(Line::from(spans));
+            if row + 1 < SIZE {
+                lines.push(Line::from(spacer));
+            }
+        }
+        lines.push(Line::from(Span::styled(format!("   {letters}"), label)));
+        lines
+    }
+}
+
+impl Game for Go {
+    /// The point to play.
+    type Move = [usize; 2];
+
+    fn dimensions(&self) -> (usize, usize) {
+        (SIZE, SIZE)
+    }
+
+    fn legal_moves(&self) -> Vec<[usize; 2]> {
+        if self.phase != Phase::Playing || self.turn != Player::Human {
+            return Vec::new();
+        }
+        (0..SIZE)
+            .flat_map(|row| (0..SIZE).map(move |col| [row, col]))
+            .filter(|&cell| self.position.allows(Player::Human, cell))
+            .collect()
+    }
+
+    fn apply(&mut self, cell: [usize; 2]) -> bool {
+        if !self.legal_moves().contains(&cell) {
+            return false;
+        }
+        self.last_bot_move = None;
+        self.play(Some(cell))
+    }
+
+    fn status(&self) -> Status {
+        match self.outcome {
+            None => Status::Playing,
+            Some(Outcome::Winner(Player::Human)) => Status::Won,
+            Some(Outcome::Winner(Player::Bot)) => Status::Lost,
+            Some(Outcome::Draw) => Status::Draw,
+        }
+    }
+
+    fn opponent(&self) -> Option<bot::Opponent> {
+        Some(self.opponent)
+    }
+
+    /// A point of the board with the grid lines leading off it, `POINT_WIDTH` columns wide;
+    /// the cursor is drawn as brackets around it.
+    fn render_cell(&self, cell: [usize; 2]) -> (String, Style) {
+        let [row, col] = cell;
+        let mut style = Style::default()
+            .bg(tailwind::AMBER.c600)
+            .fg(tailwind::AMBER.c950);
+        let scoring = self.phase != Phase::Playing;
+        let glyph = match self.position.board[row][col] {
+            Some(stone) if scoring && self.dead[row][col] => {
+                style = style.fg(self.stone_color(stone));
+                '×'
+            }
+            Some(stone) => {
+                style = style.fg(self.stone_color(stone));
+                if self.last_bot_move == Some(cell) {
+                    style = style.bg(tailwind::AMBER.c500);
+                }
+                '●'
+            }
+            None if scoring => match owners(&self.alive_stones())[row][col] {
+                Some(owner) => {
+                    style = style.fg(self.stone_color(owner));
+                    '▪'
+                }
+                None => '┼',
+            },
+            None => match (row, col) {
+                (0, 0) => '┌',
+                (0, c) if c == SIZE - 1 => '┐',
+                (r, 0) if r == SIZE - 1 => '└',
+                (r, c) if r == SIZE - 1 && c == SIZE - 1 => '┘',
+                (0, _) => '┬',
+                (r, _) if r == SIZE - 1 => '┴',
+                (_, 0) => '├',
+                (_, c) if c == SIZE - 1 => '┤',
+                _ => '┼',
+            },
+        };
+        // the lines stop at the edges of the board
+        let line = |edge: bool| if edge { ' ' } else { '─' };
+        let (left, right) = (line(col == 0), line(col == SIZE - 1));
+        let text = match cell == self.cursor.cell() && self.phase != Phase::Over {
+            true => format!("{left}[{glyph}]{right}"),
+            false => format!("{left}{left}{glyph}{right}{right}"),
+        };
+        (text, style.add_modifier(Modifier::BOLD))
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        match (self.phase, key.code) {
+            (_, KeyCode::Char('r')) => self.rematch(),
+            (_, KeyCode::Char('b')) => self.opponent = self.opponent.next(),
+            (Phase::Over, KeyCode::Char('e')) => self.export(),
+            (Phase::Scoring, KeyCode::Char(' ') | KeyCode::Enter) if self.marking.is_some() => {}
+            (Phase::Scoring, KeyCode::Char(' ')) => self.toggle_dead(self.cursor.cell()),
+            (Phase::Scoring, KeyCode::Enter) => self.accept_score(),
+            (Phase::Playing, _) if self.turn != Player::Human => {}
+            (Phase::Playing, KeyCode::Char(' ') | KeyCode::Enter) => {
+                self.notice = None;
+                if !self.apply(self.cursor.cell()) {
+                    self.notice = Some("You can't play there".into());
+                }
+            }
+            (Phase::Playing, KeyCode::Char('p')) => {
+                self.notice = None;
+                self.last_bot_move = None;
+                self.play(None);
+            }
+            (Phase::Playing, KeyCode::Char('x')) => self.resign(),
+            (Phase::Over, _) => {}
+            (_, code) => {
+                self.cursor.move_with(code);
+            }
+        }
+    }
+
+    /// Search for the bot's stone, or its pass, once the human has played, and play it when
+    /// found; once both sides passed, mark the stones the guess at the dead ones found.
+    fn tick(&mut self) -> bool {
+        if let Some(marking) = &self.marking {
+            self.dead = match marking.answer() {
+                bot::Answer::Pending => return false,
+                bot::Answer::Ready(dead) => dead,
+                // every stone starts out alive, to be marked dead by hand
+                bot::Answer::Failed => vec![vec![false; SIZE]; SIZE],
+            };
+            self.marking = None;
+            return true;
+        }
+        if self.phase != Phase::Playing || self.turn != Player::Bot {
+            return false;
+        }
+        let (position, passes, black, opponent) =
+            (self.position, self.passes, self.black, self.opponent);
+        let thinking = self.thinking.get_or_insert_with(|| {
+            bot::Thinking::start(move || {
+                choose_move(&position, Player::Bot, passes, black, opponent)
+            })
+        });
+        let play = match thinking.answer() {
+            bot::Answer::Pending => return false,
+            bot::Answer::Ready(play) => play,
+            bot::Answer::Failed => choose_move(
+                &self.position,
+                Player::Bot,
+                self.passes,
+                self.black,
+                bot::Opponent::Random,
+            ),
+        };
+        self.thinking = None;
+        self.bot_move(play);
+        true
+    }
+
+    /// `p` passes rather than opening the pause menu while stones are played.
+    fn uses_key(&self, code: KeyCode) -> bool {
+        code == KeyCode::Char('p') && self.phase == Phase::Playing
+    }
+
+    fn apply_settings(&mut self, settings: &Settings) {
+        self.colors = settings.colors();
+        self.opponent = settings.opponent(self.opponent);
+    }
+
+    fn restart(&mut self) {
+        self.rematch();
+    }
+
+    fn help(&self) -> Help {
+        Help {
+            rules: "Take turns placing stones on the points of the board against the bot. Stones \
+                    without liberties are captured, and a single stone can't be retaken straight \
+                    back. Once both sides pass, mark the dead stones; territory plus captures \
+                    wins, with 6.5 points of komi for White."
+                .into(),
+            keys: vec![
+                ("←↑↓→/wasd", "move the cursor"),
+                ("Space/Enter", "play a stone"),
+                ("p", "pass"),
+                ("x", "resign"),
+                ("Space", "mark a group dead while scoring"),
+                ("Enter", "accept the score"),
+                ("e", "save the game as SGF once over"),
+                ("b", "switch bot"),
+                ("r", "rematch"),
+            ],
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let area = frame.area();
+        let board_width = (3 + SIZE * POINT_WIDTH + 3) as u16;
+        let board_height = (2 * SIZE - 1 + 2) as u16;
+        let min_width = board_width.max(80);
+        let min_height = board_height + 2;
+        if area.width < min_width || area.height < min_height {
+            grid::render_too_small(frame, area, min_width, min_height);
+            return;
+        }
+
+        let area = center(area, Constraint::Fill(1), Constraint::Length(min_height));
+        let [title_area, board_area, help_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(board_height),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+        frame.render_widget(Paragraph::new(self.render_title()).centered(), title_area);
+        let [board_area] = Layout::horizontal([Constraint::Length(board_width)])
+            .flex(Flex::Center)
+            .areas(board_area);
+        frame.render_widget(Paragraph::new(self.render_board()), board_area);
+        let help = match self.phase {
+            Phase::Playing => "←↑↓→/wasd move · Space play · p pass · x resign · b switch bot",
+            Phase::Scoring => "←↑↓→/wasd move · Space mark group dead · Enter accept score",
+            Phase::Over => "e save SGF · r rematch · b switch bot",
+        };
+        frame.render_widget(Paragraph::new(help).centered().dark_gray(), help_area);
+    }
+}
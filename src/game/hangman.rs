@@ -0,0 +1,299 @@
+//! Hangman: guess the hidden word a letter at a time before the gallows are complete.
+//!
+//! Words come from a bundled list, or from a file of the player's own given with `--words`.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use rand::seq::IndexedRandom;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style, Stylize, palette::tailwind},
+    text::{Line, Span, Text},
+    widgets::Paragraph,
+};
+
+use crate::{
+    game::{Game, Help, Status},
+    grid::{self, Viewport},
+    settings::Settings,
+    ui::{
+        layout::{center, table_extent},
+        palette::{PALETTES, TableColors},
+    },
+};
+
+/// The words played when no word file is given, one per line.
+const BUNDLED_WORDS: &str = include_str!("words.txt");
+
+/// How many wrong guesses complete the gallows.
+const MAX_MISSES: usize = 6;
+
+/// The gallows before anyone hangs from them.
+const GALLOWS: [&str; 7] = [
+    "  +---+",
+    "  |   |",
+    "      |",
+    "      |",
+    "      |",
+    "      |",
+    "=========",
+];
+
+/// The parts of the figure added with each wrong guess, as line, column and character.
+const FIGURE: [(usize, usize, char); MAX_MISSES] = [
+    (2, 2, 'O'),
+    (3, 2, '|'),
+    (3, 1, '/'),
+    (3, 3, '\\'),
+    (4, 1, '/'),
+    (4, 3, '\\'),
+];
+
+/// Letters per row of the on-screen keyboard.
+const KEYBOARD_WIDTH: usize = 13;
+
+/// The words of `text` that can be played: at least three letters long, with no other
+/// characters. They are lowercased, one entry per word.
+pub fn parse_words(text: &str) -> Vec<String> {
+    text.split_whitespace()
+        .filter(|word| word.len() >= 3 && word.chars().all(|c| c.is_ascii_alphabetic()))
+        .map(str::to_ascii_lowercase)
+        .collect()
+}
+
+/// The bundled words.
+pub fn bundled_words() -> Vec<String> {
+    parse_words(BUNDLED_WORDS)
+}
+
+/// A game of Hangman.
+pub struct Hangman {
+    /// The words a new game picks from.
+    words: Vec<String>,
+    word: String,
+    /// The letters guessed so far, in the order they were.
+    guessed: Vec<char>,
+    colors: TableColors,
+}
+
+impl Hangman {
+    /// A game on a word picked from `words`, which must not be empty.
+    pub fn new(words: Vec<String>) -> Self {
+        let word = words.choose(&mut rand::rng()).cloned().unwrap_or_default();
+        Self {
+            words,
+            word,
+            guessed: Vec::new(),
+            colors: TableColors::new(&PALETTES[0]),
+        }
+    }
+
+    /// Start over on a new word from the same list.
+    fn restart(&mut self) {
+        *self = Self {
+            colors: self.colors,
+            ..Self::new(std::mem::take(&mut self.words))
+        };
+    }
+
+    fn misses(&self) -> usize {
+        self.guessed
+            .iter()
+            .filter(|&&letter| !self.word.contains(letter))
+            .count()
+    }
+
+    /// The gallows with a part of the figure for each wrong guess.
+    fn render_gallows(&self) -> Text<'static> {
+        let mut lines: Vec<Vec<char>> = GALLOWS.iter().map(|line| line.chars().collect()).collect();
+        for &(line, col, part) in &FIGURE[..self.misses().min(MAX_MISSES)] {
+            lines[line][col] = part;
+        }
+        let color = match self.status() {
+            Status::Lost => Color::Red,
+            _ => self.colors.row_fg,
+        };
+        lines
+            .into_iter()
+            .map(|line| Line::from(line.into_iter().collect::<String>()).fg(color))
+            .collect()
+    }
+
+    /// The word with the letters not guessed yet blanked out, or shown in red once the game
+    /// is lost.
+    fn render_word(&self) -> Line<'static> {
+        let lost = self.status() == Status::Lost;
+        let spans = self.word.chars().map(|letter| {
+            let span = match (self.guessed.contains(&letter), lost) {
+                (true, _) => Span::raw(format!("{letter} ")),
+                (false, true) => format!("{letter} ").fg(Color::Red),
+                (false, false) => Span::raw("_ "),
+            };
+            span.add_modifier(Modifier::BOLD)
+        });
+        Line::from_iter(spans)
+    }
+
+    fn render_title(&self) -> Line<'static> {
+        let status = match self.status() {
+            Status::Playing => {
+                "Type a letter to guess · Esc: leave".fg(self.colors.selected_cell_style_fg)
+            }
+            Status::Won => "You got it! (r: new word)".fg(self.colors.selected_cell_style_fg),
+            Status::Lost | Status::Draw => "Hanged! (r: new word)".fg(Color::Red),
+        };
+        Line::from(vec![
+            format!("Hangman · Misses {}/{MAX_MISSES}", self.misses()).into(),
+            " · ".dark_gray(),
+            status,
+        ])
+    }
+}
+
+impl Game for Hangman {
+    type Move = char;
+
+    /// The letters of the on-screen keyboard.
+    fn dimensions(&self) -> (usize, usize) {
+        (26 / KEYBOARD_WIDTH, KEYBOARD_WIDTH)
+    }
+
+    /// The letters not guessed yet.
+    fn legal_moves(&self) -> Vec<char> {
+        if self.status() != Status::Playing {
+            return Vec::new();
+        }
+        ('a'..='z')
+            .filter(|letter| !self.guessed.contains(letter))
+            .collect()
+    }
+
+    fn apply(&mut self, letter: char) -> bool {
+        let letter = letter.to_ascii_lowercase();
+        if !self.legal_moves().contains(&letter) {
+            return false;
+        }
+        self.guessed.push(letter);
+        true
+    }
+
+    fn status(&self) -> Status {
+        if self.misses() >= MAX_MISSES {
+            Status::Lost
+        } else if self
+            .word
+            .chars()
+            .all(|letter| self.guessed.contains(&letter))
+        {
+            Status::Won
+        } else {
+            Status::Playing
+        }
+    }
+
+    /// A key of the on-screen keyboard, green once found in the word and dimmed once missed.
+    fn render_cell(&self, [row, col]: [usize; 2]) -> (String, Style) {
+        let letter = char::from(b'a' + (row * KEYBOARD_WIDTH + col) as u8);
+        let style = match (self.guessed.contains(&letter), self.word.contains(letter)) {
+            (true, true) => Style::default()
+                .fg(tailwind::GREEN.c400)
+                .add_modifier(Modifier::BOLD),
+            (true, false) => Style::default()
+                .fg(tailwind::SLATE.c600)
+                .add_modifier(Modifier::CROSSED_OUT),
+            (false, _) => Style::default().add_modifier(Modifier::BOLD),
+        };
+        (letter.to_string(), style)
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char(letter) if self.takes_letters() => {
+                self.apply(letter);
+            }
+            KeyCode::Char('r') | KeyCode::Enter => self.restart(),
+            _ => {}
+        }
+    }
+
+    /// Every letter is a guess while the word is still being guessed.
+    fn takes_letters(&self) -> bool {
+        self.status() == Status::Playing
+    }
+
+    fn apply_settings(&mut self, settings: &Settings) {
+        self.colors = settings.colors();
+    }
+
+    fn restart(&mut self) {
+        self.restart();
+    }
+
+    fn help(&self) -> Help {
+        Help {
+            rules: "Guess the hidden word a letter at a time. Every wrong letter adds to the \
+                    gallows; finish the word before they are complete."
+                .into(),
+            keys: vec![("a-z", "guess a letter"), ("r/Enter", "new word once over")],
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [area, help_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(frame.area());
+        let help = match self.status() {
+            Status::Playing => "a-z guess a letter · Esc quit",
+            _ => "r/Enter new word",
+        };
+        frame.render_widget(Paragraph::new(help).centered().dark_gray(), help_area);
+        let (rows, cols) = self.dimensions();
+        let keyboard_width = table_extent(cols, 3, 1);
+        let keyboard_height = table_extent(rows, 1, 0);
+        let width = keyboard_width.max(self.word.len() as u16 * 2).max(50);
+        let height = GALLOWS.len() as u16 + keyboard_height + 5;
+        if area.width < width || area.height < height {
+            grid::render_too_small(frame, area, width, height);
+            return;
+        }
+
+        let area = center(
+            area,
+            Constraint::Percentage(100),
+            Constraint::Length(height),
+        );
+        let [title_area, gallows_area, word_area, keyboard_area] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Length(GALLOWS.len() as u16),
+            Constraint::Length(3),
+            Constraint::Length(keyboard_height),
+        ])
+        .areas(area);
+        frame.render_widget(Paragraph::new(self.render_title()).centered(), title_area);
+        let gallows_area = center(
+            gallows_area,
+            Constraint::Length(GALLOWS[GALLOWS.len() - 1].len() as u16),
+            Constraint::Percentage(100),
+        );
+        frame.render_widget(Paragraph::new(self.render_gallows()), gallows_area);
+        let word_area = center(
+            word_area,
+            Constraint::Percentage(100),
+            Constraint::Length(1),
+        );
+        frame.render_widget(Paragraph::new(self.render_word()).centered(), word_area);
+
+        let viewport = Viewport {
+            row: 0,
+            col: 0,
+            rows,
+            cols,
+        };
+        let keyboard_area = center(
+            keyboard_area,
+            Constraint::Length(keyboard_width),
+            Constraint::Percentage(100),
+        );
+        let keyboard = grid::table(self, viewport, 3, 1, &self.colors);
+        frame.render_widget(keyboard, keyboard_area);
+    }
+}
@@ -0,0 +1,476 @@
+//! Tower of Hanoi: move the tower of discs from the first peg to the last, one disc at a
+//! time, never putting a disc on a smaller one.
+//!
+//! A tower of `n` discs takes at least 2ⁿ − 1 moves. The solver shows how from wherever the
+//! discs are: to bring the largest disc onto a peg, the smaller ones first go to the peg it
+//! isn't moving between, and so on down.
+
+use std::{ops::RangeInclusive, time::Duration};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style, Stylize, palette::tailwind},
+    text::Line,
+    widgets::Paragraph,
+};
+
+use crate::{
+    game::{Game, Help, Status, TICK_RATE},
+    grid,
+    settings::Settings,
+    ui::{
+        layout::center,
+        palette::{PALETTES, TableColors},
+    },
+};
+
+/// The fewest and most discs a tower may have.
+const DISC_COUNTS: RangeInclusive<usize> = 3..=8;
+
+/// The discs of a tower when the game starts.
+const DEFAULT_DISCS: usize = 5;
+
+/// Pegs the discs move between.
+const PEGS: usize = 3;
+
+/// The peg the tower is moved to.
+const GOAL_PEG: usize = PEGS - 1;
+
+/// How long a moving disc takes to travel one step of its way.
+const FLIGHT_STEP: Duration = Duration::from_millis(10);
+
+/// The colors of the discs, smallest first.
+const DISC_COLORS: [Color; 8] = [
+    tailwind::RED.c400,
+    tailwind::ORANGE.c400,
+    tailwind::AMBER.c300,
+    tailwind::LIME.c400,
+    tailwind::EMERALD.c400,
+    tailwind::SKY.c400,
+    tailwind::INDIGO.c400,
+    tailwind::FUCHSIA.c400,
+];
+
+/// The next move bringing the smallest `discs` of `position`, which gives the peg each disc
+/// is on from the smallest, onto `target` in as few moves as can be, if any is needed.
+fn next_move(position: &[usize], discs: usize, target: usize) -> Option<(usize, usize)> {
+    let largest = discs.checked_sub(1)?;
+    let from = position[largest];
+    if from == target {
+        return next_move(position, largest, target);
+    }
+    // the smaller discs clear out of the way onto the third peg before the largest moves
+    let spare = PEGS - from - target;
+    next_move(position, largest, spare).or(Some((from, target)))
+}
+
+/// A disc on its way between pegs, drawn at each point of its path in turn.
+struct Flight {
+    disc: usize,
+    /// The peg the disc lands on, where it is stacked already.
+    to: usize,
+    /// The columns and rows of the tower's drawing the disc passes through.
+    path: Vec<(u16, u16)>,
+    step: usize,
+}
+
+/// A game of Tower of Hanoi.
+pub struct Hanoi {
+    /// The discs on each peg, from the bottom up; discs are numbered from 0, the smallest.
+    pegs: [Vec<usize>; PEGS],
+    discs: usize,
+    moves: usize,
+    /// The moves made, for undoing them.
+    history: Vec<(usize, usize)>,
+    /// The tower was solved and a move has been taken back since.
+    reopened: bool,
+    /// The peg whose top disc is lifted to be moved.
+    lifted: Option<usize>,
+    flight: Option<Flight>,
+    /// Whether the solver is making the moves.
+    solving: bool,
+    /// What went wrong with the last move tried.
+    notice: Option<&'static str>,
+    /// The peg under the cursor.
+    cursor: usize,
+    colors: TableColors,
+}
+
+impl Hanoi {
+    pub fn new() -> Self {
+        Self::with_discs(DEFAULT_DISCS)
+    }
+
+    /// A tower of `discs` discs on the first peg.
+    fn with_discs(discs: usize) -> Self {
+        Self {
+            pegs: [(0..discs).rev().collect(), Vec::new(), Vec::new()],
+            discs,
+            moves: 0,
+            history: Vec::new(),
+            reopened: false,
+            lifted: None,
+            flight: None,
+            solving: false,
+            notice: None,
+            cursor: 0,
+            colors: TableColors::new(&PALETTES[0]),
+        }
+    }
+
+    /// Start over with a tower of as many discs.
+    fn restart(&mut self) {
+        *self = Self {
+            colors: self.colors,
+            ..Self::with_discs(self.discs)
+        };
+    }
+
+    /// Start over with one disc more or fewer.
+    fn change_discs(&mut self, step: isize) {
+        let discs = self.discs.saturating_add_signed(step);
+        if DISC_COUNTS.contains(&discs) {
+            *self = Self {
+                colors: self.colors,
+                ..Self::with_discs(discs)
+            };
+        }
+    }
+
+    /// The fewest moves the tower can be moved in.
+    fn minimum_moves(&self) -> usize {
+        (1 << self.discs) - 1
+    }
+
+    /// The peg each disc is on, from the smallest.
+    fn position(&self) -> Vec<usize> {
+        let mut position = vec![0; self.discs];
+        for (peg, discs) in self.pegs.iter().enumerate() {
+            for &disc in discs {
+                position[disc] = peg;
+            }
+        }
+        position
+    }
+
+    fn is_solved(&self) -> bool {
+        self.pegs[GOAL_PEG].len() == self.discs
+    }
+
+    /// Whether the top disc of `from` may go on `to`.
+    fn allows(&self, (from, to): (usize, usize)) -> bool {
+        match (self.pegs[from].last(), self.pegs[to].last()) {
+            (None, _) => false,
+            (Some(_), None) => from != to,
+            (Some(disc), Some(top)) => disc < top,
+        }
+    }
+
+    /// Columns each peg takes in the drawing: room for the largest disc and a gap.
+    fn peg_width(&self) -> u16 {
+        2 * self.discs as u16 + 3
+    }
+
+    /// The column of the middle of `peg` in the drawing.
+    fn peg_center(&self, peg: usize) -> u16 {
+        peg as u16 * self.peg_width() + self.peg_width() / 2
+    }
+
+    /// The row of the drawing of the `height`th disc up a peg, from 0 at the bottom. Row 0 is
+    /// the lane discs are carried across in, above the pegs.
+    fn disc_row(&self, height: usize) -> u16 {
+        (self.discs - height) as u16 + 1
+    }
+
+    /// Move the top disc of `from` onto `to`, flying it there from where it is drawn: lifted
+    /// into the lane if it was, or else on its peg.
+    fn play(&mut self, (from, to): (usize, usize)) {
+        let Some(disc) = self.pegs[from].pop() else {
+            return;
+        };
+        let start_row = match self.lifted.take() {
+            Some(_) => 0,
+            None => self.disc_row(self.pegs[from].len()),
+        };
+        let (start, end) = (self.peg_center(from), self.peg_center(to));
+        let mut path: Vec<(u16, u16)> = (0..=start_row).rev().map(|row| (start, row)).collect();
+        let mut col = start;
+        while col != end {
+            col = match col < end {
+                true => (col + 2).min(end),
+                false => col.saturating_sub(2).max(end),
+            };
+            path.push((col, 0));
+        }
+        path.extend((1..=self.disc_row(self.pegs[to].len())).map(|row| (end, row)));
+        self.flight = Some(Flight {
+            disc,
+            to,
+            path,
+            step: 0,
+        });
+        self.pegs[to].push(disc);
+        self.moves += 1;
+        self.history.push((from, to));
+    }
+
+    /// Lift the top disc of `peg`, or drop the lifted one on it.
+    fn pick(&mut self, peg: usize) {
+        self.notice = None;
+        match self.lifted {
+            None if self.pegs[peg].is_empty() => self.notice = Some("That peg is empty"),
+            None => self.lifted = Some(peg),
+            Some(from) if from == peg => self.lifted = None,
+            Some(from) => {
+                if !self.apply((from, peg)) {
+                    self.notice = Some("A disc can't go on a smaller one");
+                }
+            }
+        }
+    }
+
+    /// Take back the last move.
+    fn undo(&mut self) {
+        if self.flight.is_some() || self.solving {
+            return;
+        }
+        self.lifted = None;
+        let solved = self.status() != Status::Playing;
+        if let Some((from, to)) = self.history.pop()
+            && let Some(disc) = self.pegs[to].pop()
+        {
+            self.reopened |= solved;
+            self.pegs[from].push(disc);
+            self.moves -= 1;
+        }
+    }
+
+    /// Have the solver take over from here, or hand back to the player.
+    fn toggle_solver(&mut self) {
+        self.solving = !self.solving && !self.is_solved();
+        self.notice = None;
+        // a lifted disc is set back on its peg for the solver to move
+        self.lifted = None;
+    }
+
+    fn render_title(&self) -> Line<'static> {
+        let counter = format!(
+            "Tower of Hanoi · {} discs · Moves {} · Minimum {}",
+            self.discs,
+            self.moves,
+            self.minimum_moves()
+        );
+        let highlight = self.colors.selected_cell_style_fg;
+        let status = match self.notice {
+            Some(notice) => notice.fg(Color::Red),
+            None if self.is_solved() && self.moves == self.minimum_moves() => {
+                "Solved in the fewest moves! (r: again)".fg(highlight)
+            }
+            None if self.is_solved() => "Solved! (r: again)".fg(highlight),
+            None if self.solving => "Solving… (s: stop)".fg(self.colors.row_fg),
+            None if self.lifted.is_some() => "Drop it on a peg".fg(highlight),
+            None => "Move the tower to peg 3".fg(highlight),
+        };
+        Line::from(vec![counter.into(), " · ".dark_gray(), status])
+    }
+
+    /// Draw a disc centered on column `col` of `row` of the drawing at `area`.
+    fn render_disc(&self, buffer: &mut Buffer, area: Rect, disc: usize, (col, row): (u16, u16)) {
+        let width = 2 * disc as u16 + 3;
+        let x = area.x + col - width / 2;
+        let style = Style::default().fg(DISC_COLORS[disc % DISC_COLORS.len()]);
+        buffer.set_string(x, area.y + row, "█".repeat(usize::from(width)), style);
+    }
+
+    /// The pegs, their discs and the disc in flight, and a label under each peg.
+    fn render_tower(&self, buffer: &mut Buffer, area: Rect) {
+        let base_row = self.disc_row(0) + 1;
+        let pole = Style::default().fg(tailwind::SLATE.c500);
+        for peg in 0..PEGS {
+            let center = self.peg_center(peg);
+            for row in 1..base_row {
+                buffer.set_string(area.x + center, area.y + row, "┃", pole);
+            }
+            let left = area.x + peg as u16 * self.peg_width() + 1;
+            let base = "▀".repeat(usize::from(self.peg_width() - 2));
+            buffer.set_string(left, area.y + base_row, base, pole);
+            let (label, style) = self.render_cell([0, peg]);
+            let label_x = area.x + center - label.len() as u16 / 2;
+            buffer.set_string(label_x, area.y + base_row + 1, label, style);
+
+            let landing = self.flight.as_ref().is_some_and(|flight| flight.to == peg);
+            let stacked = self.pegs[peg].len() - usize::from(landing);
+            for (height, &disc) in self.pegs[peg][..stacked].iter().enumerate() {
+                let row = match self.lifted {
+                    Some(lifted) if lifted == peg && height + 1 == stacked => 0,
+                    _ => self.disc_row(height),
+                };
+                self.render_disc(buffer, area, disc, (center, row));
+            }
+        }
+        if let Some(flight) = &self.flight {
+            let at = flight.path[flight.step.min(flight.path.len() - 1)];
+            self.render_disc(buffer, area, flight.disc, at);
+        }
+    }
+}
+
+impl Game for Hanoi {
+    /// The pegs to move the top disc from and to.
+    type Move = (usize, usize);
+
+    /// The pegs, in a row.
+    fn dimensions(&self) -> (usize, usize) {
+        (1, PEGS)
+    }
+
+    fn legal_moves(&self) -> Vec<(usize, usize)> {
+        if self.flight.is_some() || self.is_solved() {
+            return Vec::new();
+        }
+        (0..PEGS)
+            .flat_map(|from| (0..PEGS).map(move |to| (from, to)))
+            .filter(|&step| self.allows(step))
+            .collect()
+    }
+
+    fn apply(&mut self, step: (usize, usize)) -> bool {
+        if !self.legal_moves().contains(&step) {
+            return false;
+        }
+        self.play(step);
+        true
+    }
+
+    /// The puzzle is won once the whole tower stands on the last peg.
+    fn status(&self) -> Status {
+        match self.is_solved() && self.flight.is_none() {
+            true => Status::Won,
+            false => Status::Playing,
+        }
+    }
+
+    /// The number of a peg, highlighted under the cursor.
+    fn render_cell(&self, [_, peg]: [usize; 2]) -> (String, Style) {
+        let label = format!(" {} ", peg + 1);
+        match peg == self.cursor && !self.solving {
+            true => (
+                label,
+                Style::default()
+                    .fg(self.colors.selected_cell_style_fg)
+                    .add_modifier(Modifier::REVERSED),
+            ),
+            false => (label, Style::default().fg(self.colors.row_fg)),
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char('r') => self.restart(),
+            KeyCode::Char('s') => self.toggle_solver(),
+            KeyCode::Char('+') | KeyCode::Char('=') => self.change_discs(1),
+            KeyCode::Char('-') => self.change_discs(-1),
+            // the discs are left alone while the solver moves them, or one is flying
+            _ if self.solving || self.flight.is_some() => {}
+            KeyCode::Char('u') => self.undo(),
+            KeyCode::Left | KeyCode::Char('a') => self.cursor = (self.cursor + PEGS - 1) % PEGS,
+            KeyCode::Right | KeyCode::Char('d') => self.cursor = (self.cursor + 1) % PEGS,
+            KeyCode::Char(digit @ '1'..='3') => {
+                self.cursor = digit as usize - '1' as usize;
+                self.pick(self.cursor);
+            }
+            KeyCode::Char(' ') | KeyCode::Enter => self.pick(self.cursor),
+            _ => {}
+        }
+    }
+
+    /// Fly the moving disc a step on, or have the solver make its next move once it lands.
+    fn tick(&mut self) -> bool {
+        if let Some(flight) = &mut self.flight {
+            flight.step += 1;
+            if flight.step >= flight.path.len() {
+                self.flight = None;
+            }
+            return true;
+        }
+        if !self.solving {
+            return false;
+        }
+        match next_move(&self.position(), self.discs, GOAL_PEG) {
+            Some(step) => self.play(step),
+            None => self.solving = false,
+        }
+        true
+    }
+
+    fn tick_rate(&self) -> Duration {
+        match self.flight.is_some() || self.solving {
+            true => FLIGHT_STEP,
+            false => TICK_RATE,
+        }
+    }
+
+    fn apply_settings(&mut self, settings: &Settings) {
+        self.colors = settings.colors();
+    }
+
+    fn restart(&mut self) {
+        self.restart();
+    }
+
+    fn reopened(&self) -> bool {
+        self.reopened
+    }
+
+    fn help(&self) -> Help {
+        Help {
+            rules: "Move the tower from the first peg to the last, one disc at a time, never \
+                    putting a disc on a smaller one. A tower of n discs takes at least 2ⁿ − 1 \
+                    moves."
+                .into(),
+            keys: vec![
+                ("←/→", "choose a peg"),
+                ("Space/Enter", "lift or drop a disc"),
+                ("1-3", "lift or drop on that peg"),
+                ("u", "undo"),
+                ("s", "let the solver finish"),
+                ("+/-", "more or fewer discs"),
+                ("r", "restart"),
+            ],
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let area = frame.area();
+        let tower_width = PEGS as u16 * self.peg_width();
+        let tower_height = self.disc_row(0) + 3;
+        let width = tower_width.max(76);
+        let height = tower_height + 4;
+        if area.width < width || area.height < height {
+            grid::render_too_small(frame, area, width, height);
+            return;
+        }
+
+        let area = center(area, Constraint::Length(width), Constraint::Length(height));
+        let [title_area, _, tower_area, _, help_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(tower_height),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+        frame.render_widget(Paragraph::new(self.render_title()).centered(), title_area);
+        let tower_area = center(
+            tower_area,
+            Constraint::Length(tower_width),
+            Constraint::Percentage(100),
+        );
+        self.render_tower(frame.buffer_mut(), tower_area);
+        let help = "1-3 or ←/→ and Space lift/drop · u undo · s solve · +/- discs · r restart";
+        frame.render_widget(Paragraph::new(help).centered().dark_gray(), help_area);
+    }
+}
@@ -0,0 +1,694 @@
+//! Hex on an 11x11 rhombus against the bot: you join the top and bottom edges with a chain of
+//! stones, the bot the left and right ones.
+//!
+//! A filled board always has exactly one such chain, so the game can't end in a draw. Chains
+//! are tracked with a union-find, which the bot also uses to score the games it plays out
+//! when searching.
+//!
+//! Opening first is a big enough edge that the second side may swap instead of replying:
+//! the opening stone becomes theirs, mirrored across the short diagonal so it stands the
+//! same way to their own edges, and the opener moves again.
+
+use std::time::{Duration, Instant};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use rand::seq::{IndexedRandom, SliceRandom};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Flex, Layout},
+    style::{Color, Modifier, Style, Stylize, palette::tailwind},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+use crate::{
+    Outcome, Player, bot,
+    game::{Game, Help, Status},
+    grid::{self, Cursor},
+    settings::Settings,
+    ui::{
+        layout::center,
+        palette::{PALETTES, TableColors},
+    },
+};
+
+/// Cells along one side of the board.
+const SIDE: usize = 11;
+
+/// How long the hard bot searches for each move.
+const THINK_TIME: Duration = Duration::from_millis(1500);
+
+/// How strongly the search tries moves it knows little about over those that did well.
+const EXPLORATION: f64 = 1.0;
+
+/// Columns each cell takes; every row is shifted half a cell right of the one above, so
+/// each cell touches two cells in each of the rows next to it.
+const CELL_WIDTH: usize = 4;
+
+/// The six neighbours of a cell on the rhombus, as steps in rows and columns.
+const NEIGHBOURS: [(isize, isize); 6] = [(-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0)];
+
+type Board = [[Option<Player>; SIDE]; SIDE];
+
+/// The colours of the human's and the bot's stones and edges.
+const fn side_color(player: Player) -> Color {
+    match player {
+        Player::Human => tailwind::ROSE.c500,
+        Player::Bot => tailwind::BLUE.c400,
+    }
+}
+
+/// The cells next to `[row, col]`.
+fn neighbours([row, col]: [usize; 2]) -> impl Iterator<Item = [usize; 2]> {
+    NEIGHBOURS.into_iter().filter_map(move |(d_row, d_col)| {
+        let row = row.checked_add_signed(d_row).filter(|&row| row < SIDE)?;
+        let col = col.checked_add_signed(d_col).filter(|&col| col < SIDE)?;
+        Some([row, col])
+    })
+}
+
+/// The cells with no stone on them.
+fn empty_cells(board: &Board) -> Vec<[usize; 2]> {
+    (0..SIDE)
+        .flat_map(|row| (0..SIDE).map(move |col| [row, col]))
+        .filter(|&[row, col]| board[row][col].is_none())
+        .collect()
+}
+
+/// The chains of stones on the board as a union-find over its cells, with one more node for
+/// each edge so a chain touching an edge joins it.
+#[derive(Debug, Clone)]
+struct Chains {
+    parent: Vec<usize>,
+}
+
+impl Chains {
+    const TOP: usize = SIDE * SIDE;
+    const BOTTOM: usize = SIDE * SIDE + 1;
+    const LEFT: usize = SIDE * SIDE + 2;
+    const RIGHT: usize = SIDE * SIDE + 3;
+
+    fn new() -> Self {
+        Self {
+            parent: (0..SIDE * SIDE + 4).collect(),
+        }
+    }
+
+    /// The chains of every stone on `board`.
+    fn of(board: &Board) -> Self {
+        let mut chains = Self::new();
+        for row in 0..SIDE {
+            for col in 0..SIDE {
+                if board[row][col].is_some() {
+                    chains.add(board, [row, col]);
+                }
+            }
+        }
+        chains
+    }
+
+    /// The node standing for the chain `node` is in.
+    fn find(&mut self, mut node: usize) -> usize {
+        while self.parent[node] != node {
+            self.parent[node] = self.parent[self.parent[node]];
+            node = self.parent[node];
+        }
+        node
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let (a, b) = (self.find(a), self.find(b));
+        self.parent[a] = b;
+    }
+
+    /// Join the stone just placed at `cell` to the stones of its side next to it, and to the
+    /// edges its side joins that it touches.
+    fn add(&mut self, board: &Board, cell: [usize; 2]) {
+        let [row, col] = cell;
+        let Some(owner) = board[row][col] else {
+            return;
+        };
+        let node = row * SIDE + col;
+        for [r, c] in neighbours(cell) {
+            if board[r][c] == Some(owner) {
+                self.union(node, r * SIDE + c);
+            }
+        }
+        match owner {
+            Player::Human if row == 0 => self.union(node, Self::TOP),
+            Player::Human if row == SIDE - 1 => self.union(node, Self::BOTTOM),
+            Player::Bot if col == 0 => self.union(node, Self::LEFT),
+            Player::Bot if col == SIDE - 1 => self.union(node, Self::RIGHT),
+            _ => {}
+        }
+    }
+
+    /// The side whose edges are joined, if either's are.
+    fn winner(&mut self) -> Option<Player> {
+        if self.find(Self::TOP) == self.find(Self::BOTTOM) {
+            Some(Player::Human)
+        } else if self.find(Self::LEFT) == self.find(Self::RIGHT) {
+            Some(Player::Bot)
+        } else {
+            None
+        }
+    }
+
+    /// The cells of the chain joining the winner's edges.
+    fn winning_chain(&mut self, winner: Player) -> Vec<[usize; 2]> {
+        let edge = match winner {
+            Player::Human => Self::TOP,
+            Player::Bot => Self::LEFT,
+        };
+        let root = self.find(edge);
+        (0..SIDE * SIDE)
+            .filter(|&node| self.find(node) == root)
+            .map(|node| [node / SIDE, node % SIDE])
+            .collect()
+    }
+}
+
+/// Whether `player` placing at `cell` joins their edges.
+fn wins_with(board: &Board, player: Player, cell: [usize; 2]) -> bool {
+    let mut next = *board;
+    next[cell[0]][cell[1]] = Some(player);
+    Chains::of(&next).winner() == Some(player)
+}
+
+/// Fill the rest of `board` at random, `player` first, and return who wins it.
+fn play_out(board: &mut Board, player: Player) -> Player {
+    let mut empty = empty_cells(board);
+    empty.shuffle(&mut rand::rng());
+    let mut turn = player;
+    for [row, col] in empty {
+        board[row][col] = Some(turn);
+        turn = turn.other();
+    }
+    // a filled board always joins one side's edges, and only one
+    Chains::of(board).winner().unwrap_or(Player::Bot)
+}
+
+/// A position in the search tree, reached by the move of the node above it.
+struct Node {
+    /// The cell played to reach it, and who played it.
+    cell: [usize; 2],
+    mover: Player,
+    parent: Option<usize>,
+    children: Vec<usize>,
+    /// Moves from here with no node of their own yet.
+    untried: Vec<[usize; 2]>,
+    visits: f64,
+    /// How many of the games played out through here `mover` won.
+    wins: f64,
+}
+
+/// The move Monte Carlo tree search picks for `player` in `think` time.
+///
+/// Each round follows the tree down by the upper confidence bound, adds a node for a move not
+/// tried yet, plays the rest of the game out at random and counts who won on every node on
+/// the way back up. The move tried most often wins.
+fn search(board: &Board, player: Player, think: Duration) -> Option<[usize; 2]> {
+    let empty = empty_cells(board);
+    // a winning move, or one stopping the other side's, needs no search
+    let decisive = |player: Player| {
+        empty
+            .iter()
+            .copied()
+            .find(|&cell| wins_with(board, player, cell))
+    };
+    if let Some(cell) = decisive(player).or_else(|| decisive(player.other())) {
+        return Some(cell);
+    }
+    let mut untried = empty.clone();
+    untried.shuffle(&mut rand::rng());
+    let mut nodes = vec![Node {
+        cell: [0, 0],
+        mover: player.other(),
+        parent: None,
+        children: Vec::new(),
+        untried,
+        visits: 0.0,
+        wins: 0.0,
+    }];
+    let deadline = Instant::now() + think;
+    while Instant::now() < deadline {
+        let mut board = *board;
+        let mut turn = player;
+        let mut node = 0;
+        while nodes[node].untried.is_empty() && !nodes[node].children.is_empty() {
+            let log_visits = nodes[node].visits.ln();
+            let bound = |child: &Node| {
+                child.wins / child.visits + EXPLORATION * (log_visits / child.visits).sqrt()
+            };
+            node = nodes[node]
+                .children
+                .iter()
+                .copied()
+                .max_by(|&a, &b| bound(&nodes[a]).total_cmp(&bound(&nodes[b])))
+                .unwrap_or(node);
+            let [row, col] = nodes[node].cell;
+            board[row][col] = Some(turn);
+            turn = turn.other();
+        }
+        if let Some(cell) = nodes[node].untried.pop() {
+            board[cell[0]][cell[1]] = Some(turn);
+            let mut untried = empty_cells(&board);
+            untried.shuffle(&mut rand::rng());
+            nodes.push(Node {
+                cell,
+                mover: turn,
+                parent: Some(node),
+                children: Vec::new(),
+                untried,
+                visits: 0.0,
+                wins: 0.0,
+            });
+            let child = nodes.len() - 1;
+            nodes[node].children.push(child);
+            node = child;
+            turn = turn.other();
+        }
+        let winner = play_out(&mut board, turn);
+        let mut next = Some(node);
+        while let Some(node) = next {
+            nodes[node].visits += 1.0;
+            if nodes[node].mover == winner {
+                nodes[node].wins += 1.0;
+            }
+            next = nodes[node].parent;
+        }
+    }
+    nodes[0]
+        .children
+        .iter()
+        .max_by(|&&a, &&b| nodes[a].visits.total_cmp(&nodes[b].visits))
+        .map(|&child| nodes[child].cell)
+        .or_else(|| empty.first().copied())
+}
+
+/// The cell an opening stone at `cell` takes when the other side swaps it for their own,
+/// standing to their edges the way it stood to the opener's.
+const fn mirrored(cell: [usize; 2]) -> [usize; 2] {
+    [cell[1], cell[0]]
+}
+
+/// Whether the bot takes over an opening at `cell`: any stone clear of the two rows along
+/// the edges is too strong to answer.
+fn worth_swapping(cell: [usize; 2]) -> bool {
+    cell.iter().all(|&line| (2..SIDE - 2).contains(&line))
+}
+
+/// The cell `opponent` places `player`'s stone on, unless the board is full.
+fn choose_move(board: &Board, player: Player, opponent: bot::Opponent) -> Option<[usize; 2]> {
+    match opponent {
+        bot::Opponent::Random => empty_cells(board).choose(&mut rand::rng()).copied(),
+        bot::Opponent::Heuristic | bot::Opponent::Minimax => search(board, player, THINK_TIME),
+    }
+}
+
+/// A game of Hex against the bot.
+pub struct Hex {
+    board: Board,
+    chains: Chains,
+    turn: Player,
+    /// Who moved first this game; the sides take turns opening.
+    starter: Player,
+    opponent: bot::Opponent,
+    outcome: Option<Outcome>,
+    /// The chain joining the winner's edges, once there is one.
+    winning_chain: Vec<[usize; 2]>,
+    last_bot_move: Option<[usize; 2]>,
+    /// The stone the bot is still picking.
+    thinking: Option<bot::Thinking<Option<[usize; 2]>>>,
+    cursor: Cursor,
+    colors: TableColors,
+}
+
+impl Hex {
+    pub fn new() -> Self {
+        Self {
+            board: [[None; SIDE]; SIDE],
+            chains: Chains::new(),
+            turn: Player::Human,
+            starter: Player::Human,
+            opponent: bot::Opponent::Minimax,
+            outcome: None,
+            winning_chain: Vec::new(),
+            last_bot_move: None,
+            thinking: None,
+            cursor: Cursor::new(SIDE, SIDE),
+            colors: TableColors::new(&PALETTES[0]),
+        }
+    }
+
+    /// Start a new game, with the other side opening.
+    fn rematch(&mut self) {
+        let starter = self.starter.other();
+        *self = Self {
+            colors: self.colors,
+            turn: starter,
+            starter,
+            opponent: self.opponent,
+            ..Self::new()
+        };
+    }
+
+    /// Place a stone for the side to move and hand the turn over, unless it wins.
+    fn play(&mut self, cell: [usize; 2]) {
+        self.board[cell[0]][cell[1]] = Some(self.turn);
+        self.chains.add(&self.board, cell);
+        if let Some(winner) = self.chains.winner() {
+            self.outcome = Some(Outcome::Winner(winner));
+            self.winning_chain = self.chains.winning_chain(winner);
+        }
+        self.turn = self.turn.other();
+    }
+
+    /// The opening stone, while the side to reply may still swap it for their own.
+    fn swap_offered(&self) -> Option<[usize; 2]> {
+        let mut stones = (0..SIDE)
+            .flat_map(|row| (0..SIDE).map(move |col| [row, col]))
+            .filter(|&[row, col]| self.board[row][col].is_some());
+        match (stones.next(), stones.next()) {
+            (Some(cell), None) if self.board[cell[0]][cell[1]] == Some(self.starter) => Some(cell),
+            _ => None,
+        }
+    }
+
+    /// Take the opening stone over for the side to move, mirrored to face their edges, and
+    /// hand the turn back to the opener.
+    fn swap(&mut self) {
+        let Some(opening) = self.swap_offered() else {
+            return;
+        };
+        let cell = mirrored(opening);
+        self.board[opening[0]][opening[1]] = None;
+        self.board[cell[0]][cell[1]] = Some(self.turn);
+        self.chains = Chains::of(&self.board);
+        self.last_bot_move = (self.turn == Player::Bot).then_some(cell);
+        self.turn = self.turn.other();
+    }
+
+    /// Place the stone the bot picked and join it to its chains.
+    fn bot_move(&mut self, cell: [usize; 2]) {
+        self.play(cell);
+        self.last_bot_move = Some(cell);
+    }
+
+    fn render_title(&self) -> Line<'static> {
+        let status = match (self.outcome, self.turn) {
+            (Some(outcome), _) => format!("{} (r: rematch)", outcome.message()).into(),
+            (None, Player::Bot) => "Bot thinking…".fg(self.colors.row_fg),
+            (None, Player::Human) if self.swap_offered().is_some() => {
+                "Your turn (y: swap)".fg(self.colors.selected_cell_style_fg)
+            }
+            (None, Player::Human) => "Your turn".fg(self.colors.selected_cell_style_fg),
+        };
+        Line::from(vec![
+            "Hex · You ".into(),
+            "●".fg(side_color(Player::Human)),
+            " top to bottom · Bot ".into(),
+            "●".fg(side_color(Player::Bot)),
+            format!(" left to right [{}]", self.opponent.label()).into(),
+            " · ".dark_gray(),
+            status,
+        ])
+    }
+
+    /// The board as lines of text, each row shifted right of the one above, between the
+    /// edges coloured for the side joining them.
+    fn render_board(&self) -> Vec<Line<'static>> {
+        let human_edge = Style::default().fg(side_color(Player::Human));
+        let bot_edge = Style::default().fg(side_color(Player::Bot));
+        let edge = |indent: usize, glyph: &str| {
+            let cells = format!("{} ", glyph.repeat(CELL_WIDTH - 1)).repeat(SIDE);
+            Line::from(vec![
+                " ".repeat(indent).into(),
+                Span::styled(cells, human_edge),
+            ])
+        };
+        let mut lines = vec![edge(2, "▁")];
+        for row in 0..SIDE {
+            let mut spans = vec![" ".repeat(row * CELL_WIDTH / 2).into()];
+            spans.push(Span::styled("╲ ", bot_edge));
+            for col in 0..SIDE {
+                let (text, style) = self.render_cell([row, col]);
+                spans.push(Span::styled(text, style));
+            }
+            spans.push(Span::styled("╲", bot_edge));
+            lines.push(Line::from(spans));
+        }
+        lines.push(edge((SIDE - 1) * CELL_WIDTH / 2 + 2, "▔"));
+        lines
+    }
+}
+
+impl Game for Hex {
+    type Move = [usize; 2];
+
+    fn dimensions(&self) -> (usize, usize) {
+        (SIDE, SIDE)
+    }
+
+    fn legal_moves(&self) -> Vec<[usize; 2]> {
+        if self.outcome.is_some() || self.turn != Player::Human {
+            return Vec::new();
+        }
+        empty_cells(&self.board)
+    }
+
+    fn apply(&mut self, cell: [usize; 2]) -> bool {
+        if !self.legal_moves().contains(&cell) {
+            return false;
+        }
+        self.last_bot_move = None;
+        self.play(cell);
+        true
+    }
+
+    fn status(&self) -> Status {
+        match self.outcome {
+            None => Status::Playing,
+            Some(Outcome::Winner(Player::Human)) => Status::Won,
+            Some(Outcome::Winner(Player::Bot)) => Status::Lost,
+            Some(Outcome::Draw) => Status::Draw,
+        }
+    }
+
+    fn opponent(&self) -> Option<bot::Opponent> {
+        Some(self.opponent)
+    }
+
+    /// A cell of the board, `CELL_WIDTH` columns wide, with the cursor drawn as brackets
+    /// around it.
+    fn render_cell(&self, cell: [usize; 2]) -> (String, Style) {
+        let [row, col] = cell;
+        let (stone, mut style) = match self.board[row][col] {
+            Some(owner) => ('●', Style::default().fg(side_color(owner))),
+            None => ('·', Style::default().fg(tailwind::SLATE.c600)),
+        };
+        if self.winning_chain.contains(&cell) || self.last_bot_move == Some(cell) {
+            style = style.add_modifier(Modifier::BOLD).bg(tailwind::SLATE.c700);
+        }
+        match cell == self.cursor.cell() && self.outcome.is_none() {
+            true => (format!("[{stone}] "), style.bold()),
+            false => (format!(" {stone}  "), style),
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char('r') => self.rematch(),
+            KeyCode::Char('b') => self.opponent = self.opponent.next(),
+            // the board is frozen once the game is decided
+            _ if self.outcome.is_some() || self.turn != Player::Human => {}
+            KeyCode::Char('y') => self.swap(),
+            KeyCode::Char(' ') | KeyCode::Enter => {
+                self.apply(self.cursor.cell());
+            }
+            code => {
+                self.cursor.move_with(code);
+            }
+        }
+    }
+
+    /// Search for the bot's stone once the human's is down, and place it when found; the
+    /// harder bots play out games until their time to think runs out.
+    fn tick(&mut self) -> bool {
+        if self.outcome.is_some() || self.turn != Player::Bot {
+            return false;
+        }
+        if self.opponent != bot::Opponent::Random && self.swap_offered().is_some_and(worth_swapping)
+        {
+            self.swap();
+            return true;
+        }
+        let (board, opponent) = (self.board, self.opponent);
+        let thinking = self.thinking.get_or_insert_with(|| {
+            bot::Thinking::start(move || choose_move(&board, Player::Bot, opponent))
+        });
+        let answer = match thinking.answer() {
+            bot::Answer::Pending => return false,
+            bot::Answer::Ready(answer) => answer,
+            bot::Answer::Failed => choose_move(&self.board, Player::Bot, bot::Opponent::Random),
+        };
+        self.thinking = None;
+        if let Some(cell) = answer {
+            self.bot_move(cell);
+        }
+        true
+    }
+
+    fn apply_settings(&mut self, settings: &Settings) {
+        self.colors = settings.colors();
+        self.opponent = settings.opponent(self.opponent);
+    }
+
+    fn restart(&mut self) {
+        self.rematch();
+    }
+
+    fn help(&self) -> Help {
+        Help {
+            rules: "You join the top and bottom edges with a chain of your stones, the bot the \
+                    left and right ones. The board can't fill up without one chain being made, so \
+                    there are no draws. After the first stone, the other side may swap: it becomes \
+                    theirs, mirrored to face their edges, and the opener moves again."
+                .into(),
+            keys: vec![
+                ("←↑↓→/wasd", "move the cursor"),
+                ("Space/Enter", "place a stone"),
+                ("y", "swap for the bot's opening stone"),
+                ("b", "switch bot"),
+                ("r", "rematch"),
+            ],
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let area = frame.area();
+        let board_width = ((SIDE - 1) * CELL_WIDTH / 2 + 2 + SIDE * CELL_WIDTH + 1) as u16;
+        let board_height = SIDE as u16 + 2;
+        let min_width = board_width.max(80);
+        let min_height = board_height + 2;
+        if area.width < min_width || area.height < min_height {
+            grid::render_too_small(frame, area, min_width, min_height);
+            return;
+        }
+
+        let area = center(area, Constraint::Fill(1), Constraint::Length(min_height));
+        let [title_area, board_area, help_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(board_height),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+        frame.render_widget(Paragraph::new(self.render_title()).centered(), title_area);
+        let [board_area] = Layout::horizontal([Constraint::Length(board_width)])
+            .flex(Flex::Center)
+            .areas(board_area);
+        frame.render_widget(Paragraph::new(self.render_board()), board_area);
+        let help = "←↑↓→/wasd move · Space place · b switch bot · r rematch";
+        frame.render_widget(Paragraph::new(help).centered().dark_gray(), help_area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A board with `player`'s stones on `cells` and nothing else.
+    fn board(player: Player, cells: impl IntoIterator<Item = [usize; 2]>) -> Board {
+        let mut board = [[None; SIDE]; SIDE];
+        for [row, col] in cells {
+            board[row][col] = Some(player);
+        }
+        board
+    }
+
+    #[test]
+    fn a_chain_from_top_to_bottom_wins_for_the_human_only() {
+        let column = board(Player::Human, (0..SIDE).map(|row| [row, 5]));
+        let mut chains = Chains::of(&column);
+        assert_eq!(chains.winner(), Some(Player::Human));
+        assert_eq!(chains.winning_chain(Player::Human).len(), SIDE);
+
+        let row = board(Player::Human, (0..SIDE).map(|col| [5, col]));
+        assert_eq!(Chains::of(&row).winner(), None);
+    }
+
+    #[test]
+    fn a_chain_from_left_to_right_wins_for_the_bot_only() {
+        let row = board(Player::Bot, (0..SIDE).map(|col| [3, col]));
+        assert_eq!(Chains::of(&row).winner(), Some(Player::Bot));
+
+        let column = board(Player::Bot, (0..SIDE).map(|row| [row, 3]));
+        assert_eq!(Chains::of(&column).winner(), None);
+    }
+
+    #[test]
+    fn stones_touching_across_the_long_diagonal_are_not_joined() {
+        let short = board(Player::Human, (0..SIDE).map(|row| [row, SIDE - 1 - row]));
+        assert_eq!(Chains::of(&short).winner(), Some(Player::Human));
+
+        let long = board(Player::Human, (0..SIDE).map(|row| [row, row]));
+        assert_eq!(Chains::of(&long).winner(), None);
+    }
+
+    #[test]
+    fn wins_with_finds_the_stone_closing_a_chain() {
+        let gapped = board(
+            Player::Human,
+            (0..SIDE).filter(|&row| row != 6).map(|row| [row, 5]),
+        );
+        assert!(wins_with(&gapped, Player::Human, [6, 5]));
+        assert!(!wins_with(&gapped, Player::Human, [6, 4]));
+        assert!(!wins_with(&gapped, Player::Bot, [6, 5]));
+        // the stone closing a chain is the first thing the bot looks for
+        assert_eq!(search(&gapped, Player::Human, Duration::ZERO), Some([6, 5]));
+    }
+
+    #[test]
+    fn swapping_mirrors_the_opening_stone_and_hands_the_turn_back() {
+        let mut hex = Hex::new();
+        assert!(hex.apply([2, 7]));
+        assert_eq!(hex.swap_offered(), Some([2, 7]));
+
+        hex.swap();
+        assert_eq!(hex.board[2][7], None);
+        assert_eq!(hex.board[7][2], Some(Player::Bot));
+        assert_eq!(hex.turn, Player::Human);
+        assert_eq!(hex.swap_offered(), None);
+    }
+
+    #[test]
+    fn the_human_may_swap_only_in_answer_to_the_opening() {
+        let mut hex = Hex::new();
+        hex.rematch();
+        hex.play([1, 4]);
+        hex.handle_key(KeyCode::Char('y').into());
+        assert_eq!(hex.board[4][1], Some(Player::Human));
+        assert_eq!(hex.turn, Player::Bot);
+
+        let mut hex = Hex::new();
+        hex.rematch();
+        hex.play([1, 4]);
+        assert!(hex.apply([5, 5]));
+        hex.play([0, 0]);
+        hex.handle_key(KeyCode::Char('y').into());
+        assert_eq!(hex.board[1][4], Some(Player::Bot));
+        assert_eq!(hex.turn, Player::Human);
+    }
+
+    #[test]
+    fn the_bot_swaps_for_a_central_opening_but_not_one_by_the_edge() {
+        let mut hex = Hex::new();
+        assert!(hex.apply([5, 6]));
+        assert!(hex.tick());
+        assert_eq!(hex.board[6][5], Some(Player::Bot));
+        assert_eq!(hex.turn, Player::Human);
+
+        assert!(!worth_swapping([0, 5]));
+        assert!(!worth_swapping([5, SIDE - 1]));
+    }
+}
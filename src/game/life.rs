@@ -0,0 +1,390 @@
+//! Conway's Game of Life: a sandbox of cells that live, die and are born by the rules of
+//! their neighbours.
+//!
+//! The grid is larger than the screen and wraps around at its edges; the view scrolls to
+//! follow the cursor. Patterns can be loaded from files in the RLE format most pattern
+//! collections use.
+
+use std::{
+    ops::RangeInclusive,
+    time::{Duration, Instant},
+};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Flex, Layout},
+    style::{Style, Stylize, palette::tailwind},
+    text::Line,
+    widgets::Paragraph,
+};
+
+use crate::{
+    game::{Game, Help, Status, TICK_RATE},
+    grid::{self, Cursor, Viewport},
+    settings::Settings,
+    ui::palette::{PALETTES, TableColors},
+};
+
+/// The smallest and largest number of rows or columns of the grid.
+pub const GRID_SIZES: RangeInclusive<usize> = 10..=500;
+
+/// The grid played when none is given.
+pub const DEFAULT_GRID: (usize, usize) = (200, 200);
+
+/// Columns of the terminal each cell takes, so cells come out roughly square.
+const CELL_WIDTH: u16 = 2;
+
+/// The speeds the simulation can run at, in generations per second.
+const SPEEDS: [u32; 7] = [1, 2, 5, 10, 20, 30, 60];
+
+/// The speed the simulation starts at, as an index into [`SPEEDS`].
+const DEFAULT_SPEED: usize = 3;
+
+/// How many cells the cursor jumps with a shifted movement key.
+const JUMP: usize = 10;
+
+/// A pattern of live cells, as read from an RLE file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern {
+    rows: usize,
+    cols: usize,
+    /// The live cells, counted from the pattern's top left corner.
+    cells: Vec<[usize; 2]>,
+}
+
+impl Pattern {
+    /// Read a pattern in the RLE format: `#` comment lines, an optional `x = …, y = …` header,
+    /// then runs of dead (`b`) and live (`o`) cells with `$` ending each row and `!` the
+    /// pattern.
+    pub fn parse_rle(text: &str) -> Result<Self, String> {
+        let body = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.starts_with('#') && !line.starts_with('x'));
+        let mut cells = Vec::new();
+        let (mut row, mut col) = (0, 0);
+        let mut count = String::new();
+        'lines: for line in body {
+            for c in line.chars() {
+                let run = match count.as_str() {
+                    "" => 1,
+                    count => count
+                        .parse::<usize>()
+                        .map_err(|err| format!("invalid run length `{count}`: {err}"))?,
+                };
+                match c {
+                    '0'..='9' => {
+                        count.push(c);
+                        continue;
+                    }
+                    'b' | '.' => col += run,
+                    '$' => {
+                        row += run;
+                        col = 0;
+                    }
+                    '!' => break 'lines,
+                    // any other letter is a live cell, as patterns with more states mark them
+                    c if c.is_ascii_alphabetic() => {
+                        cells.extend((col..col + run).map(|col| [row, col]));
+                        col += run;
+                    }
+                    c if c.is_whitespace() => {}
+                    c => return Err(format!("invalid character `{c}` in RLE pattern")),
+                }
+                count.clear();
+            }
+        }
+        if cells.is_empty() {
+            return Err("the RLE pattern has no live cells".into());
+        }
+        let rows = cells.iter().map(|&[row, _]| row + 1).max().unwrap_or(0);
+        let cols = cells.iter().map(|&[_, col]| col + 1).max().unwrap_or(0);
+        Ok(Self { rows, cols, cells })
+    }
+
+    pub const fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub const fn cols(&self) -> usize {
+        self.cols
+    }
+}
+
+/// A Game of Life sandbox.
+pub struct Life {
+    rows: usize,
+    cols: usize,
+    cells: Vec<Vec<bool>>,
+    generation: usize,
+    /// The speed, as an index into [`SPEEDS`].
+    speed: usize,
+    /// When the next generation is due, while the simulation runs.
+    next_step: Option<Instant>,
+    cursor: Cursor,
+    viewport: Viewport,
+    colors: TableColors,
+}
+
+impl Life {
+    /// An empty grid of `rows`×`cols` cells, with `pattern` in the middle of it if one is
+    /// given; the pattern must fit.
+    pub fn new(rows: usize, cols: usize, pattern: Option<&Pattern>) -> Self {
+        let mut cells = vec![vec![false; cols]; rows];
+        if let Some(pattern) = pattern {
+            let (top, left) = ((rows - pattern.rows) / 2, (cols - pattern.cols) / 2);
+            for &[row, col] in &pattern.cells {
+                cells[top + row][left + col] = true;
+            }
+        }
+        Self {
+            rows,
+            cols,
+            cells,
+            generation: 0,
+            speed: DEFAULT_SPEED,
+            next_step: None,
+            cursor: Cursor::new(rows, cols),
+            viewport: Viewport::default(),
+            colors: TableColors::new(&PALETTES[0]),
+        }
+    }
+
+    /// How many of the eight cells around `[row, col]` are alive, wrapping around the edges.
+    fn neighbours(&self, [row, col]: [usize; 2]) -> usize {
+        let mut count = 0;
+        for dr in [self.rows - 1, 0, 1] {
+            for dc in [self.cols - 1, 0, 1] {
+                if (dr, dc) != (0, 0) && self.cells[(row + dr) % self.rows][(col + dc) % self.cols]
+                {
+                    count += 1;
+                }
+            }
+        }
+        count
+    }
+
+    /// Advance one generation: a live cell with two or three live neighbours survives, and a
+    /// dead one with exactly three comes alive.
+    fn step(&mut self) {
+        self.cells = (0..self.rows)
+            .map(|row| {
+                (0..self.cols)
+                    .map(|col| {
+                        matches!(
+                            (self.cells[row][col], self.neighbours([row, col])),
+                            (true, 2 | 3) | (false, 3)
+                        )
+                    })
+                    .collect()
+            })
+            .collect();
+        self.generation += 1;
+    }
+
+    fn interval(&self) -> Duration {
+        Duration::from_secs(1) / SPEEDS[self.speed]
+    }
+
+    fn toggle_running(&mut self) {
+        self.next_step = match self.next_step {
+            Some(_) => None,
+            None => Some(Instant::now() + self.interval()),
+        };
+    }
+
+    /// Kill every cell.
+    fn clear(&mut self) {
+        self.cells = vec![vec![false; self.cols]; self.rows];
+        self.generation = 0;
+    }
+
+    /// Bring about a fifth of the cells to life at random.
+    fn randomize(&mut self) {
+        for cell in self.cells.iter_mut().flatten() {
+            *cell = rand::random_ratio(1, 5);
+        }
+        self.generation = 0;
+    }
+
+    fn population(&self) -> usize {
+        self.cells.iter().flatten().filter(|&&alive| alive).count()
+    }
+
+    fn render_title(&self) -> Line<'static> {
+        let state = match self.next_step {
+            Some(_) => "Running".fg(self.colors.selected_cell_style_fg),
+            None => "Paused".fg(self.colors.row_fg),
+        };
+        Line::from(vec![
+            format!(
+                "Life · Generation {} · Population {} · {}/s · ",
+                self.generation,
+                self.population(),
+                SPEEDS[self.speed]
+            )
+            .into(),
+            state,
+        ])
+    }
+}
+
+impl Game for Life {
+    /// A cell to bring to life or kill.
+    type Move = [usize; 2];
+
+    fn dimensions(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    fn legal_moves(&self) -> Vec<[usize; 2]> {
+        (0..self.rows)
+            .flat_map(|row| (0..self.cols).map(move |col| [row, col]))
+            .collect()
+    }
+
+    fn apply(&mut self, [row, col]: [usize; 2]) -> bool {
+        if row >= self.rows || col >= self.cols {
+            return false;
+        }
+        self.cells[row][col] = !self.cells[row][col];
+        true
+    }
+
+    /// The sandbox is never won or lost.
+    fn status(&self) -> Status {
+        Status::Playing
+    }
+
+    fn render_cell(&self, cell: [usize; 2]) -> (String, Style) {
+        let [row, col] = cell;
+        let bg = match (self.cells[row][col], self.cursor.cell() == cell) {
+            (true, true) => tailwind::EMERALD.c200,
+            (true, false) => tailwind::EMERALD.c500,
+            (false, true) => self.colors.hint_cell_style_bg,
+            (false, false) => self.colors.buffer_bg,
+        };
+        (String::new(), Style::default().bg(bg))
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char(' ') => {
+                self.apply(self.cursor.cell());
+            }
+            KeyCode::Enter | KeyCode::Char('p') => self.toggle_running(),
+            KeyCode::Char('n') => self.step(),
+            KeyCode::Char('+' | '=') => self.speed = (self.speed + 1).min(SPEEDS.len() - 1),
+            KeyCode::Char('-') => self.speed = self.speed.saturating_sub(1),
+            KeyCode::Char('c') => self.clear(),
+            KeyCode::Char('r') => self.randomize(),
+            // shifted movement keys jump several cells at a time
+            KeyCode::Char(c @ ('W' | 'A' | 'S' | 'D')) => {
+                for _ in 0..JUMP {
+                    self.cursor.move_with(KeyCode::Char(c.to_ascii_lowercase()));
+                }
+            }
+            code if key.modifiers.contains(KeyModifiers::SHIFT) => {
+                for _ in 0..JUMP {
+                    self.cursor.move_with(code);
+                }
+            }
+            code => {
+                self.cursor.move_with(code);
+            }
+        }
+    }
+
+    /// Advance a generation whenever one is due while the simulation runs.
+    fn tick(&mut self) -> bool {
+        match self.next_step {
+            Some(due) if Instant::now() >= due => {
+                self.step();
+                self.next_step = Some(Instant::now() + self.interval());
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Faster speeds need waking up more often than the usual tick.
+    fn tick_rate(&self) -> Duration {
+        match self.next_step {
+            Some(_) => self.interval().min(TICK_RATE),
+            None => TICK_RATE,
+        }
+    }
+
+    fn apply_settings(&mut self, settings: &Settings) {
+        self.colors = settings.colors();
+    }
+
+    /// Shifted arrows jump the cursor rather than change the theme, and `p` runs or stops the
+    /// simulation rather than opening the pause menu.
+    fn uses_key(&self, code: KeyCode) -> bool {
+        matches!(code, KeyCode::Left | KeyCode::Right | KeyCode::Char('p'))
+    }
+
+    fn restart(&mut self) {
+        self.randomize();
+    }
+
+    fn help(&self) -> Help {
+        Help {
+            rules: "A cell with two or three live neighbours lives on, an empty cell with exactly \
+                    three comes alive, and every other cell dies. Draw cells, then watch them go; \
+                    the grid wraps around at its edges."
+                .into(),
+            keys: vec![
+                ("←↑↓→/wasd", "move the cursor"),
+                ("Shift+move", "jump several cells"),
+                ("Space", "toggle a cell"),
+                ("Enter/p", "run or pause"),
+                ("n", "step one generation"),
+                ("+/-", "faster or slower"),
+                ("c", "clear"),
+                ("r", "random cells"),
+            ],
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let area = frame.area();
+        let min_width = 72;
+        let min_height = 12;
+        if area.width < min_width || area.height < min_height {
+            grid::render_too_small(frame, area, min_width, min_height);
+            return;
+        }
+
+        let [title_area, board_area, help_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+        let view_rows = self.rows.min(usize::from(board_area.height));
+        let view_cols = self.cols.min(usize::from(board_area.width / CELL_WIDTH));
+        let viewport = &mut self.viewport;
+        viewport.rows = view_rows;
+        viewport.cols = view_cols;
+        viewport.row = Viewport::follow(viewport.row, view_rows, self.rows, self.cursor.row);
+        viewport.col = Viewport::follow(viewport.col, view_cols, self.cols, self.cursor.col);
+        let viewport = self.viewport;
+
+        frame.render_widget(Paragraph::new(self.render_title()).centered(), title_area);
+        let [board_area] = Layout::horizontal([Constraint::Length(view_cols as u16 * CELL_WIDTH)])
+            .flex(Flex::Center)
+            .areas(board_area);
+        grid::render_pixels(
+            frame,
+            board_area,
+            (view_rows, view_cols),
+            CELL_WIDTH,
+            |[row, col]| self.render_cell([viewport.row + row, viewport.col + col]),
+        );
+        let help = "Space toggle · Enter run/pause · n step · +/- speed · c clear · r random";
+        frame.render_widget(Paragraph::new(help).centered().dark_gray(), help_area);
+    }
+}
@@ -0,0 +1,332 @@
+//! Lights Out: pressing a light toggles it and its four neighbours; switch every light off.
+//!
+//! Boards are made by pressing random lights of a dark board, so every one can be solved.
+//! Solving a board is a system of linear equations over GF(2), one press per unknown, which
+//! is what the hint solves.
+
+use std::ops::RangeInclusive;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout},
+    style::{Modifier, Style, Stylize, palette::tailwind},
+    text::Line,
+    widgets::{Paragraph, TableState},
+};
+
+use crate::{
+    game::{Game, Help, Status},
+    grid::{self, Cursor, Viewport},
+    settings::Settings,
+    ui::{
+        layout::{calculate_layout, cell_size, table_extent},
+        palette::{PALETTES, TableColors},
+    },
+};
+
+/// The smallest and largest number of rows or columns of the board.
+pub const GRID_SIZES: RangeInclusive<usize> = 3..=9;
+
+/// The board played when none is given.
+pub const DEFAULT_GRID: (usize, usize) = (5, 5);
+
+/// The lights a press at `[row, col]` toggles: itself and its neighbours on the board.
+fn toggled(rows: usize, cols: usize, [row, col]: [usize; 2]) -> Vec<[usize; 2]> {
+    [
+        [row, col],
+        [row.wrapping_sub(1), col],
+        [row + 1, col],
+        [row, col.wrapping_sub(1)],
+        [row, col + 1],
+    ]
+    .into_iter()
+    .filter(|&[row, col]| row < rows && col < cols)
+    .collect()
+}
+
+/// The fewest presses switching off every light of `lights`, or `None` if it can't be done.
+///
+/// Gaussian elimination over GF(2) leaves a press for each pivot; any presses left free
+/// can each be made or not, so every choice of them is tried for the shortest solution.
+fn solve(lights: &[Vec<bool>]) -> Option<Vec<[usize; 2]>> {
+    let (rows, cols) = (lights.len(), lights[0].len());
+    let cells = rows * cols;
+    // one equation per light: the presses toggling it must add up to whether it is on
+    let mut equations: Vec<Vec<bool>> = (0..cells)
+        .map(|light| {
+            let mut equation = vec![false; cells + 1];
+            for [row, col] in toggled(rows, cols, [light / cols, light % cols]) {
+                equation[row * cols + col] = true;
+            }
+            equation[cells] = lights[light / cols][light % cols];
+            equation
+        })
+        .collect();
+    let mut pivots = Vec::new();
+    for press in 0..cells {
+        let Some(found) = (pivots.len()..cells).find(|&row| equations[row][press]) else {
+            continue;
+        };
+        equations.swap(pivots.len(), found);
+        let pivot = equations[pivots.len()].clone();
+        for (row, equation) in equations.iter_mut().enumerate() {
+            if row != pivots.len() && equation[press] {
+                for (term, &pivot) in equation.iter_mut().zip(&pivot) {
+                    *term ^= pivot;
+                }
+            }
+        }
+        pivots.push(press);
+    }
+    // an equation left with no presses but a light still on can never hold
+    if equations[pivots.len()..]
+        .iter()
+        .any(|equation| equation[cells])
+    {
+        return None;
+    }
+    let free: Vec<usize> = (0..cells).filter(|press| !pivots.contains(press)).collect();
+    (0..1_u32 << free.len())
+        .map(|choice| {
+            let mut presses = vec![false; cells];
+            for (bit, &press) in free.iter().enumerate() {
+                presses[press] = choice >> bit & 1 == 1;
+            }
+            for (equation, &pivot) in equations.iter().zip(&pivots) {
+                presses[pivot] = free.iter().fold(equation[cells], |sum, &press| {
+                    sum ^ (equation[press] && presses[press])
+                });
+            }
+            (0..cells)
+                .filter(|&press| presses[press])
+                .map(|press| [press / cols, press % cols])
+                .collect::<Vec<_>>()
+        })
+        .min_by_key(Vec::len)
+}
+
+/// A game of Lights Out.
+pub struct LightsOut {
+    rows: usize,
+    cols: usize,
+    lights: Vec<Vec<bool>>,
+    moves: usize,
+    hints: usize,
+    /// The light the last hint suggested pressing, until the next press.
+    hint: Option<[usize; 2]>,
+    cursor: Cursor,
+    state: TableState,
+    colors: TableColors,
+}
+
+impl LightsOut {
+    /// A random board of `rows`×`cols` lights with at least one on.
+    pub fn new(rows: usize, cols: usize) -> Self {
+        let mut game = Self {
+            rows,
+            cols,
+            lights: vec![vec![false; cols]; rows],
+            moves: 0,
+            hints: 0,
+            hint: None,
+            cursor: Cursor::new(rows, cols),
+            state: TableState::default(),
+            colors: TableColors::new(&PALETTES[0]),
+        };
+        // pressing lights of a dark board only ever makes boards that can be switched off
+        while game.status() == Status::Won {
+            for row in 0..rows {
+                for col in 0..cols {
+                    if rand::random_bool(0.5) {
+                        game.press([row, col]);
+                    }
+                }
+            }
+        }
+        game
+    }
+
+    /// Start over on a new board of the same size.
+    fn restart(&mut self) {
+        *self = Self {
+            colors: self.colors,
+            ..Self::new(self.rows, self.cols)
+        };
+    }
+
+    fn press(&mut self, cell: [usize; 2]) {
+        for [row, col] in toggled(self.rows, self.cols, cell) {
+            self.lights[row][col] = !self.lights[row][col];
+        }
+    }
+
+    /// Suggest the press of the shortest solution nearest the cursor.
+    fn show_hint(&mut self) {
+        let [row, col] = self.cursor.cell();
+        self.hint = solve(&self.lights).and_then(|presses| {
+            presses
+                .into_iter()
+                .min_by_key(|&[r, c]| r.abs_diff(row) + c.abs_diff(col))
+        });
+        if self.hint.is_some() {
+            self.hints += 1;
+        }
+    }
+
+    fn lights_on(&self) -> usize {
+        self.lights.iter().flatten().filter(|&&on| on).count()
+    }
+
+    fn render_title(&self) -> Line<'static> {
+        let status = match self.status() {
+            Status::Won => "All lights out! (r: new board)".fg(self.colors.selected_cell_style_fg),
+            _ => "Space: press · h: hint".fg(self.colors.selected_cell_style_fg),
+        };
+        Line::from(vec![
+            format!(
+                "Lights Out · Moves {} · Lights on {} · Hints {}",
+                self.moves,
+                self.lights_on(),
+                self.hints
+            )
+            .into(),
+            " · ".dark_gray(),
+            status,
+        ])
+    }
+}
+
+impl Game for LightsOut {
+    type Move = [usize; 2];
+
+    fn dimensions(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    /// Any light may be pressed until they are all off.
+    fn legal_moves(&self) -> Vec<[usize; 2]> {
+        if self.status() != Status::Playing {
+            return Vec::new();
+        }
+        (0..self.rows)
+            .flat_map(|row| (0..self.cols).map(move |col| [row, col]))
+            .collect()
+    }
+
+    fn apply(&mut self, cell: [usize; 2]) -> bool {
+        if !self.legal_moves().contains(&cell) {
+            return false;
+        }
+        self.press(cell);
+        self.moves += 1;
+        self.hint = None;
+        true
+    }
+
+    fn status(&self) -> Status {
+        match self.lights_on() {
+            0 => Status::Won,
+            _ => Status::Playing,
+        }
+    }
+
+    /// A light, on or off, with the one a hint suggests pressing marked.
+    fn render_cell(&self, cell: [usize; 2]) -> (String, Style) {
+        let [row, col] = cell;
+        let style = match self.lights[row][col] {
+            true => Style::default().bg(tailwind::AMBER.c300),
+            false => Style::default().bg(tailwind::SLATE.c800),
+        };
+        match self.hint == Some(cell) {
+            true => (
+                "◆".into(),
+                style
+                    .fg(self.colors.selected_cell_style_fg)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            false => (String::new(), style),
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char('r') => self.restart(),
+            // the board is frozen once every light is off
+            _ if self.status() != Status::Playing => {}
+            KeyCode::Char(' ') | KeyCode::Enter => {
+                self.apply(self.cursor.cell());
+            }
+            KeyCode::Char('h') => self.show_hint(),
+            code => {
+                self.cursor.move_with(code);
+            }
+        }
+    }
+
+    /// `h` asks for a hint rather than moving the cursor.
+    fn uses_key(&self, code: KeyCode) -> bool {
+        code == KeyCode::Char('h')
+    }
+
+    fn apply_settings(&mut self, settings: &Settings) {
+        self.colors = settings.colors();
+    }
+
+    fn restart(&mut self) {
+        self.restart();
+    }
+
+    fn help(&self) -> Help {
+        Help {
+            rules: "Pressing a light toggles it and its four neighbours. Switch every light off, \
+                    in as few presses as you can."
+                .into(),
+            keys: vec![
+                ("←↑↓→/wasd", "move the cursor"),
+                ("Space/Enter", "press a light"),
+                ("h", "hint"),
+                ("r", "new board"),
+            ],
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [area, help_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(frame.area());
+        let help = match self.status() {
+            Status::Playing => "←↑↓→/wasd move · Space press · h hint · r new board",
+            _ => "r new board",
+        };
+        frame.render_widget(Paragraph::new(help).centered().dark_gray(), help_area);
+        let (cell_width, cell_height) = cell_size(self.rows.max(self.cols));
+        let table_width = table_extent(self.cols, cell_width, 1);
+        let table_height = table_extent(self.rows, cell_height, 0);
+        let min_width = table_width.max(80);
+        if area.width < min_width || area.height < table_height + 1 {
+            grid::render_too_small(frame, area, min_width, table_height + 1);
+            return;
+        }
+
+        let layout = calculate_layout(area, false, table_width, table_height);
+        let viewport = Viewport {
+            row: 0,
+            col: 0,
+            rows: self.rows,
+            cols: self.cols,
+        };
+        self.state.select(Some(self.cursor.row));
+        self.state.select_column(Some(self.cursor.col));
+        frame.render_widget(Paragraph::new(self.render_title()).centered(), layout.title);
+        // the cursor is hidden once the puzzle is solved
+        let selected_cell_style = match self.status() {
+            Status::Playing => Style::default()
+                .add_modifier(Modifier::REVERSED)
+                .fg(self.colors.selected_cell_style_fg),
+            _ => Style::default(),
+        };
+        let table = grid::table(self, viewport, cell_width, cell_height, &self.colors)
+            .cell_highlight_style(selected_cell_style);
+        frame.render_stateful_widget(table, layout.table, &mut self.state);
+    }
+}
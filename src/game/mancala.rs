@@ -0,0 +1,563 @@
+//! Mancala, by the rules of Kalah: six pits a side with four seeds in each, and a store at
+//! either end.
+//!
+//! A move takes every seed from one of the mover's pits and sows them one by one around the
+//! board, counter-clockwise, skipping the other side's store. The last seed landing in the
+//! mover's store earns another move; landing in an empty pit of the mover's captures it along
+//! with the seeds across from it. Once either side's pits are empty the rest go to their
+//! owner's store, and the larger store wins.
+
+use std::time::{Duration, Instant};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use rand::seq::IndexedRandom;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout},
+    style::{Modifier, Style, Stylize, palette::tailwind},
+    text::Line,
+    widgets::{Paragraph, TableState},
+};
+
+use crate::{
+    Outcome, Player, bot,
+    game::{Game, Help, Status},
+    grid::{self, Viewport},
+    settings::Settings,
+    ui::{
+        layout::{calculate_layout, table_extent},
+        palette::{PALETTES, TableColors},
+    },
+};
+
+/// Pits on each side of the board.
+const PITS: usize = 6;
+
+/// Seeds in each pit at the start.
+const SEEDS: u32 = 4;
+
+/// Where the human's store is; their pits come before it, and the bot's pits after it.
+const HUMAN_STORE: usize = PITS;
+
+/// Where the bot's store is, last on the board.
+const BOT_STORE: usize = 2 * PITS + 1;
+
+/// How many moves ahead the hard bot searches.
+const SEARCH_DEPTH: usize = 8;
+
+/// A score beyond any lead in seeds, for finished games.
+const WIN: i32 = 10_000;
+
+/// How long each seed takes to be sown.
+const SOW_STEP: Duration = Duration::from_millis(150);
+
+/// The seeds in each pit and store, counter-clockwise from the human's leftmost pit.
+type Board = [u32; 2 * PITS + 2];
+
+/// The pits of `player`, as indices into the board.
+const fn pits(player: Player) -> std::ops::Range<usize> {
+    match player {
+        Player::Human => 0..PITS,
+        Player::Bot => PITS + 1..BOT_STORE,
+    }
+}
+
+const fn store(player: Player) -> usize {
+    match player {
+        Player::Human => HUMAN_STORE,
+        Player::Bot => BOT_STORE,
+    }
+}
+
+/// The pit across the board from `pit`.
+const fn opposite(pit: usize) -> usize {
+    2 * PITS - pit
+}
+
+fn initial_board() -> Board {
+    let mut board = [SEEDS; 2 * PITS + 2];
+    board[HUMAN_STORE] = 0;
+    board[BOT_STORE] = 0;
+    board
+}
+
+/// The pits `player` may sow from.
+fn moves(board: &Board, player: Player) -> Vec<usize> {
+    pits(player).filter(|&pit| board[pit] > 0).collect()
+}
+
+/// The pits and stores the seeds of `pit` are sown into, in order.
+fn trail(board: &Board, player: Player, pit: usize) -> Vec<usize> {
+    let skipped = store(player.other());
+    let mut trail = Vec::new();
+    let mut at = pit;
+    while trail.len() < board[pit] as usize {
+        at = (at + 1) % board.len();
+        if at != skipped {
+            trail.push(at);
+        }
+    }
+    trail
+}
+
+/// What a move led to besides the sowing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Sown {
+    /// The last seed landed in the mover's store, who moves again.
+    ExtraTurn,
+    /// The last seed landed in an empty pit of the mover's, capturing this many seeds.
+    Capture(u32),
+    Plain,
+}
+
+/// Sow the seeds of `pit` for `player`, capture what the last one earns, and sweep the
+/// board once either side is empty.
+fn sow(board: &mut Board, player: Player, pit: usize) -> Sown {
+    let trail = trail(board, player, pit);
+    board[pit] = 0;
+    for &at in &trail {
+        board[at] += 1;
+    }
+    let last = trail.last().copied().unwrap_or(pit);
+    let sown = if last == store(player) {
+        Sown::ExtraTurn
+    } else if pits(player).contains(&last) && board[last] == 1 && board[opposite(last)] > 0 {
+        // only a pit has another across from it, so this waits until `last` is known to be one
+        let across = opposite(last);
+        let captured = board[last] + board[across];
+        board[store(player)] += captured;
+        board[last] = 0;
+        board[across] = 0;
+        Sown::Capture(captured)
+    } else {
+        Sown::Plain
+    };
+    if is_over(board) {
+        for player in [Player::Human, Player::Bot] {
+            let left: u32 = pits(player)
+                .map(|pit| std::mem::take(&mut board[pit]))
+                .sum();
+            board[store(player)] += left;
+        }
+    }
+    sown
+}
+
+/// Whether either side has run out of seeds in its pits.
+fn is_over(board: &Board) -> bool {
+    [Player::Human, Player::Bot]
+        .into_iter()
+        .any(|player| pits(player).all(|pit| board[pit] == 0))
+}
+
+/// How far `player`'s store leads the other's.
+fn lead(board: &Board, player: Player) -> i32 {
+    board[store(player)] as i32 - board[store(player.other())] as i32
+}
+
+/// Negamax with alpha-beta pruning, scoring the board for `player` to move. A move earning
+/// another turn is searched as the same player's.
+fn negamax(board: &Board, player: Player, depth: usize, mut alpha: i32, beta: i32) -> i32 {
+    if is_over(board) {
+        let lead = lead(board, player);
+        return lead.signum() * WIN + lead;
+    }
+    if depth == 0 {
+        return lead(board, player);
+    }
+    let mut best = -i32::MAX;
+    for pit in moves(board, player) {
+        let mut next = *board;
+        let score = match sow(&mut next, player, pit) {
+            Sown::ExtraTurn => negamax(&next, player, depth - 1, alpha, beta),
+            _ => -negamax(&next, player.other(), depth - 1, -beta, -alpha),
+        };
+        best = best.max(score);
+        alpha = alpha.max(score);
+        if alpha >= beta {
+            break;
+        }
+    }
+    best
+}
+
+/// The pit `opponent` sows from for `player`.
+fn choose_pit(board: &Board, player: Player, opponent: bot::Opponent) -> Option<usize> {
+    let options = moves(board, player);
+    match opponent {
+        bot::Opponent::Random => options.choose(&mut rand::rng()).copied(),
+        bot::Opponent::Heuristic | bot::Opponent::Minimax => {
+            let mut best = None;
+            let mut alpha = -i32::MAX;
+            for pit in options {
+                let mut next = *board;
+                let score = match sow(&mut next, player, pit) {
+                    Sown::ExtraTurn => negamax(&next, player, SEARCH_DEPTH - 1, alpha, i32::MAX),
+                    _ => -negamax(&next, player.other(), SEARCH_DEPTH - 1, -i32::MAX, -alpha),
+                };
+                if best.is_none() || score > alpha {
+                    alpha = score;
+                    best = Some(pit);
+                }
+            }
+            best
+        }
+    }
+}
+
+/// The seeds of a move being sown one at a time.
+struct Sowing {
+    /// The board before the move.
+    start: Board,
+    pit: usize,
+    trail: Vec<usize>,
+    /// How many seeds have been sown so far.
+    shown: usize,
+    next_at: Instant,
+}
+
+/// A game of Mancala against the bot.
+pub struct Mancala {
+    board: Board,
+    turn: Player,
+    /// Who moved first this game; the sides take turns opening.
+    starter: Player,
+    opponent: bot::Opponent,
+    outcome: Option<Outcome>,
+    sowing: Option<Sowing>,
+    /// The bot's search for the pit it sows next.
+    thinking: Option<bot::Thinking<Option<usize>>>,
+    /// What the last move earned, and for whom.
+    notice: Option<(Player, Sown)>,
+    /// The human's pit under the cursor.
+    cursor: usize,
+    state: TableState,
+    colors: TableColors,
+}
+
+impl Mancala {
+    pub fn new() -> Self {
+        Self {
+            board: initial_board(),
+            turn: Player::Human,
+            starter: Player::Human,
+            opponent: bot::Opponent::Minimax,
+            outcome: None,
+            sowing: None,
+            thinking: None,
+            notice: None,
+            cursor: 0,
+            state: TableState::default(),
+            colors: TableColors::new(&PALETTES[0]),
+        }
+    }
+
+    /// Start a new game, with the other side opening.
+    fn rematch(&mut self) {
+        let starter = self.starter.other();
+        *self = Self {
+            colors: self.colors,
+            turn: starter,
+            starter,
+            opponent: self.opponent,
+            ..Self::new()
+        };
+    }
+
+    /// Sow `pit` for the side to move, starting the animation of its seeds, and hand the turn
+    /// over unless the move earned another.
+    fn play(&mut self, pit: usize) {
+        let mover = self.turn;
+        self.sowing = Some(Sowing {
+            start: self.board,
+            pit,
+            trail: trail(&self.board, mover, pit),
+            shown: 0,
+            next_at: Instant::now() + SOW_STEP,
+        });
+        let sown = sow(&mut self.board, mover, pit);
+        self.notice = Some((mover, sown));
+        if sown != Sown::ExtraTurn {
+            self.turn = mover.other();
+        }
+        if is_over(&self.board) {
+            self.outcome = Some(match lead(&self.board, Player::Human).cmp(&0) {
+                std::cmp::Ordering::Greater => Outcome::Winner(Player::Human),
+                std::cmp::Ordering::Less => Outcome::Winner(Player::Bot),
+                std::cmp::Ordering::Equal => Outcome::Draw,
+            });
+        }
+    }
+
+    /// The board as the animation shows it: while seeds are being sown, the pit they were
+    /// taken from is empty and only the seeds sown so far have landed.
+    fn shown_board(&self) -> Board {
+        let Some(sowing) = &self.sowing else {
+            return self.board;
+        };
+        let mut board = sowing.start;
+        board[sowing.pit] = 0;
+        for &at in &sowing.trail[..sowing.shown.min(sowing.trail.len())] {
+            board[at] += 1;
+        }
+        board
+    }
+
+    /// The pit or store shown in `cell` of the table: the bot's pits run right to left
+    /// along the top, the human's left to right along the bottom, and each store stands at
+    /// its owner's end.
+    fn pit_at([row, col]: [usize; 2]) -> Option<usize> {
+        match (row, col) {
+            (0, 0) => Some(BOT_STORE),
+            (1, 7) => Some(HUMAN_STORE),
+            (0, 1..=6) => Some(BOT_STORE - col),
+            (1, 1..=6) => Some(col - 1),
+            _ => None,
+        }
+    }
+
+    fn render_title(&self) -> Line<'static> {
+        let board = self.shown_board();
+        let versus = format!(
+            "Mancala · You {} : {} Bot [{}]",
+            board[HUMAN_STORE],
+            board[BOT_STORE],
+            self.opponent.label()
+        );
+        let highlight = self.colors.selected_cell_style_fg;
+        let status = match (self.outcome, self.turn, self.notice) {
+            (Some(outcome), _, _) => format!("{} (r: rematch)", outcome.message()).into(),
+            _ if self.sowing.is_some() => "Sowing…".fg(self.colors.row_fg),
+            (None, Player::Bot, _) => "Bot thinking…".fg(self.colors.row_fg),
+            (None, Player::Human, Some((Player::Human, Sown::ExtraTurn))) => {
+                "Extra turn!".fg(highlight)
+            }
+            (None, Player::Human, Some((mover, Sown::Capture(seeds)))) => {
+                let who = match mover {
+                    Player::Human => "You",
+                    Player::Bot => "The bot",
+                };
+                format!("{who} captured {seeds} · Your turn").fg(highlight)
+            }
+            (None, Player::Human, _) => "Your turn".fg(highlight),
+        };
+        Line::from(vec![versus.into(), " · ".dark_gray(), status])
+    }
+}
+
+impl Game for Mancala {
+    /// One of the human's pits, counted from the left.
+    type Move = usize;
+
+    /// The bot's store and pits along the top, the human's pits and store along the bottom.
+    fn dimensions(&self) -> (usize, usize) {
+        (2, PITS + 2)
+    }
+
+    fn legal_moves(&self) -> Vec<usize> {
+        if self.outcome.is_some() || self.turn != Player::Human || self.sowing.is_some() {
+            return Vec::new();
+        }
+        moves(&self.board, Player::Human)
+    }
+
+    fn apply(&mut self, pit: usize) -> bool {
+        if !self.legal_moves().contains(&pit) {
+            return false;
+        }
+        self.play(pit);
+        true
+    }
+
+    fn status(&self) -> Status {
+        match self.outcome {
+            None => Status::Playing,
+            Some(Outcome::Winner(Player::Human)) => Status::Won,
+            Some(Outcome::Winner(Player::Bot)) => Status::Lost,
+            Some(Outcome::Draw) => Status::Draw,
+        }
+    }
+
+    fn opponent(&self) -> Option<bot::Opponent> {
+        Some(self.opponent)
+    }
+
+    /// A pit or store with its seeds, the one a seed was last sown into lit up.
+    fn render_cell(&self, cell: [usize; 2]) -> (String, Style) {
+        let Some(pit) = Self::pit_at(cell) else {
+            return (String::new(), Style::default());
+        };
+        let board = self.shown_board();
+        let bg = match pit {
+            HUMAN_STORE | BOT_STORE => tailwind::AMBER.c800,
+            _ => tailwind::AMBER.c900,
+        };
+        let style = Style::default()
+            .bg(bg)
+            .fg(tailwind::AMBER.c100)
+            .add_modifier(Modifier::BOLD);
+        let last_sown = self
+            .sowing
+            .as_ref()
+            .and_then(|sowing| sowing.trail[..sowing.shown.min(sowing.trail.len())].last());
+        let style = match last_sown == Some(&pit) {
+            true => style.bg(tailwind::AMBER.c500).fg(tailwind::SLATE.c950),
+            false => style,
+        };
+        (board[pit].to_string(), style)
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char('r') => self.rematch(),
+            KeyCode::Char('b') => self.opponent = self.opponent.next(),
+            // the pits are left alone once the game is decided, and while it isn't the human's
+            // move
+            _ if self.legal_moves().is_empty() => {}
+            KeyCode::Left | KeyCode::Char('a') => self.cursor = (self.cursor + PITS - 1) % PITS,
+            KeyCode::Right | KeyCode::Char('d') => self.cursor = (self.cursor + 1) % PITS,
+            KeyCode::Char(digit @ '1'..='6') => self.cursor = digit as usize - '1' as usize,
+            KeyCode::Char(' ') | KeyCode::Enter => {
+                self.apply(self.cursor);
+            }
+            _ => {}
+        }
+    }
+
+    /// Sow the next seed of a move being animated, or, once the seeds have all landed, search
+    /// for the bot's pit and sow it when found.
+    fn tick(&mut self) -> bool {
+        if let Some(sowing) = &mut self.sowing {
+            if Instant::now() < sowing.next_at {
+                return false;
+            }
+            // one step past the last seed shows the board before its capture
+            sowing.shown += 1;
+            sowing.next_at += SOW_STEP;
+            if sowing.shown > sowing.trail.len() {
+                self.sowing = None;
+            }
+            return true;
+        }
+        if self.outcome.is_some() || self.turn != Player::Bot {
+            return false;
+        }
+        let (board, opponent) = (self.board, self.opponent);
+        let thinking = self.thinking.get_or_insert_with(|| {
+            bot::Thinking::start(move || choose_pit(&board, Player::Bot, opponent))
+        });
+        let answer = match thinking.answer() {
+            bot::Answer::Pending => return false,
+            bot::Answer::Ready(answer) => answer,
+            bot::Answer::Failed => choose_pit(&self.board, Player::Bot, bot::Opponent::Random),
+        };
+        self.thinking = None;
+        if let Some(pit) = answer {
+            self.play(pit);
+        }
+        true
+    }
+
+    fn apply_settings(&mut self, settings: &Settings) {
+        self.colors = settings.colors();
+        self.opponent = settings.opponent(self.opponent);
+    }
+
+    fn restart(&mut self) {
+        self.rematch();
+    }
+
+    fn help(&self) -> Help {
+        Help {
+            rules: "Take every seed from one of your pits and sow them one by one \
+                    counter-clockwise. The last seed landing in your store earns another move, \
+                    and in an empty pit of yours captures the seeds across. Once a side runs out, \
+                    the larger store wins."
+                .into(),
+            keys: vec![
+                ("←/→ 1-6", "choose a pit"),
+                ("Space/Enter", "sow it"),
+                ("b", "switch bot"),
+                ("r", "rematch"),
+            ],
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [area, help_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(frame.area());
+        let help = match self.outcome {
+            Some(_) => "r rematch · b switch bot",
+            None => "←/→ or 1-6 choose a pit · Space sow · b switch bot · r rematch",
+        };
+        frame.render_widget(Paragraph::new(help).centered().dark_gray(), help_area);
+        let (rows, cols) = self.dimensions();
+        let (cell_width, cell_height) = (7, 3);
+        let table_width = table_extent(cols, cell_width, 1);
+        let table_height = table_extent(rows, cell_height, 0);
+        let min_width = table_width.max(64);
+        if area.width < min_width || area.height < table_height + 3 {
+            grid::render_too_small(frame, area, min_width, table_height + 3);
+            return;
+        }
+
+        let layout = calculate_layout(area, false, table_width, table_height);
+        let viewport = Viewport {
+            row: 0,
+            col: 0,
+            rows,
+            cols,
+        };
+        self.state.select(Some(1));
+        self.state.select_column(Some(self.cursor + 1));
+        frame.render_widget(Paragraph::new(self.render_title()).centered(), layout.title);
+        let cursor_style = match self.legal_moves().is_empty() {
+            false => Style::default().add_modifier(Modifier::REVERSED),
+            true => Style::default(),
+        };
+        let table = grid::table(self, viewport, cell_width, cell_height, &self.colors)
+            .cell_highlight_style(cursor_style);
+        frame.render_stateful_widget(table, layout.table, &mut self.state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sow_ending_in_human_store_earns_another_turn() {
+        let mut board = initial_board();
+        assert_eq!(sow(&mut board, Player::Human, 2), Sown::ExtraTurn);
+        assert_eq!(board[HUMAN_STORE], 1);
+        assert_eq!(board[2], 0);
+    }
+
+    #[test]
+    fn sow_ending_in_bot_store_earns_another_turn() {
+        let mut board = initial_board();
+        assert_eq!(sow(&mut board, Player::Bot, 9), Sown::ExtraTurn);
+        assert_eq!(board[BOT_STORE], 1);
+        assert_eq!(board[9], 0);
+    }
+
+    #[test]
+    fn sow_skips_the_other_store() {
+        let mut board = initial_board();
+        board[5] = 8;
+        // 6 through 12, past the bot's store and on to the human's first pit
+        assert_eq!(sow(&mut board, Player::Human, 5), Sown::Plain);
+        assert_eq!(board[BOT_STORE], 0);
+        assert_eq!(board[0], SEEDS + 1);
+    }
+
+    #[test]
+    fn sow_into_an_empty_pit_captures_across() {
+        let mut board = initial_board();
+        board[0] = 1;
+        board[1] = 0;
+        assert_eq!(sow(&mut board, Player::Human, 0), Sown::Capture(SEEDS + 1));
+        assert_eq!(board[HUMAN_STORE], SEEDS + 1);
+        assert_eq!(board[opposite(1)], 0);
+    }
+}
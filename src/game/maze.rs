@@ -0,0 +1,428 @@
+//! A maze runner: find the way from the top left corner of a freshly dug maze to the exit in
+//! its bottom right corner before the clock runs out.
+//!
+//! Mazes are dug by a recursive backtracker, which makes long winding corridors with few
+//! dead ends close to each other. Only the corridors in sight of the runner are shown, and
+//! the rest of the maze stays as dark as it was until walked through.
+
+use std::{
+    ops::RangeInclusive,
+    time::{Duration, Instant},
+};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use rand::seq::IndexedRandom;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Flex, Layout},
+    style::{Color, Style, Stylize, palette::tailwind},
+    text::Line,
+    widgets::Paragraph,
+};
+
+use crate::{
+    game::{Game, Help, Status},
+    grid, records,
+    settings::Settings,
+    ui::palette::{PALETTES, TableColors},
+};
+
+/// The smallest and largest number of rows or columns of rooms in the maze.
+pub const GRID_SIZES: RangeInclusive<usize> = 4..=60;
+
+/// The sizes picked with the number keys, in rows and columns of rooms.
+const SIZES: [(&str, (usize, usize)); 4] = [
+    ("Small", (8, 12)),
+    ("Medium", (12, 20)),
+    ("Large", (18, 32)),
+    ("Huge", (30, 56)),
+];
+
+/// The maze run when no size is given.
+pub const DEFAULT_GRID: (usize, usize) = SIZES[1].1;
+
+/// Columns of the terminal each cell of the maze takes, so cells come out roughly square.
+const CELL_WIDTH: u16 = 2;
+
+/// The time allowed for each room of the maze.
+const TIME_PER_ROOM: Duration = Duration::from_millis(500);
+
+/// A direction to move in, as a step in rows and columns.
+type Step = (isize, isize);
+
+const STEPS: [Step; 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+
+/// The cell `steps` cells away from `cell` along `step`, if it is on a board of `rows`×`cols`
+/// cells.
+fn offset(
+    [row, col]: [usize; 2],
+    (d_row, d_col): Step,
+    steps: isize,
+    (rows, cols): (usize, usize),
+) -> Option<[usize; 2]> {
+    let row = row
+        .checked_add_signed(d_row * steps)
+        .filter(|&row| row < rows)?;
+    let col = col
+        .checked_add_signed(d_col * steps)
+        .filter(|&col| col < cols)?;
+    Some([row, col])
+}
+
+/// Dig a maze of `rows`×`cols` rooms, returned as the open cells of a grid twice as large
+/// plus one: rooms sit at odd rows and columns, and the walls between them at even ones.
+///
+/// The backtracker digs from a random room into a random room not dug yet next to it, and
+/// backs up whenever it is stuck, until it has backed up all the way.
+fn dig(rows: usize, cols: usize) -> Vec<Vec<bool>> {
+    let size = (2 * rows + 1, 2 * cols + 1);
+    let mut open = vec![vec![false; size.1]; size.0];
+    let start = [
+        2 * rand::random_range(0..rows) + 1,
+        2 * rand::random_range(0..cols) + 1,
+    ];
+    open[start[0]][start[1]] = true;
+    // the rooms being dug from, kept on a stack of our own so large mazes can't overflow it
+    let mut path = vec![start];
+    while let Some(&room) = path.last() {
+        let unvisited: Vec<Step> = STEPS
+            .into_iter()
+            .filter(|&step| offset(room, step, 2, size).is_some_and(|[r, c]| !open[r][c]))
+            .collect();
+        let Some(&step) = unvisited.choose(&mut rand::rng()) else {
+            path.pop();
+            continue;
+        };
+        for steps in 1..=2 {
+            if let Some([row, col]) = offset(room, step, steps, size) {
+                open[row][col] = true;
+            }
+        }
+        path.extend(offset(room, step, 2, size));
+    }
+    open
+}
+
+/// Format `time` as minutes, seconds and tenths.
+fn clock(time: Duration) -> String {
+    let tenths = time.as_millis() / 100;
+    format!("{}:{:02}.{}", tenths / 600, tenths / 10 % 60, tenths % 10)
+}
+
+/// A maze and the runner finding the way out of it.
+pub struct Maze {
+    rows: usize,
+    cols: usize,
+    /// The open cells, rooms and the gaps between them, as [`dig`] makes them.
+    open: Vec<Vec<bool>>,
+    /// The cell the runner is in.
+    runner: [usize; 2],
+    /// The gap in the outer wall leading out.
+    exit: [usize; 2],
+    /// Cells the runner has seen, and those in sight from where the runner is now.
+    seen: Vec<Vec<bool>>,
+    in_sight: Vec<Vec<bool>>,
+    /// Rooms the runner has been through.
+    trail: Vec<Vec<bool>>,
+    /// When the runner set off, once the first step is taken.
+    started: Option<Instant>,
+    /// When the game was paused, to push back the start by as long on resuming.
+    paused: Option<Instant>,
+    /// How long the run took, once the runner is out or the time is up.
+    finished: Option<Duration>,
+    /// The fastest run through a maze of this size, from this or an earlier session.
+    best: Option<Duration>,
+    colors: TableColors,
+}
+
+impl Maze {
+    /// A freshly dug maze of `rows`×`cols` rooms.
+    pub fn new(rows: usize, cols: usize) -> Self {
+        let open = dig(rows, cols);
+        let (height, width) = (2 * rows + 1, 2 * cols + 1);
+        let exit = [height - 2, width - 1];
+        let mut maze = Self {
+            rows,
+            cols,
+            open,
+            runner: [1, 1],
+            exit,
+            seen: vec![vec![false; width]; height],
+            in_sight: vec![vec![false; width]; height],
+            trail: vec![vec![false; width]; height],
+            started: None,
+            paused: None,
+            finished: None,
+            best: records::load(&Self::record(rows, cols)).map(Duration::from_millis),
+            colors: TableColors::new(&PALETTES[0]),
+        };
+        maze.open[exit[0]][exit[1]] = true;
+        maze.trail[1][1] = true;
+        maze.look();
+        maze
+    }
+
+    /// The record keeping the best time through mazes of `rows`×`cols` rooms.
+    fn record(rows: usize, cols: usize) -> String {
+        format!("maze-best-{rows}x{cols}")
+    }
+
+    /// Dig a new maze of `rows`×`cols` rooms.
+    fn restart(&mut self, rows: usize, cols: usize) {
+        *self = Self {
+            colors: self.colors,
+            ..Self::new(rows, cols)
+        };
+    }
+
+    fn size(&self) -> (usize, usize) {
+        (self.open.len(), self.open[0].len())
+    }
+
+    fn time_limit(&self) -> Duration {
+        TIME_PER_ROOM * (self.rows * self.cols) as u32
+    }
+
+    fn elapsed(&self) -> Duration {
+        self.finished
+            .or(self.started.map(|started| started.elapsed()))
+            .unwrap_or_default()
+    }
+
+    /// Work out which cells are in sight of the runner: the walls all around, and all the way
+    /// down each corridor leading off in a straight line.
+    fn look(&mut self) {
+        let size = self.size();
+        for row in &mut self.in_sight {
+            row.fill(false);
+        }
+        for step in STEPS {
+            let mut steps = 0;
+            while let Some(cell) = offset(self.runner, step, steps, size)
+                && self.open[cell[0]][cell[1]]
+            {
+                // the walls along the corridor, corners included, are in sight too
+                for row in cell[0].saturating_sub(1)..=(cell[0] + 1).min(size.0 - 1) {
+                    for col in cell[1].saturating_sub(1)..=(cell[1] + 1).min(size.1 - 1) {
+                        self.in_sight[row][col] = true;
+                        self.seen[row][col] = true;
+                    }
+                }
+                steps += 1;
+            }
+        }
+    }
+
+    /// The cell a move along `step` takes the runner to: the next room, or the exit.
+    fn destination(&self, step: Step) -> Option<[usize; 2]> {
+        let size = self.size();
+        let gap = offset(self.runner, step, 1, size).filter(|&[r, c]| self.open[r][c])?;
+        match gap == self.exit {
+            true => Some(gap),
+            false => offset(self.runner, step, 2, size),
+        }
+    }
+
+    fn render_title(&self) -> Line<'static> {
+        let name = SIZES
+            .iter()
+            .find(|(_, size)| *size == (self.rows, self.cols))
+            .map_or("Custom", |(name, _)| name);
+        let left = self.time_limit().saturating_sub(self.elapsed());
+        let status = match self.status() {
+            Status::Won => format!("Out in {}! (n: new maze)", clock(self.elapsed()))
+                .fg(self.colors.selected_cell_style_fg),
+            Status::Lost => "Time's up! (n: new maze)".fg(tailwind::RED.c400),
+            _ if self.started.is_none() => "Find the exit at the bottom right".into(),
+            _ => format!("{} left", clock(left)).fg(match left < Duration::from_secs(10) {
+                true => tailwind::RED.c400,
+                false => self.colors.row_fg,
+            }),
+        };
+        let best = self.best.map_or("–".into(), clock);
+        Line::from(vec![
+            format!("Maze · {name} {}x{} · Best {best}", self.rows, self.cols).into(),
+            " · ".dark_gray(),
+            status,
+        ])
+    }
+}
+
+impl Game for Maze {
+    /// The cell the runner moves into.
+    type Move = [usize; 2];
+
+    fn dimensions(&self) -> (usize, usize) {
+        self.size()
+    }
+
+    /// The rooms, or the exit, reachable in one move.
+    fn legal_moves(&self) -> Vec<[usize; 2]> {
+        if self.status() != Status::Playing {
+            return Vec::new();
+        }
+        STEPS
+            .into_iter()
+            .filter_map(|step| self.destination(step))
+            .collect()
+    }
+
+    fn apply(&mut self, cell: [usize; 2]) -> bool {
+        if !self.legal_moves().contains(&cell) {
+            return false;
+        }
+        // the clock starts with the first step
+        self.started.get_or_insert_with(Instant::now);
+        self.runner = cell;
+        self.trail[cell[0]][cell[1]] = true;
+        self.look();
+        if cell == self.exit {
+            let time = self.elapsed();
+            self.finished = Some(time);
+            if self.best.is_none_or(|best| time < best) {
+                self.best = Some(time);
+                records::save(&Self::record(self.rows, self.cols), time.as_millis());
+            }
+        }
+        true
+    }
+
+    fn status(&self) -> Status {
+        if self.runner == self.exit {
+            Status::Won
+        } else if self.finished.is_some() {
+            Status::Lost
+        } else {
+            Status::Playing
+        }
+    }
+
+    /// A cell of the maze, dark until seen and dimmer once out of sight.
+    fn render_cell(&self, [row, col]: [usize; 2]) -> (String, Style) {
+        if !self.seen[row][col] {
+            return (String::new(), Style::default().bg(self.colors.buffer_bg));
+        }
+        let in_sight = self.in_sight[row][col];
+        let bg: Color = match (self.open[row][col], in_sight) {
+            (false, true) => tailwind::SLATE.c400,
+            (false, false) => tailwind::SLATE.c600,
+            (true, true) => tailwind::SLATE.c800,
+            (true, false) => tailwind::SLATE.c900,
+        };
+        let style = Style::default().bg(bg);
+        if [row, col] == self.runner {
+            ("●".into(), style.fg(tailwind::AMBER.c300).bold())
+        } else if [row, col] == self.exit {
+            ("⚑".into(), style.fg(tailwind::EMERALD.c400).bold())
+        } else if self.trail[row][col] {
+            ("·".into(), style.fg(tailwind::SLATE.c500))
+        } else {
+            (String::new(), style)
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        let step = match key.code {
+            KeyCode::Char('n') => return self.restart(self.rows, self.cols),
+            KeyCode::Char(digit @ '1'..='4') => {
+                let (_, (rows, cols)) = SIZES[digit as usize - '1' as usize];
+                return self.restart(rows, cols);
+            }
+            KeyCode::Char('w') | KeyCode::Up => STEPS[0],
+            KeyCode::Char('s') | KeyCode::Down => STEPS[1],
+            KeyCode::Char('a') | KeyCode::Left => STEPS[2],
+            KeyCode::Char('d') | KeyCode::Right => STEPS[3],
+            _ => return,
+        };
+        if let Some(cell) = self.destination(step) {
+            self.apply(cell);
+        }
+    }
+
+    /// Stop the run once its time is up.
+    fn tick(&mut self) -> bool {
+        if self.finished.is_none() && self.elapsed() >= self.time_limit() {
+            self.finished = Some(self.time_limit());
+            return true;
+        }
+        false
+    }
+
+    fn apply_settings(&mut self, settings: &Settings) {
+        self.colors = settings.colors();
+    }
+
+    fn pause(&mut self) {
+        self.paused = Some(Instant::now());
+    }
+
+    fn resume(&mut self) {
+        if let Some(paused) = self.paused.take()
+            && let Some(started) = &mut self.started
+        {
+            *started += paused.elapsed();
+        }
+    }
+
+    fn restart(&mut self) {
+        self.restart(self.rows, self.cols);
+    }
+
+    fn help(&self) -> Help {
+        Help {
+            rules: "Find the way from the top left corner to the exit in the bottom right before \
+                    the clock runs out. Only the corridors in sight are shown."
+                .into(),
+            keys: vec![
+                ("←↑↓→/wasd", "walk"),
+                ("n", "new maze"),
+                ("1-4", "small to huge maze"),
+            ],
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let area = frame.area();
+        let min_width = 72;
+        let min_height = 12;
+        if area.width < min_width || area.height < min_height {
+            grid::render_too_small(frame, area, min_width, min_height);
+            return;
+        }
+
+        let [title_area, maze_area, help_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+        let (rows, cols) = self.size();
+        let view_rows = rows.min(usize::from(maze_area.height));
+        let view_cols = cols.min(usize::from(maze_area.width / CELL_WIDTH));
+        // large mazes scroll to keep the runner in the middle of the view
+        let [runner_row, runner_col] = self.runner;
+        let first_row = runner_row
+            .saturating_sub(view_rows / 2)
+            .min(rows - view_rows);
+        let first_col = runner_col
+            .saturating_sub(view_cols / 2)
+            .min(cols - view_cols);
+
+        frame.render_widget(Paragraph::new(self.render_title()).centered(), title_area);
+        let [maze_area] = Layout::horizontal([Constraint::Length(view_cols as u16 * CELL_WIDTH)])
+            .flex(Flex::Center)
+            .areas(maze_area);
+        let [maze_area] = Layout::vertical([Constraint::Length(view_rows as u16)])
+            .flex(Flex::Center)
+            .areas(maze_area);
+        grid::render_pixels(
+            frame,
+            maze_area,
+            (view_rows, view_cols),
+            CELL_WIDTH,
+            |[row, col]| self.render_cell([first_row + row, first_col + col]),
+        );
+        let help = "←↑↓→/wasd move · n new maze · 1-4 small to huge";
+        frame.render_widget(Paragraph::new(help).centered().dark_gray(), help_area);
+    }
+}
@@ -0,0 +1,357 @@
+//! Memory, or Concentration: turn over two face-down cards at a time to find every pair.
+//!
+//! A pair that matches stays face up; two cards that don't are shown for a moment before they
+//! turn back over. The fastest time on each grid size is kept between runs.
+
+use std::{
+    ops::RangeInclusive,
+    time::{Duration, Instant},
+};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use rand::seq::SliceRandom;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style, Stylize, palette::tailwind},
+    text::Line,
+    widgets::{Paragraph, TableState},
+};
+
+use crate::{
+    game::{Game, Help, Status},
+    grid::{self, Cursor, Viewport},
+    records,
+    settings::Settings,
+    ui::{
+        layout::{calculate_layout, cell_size, table_extent},
+        palette::{PALETTES, TableColors},
+    },
+};
+
+/// The smallest and largest number of rows or columns of the grid.
+pub const GRID_SIZES: RangeInclusive<usize> = 2..=8;
+
+/// The grid played when none is given.
+pub const DEFAULT_GRID: (usize, usize) = (4, 4);
+
+/// The faces of the cards, one per pair; enough for the largest grid.
+const FACES: [&str; 32] = [
+    "♠", "♥", "♦", "♣", "★", "☀", "☂", "☃", "♪", "♜", "✈", "✿", "❄", "☯", "☮", "☾", "☘", "✂", "✉",
+    "✚", "⚙", "☎", "♻", "⚑", "✦", "❖", "◆", "●", "▲", "■", "♞", "♛",
+];
+
+/// The colors the faces are drawn in, taken in turn.
+const FACE_COLORS: [Color; 6] = [
+    tailwind::RED.c400,
+    tailwind::AMBER.c400,
+    tailwind::GREEN.c400,
+    tailwind::CYAN.c400,
+    tailwind::VIOLET.c400,
+    tailwind::PINK.c400,
+];
+
+/// How long a card takes to turn face up.
+const FLIP_DURATION: Duration = Duration::from_millis(300);
+
+/// How long two cards that don't match stay face up.
+const MISMATCH_DELAY: Duration = Duration::from_millis(1000);
+
+/// A game of Memory.
+pub struct Memory {
+    rows: usize,
+    cols: usize,
+    /// The pair each card belongs to, as an index into [`FACES`].
+    faces: Vec<Vec<usize>>,
+    matched: Vec<Vec<bool>>,
+    /// The cards turned face up that are not matched yet, at most two.
+    open: Vec<[usize; 2]>,
+    /// When each open card was turned over, for its flip animation.
+    flipped: Vec<([usize; 2], Instant)>,
+    /// When two open cards that don't match turn back over.
+    hide_at: Option<Instant>,
+    /// How many pairs of cards have been turned over.
+    moves: usize,
+    /// When the first card was turned over.
+    started: Option<Instant>,
+    /// When the game was paused, to push back the start by as long on resuming.
+    paused: Option<Instant>,
+    /// How long the finished game took.
+    finished: Option<Duration>,
+    /// The fastest finish on this grid size, in milliseconds.
+    best: Option<u64>,
+    cursor: Cursor,
+    state: TableState,
+    colors: TableColors,
+}
+
+impl Memory {
+    /// A shuffled grid of `rows`×`cols` cards, which must be an even number of them.
+    pub fn new(rows: usize, cols: usize) -> Self {
+        let mut deck: Vec<_> = (0..rows * cols / 2).flat_map(|pair| [pair, pair]).collect();
+        deck.shuffle(&mut rand::rng());
+        let faces = deck.chunks(cols).map(<[usize]>::to_vec).collect();
+        Self {
+            rows,
+            cols,
+            faces,
+            matched: vec![vec![false; cols]; rows],
+            open: Vec::new(),
+            flipped: Vec::new(),
+            hide_at: None,
+            moves: 0,
+            started: None,
+            paused: None,
+            finished: None,
+            best: records::load(&Self::best_time_record(rows, cols)),
+            cursor: Cursor::new(rows, cols),
+            state: TableState::default(),
+            colors: TableColors::new(&PALETTES[0]),
+        }
+    }
+
+    /// Start over on a freshly shuffled grid of the same size.
+    fn restart(&mut self) {
+        *self = Self {
+            colors: self.colors,
+            ..Self::new(self.rows, self.cols)
+        };
+    }
+
+    /// The record the best time on a grid of `rows`×`cols` is kept under.
+    fn best_time_record(rows: usize, cols: usize) -> String {
+        format!("memory-best-{rows}x{cols}")
+    }
+
+    /// Turn the two open cards that don't match back over.
+    fn hide_open(&mut self) {
+        self.open.clear();
+        self.flipped.clear();
+        self.hide_at = None;
+    }
+
+    /// Stop the timer, and keep the time if it beats the best one.
+    fn finish(&mut self) {
+        let elapsed = self.elapsed();
+        self.finished = Some(elapsed);
+        let millis = elapsed.as_millis() as u64;
+        if self.best.is_none_or(|best| millis < best) {
+            self.best = Some(millis);
+            records::save(&Self::best_time_record(self.rows, self.cols), millis);
+        }
+    }
+
+    /// How long the game has been going, frozen once it is over.
+    fn elapsed(&self) -> Duration {
+        match (self.finished, self.started) {
+            (Some(finished), _) => finished,
+            (None, Some(started)) => started.elapsed(),
+            (None, None) => Duration::ZERO,
+        }
+    }
+
+    /// How a card that is turning over looks `elapsed` into its flip, if it still is.
+    fn flip_frame(elapsed: Duration) -> Option<&'static str> {
+        match elapsed.as_millis() * 3 / FLIP_DURATION.as_millis() {
+            0 => Some("▐█▌"),
+            1 => Some("▐▌"),
+            _ => None,
+        }
+    }
+
+    fn render_title(&self) -> Line<'static> {
+        let secs = self.elapsed().as_secs();
+        let best = match self.best {
+            Some(best) => format!(" · Best {}:{:02}", best / 60_000, best / 1000 % 60),
+            None => String::new(),
+        };
+        let status = match self.status() {
+            Status::Won => "All pairs found! (r: new grid)".fg(self.colors.selected_cell_style_fg),
+            _ => "Space: turn over".fg(self.colors.selected_cell_style_fg),
+        };
+        Line::from(vec![
+            format!("Memory · Moves {}", self.moves).into(),
+            format!(" · {}:{:02}{best}", secs / 60, secs % 60).into(),
+            " · ".dark_gray(),
+            status,
+        ])
+    }
+}
+
+impl Game for Memory {
+    type Move = [usize; 2];
+
+    fn dimensions(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    /// The cards face down, or about to turn back over.
+    fn legal_moves(&self) -> Vec<[usize; 2]> {
+        let closing = self.hide_at.is_some();
+        (0..self.rows)
+            .flat_map(|row| (0..self.cols).map(move |col| [row, col]))
+            .filter(|&[row, col]| !self.matched[row][col])
+            .filter(|cell| closing || !self.open.contains(cell))
+            .collect()
+    }
+
+    /// Turn the card at `cell` face up; a pair that didn't match is turned back over first.
+    fn apply(&mut self, cell: [usize; 2]) -> bool {
+        if !self.legal_moves().contains(&cell) {
+            return false;
+        }
+        if self.hide_at.is_some() {
+            self.hide_open();
+        }
+        let now = Instant::now();
+        self.started.get_or_insert(now);
+        self.open.push(cell);
+        self.flipped.retain(|(_, at)| at.elapsed() < FLIP_DURATION);
+        self.flipped.push((cell, now));
+        if let &[[r1, c1], [r2, c2]] = self.open.as_slice() {
+            self.moves += 1;
+            if self.faces[r1][c1] == self.faces[r2][c2] {
+                self.matched[r1][c1] = true;
+                self.matched[r2][c2] = true;
+                self.open.clear();
+                if self.status() == Status::Won {
+                    self.finish();
+                }
+            } else {
+                self.hide_at = Some(now + MISMATCH_DELAY);
+            }
+        }
+        true
+    }
+
+    fn status(&self) -> Status {
+        match self.matched.iter().flatten().all(|&matched| matched) {
+            true => Status::Won,
+            false => Status::Playing,
+        }
+    }
+
+    fn render_cell(&self, cell: [usize; 2]) -> (String, Style) {
+        let [row, col] = cell;
+        let face_down = Style::default().bg(self.colors.hint_cell_style_bg);
+        let flipping = self
+            .flipped
+            .iter()
+            .find(|&&(flipped, _)| flipped == cell)
+            .and_then(|(_, at)| Self::flip_frame(at.elapsed()));
+        if let Some(frame) = flipping {
+            return (
+                frame.into(),
+                Style::default().fg(self.colors.hint_cell_style_bg),
+            );
+        }
+        if !self.matched[row][col] && !self.open.contains(&cell) {
+            return (String::new(), face_down);
+        }
+        let pair = self.faces[row][col];
+        let style = Style::default()
+            .fg(FACE_COLORS[pair % FACE_COLORS.len()])
+            .add_modifier(Modifier::BOLD);
+        // the cards of a pair that matched are dimmed, leaving the open ones to stand out
+        let style = match self.matched[row][col] {
+            true => style.add_modifier(Modifier::DIM),
+            false => style,
+        };
+        (FACES[pair].into(), style)
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char('r') => self.restart(),
+            // the grid is frozen once every pair is found
+            _ if self.status() != Status::Playing => {}
+            KeyCode::Char(' ') | KeyCode::Enter => {
+                self.apply(self.cursor.cell());
+            }
+            code => {
+                self.cursor.move_with(code);
+            }
+        }
+    }
+
+    /// Turn two cards that don't match back over once they have been shown long enough.
+    fn tick(&mut self) -> bool {
+        if self.hide_at.is_some_and(|at| Instant::now() >= at) {
+            self.hide_open();
+            return true;
+        }
+        false
+    }
+
+    fn apply_settings(&mut self, settings: &Settings) {
+        self.colors = settings.colors();
+    }
+
+    fn pause(&mut self) {
+        self.paused = Some(Instant::now());
+    }
+
+    fn resume(&mut self) {
+        if let Some(paused) = self.paused.take()
+            && let Some(started) = &mut self.started
+        {
+            *started += paused.elapsed();
+        }
+    }
+
+    fn restart(&mut self) {
+        self.restart();
+    }
+
+    fn help(&self) -> Help {
+        Help {
+            rules: "Turn over two face-down cards at a time. A matching pair stays face up, and \
+                    two that don't turn back over. Find every pair as fast as you can."
+                .into(),
+            keys: vec![
+                ("←↑↓→/wasd", "move the cursor"),
+                ("Space/Enter", "turn a card over"),
+                ("r", "new game"),
+            ],
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [area, help_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(frame.area());
+        let help = match self.status() {
+            Status::Playing => "←↑↓→/wasd move · Space turn over · r new grid",
+            _ => "r new grid",
+        };
+        frame.render_widget(Paragraph::new(help).centered().dark_gray(), help_area);
+        let (cell_width, cell_height) = cell_size(self.rows.max(self.cols));
+        let table_width = table_extent(self.cols, cell_width, 1);
+        let table_height = table_extent(self.rows, cell_height, 0);
+        let min_width = table_width.max(70);
+        if area.width < min_width || area.height < table_height + 1 {
+            grid::render_too_small(frame, area, min_width, table_height + 1);
+            return;
+        }
+
+        let layout = calculate_layout(area, false, table_width, table_height);
+        let viewport = Viewport {
+            row: 0,
+            col: 0,
+            rows: self.rows,
+            cols: self.cols,
+        };
+        self.state.select(Some(self.cursor.row));
+        self.state.select_column(Some(self.cursor.col));
+        frame.render_widget(Paragraph::new(self.render_title()).centered(), layout.title);
+        // the cursor is hidden once the game is over
+        let selected_cell_style = match self.status() {
+            Status::Playing => Style::default()
+                .add_modifier(Modifier::REVERSED)
+                .fg(self.colors.selected_cell_style_fg),
+            _ => Style::default(),
+        };
+        let table = grid::table(self, viewport, cell_width, cell_height, &self.colors)
+            .cell_highlight_style(selected_cell_style);
+        frame.render_stateful_widget(table, layout.table, &mut self.state);
+    }
+}
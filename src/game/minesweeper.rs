@@ -0,0 +1,381 @@
+//! Minesweeper: uncover every cell of the grid that does not hide a mine.
+//!
+//! Mines are only laid once the first cell is uncovered, away from that cell and its
+//! neighbours, so the opening move is always safe.
+
+use std::{
+    ops::RangeInclusive,
+    time::{Duration, Instant},
+};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use rand::seq::IteratorRandom;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style, Stylize, palette::tailwind},
+    text::Line,
+    widgets::{Paragraph, TableState},
+};
+
+use crate::{
+    game::{Game, Help, Status},
+    grid::{self, Cursor, Viewport},
+    settings::Settings,
+    ui::{
+        layout::{calculate_layout, cell_size, cells_fitting, table_extent},
+        palette::{PALETTES, TableColors},
+    },
+};
+
+/// The smallest and largest number of rows or columns of the grid.
+pub const GRID_SIZES: RangeInclusive<usize> = 5..=30;
+
+/// The share of cells hiding a mine, in percent, that can be asked for.
+pub const MINE_DENSITIES: RangeInclusive<usize> = 5..=40;
+
+/// The grid and mine density played when none are given.
+pub const DEFAULT_GRID: (usize, usize) = (9, 9);
+pub const DEFAULT_DENSITY: usize = 15;
+
+/// The colors of the counts of neighbouring mines, from one to eight.
+const COUNT_COLORS: [Color; 8] = [
+    tailwind::BLUE.c400,
+    tailwind::GREEN.c400,
+    tailwind::RED.c400,
+    tailwind::PURPLE.c400,
+    tailwind::AMBER.c400,
+    tailwind::CYAN.c400,
+    tailwind::PINK.c400,
+    tailwind::SLATE.c400,
+];
+
+/// What the player knows about a cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tile {
+    Hidden,
+    Flagged,
+    Revealed,
+}
+
+/// A game of Minesweeper.
+pub struct Minesweeper {
+    rows: usize,
+    cols: usize,
+    /// How many of the cells hide a mine, in percent.
+    density: usize,
+    /// Where the mines are; empty until the first cell is uncovered.
+    mines: Vec<Vec<bool>>,
+    tiles: Vec<Vec<Tile>>,
+    cursor: Cursor,
+    /// When the first cell was uncovered.
+    started: Option<Instant>,
+    /// How long the finished game took.
+    finished: Option<Duration>,
+    /// The mine that went off, once the game is lost.
+    exploded: Option<[usize; 2]>,
+    state: TableState,
+    viewport: Viewport,
+    colors: TableColors,
+}
+
+impl Minesweeper {
+    /// A grid of `rows`×`cols` cells, `density` percent of which hide a mine.
+    pub fn new(rows: usize, cols: usize, density: usize) -> Self {
+        Self {
+            rows,
+            cols,
+            density,
+            mines: Vec::new(),
+            tiles: vec![vec![Tile::Hidden; cols]; rows],
+            cursor: Cursor::new(rows, cols),
+            started: None,
+            finished: None,
+            exploded: None,
+            state: TableState::default(),
+            viewport: Viewport::default(),
+            colors: TableColors::new(&PALETTES[0]),
+        }
+    }
+
+    /// Start over on a fresh grid of the same size and density.
+    fn restart(&mut self) {
+        *self = Self {
+            colors: self.colors,
+            ..Self::new(self.rows, self.cols, self.density)
+        };
+    }
+
+    /// How many mines the grid holds; always at least one, and few enough to leave the
+    /// opening cell and its neighbours clear.
+    fn mine_count(&self) -> usize {
+        let cells = self.rows * self.cols;
+        (cells * self.density / 100).clamp(1, cells - 9)
+    }
+
+    /// The cells around `[row, col]`, without the cell itself.
+    fn neighbours(&self, [row, col]: [usize; 2]) -> impl Iterator<Item = [usize; 2]> {
+        let (rows, cols) = (self.rows, self.cols);
+        (row.saturating_sub(1)..=(row + 1).min(rows - 1))
+            .flat_map(move |r| {
+                (col.saturating_sub(1)..=(col + 1).min(cols - 1)).map(move |c| [r, c])
+            })
+            .filter(move |&cell| cell != [row, col])
+    }
+
+    /// How many mines border `cell`.
+    fn count(&self, cell: [usize; 2]) -> usize {
+        self.neighbours(cell)
+            .filter(|&[row, col]| self.mines[row][col])
+            .count()
+    }
+
+    /// Lay the mines anywhere but on `safe` and its neighbours.
+    fn lay_mines(&mut self, safe: [usize; 2]) {
+        let clear: Vec<_> = self.neighbours(safe).chain([safe]).collect();
+        let mut mines = vec![vec![false; self.cols]; self.rows];
+        let cells = (0..self.rows)
+            .flat_map(|row| (0..self.cols).map(move |col| [row, col]))
+            .filter(|cell| !clear.contains(cell));
+        for [row, col] in cells.choose_multiple(&mut rand::rng(), self.mine_count()) {
+            mines[row][col] = true;
+        }
+        self.mines = mines;
+        self.started = Some(Instant::now());
+    }
+
+    /// Uncover `cell`, and every cell reachable from it through cells bordering no mine.
+    fn reveal(&mut self, cell: [usize; 2]) {
+        let mut queue = vec![cell];
+        while let Some([row, col]) = queue.pop() {
+            if self.tiles[row][col] != Tile::Hidden {
+                continue;
+            }
+            self.tiles[row][col] = Tile::Revealed;
+            if !self.mines[row][col] && self.count([row, col]) == 0 {
+                queue.extend(self.neighbours([row, col]));
+            }
+        }
+    }
+
+    /// Flag or unflag the cell under the cursor as hiding a mine.
+    fn toggle_flag(&mut self) {
+        let [row, col] = self.cursor.cell();
+        let tile = &mut self.tiles[row][col];
+        *tile = match tile {
+            Tile::Hidden => Tile::Flagged,
+            Tile::Flagged => Tile::Hidden,
+            Tile::Revealed => Tile::Revealed,
+        };
+    }
+
+    /// Stop the timer and show where every mine was.
+    fn finish(&mut self) {
+        self.finished = self.started.map(|started| started.elapsed());
+        for (row, mines) in self.mines.iter().enumerate() {
+            for (col, &mine) in mines.iter().enumerate() {
+                if mine && self.exploded.is_none() {
+                    self.tiles[row][col] = Tile::Flagged;
+                } else if mine && self.tiles[row][col] == Tile::Hidden {
+                    self.tiles[row][col] = Tile::Revealed;
+                }
+            }
+        }
+    }
+
+    /// How long the game has been going, frozen once it is over.
+    fn elapsed(&self) -> Duration {
+        match (self.finished, self.started) {
+            (Some(finished), _) => finished,
+            (None, Some(started)) => started.elapsed(),
+            (None, None) => Duration::ZERO,
+        }
+    }
+
+    fn render_title(&self) -> Line<'static> {
+        let flags = self
+            .tiles
+            .iter()
+            .flatten()
+            .filter(|&&tile| tile == Tile::Flagged);
+        let secs = self.elapsed().as_secs();
+        let status = match self.status() {
+            Status::Playing => "Space: uncover · f: flag".fg(self.colors.selected_cell_style_fg),
+            Status::Won => "Cleared! (r: new grid)".fg(self.colors.selected_cell_style_fg),
+            Status::Lost | Status::Draw => "Boom! (r: new grid)".fg(Color::Red),
+        };
+        Line::from(vec![
+            format!("Minesweeper · ⚑ {}/{}", flags.count(), self.mine_count()).into(),
+            format!(" · {}:{:02}", secs / 60, secs % 60).into(),
+            " · ".dark_gray(),
+            status,
+        ])
+    }
+}
+
+impl Game for Minesweeper {
+    type Move = [usize; 2];
+
+    fn dimensions(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    fn legal_moves(&self) -> Vec<[usize; 2]> {
+        if self.status() != Status::Playing {
+            return Vec::new();
+        }
+        (0..self.rows)
+            .flat_map(|row| (0..self.cols).map(move |col| [row, col]))
+            .filter(|&[row, col]| self.tiles[row][col] == Tile::Hidden)
+            .collect()
+    }
+
+    /// Uncover `cell`; flagged and uncovered cells cannot be.
+    fn apply(&mut self, cell: [usize; 2]) -> bool {
+        if !self.legal_moves().contains(&cell) {
+            return false;
+        }
+        if self.mines.is_empty() {
+            self.lay_mines(cell);
+        }
+        let [row, col] = cell;
+        if self.mines[row][col] {
+            self.exploded = Some(cell);
+            self.finish();
+        } else {
+            self.reveal(cell);
+            if self.status() == Status::Won {
+                self.finish();
+            }
+        }
+        true
+    }
+
+    fn status(&self) -> Status {
+        if self.exploded.is_some() {
+            return Status::Lost;
+        }
+        let cleared = !self.mines.is_empty()
+            && self
+                .tiles
+                .iter()
+                .flatten()
+                .zip(self.mines.iter().flatten())
+                .all(|(&tile, &mine)| mine || tile == Tile::Revealed);
+        match cleared {
+            true => Status::Won,
+            false => Status::Playing,
+        }
+    }
+
+    fn render_cell(&self, cell: [usize; 2]) -> (String, Style) {
+        let [row, col] = cell;
+        let hidden = Style::default().bg(self.colors.hint_cell_style_bg);
+        match self.tiles[row][col] {
+            Tile::Hidden => (String::new(), hidden),
+            Tile::Flagged => (
+                "⚑".into(),
+                hidden.fg(Color::Red).add_modifier(Modifier::BOLD),
+            ),
+            Tile::Revealed if self.mines[row][col] => {
+                let style = match self.exploded == Some(cell) {
+                    true => Style::default().bg(Color::Red),
+                    false => Style::default(),
+                };
+                ("✱".into(), style.add_modifier(Modifier::BOLD))
+            }
+            Tile::Revealed => match self.count(cell) {
+                0 => (String::new(), Style::default()),
+                count => (
+                    count.to_string(),
+                    Style::default()
+                        .fg(COUNT_COLORS[count - 1])
+                        .add_modifier(Modifier::BOLD),
+                ),
+            },
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char('r') => self.restart(),
+            // the grid is frozen once the game is decided
+            _ if self.status() != Status::Playing => {}
+            KeyCode::Char(' ') | KeyCode::Enter => {
+                self.apply(self.cursor.cell());
+            }
+            KeyCode::Char('f') => self.toggle_flag(),
+            code => {
+                self.cursor.move_with(code);
+            }
+        }
+    }
+
+    fn apply_settings(&mut self, settings: &Settings) {
+        self.colors = settings.colors();
+    }
+
+    fn restart(&mut self) {
+        self.restart();
+    }
+
+    fn help(&self) -> Help {
+        Help {
+            rules: "Uncover every cell that doesn't hide a mine. A number tells how many of the \
+                    cell's neighbours hide one. The first cell uncovered is always safe."
+                .into(),
+            keys: vec![
+                ("←↑↓→/wasd", "move the cursor"),
+                ("Space/Enter", "uncover"),
+                ("f", "flag a mine"),
+                ("r", "new game"),
+            ],
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [area, help_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(frame.area());
+        let help = match self.status() {
+            Status::Playing => "←↑↓→/wasd move · Space uncover · f flag · r new grid",
+            _ => "r new grid",
+        };
+        frame.render_widget(Paragraph::new(help).centered().dark_gray(), help_area);
+        let (cell_width, cell_height) = cell_size(self.rows.max(self.cols));
+        let view_rows = self
+            .rows
+            .min(cells_fitting(area.height.saturating_sub(1), cell_height, 0));
+        let view_cols = self.cols.min(cells_fitting(area.width, cell_width, 1));
+        let min_width = table_extent(self.cols.min(5), cell_width, 1).max(40);
+        let min_height = table_extent(self.rows.min(5), cell_height, 0) + 1;
+        if area.width < min_width || view_rows < self.rows.min(5) || view_cols < self.cols.min(5) {
+            grid::render_too_small(frame, area, min_width, min_height);
+            return;
+        }
+
+        let table_width = table_extent(view_cols, cell_width, 1);
+        let table_height = table_extent(view_rows, cell_height, 0);
+        let layout = calculate_layout(area, false, table_width, table_height);
+
+        let viewport = &mut self.viewport;
+        viewport.rows = view_rows;
+        viewport.cols = view_cols;
+        viewport.row = Viewport::follow(viewport.row, view_rows, self.rows, self.cursor.row);
+        viewport.col = Viewport::follow(viewport.col, view_cols, self.cols, self.cursor.col);
+        self.state.select(Some(self.cursor.row - viewport.row));
+        self.state
+            .select_column(Some(self.cursor.col - viewport.col));
+
+        frame.render_widget(Paragraph::new(self.render_title()).centered(), layout.title);
+        // the cursor is hidden once the game is over
+        let selected_cell_style = match self.status() {
+            Status::Playing => Style::default()
+                .add_modifier(Modifier::REVERSED)
+                .fg(self.colors.selected_cell_style_fg),
+            _ => Style::default(),
+        };
+        let table = grid::table(self, self.viewport, cell_width, cell_height, &self.colors)
+            .cell_highlight_style(selected_cell_style);
+        frame.render_stateful_widget(table, layout.table, &mut self.state);
+    }
+}
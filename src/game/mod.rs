@@ -0,0 +1,184 @@
+//! The interface every game played in the terminal implements, the rules of the games of
+//! placing marks in a row, and the other games on the menu, one module each.
+
+pub mod board;
+pub mod quantum;
+pub mod ultimate;
+
+// the games beside those of marks in a row, which are only played through `Game`
+pub(crate) mod battleship;
+pub(crate) mod blackjack;
+pub(crate) mod checkers;
+pub(crate) mod chess;
+pub(crate) mod dots;
+pub(crate) mod game2048;
+pub(crate) mod go;
+pub(crate) mod hangman;
+pub(crate) mod hanoi;
+pub(crate) mod hex;
+pub(crate) mod life;
+pub(crate) mod lights;
+pub(crate) mod mancala;
+pub(crate) mod maze;
+pub(crate) mod memory;
+pub(crate) mod minesweeper;
+pub(crate) mod nim;
+pub(crate) mod nonogram;
+pub(crate) mod pente;
+pub(crate) mod pong;
+pub(crate) mod quiz;
+pub(crate) mod reversi;
+pub(crate) mod rps;
+pub(crate) mod simon;
+pub(crate) mod sokoban;
+pub(crate) mod sos;
+pub(crate) mod sudoku;
+pub(crate) mod tetris;
+pub(crate) mod typing;
+pub(crate) mod yahtzee;
+
+use std::time::Duration;
+
+use crossterm::event::{KeyCode, KeyEvent, MouseEvent};
+use ratatui::{Frame, style::Style};
+
+use crate::{bot, settings::Settings};
+
+/// How often every screen ticks and is redrawn, about 30 times a second, so animations, clocks
+/// and the bots keep moving while no key is pressed.
+pub const TICK_RATE: Duration = Duration::from_millis(33);
+
+/// Whether a game is still under way, and how it ended for the player at the keyboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Playing,
+    Won,
+    Lost,
+    Draw,
+}
+
+/// What the help overlay tells about a game.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Help {
+    /// How the game is played, in a few sentences.
+    pub rules: String,
+    /// The keys the game takes, each with what it does; the keys every game shares are listed
+    /// after them.
+    pub keys: Vec<(&'static str, &'static str)>,
+}
+
+/// A game played on a grid of cells.
+pub trait Game {
+    /// A move as the rules of the game describe it.
+    type Move;
+
+    /// The number of rows and columns of the board.
+    fn dimensions(&self) -> (usize, usize);
+
+    /// Every move the side to move may make.
+    fn legal_moves(&self) -> Vec<Self::Move>;
+
+    /// Make `mv` for the side to move.
+    ///
+    /// Returns `false` and leaves the game untouched when the move is not allowed.
+    fn apply(&mut self, mv: Self::Move) -> bool;
+
+    /// Whether the game is still under way.
+    fn status(&self) -> Status;
+
+    /// How strong the bot the player is up against plays, in games against one.
+    fn opponent(&self) -> Option<bot::Opponent> {
+        None
+    }
+
+    /// The text shown in `cell`, together with the style hinting at its role, such as a
+    /// suggested move or part of a winning line.
+    fn render_cell(&self, cell: [usize; 2]) -> (String, Style);
+
+    /// React to a key press other than the ones leaving the game.
+    fn handle_key(&mut self, key: KeyEvent);
+
+    /// Whether the game tracks which keys are held down, and so wants to hear when they are
+    /// let go; only terminals supporting the keyboard enhancement protocol report that.
+    fn wants_key_releases(&self) -> bool {
+        false
+    }
+
+    /// React to a key being let go, in terminals reporting it to games that
+    /// [want releases](Game::wants_key_releases).
+    fn handle_key_release(&mut self, _key: KeyEvent) {}
+
+    /// Whether the game can be played with the mouse, and so wants the terminal to report it;
+    /// the terminal no longer selects text with the mouse while such a game runs.
+    fn wants_mouse(&self) -> bool {
+        false
+    }
+
+    /// React to the mouse being pressed, let go, dragged or scrolled, in games that
+    /// [want the mouse](Game::wants_mouse).
+    fn handle_mouse(&mut self, _event: MouseEvent) {}
+
+    /// How long to wait before ticking again, never longer than [`TICK_RATE`]; games that move
+    /// on their own in real time wait less.
+    fn tick_rate(&self) -> Duration {
+        TICK_RATE
+    }
+
+    /// Whether the game takes every letter key as input, so `q` no longer leaves it; `Esc`,
+    /// unless the game [uses it](Game::uses_key), and `Ctrl+C` still do.
+    fn takes_letters(&self) -> bool {
+        false
+    }
+
+    /// Whether the game takes `code` as input even though its screen would otherwise act on
+    /// it, as it does on `o` by opening the settings, on `Shift+←/→` by changing the theme, on
+    /// `Esc` by leaving the game and on HJKL when those move the cursor.
+    fn uses_key(&self, _code: KeyCode) -> bool {
+        false
+    }
+
+    /// Take on the player's settings, when the game starts and whenever they change during it.
+    fn apply_settings(&mut self, _settings: &Settings) {}
+
+    /// Stop anything moving in real time, such as timers, while the game is hidden behind the
+    /// help, the settings or the pause menu.
+    fn pause(&mut self) {}
+
+    /// Go on with whatever [`pause`](Game::pause) stopped, once the game shows again.
+    fn resume(&mut self) {}
+
+    /// Start the game over, as chosen from the pause menu.
+    fn restart(&mut self);
+
+    /// The messages the game has for the player since it was last asked, such as that it is
+    /// their turn; they show for a moment in a corner of the screen.
+    fn toasts(&mut self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Whether the game was decided and then taken back with undo, so that finishing it again
+    /// ends the game already counted in the stats rather than a new one. Starting over clears
+    /// it.
+    fn reopened(&self) -> bool {
+        false
+    }
+
+    /// Whether the player asked from within the game to go back to the menu, such as from a
+    /// screen of its own, since it was last asked.
+    fn wants_to_leave(&mut self) -> bool {
+        false
+    }
+
+    /// The rules and keys shown when the player asks for help with `?`.
+    fn help(&self) -> Help;
+
+    /// Advance anything that happens without input, such as timers or a computer opponent.
+    ///
+    /// Returns `true` when the game changed and should tick again as soon as it is redrawn.
+    fn tick(&mut self) -> bool {
+        false
+    }
+
+    /// Draw the whole game screen.
+    fn draw(&mut self, frame: &mut Frame);
+}
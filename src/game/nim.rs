@@ -0,0 +1,398 @@
+//! Nim: take turns taking stones from one heap at a time; whoever takes the last stone wins.
+//!
+//! The heaps are drawn as rows of stones rather than a board of cells. The hard bot plays
+//! perfectly by the nim-sum, the xor of the heap sizes: it always hands the player a position
+//! whose nim-sum is zero, from which every move loses if the bot keeps it up.
+
+use std::ops::RangeInclusive;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use rand::{Rng, seq::IteratorRandom};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout},
+    style::{Modifier, Style, Stylize},
+    text::{Line, Span, Text},
+    widgets::Paragraph,
+};
+
+use crate::{
+    Outcome, Player, bot,
+    game::{Game, Help, Status},
+    grid,
+    settings::Settings,
+    ui::{
+        layout::center,
+        palette::{PALETTES, TableColors},
+    },
+};
+
+/// How many heaps a game may have.
+pub const HEAP_COUNTS: RangeInclusive<usize> = 1..=8;
+
+/// How many stones a heap may start with.
+pub const HEAP_SIZES: RangeInclusive<usize> = 1..=20;
+
+/// The heaps played when none are given.
+pub const DEFAULT_HEAPS: [usize; 3] = [3, 4, 5];
+
+/// A move: how many stones to take from which heap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Take {
+    pub heap: usize,
+    pub stones: usize,
+}
+
+/// The xor of the heap sizes; the side to move can only win when it is not zero.
+fn nim_sum(heaps: &[usize]) -> usize {
+    heaps.iter().fold(0, |sum, &heap| sum ^ heap)
+}
+
+/// The take `opponent` makes from `heaps`, if any stones are left.
+fn choose_take(heaps: &[usize], opponent: bot::Opponent) -> Option<Take> {
+    let mut rng = rand::rng();
+    let (heap, &size) = heaps
+        .iter()
+        .enumerate()
+        .filter(|&(_, &size)| size > 0)
+        .choose(&mut rng)?;
+    let random = Take {
+        heap,
+        stones: rng.random_range(1..=size),
+    };
+    if opponent == bot::Opponent::Random {
+        return Some(random);
+    }
+    let sum = nim_sum(heaps);
+    if sum == 0 {
+        // every move loses against perfect play, so take a single stone and wait for a slip
+        return Some(Take { heap, stones: 1 });
+    }
+    // a heap with the top bit of the nim-sum set can be cut down to zero the nim-sum
+    heaps
+        .iter()
+        .position(|&size| size ^ sum < size)
+        .map(|heap| Take {
+            heap,
+            stones: heaps[heap] - (heaps[heap] ^ sum),
+        })
+}
+
+/// A game of Nim against the bot.
+pub struct Nim {
+    /// The heaps the game started with, for a rematch.
+    initial: Vec<usize>,
+    heaps: Vec<usize>,
+    turn: Player,
+    /// Who moved first this game; the sides take turns opening.
+    starter: Player,
+    opponent: bot::Opponent,
+    outcome: Option<Outcome>,
+    /// The heap picked to take from.
+    heap: usize,
+    /// How many stones of the picked heap to take.
+    stones: usize,
+    last_bot_take: Option<Take>,
+    /// The bot working out its next take.
+    thinking: Option<bot::Thinking<Option<Take>>>,
+    colors: TableColors,
+}
+
+impl Nim {
+    /// A game on `heaps` of stones, each of them holding at least one.
+    pub fn new(heaps: Vec<usize>) -> Self {
+        Self {
+            initial: heaps.clone(),
+            heaps,
+            turn: Player::Human,
+            starter: Player::Human,
+            opponent: bot::Opponent::Minimax,
+            outcome: None,
+            heap: 0,
+            stones: 1,
+            last_bot_take: None,
+            thinking: None,
+            colors: TableColors::new(&PALETTES[0]),
+        }
+    }
+
+    /// Start a new game on the same heaps, with the other side opening.
+    fn rematch(&mut self) {
+        let starter = self.starter.other();
+        *self = Self {
+            colors: self.colors,
+            turn: starter,
+            starter,
+            opponent: self.opponent,
+            ..Self::new(std::mem::take(&mut self.initial))
+        };
+    }
+
+    /// Take stones for the side to move and hand the turn over; whoever empties the last heap
+    /// wins.
+    fn play(&mut self, take: Take) {
+        self.heaps[take.heap] -= take.stones;
+        if self.heaps.iter().all(|&size| size == 0) {
+            self.outcome = Some(Outcome::Winner(self.turn));
+        }
+        self.turn = self.turn.other();
+        self.pick_heap(self.heap);
+    }
+
+    /// Pick `heap`, or the nearest one after it still holding stones, keeping the number of
+    /// stones to take within it.
+    fn pick_heap(&mut self, heap: usize) {
+        let count = self.heaps.len();
+        if let Some(heap) = (0..count)
+            .map(|offset| (heap + offset) % count)
+            .find(|&heap| self.heaps[heap] > 0)
+        {
+            self.heap = heap;
+            self.stones = self.stones.clamp(1, self.heaps[heap]);
+        }
+    }
+
+    /// Move the pick `step` heaps up or down, skipping the empty ones.
+    fn move_pick(&mut self, step: isize) {
+        let count = self.heaps.len() as isize;
+        let mut heap = self.heap as isize;
+        for _ in 0..count {
+            heap = (heap + step).rem_euclid(count);
+            if self.heaps[heap as usize] > 0 {
+                self.pick_heap(heap as usize);
+                return;
+            }
+        }
+    }
+
+    /// Make the take the bot settled on.
+    fn bot_move(&mut self, take: Take) {
+        self.play(take);
+        self.last_bot_take = Some(take);
+    }
+
+    fn render_title(&self) -> Line<'static> {
+        let versus = format!(
+            "Nim · {} stones left · Bot [{}]",
+            self.heaps.iter().sum::<usize>(),
+            self.opponent.label()
+        );
+        let status = match (self.outcome, self.turn) {
+            (Some(outcome), _) => format!("{} (r: rematch)", outcome.message()).into(),
+            (None, Player::Bot) => "Bot thinking…".fg(self.colors.row_fg),
+            (None, Player::Human) => match self.last_bot_take {
+                Some(take) => format!(
+                    "The bot took {} from heap {} · Your turn",
+                    take.stones,
+                    take.heap + 1
+                )
+                .fg(self.colors.selected_cell_style_fg),
+                None => "Your turn".fg(self.colors.selected_cell_style_fg),
+            },
+        };
+        Line::from(vec![versus.into(), " · ".dark_gray(), status])
+    }
+
+    /// A line for each heap, its stones marked where the picked ones would be taken from the
+    /// end of it.
+    fn render_heaps(&self) -> Text<'static> {
+        let picking = self.outcome.is_none() && self.turn == Player::Human;
+        let (rows, cols) = self.dimensions();
+        (0..rows)
+            .map(|row| {
+                let picked = picking && row == self.heap;
+                let label = format!("{} Heap {:>2}  ", if picked { "▸" } else { " " }, row + 1);
+                let label = match picked {
+                    true => label.fg(self.colors.selected_cell_style_fg).bold(),
+                    false => label.fg(self.colors.row_fg),
+                };
+                let stones = (0..cols).map(|col| {
+                    let (stone, style) = self.render_cell([row, col]);
+                    Span::styled(format!("{stone} "), style)
+                });
+                let count = format!(" {:>2}", self.heaps[row]).dark_gray();
+                Line::from_iter([label].into_iter().chain(stones).chain([count]))
+            })
+            .collect()
+    }
+}
+
+impl Game for Nim {
+    type Move = Take;
+
+    /// A row for each heap, as long as the heap the game started with the most stones in.
+    fn dimensions(&self) -> (usize, usize) {
+        let largest = self.initial.iter().copied().max().unwrap_or(0);
+        (self.heaps.len(), largest)
+    }
+
+    fn legal_moves(&self) -> Vec<Take> {
+        if self.outcome.is_some() || self.turn != Player::Human {
+            return Vec::new();
+        }
+        self.heaps
+            .iter()
+            .enumerate()
+            .flat_map(|(heap, &size)| (1..=size).map(move |stones| Take { heap, stones }))
+            .collect()
+    }
+
+    fn apply(&mut self, take: Take) -> bool {
+        if !self.legal_moves().contains(&take) {
+            return false;
+        }
+        self.last_bot_take = None;
+        self.play(take);
+        true
+    }
+
+    fn status(&self) -> Status {
+        match self.outcome {
+            None => Status::Playing,
+            Some(Outcome::Winner(Player::Human)) => Status::Won,
+            Some(Outcome::Winner(Player::Bot)) => Status::Lost,
+            Some(Outcome::Draw) => Status::Draw,
+        }
+    }
+
+    fn opponent(&self) -> Option<bot::Opponent> {
+        Some(self.opponent)
+    }
+
+    /// A stone of a heap, highlighted when it is picked to be taken, or the gap a taken one
+    /// left.
+    fn render_cell(&self, [row, col]: [usize; 2]) -> (String, Style) {
+        let size = self.heaps[row];
+        if col >= size {
+            return ("·".into(), Style::default().fg(self.colors.row_fg).dim());
+        }
+        let picked = self.outcome.is_none()
+            && self.turn == Player::Human
+            && row == self.heap
+            && col >= size - self.stones;
+        match picked {
+            true => (
+                "○".into(),
+                Style::default()
+                    .fg(self.colors.selected_cell_style_fg)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            false => ("●".into(), Style::default().fg(self.colors.row_fg)),
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char('r') => self.rematch(),
+            KeyCode::Char('b') => self.opponent = self.opponent.next(),
+            // the heaps are left alone once the game is decided
+            _ if self.outcome.is_some() || self.turn != Player::Human => {}
+            KeyCode::Up | KeyCode::Char('w') => self.move_pick(-1),
+            KeyCode::Down | KeyCode::Char('s') => self.move_pick(1),
+            KeyCode::Left | KeyCode::Char('a') => self.stones = (self.stones - 1).max(1),
+            KeyCode::Right | KeyCode::Char('d') => {
+                self.stones = (self.stones + 1).min(self.heaps[self.heap]);
+            }
+            KeyCode::Char(digit @ '1'..='9') => {
+                let stones = digit as usize - '0' as usize;
+                self.stones = stones.min(self.heaps[self.heap]);
+            }
+            KeyCode::Char(' ') | KeyCode::Enter => {
+                self.apply(Take {
+                    heap: self.heap,
+                    stones: self.stones,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    /// Work out the bot's take from the heaps the human left, and make it once the human
+    /// has seen their own take land.
+    fn tick(&mut self) -> bool {
+        if self.outcome.is_some() || self.turn != Player::Bot {
+            return false;
+        }
+        let (heaps, opponent) = (self.heaps.clone(), self.opponent);
+        let thinking = self
+            .thinking
+            .get_or_insert_with(|| bot::Thinking::start(move || choose_take(&heaps, opponent)));
+        let answer = match thinking.answer() {
+            bot::Answer::Pending => return false,
+            bot::Answer::Ready(answer) => answer,
+            bot::Answer::Failed => choose_take(&self.heaps, bot::Opponent::Random),
+        };
+        self.thinking = None;
+        if let Some(take) = answer {
+            self.bot_move(take);
+        }
+        true
+    }
+
+    fn apply_settings(&mut self, settings: &Settings) {
+        self.colors = settings.colors();
+        self.opponent = settings.opponent(self.opponent);
+    }
+
+    fn restart(&mut self) {
+        self.rematch();
+    }
+
+    fn help(&self) -> Help {
+        Help {
+            rules: "Take turns taking any number of stones from one heap. Whoever takes the last \
+                    stone wins."
+                .into(),
+            keys: vec![
+                ("↑/↓", "choose a heap"),
+                ("←/→ 1-9", "how many stones"),
+                ("Space/Enter", "take them"),
+                ("b", "switch bot"),
+                ("r", "rematch"),
+            ],
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let area = frame.area();
+        let (rows, cols) = self.dimensions();
+        // the label, two columns for each stone, then the count
+        let heaps_width = 11 + cols as u16 * 2 + 3;
+        let width = heaps_width.max(66);
+        let height = rows as u16 * 2 + 4;
+        if area.width < width || area.height < height {
+            grid::render_too_small(frame, area, width, height);
+            return;
+        }
+
+        let area = center(
+            area,
+            Constraint::Percentage(100),
+            Constraint::Length(height),
+        );
+        let [title_area, heaps_area, help_area] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Length(rows as u16 * 2 - 1),
+            Constraint::Length(1),
+        ])
+        .spacing(1)
+        .areas(area);
+        frame.render_widget(Paragraph::new(self.render_title()).centered(), title_area);
+        // a blank line between the heaps keeps them apart
+        let heaps: Text = self
+            .render_heaps()
+            .lines
+            .into_iter()
+            .flat_map(|line| [line, Line::default()])
+            .take(rows * 2 - 1)
+            .collect();
+        let heaps_area = center(
+            heaps_area,
+            Constraint::Length(heaps_width),
+            Constraint::Percentage(100),
+        );
+        frame.render_widget(Paragraph::new(heaps), heaps_area);
+        let help = "↑/↓ heap · ←/→ or 1-9 stones · Space take · b bot · r rematch";
+        frame.render_widget(Paragraph::new(help).centered().dark_gray(), help_area);
+    }
+}
@@ -0,0 +1,456 @@
+//! Nonograms: fill in the cells of a grid so each row and column has the runs of filled cells
+//! its clue counts, revealing a picture.
+//!
+//! Every fill is checked against the picture, and a wrong one is marked as a mistake. A set
+//! of puzzles comes bundled, and others can be loaded from files drawing their pictures.
+
+use std::ops::RangeInclusive;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Flex, Layout},
+    style::{Color, Style, Stylize, palette::tailwind},
+    text::Line,
+    widgets::Paragraph,
+};
+
+use crate::{
+    game::{Game, Help, Status},
+    grid::{self, Cursor},
+    settings::Settings,
+    ui::palette::{PALETTES, TableColors},
+};
+
+/// The puzzles played when no puzzle file is given, drawn as [`parse_puzzles`] reads them.
+const BUNDLED_PUZZLES: &str = include_str!("nonograms.txt");
+
+/// The smallest and largest number of rows or columns of a puzzle.
+const PUZZLE_SIZES: RangeInclusive<usize> = 1..=30;
+
+/// Columns of the terminal each cell and clue number takes.
+const CELL_WIDTH: u16 = 3;
+
+/// Rows and columns of the blocks shaded alike, to help count cells.
+const BLOCK: usize = 5;
+
+/// The lengths of the runs of filled cells in `cells`, in order.
+fn runs(cells: impl IntoIterator<Item = bool>) -> Vec<usize> {
+    let mut runs = Vec::new();
+    let mut run = 0;
+    for filled in cells.into_iter().chain([false]) {
+        match filled {
+            true => run += 1,
+            false if run > 0 => {
+                runs.push(run);
+                run = 0;
+            }
+            false => {}
+        }
+    }
+    runs
+}
+
+/// A puzzle, as the picture it hides.
+#[derive(Debug, Clone)]
+pub struct Puzzle {
+    /// The name given on a `Title:` line after the picture, if there is one.
+    name: Option<String>,
+    picture: Vec<Vec<bool>>,
+}
+
+impl Puzzle {
+    /// Read the picture drawn by `lines`, `#` for a filled cell and `.` or `-` for an empty one.
+    fn parse(lines: &[&str]) -> Result<Self, String> {
+        let picture: Vec<Vec<bool>> = lines
+            .iter()
+            .map(|line| line.chars().map(|c| c == '#').collect())
+            .collect();
+        let (rows, cols) = (picture.len(), picture[0].len());
+        if picture.iter().any(|row| row.len() != cols) {
+            return Err(format!(
+                "expected every row to be as long as the first, of {cols} cells"
+            ));
+        }
+        if !PUZZLE_SIZES.contains(&rows) || !PUZZLE_SIZES.contains(&cols) {
+            return Err(format!(
+                "expected both sides from {} to {}, found {rows}x{cols}",
+                PUZZLE_SIZES.start(),
+                PUZZLE_SIZES.end()
+            ));
+        }
+        if !picture.iter().flatten().any(|&filled| filled) {
+            return Err("expected at least one filled cell".into());
+        }
+        Ok(Self {
+            name: None,
+            picture,
+        })
+    }
+
+    fn rows(&self) -> usize {
+        self.picture.len()
+    }
+
+    fn cols(&self) -> usize {
+        self.picture[0].len()
+    }
+
+    fn row_clue(&self, row: usize) -> Vec<usize> {
+        runs(self.picture[row].iter().copied())
+    }
+
+    fn col_clue(&self, col: usize) -> Vec<usize> {
+        runs(self.picture.iter().map(|row| row[col]))
+    }
+}
+
+/// Read the puzzles of a file: each puzzle is a block of lines drawing its picture with `#`
+/// for filled cells and `.` or `-` for empty ones, and any other line, blank or not, lies
+/// between puzzles.
+///
+/// A `Title:` line after a puzzle names it; comments and other lines are skipped.
+pub fn parse_puzzles(text: &str) -> Result<Vec<Puzzle>, String> {
+    let is_picture_line = |line: &str| !line.is_empty() && line.chars().all(|c| "#.-".contains(c));
+    let mut puzzles: Vec<Puzzle> = Vec::new();
+    let mut lines = Vec::new();
+    for line in text.lines().map(str::trim).chain([""]) {
+        if is_picture_line(line) {
+            lines.push(line);
+            continue;
+        }
+        if !lines.is_empty() {
+            let puzzle = Puzzle::parse(&lines)
+                .map_err(|err| format!("puzzle {}: {err}", puzzles.len() + 1))?;
+            puzzles.push(puzzle);
+            lines.clear();
+        }
+        if let Some(title) = line.strip_prefix("Title:")
+            && let Some(puzzle) = puzzles.last_mut()
+        {
+            puzzle.name = Some(title.trim().to_string());
+        }
+    }
+    if puzzles.is_empty() {
+        return Err("no puzzles found".into());
+    }
+    Ok(puzzles)
+}
+
+/// The bundled puzzles.
+pub fn bundled_puzzles() -> Vec<Puzzle> {
+    parse_puzzles(BUNDLED_PUZZLES).expect("the bundled puzzles are valid")
+}
+
+/// What the player has marked a cell as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mark {
+    Blank,
+    Filled,
+    /// Crossed out as empty, which is left unchecked.
+    Crossed,
+    /// Filled by mistake, so known to be empty.
+    Mistake,
+}
+
+/// A game of nonograms, played through a set of puzzles.
+pub struct Nonogram {
+    puzzles: Vec<Puzzle>,
+    /// The puzzle being played, as an index into `puzzles`.
+    current: usize,
+    marks: Vec<Vec<Mark>>,
+    mistakes: usize,
+    cursor: Cursor,
+    colors: TableColors,
+}
+
+impl Nonogram {
+    /// Play through `puzzles` from the first.
+    pub fn new(puzzles: Vec<Puzzle>) -> Self {
+        let (rows, cols) = (puzzles[0].rows(), puzzles[0].cols());
+        Self {
+            puzzles,
+            current: 0,
+            marks: vec![vec![Mark::Blank; cols]; rows],
+            mistakes: 0,
+            cursor: Cursor::new(rows, cols),
+            colors: TableColors::new(&PALETTES[0]),
+        }
+    }
+
+    fn puzzle(&self) -> &Puzzle {
+        &self.puzzles[self.current]
+    }
+
+    /// Start puzzle `index` from a blank grid.
+    fn load(&mut self, index: usize) {
+        self.current = index;
+        let (rows, cols) = (self.puzzle().rows(), self.puzzle().cols());
+        self.marks = vec![vec![Mark::Blank; cols]; rows];
+        self.mistakes = 0;
+        self.cursor = Cursor::new(rows, cols);
+    }
+
+    /// Cross out a blank cell, or take the cross back.
+    fn cross(&mut self, [row, col]: [usize; 2]) {
+        let mark = &mut self.marks[row][col];
+        *mark = match *mark {
+            Mark::Blank => Mark::Crossed,
+            Mark::Crossed => Mark::Blank,
+            mark => mark,
+        };
+    }
+
+    /// Whether every filled cell of the line with `cells` has been found.
+    fn line_done(&self, cells: impl IntoIterator<Item = [usize; 2]>) -> bool {
+        let picture = &self.puzzle().picture;
+        cells
+            .into_iter()
+            .all(|[row, col]| !picture[row][col] || self.marks[row][col] == Mark::Filled)
+    }
+
+    /// Rows of column clues above the grid, and columns of row clues left of it.
+    fn clue_depths(&self) -> (usize, usize) {
+        let puzzle = self.puzzle();
+        let top = (0..puzzle.cols())
+            .map(|col| puzzle.col_clue(col).len())
+            .max()
+            .unwrap_or(0);
+        let left = (0..puzzle.rows())
+            .map(|row| puzzle.row_clue(row).len())
+            .max()
+            .unwrap_or(0);
+        (top.max(1), left.max(1))
+    }
+
+    /// The number at `index` of a clue drawn `depth` numbers deep, aligned to the grid; an
+    /// empty line's clue is a lone `0`.
+    fn clue_number(clue: &[usize], depth: usize, index: usize) -> String {
+        let shown = clue.len().max(1);
+        match index.checked_sub(depth - shown) {
+            Some(i) => format!("{:>2} ", clue.get(i).copied().unwrap_or(0)),
+            None => String::new(),
+        }
+    }
+
+    /// A square of the whole picture, clues included, `top` rows of clues above the grid and
+    /// `left` columns of them to its left.
+    fn render_square(
+        &self,
+        [row, col]: [usize; 2],
+        (top, left): (usize, usize),
+    ) -> (String, Style) {
+        let puzzle = self.puzzle();
+        let clue_style = |on_cursor: bool, done: bool| {
+            let style = Style::default().bg(match on_cursor {
+                true => tailwind::SLATE.c700,
+                false => self.colors.buffer_bg,
+            });
+            match done {
+                true => style.fg(tailwind::SLATE.c500),
+                false => style.fg(self.colors.row_fg),
+            }
+        };
+        match (row.checked_sub(top), col.checked_sub(left)) {
+            (Some(row), Some(col)) => self.render_cell([row, col]),
+            (None, Some(col)) => {
+                let done = self.line_done((0..puzzle.rows()).map(|row| [row, col]));
+                let text = Self::clue_number(&puzzle.col_clue(col), top, row);
+                (text, clue_style(col == self.cursor.col, done))
+            }
+            (Some(row), None) => {
+                let done = self.line_done((0..puzzle.cols()).map(|col| [row, col]));
+                let text = Self::clue_number(&puzzle.row_clue(row), left, col);
+                (text, clue_style(row == self.cursor.row, done))
+            }
+            (None, None) => (String::new(), Style::default().bg(self.colors.buffer_bg)),
+        }
+    }
+
+    fn render_title(&self) -> Line<'static> {
+        let puzzle = self.puzzle();
+        let name = match &puzzle.name {
+            Some(name) => format!("{} {name}", self.current + 1),
+            None => format!("Puzzle {}", self.current + 1),
+        };
+        let status = match self.status() {
+            Status::Won if self.current + 1 == self.puzzles.len() => {
+                "Every puzzle solved!".fg(self.colors.selected_cell_style_fg)
+            }
+            Status::Won => "Solved! (n: next puzzle)".fg(self.colors.selected_cell_style_fg),
+            _ => "Space: fill · x: cross".fg(self.colors.selected_cell_style_fg),
+        };
+        let mistakes = match self.mistakes {
+            0 => "Mistakes 0".into(),
+            n => format!("Mistakes {n}").fg(tailwind::RED.c400),
+        };
+        Line::from(vec![
+            format!(
+                "Nonogram · {name} of {} · {}x{} · ",
+                self.puzzles.len(),
+                puzzle.rows(),
+                puzzle.cols()
+            )
+            .into(),
+            mistakes,
+            " · ".dark_gray(),
+            status,
+        ])
+    }
+}
+
+impl Game for Nonogram {
+    /// The cell to fill.
+    type Move = [usize; 2];
+
+    fn dimensions(&self) -> (usize, usize) {
+        (self.puzzle().rows(), self.puzzle().cols())
+    }
+
+    /// Any cell not yet filled or known to be empty may be filled, crossed out or not.
+    fn legal_moves(&self) -> Vec<[usize; 2]> {
+        if self.status() != Status::Playing {
+            return Vec::new();
+        }
+        let (rows, cols) = self.dimensions();
+        (0..rows)
+            .flat_map(|row| (0..cols).map(move |col| [row, col]))
+            .filter(|&[row, col]| matches!(self.marks[row][col], Mark::Blank | Mark::Crossed))
+            .collect()
+    }
+
+    /// Fill a cell, which is marked as a mistake if the picture leaves it empty.
+    fn apply(&mut self, cell: [usize; 2]) -> bool {
+        if !self.legal_moves().contains(&cell) {
+            return false;
+        }
+        let [row, col] = cell;
+        self.marks[row][col] = match self.puzzle().picture[row][col] {
+            true => Mark::Filled,
+            false => {
+                self.mistakes += 1;
+                Mark::Mistake
+            }
+        };
+        true
+    }
+
+    /// A puzzle is solved once every filled cell of its picture is found.
+    fn status(&self) -> Status {
+        let (rows, cols) = self.dimensions();
+        match self.line_done((0..rows).flat_map(|row| (0..cols).map(move |col| [row, col]))) {
+            true => Status::Won,
+            false => Status::Playing,
+        }
+    }
+
+    /// A cell of the grid, with the cursor drawn as brackets around it.
+    fn render_cell(&self, [row, col]: [usize; 2]) -> (String, Style) {
+        let solved = self.status() == Status::Won;
+        let shade: Color = match (row / BLOCK + col / BLOCK) % 2 {
+            0 => tailwind::SLATE.c800,
+            _ => tailwind::SLATE.c900,
+        };
+        let (mark, style) = match self.marks[row][col] {
+            Mark::Filled => (' ', Style::default().bg(tailwind::SKY.c400)),
+            Mark::Mistake if !solved => ('×', Style::default().bg(shade).fg(tailwind::RED.c400)),
+            Mark::Crossed if !solved => ('×', Style::default().bg(shade).fg(tailwind::SLATE.c500)),
+            _ => (' ', Style::default().bg(shade)),
+        };
+        match [row, col] == self.cursor.cell() && !solved {
+            true => (
+                format!("[{mark}]"),
+                style.fg(self.colors.selected_cell_style_fg).bold(),
+            ),
+            false => (format!(" {mark} "), style),
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        let last = self.puzzles.len() - 1;
+        match key.code {
+            KeyCode::Char('r') => self.load(self.current),
+            KeyCode::Char('n') if self.current < last => self.load(self.current + 1),
+            KeyCode::Char('p') if self.current > 0 => self.load(self.current - 1),
+            KeyCode::Char(' ') | KeyCode::Enter
+                if self.status() == Status::Won && self.current < last =>
+            {
+                self.load(self.current + 1);
+            }
+            // the grid is frozen once the picture is found
+            _ if self.status() != Status::Playing => {}
+            KeyCode::Char(' ' | 'f') | KeyCode::Enter => {
+                self.apply(self.cursor.cell());
+            }
+            KeyCode::Char('x') => self.cross(self.cursor.cell()),
+            code => {
+                self.cursor.move_with(code);
+            }
+        }
+    }
+
+    /// `p` goes back a puzzle rather than opening the pause menu.
+    fn uses_key(&self, code: KeyCode) -> bool {
+        code == KeyCode::Char('p')
+    }
+
+    fn apply_settings(&mut self, settings: &Settings) {
+        self.colors = settings.colors();
+    }
+
+    fn restart(&mut self) {
+        self.load(self.current);
+    }
+
+    fn help(&self) -> Help {
+        Help {
+            rules: "Fill in cells so each row and column has the runs of filled cells its clue \
+                    counts. Every wrong fill is marked as a mistake."
+                .into(),
+            keys: vec![
+                ("←↑↓→/wasd", "move the cursor"),
+                ("Space/Enter/f", "fill a cell"),
+                ("x", "cross a cell out"),
+                ("n/p", "next or previous puzzle"),
+                ("r", "restart"),
+            ],
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let area = frame.area();
+        let (rows, cols) = self.dimensions();
+        let (top, left) = self.clue_depths();
+        let (squares_down, squares_across) = (top + rows, left + cols);
+        let width = (squares_across as u16 * CELL_WIDTH).max(80);
+        let height = squares_down as u16 + 2;
+        if area.width < width || area.height < height {
+            grid::render_too_small(frame, area, width, height);
+            return;
+        }
+
+        let [title_area, grid_area, help_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+        frame.render_widget(Paragraph::new(self.render_title()).centered(), title_area);
+        let [grid_area] =
+            Layout::horizontal([Constraint::Length(squares_across as u16 * CELL_WIDTH)])
+                .flex(Flex::Center)
+                .areas(grid_area);
+        let [grid_area] = Layout::vertical([Constraint::Length(squares_down as u16)])
+            .flex(Flex::Center)
+            .areas(grid_area);
+        grid::render_pixels(
+            frame,
+            grid_area,
+            (squares_down, squares_across),
+            CELL_WIDTH,
+            |square| self.render_square(square, (top, left)),
+        );
+        let help = "←↑↓→/wasd move · Space fill · x cross · r restart · n/p next/previous puzzle";
+        frame.render_widget(Paragraph::new(help).centered().dark_gray(), help_area);
+    }
+}
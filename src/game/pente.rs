@@ -0,0 +1,605 @@
+//! Pente on a 19x19 board against the bot.
+//!
+//! Stones go on the points of the board as in Go, and five or more in a row wins. Bracketing
+//! exactly two stones of the other side between two of your own captures them, and
+//! capturing five pairs wins as well; a stone may still be played between two of the other
+//! side's without being taken. Boards too large for the terminal scroll with the cursor.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use rand::seq::IndexedRandom;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Flex, Layout},
+    style::{Color, Modifier, Style, Stylize, palette::tailwind},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+use crate::{
+    Outcome, Player, bot,
+    game::{Game, Help, Status},
+    grid::{self, Cursor, Viewport},
+    settings::Settings,
+    ui::{
+        layout::center,
+        palette::{PALETTES, TableColors},
+    },
+};
+
+/// Points along one side of the board.
+const SIZE: usize = 19;
+
+/// Stones in a row that win.
+const ROW_TO_WIN: usize = 5;
+
+/// Pairs captured that win.
+const PAIRS_TO_WIN: usize = 5;
+
+/// The letters naming the columns, skipping `I` as Go boards do.
+const COLUMNS: &str = "ABCDEFGHJKLMNOPQRST";
+
+/// Columns of the terminal each point takes.
+const POINT_WIDTH: usize = 3;
+
+/// Columns taken by the row numbers on either side of the board.
+const LABEL_WIDTH: usize = 3;
+
+/// How many points along each axis must fit on screen before the board is shown at all.
+const MIN_VISIBLE_POINTS: usize = 7;
+
+/// One direction along each of the four lines through a point.
+const AXES: [[isize; 2]; 4] = [[0, 1], [1, 0], [1, 1], [1, -1]];
+
+type Board = [[Option<Player>; SIZE]; SIZE];
+
+/// The point `steps` points from `[row, col]` along `direction`, if that is on the board.
+fn step([row, col]: [usize; 2], [down, across]: [isize; 2], steps: isize) -> Option<[usize; 2]> {
+    let row = row.checked_add_signed(down * steps)?;
+    let col = col.checked_add_signed(across * steps)?;
+    (row < SIZE && col < SIZE).then_some([row, col])
+}
+
+/// The value of a line of `length` stones with `open` of its two ends free to grow into.
+const fn shape(length: usize, open: usize) -> i32 {
+    match (length, open) {
+        (ROW_TO_WIN.., _) => 100_000,
+        (_, 0) => 0,
+        (4, 2) => 10_000,
+        (4, _) => 1_500,
+        (3, 2) => 1_000,
+        (3, _) => 100,
+        (2, 2) => 50,
+        (2, _) => 10,
+        _ => 1,
+    }
+}
+
+/// The stones on the board and the pairs each side has captured.
+#[derive(Debug, Clone)]
+struct Position {
+    board: Board,
+    /// Pairs captured, the human's first.
+    captures: [usize; 2],
+}
+
+impl Position {
+    const fn new() -> Self {
+        Self {
+            board: [[None; SIZE]; SIZE],
+            captures: [0; 2],
+        }
+    }
+
+    fn stone(&self, cell: Option<[usize; 2]>) -> Option<Player> {
+        cell.and_then(|[row, col]| self.board[row][col])
+    }
+
+    /// The stones `player` would have in a row along `axis` through `cell` with a stone of
+    /// theirs there, and how many ends of that row are free.
+    fn run(&self, cell: [usize; 2], player: Player, axis: [isize; 2]) -> (usize, usize) {
+        let mut length = 1;
+        let mut open = 0;
+        for sign in [1, -1] {
+            let mut steps = 1;
+            while self.stone(step(cell, axis, sign * steps)) == Some(player) {
+                length += 1;
+                steps += 1;
+            }
+            if let Some([row, col]) = step(cell, axis, sign * steps)
+                && self.board[row][col].is_none()
+            {
+                open += 1;
+            }
+        }
+        (length, open)
+    }
+
+    /// The stones of the other side `player` would capture by playing at `cell`.
+    fn captured_by(&self, cell: [usize; 2], player: Player) -> Vec<[usize; 2]> {
+        let mut captured = Vec::new();
+        for axis in AXES {
+            for sign in [1, -1] {
+                let [near, far, end] = [1, 2, 3].map(|steps| step(cell, axis, sign * steps));
+                if self.stone(near) == Some(player.other())
+                    && self.stone(far) == Some(player.other())
+                    && self.stone(end) == Some(player)
+                {
+                    captured.extend([near, far].into_iter().flatten());
+                }
+            }
+        }
+        captured
+    }
+
+    /// Whether a stone of `player` at `cell` would win, by a row of five or the fifth pair.
+    fn wins(&self, cell: [usize; 2], player: Player) -> bool {
+        let pairs = self.captures[index(player)] + self.captured_by(cell, player).len() / 2;
+        pairs >= PAIRS_TO_WIN
+            || AXES
+                .iter()
+                .any(|&axis| self.run(cell, player, axis).0 >= ROW_TO_WIN)
+    }
+
+    /// Play a stone of `player` at `cell`, taking any pairs it brackets, which are returned.
+    fn play(&mut self, cell: [usize; 2], player: Player) -> Vec<[usize; 2]> {
+        let captured = self.captured_by(cell, player);
+        for &[row, col] in &captured {
+            self.board[row][col] = None;
+        }
+        self.captures[index(player)] += captured.len() / 2;
+        self.board[cell[0]][cell[1]] = Some(player);
+        captured
+    }
+
+    /// The empty points within two of a stone, or the center of an empty board.
+    fn candidates(&self) -> Vec<[usize; 2]> {
+        let near = |[row, col]: [usize; 2]| {
+            (row.saturating_sub(2)..(row + 3).min(SIZE)).any(|r| {
+                (col.saturating_sub(2)..(col + 3).min(SIZE)).any(|c| self.board[r][c].is_some())
+            })
+        };
+        let candidates: Vec<_> = (0..SIZE)
+            .flat_map(|row| (0..SIZE).map(move |col| [row, col]))
+            .filter(|&[row, col]| self.board[row][col].is_none() && near([row, col]))
+            .collect();
+        match candidates.is_empty() && self.board[SIZE / 2][SIZE / 2].is_none() {
+            true => vec![[SIZE / 2, SIZE / 2]],
+            false => candidates,
+        }
+    }
+
+    /// How many pairs of `player`'s a stone at `cell` would leave open to capture: the stone
+    /// and a neighbour of its own, with the other side on one end and room on the other.
+    fn exposed_pairs(&self, cell: [usize; 2], player: Player) -> usize {
+        let mut exposed = 0;
+        for axis in AXES {
+            for sign in [1, -1] {
+                if self.stone(step(cell, axis, sign)) != Some(player) {
+                    continue;
+                }
+                let [behind, beyond] = [-sign, 2 * sign].map(|steps| step(cell, axis, steps));
+                let empty =
+                    |point: Option<[usize; 2]>| point.is_some() && self.stone(point).is_none();
+                let other = Some(player.other());
+                if (self.stone(behind) == other && empty(beyond))
+                    || (empty(behind) && self.stone(beyond) == other)
+                {
+                    exposed += 1;
+                }
+            }
+        }
+        exposed
+    }
+
+    /// How good a stone of `player` at `cell` looks: the rows it builds, the rows of the other
+    /// side it blocks, and the pairs it takes, saves or exposes.
+    fn appeal(&self, cell: [usize; 2], player: Player) -> i32 {
+        let other = player.other();
+        let mut appeal: i32 = AXES
+            .iter()
+            .map(|&axis| {
+                let (length, open) = self.run(cell, player, axis);
+                let (their_length, their_open) = self.run(cell, other, axis);
+                shape(length, open) + shape(their_length, their_open) * 9 / 10
+            })
+            .sum();
+        let taken = self.captured_by(cell, player).len() / 2;
+        let saved = self.captured_by(cell, other).len() / 2;
+        let progress = self.captures[index(player)] as i32;
+        appeal += taken as i32 * (800 + 400 * progress);
+        appeal += saved as i32 * 700;
+        appeal -= self.exposed_pairs(cell, player) as i32 * 500;
+        appeal
+    }
+}
+
+/// The index of `player`'s count in [`Position::captures`].
+const fn index(player: Player) -> usize {
+    match player {
+        Player::Human => 0,
+        Player::Bot => 1,
+    }
+}
+
+/// The point `opponent` plays for `player`, if any are left.
+///
+/// The hard bot wins when it can, and otherwise plays the most appealing point that leaves
+/// the other side no win on its next stone, which blocks a row of four or breaks it up by
+/// capturing part of it.
+fn choose_move(position: &Position, player: Player, opponent: bot::Opponent) -> Option<[usize; 2]> {
+    let candidates = position.candidates();
+    let mut rng = rand::rng();
+    if opponent == bot::Opponent::Random {
+        return candidates.choose(&mut rng).copied();
+    }
+    if let Some(&cell) = candidates.iter().find(|&&cell| position.wins(cell, player)) {
+        return Some(cell);
+    }
+    let mut ranked: Vec<_> = candidates
+        .into_iter()
+        .map(|cell| (position.appeal(cell, player), cell))
+        .collect();
+    ranked.sort_by_key(|&(appeal, _)| std::cmp::Reverse(appeal));
+    let safe = ranked.iter().map(|&(_, cell)| cell).find(|&cell| {
+        let mut after = position.clone();
+        after.play(cell, player);
+        !after
+            .candidates()
+            .into_iter()
+            .any(|reply| after.wins(reply, player.other()))
+    });
+    // with a win coming either way, block the most appealing point
+    safe.or(ranked.first().map(|&(_, cell)| cell))
+}
+
+/// A game of Pente against the bot.
+pub struct Pente {
+    position: Position,
+    turn: Player,
+    /// Who plays the dark stones and moved first this game.
+    black: Player,
+    opponent: bot::Opponent,
+    outcome: Option<Outcome>,
+    /// The row of five that won the game, if it was won that way.
+    winning_row: Vec<[usize; 2]>,
+    /// The stones the last move captured, marked until the next one.
+    captured: Vec<[usize; 2]>,
+    last_bot_move: Option<[usize; 2]>,
+    /// The bot ranking where its next stone would threaten or block a row.
+    thinking: Option<bot::Thinking<Option<[usize; 2]>>>,
+    cursor: Cursor,
+    viewport: Viewport,
+    colors: TableColors,
+}
+
+impl Pente {
+    pub fn new() -> Self {
+        Self {
+            position: Position::new(),
+            turn: Player::Human,
+            black: Player::Human,
+            opponent: bot::Opponent::Minimax,
+            outcome: None,
+            winning_row: Vec::new(),
+            captured: Vec::new(),
+            last_bot_move: None,
+            thinking: None,
+            cursor: Cursor::new(SIZE, SIZE),
+            viewport: Viewport {
+                row: 0,
+                col: 0,
+                rows: SIZE,
+                cols: SIZE,
+            },
+            colors: TableColors::new(&PALETTES[0]),
+        }
+    }
+
+    /// Start a new game, with the other side taking the dark stones.
+    fn rematch(&mut self) {
+        let black = self.black.other();
+        *self = Self {
+            colors: self.colors,
+            turn: black,
+            black,
+            opponent: self.opponent,
+            ..Self::new()
+        };
+    }
+
+    fn stone_color(&self, player: Player) -> Color {
+        match player == self.black {
+            true => tailwind::SLATE.c950,
+            false => tailwind::SLATE.c50,
+        }
+    }
+
+    /// Play a stone at `cell` for the side to move and hand the turn over, unless it wins.
+    fn play(&mut self, cell: [usize; 2]) {
+        let player = self.turn;
+        let wins = self.position.wins(cell, player);
+        self.captured = self.position.play(cell, player);
+        if wins {
+            self.winning_row = AXES
+                .iter()
+                .filter(|&&axis| self.position.run(cell, player, axis).0 >= ROW_TO_WIN)
+                .flat_map(|&axis| self.row_through(cell, axis))
+                .collect();
+            self.outcome = Some(Outcome::Winner(player));
+        } else if self.position.board.iter().flatten().all(Option::is_some) {
+            self.outcome = Some(Outcome::Draw);
+        } else {
+            self.turn = player.other();
+        }
+    }
+
+    /// The stones in a row along `axis` through `cell` of the same side as it.
+    fn row_through(&self, cell: [usize; 2], axis: [isize; 2]) -> Vec<[usize; 2]> {
+        let player = self.position.stone(Some(cell));
+        let mut row = vec![cell];
+        for sign in [1, -1] {
+            row.extend(
+                (1..)
+                    .map_while(|steps| step(cell, axis, sign * steps))
+                    .take_while(|&point| self.position.stone(Some(point)) == player),
+            );
+        }
+        row
+    }
+
+    /// Place the stone the bot chose, capturing any pair it brackets.
+    fn bot_move(&mut self, cell: [usize; 2]) {
+        self.play(cell);
+        self.last_bot_move = Some(cell);
+    }
+
+    fn render_title(&self) -> Line<'static> {
+        let [human, bot] = self.position.captures;
+        let stone = |player| match player == self.black {
+            true => "●",
+            false => "○",
+        };
+        let versus = format!(
+            "Pente · Pairs You {} {human} : {bot} {} Bot [{}]",
+            stone(Player::Human),
+            stone(Player::Bot),
+            self.opponent.label()
+        );
+        let status = match (self.outcome, self.turn) {
+            (Some(outcome), _) => format!("{} (r: rematch)", outcome.message()).into(),
+            (None, Player::Bot) => "Bot thinking…".fg(self.colors.row_fg),
+            (None, Player::Human) if !self.captured.is_empty() && self.last_bot_move.is_some() => {
+                "The bot captured a pair".fg(self.colors.selected_cell_style_fg)
+            }
+            (None, Player::Human) => "Your turn".fg(self.colors.selected_cell_style_fg),
+        };
+        Line::from(vec![versus.into(), " · ".dark_gray(), status])
+    }
+
+    /// The visible part of the board as lines of text, with coordinates around it.
+    fn render_board(&self) -> Vec<Line<'static>> {
+        let Viewport {
+            row,
+            col,
+            rows,
+            cols,
+        } = self.viewport;
+        let label = Style::default().fg(tailwind::SLATE.c400);
+        let letters: String = COLUMNS
+            .chars()
+            .skip(col)
+            .take(cols)
+            .map(|letter| format!("{letter:^POINT_WIDTH$}"))
+            .collect();
+        let letters = format!("{:LABEL_WIDTH$}{letters}", "");
+        let mut lines = vec![Line::from(Span::styled(letters.clone(), label))];
+        for row in row..row + rows {
+            let number = format!("{:>2} ", SIZE - row);
+            let mut spans = vec![Span::styled(number.clone(), label)];
+            for col in col..col + cols {
+                let (text, style) = self.render_cell([row, col]);
+                spans.push(Span::styled(text, style));
+            }
+            spans.push(Span::styled(format!(" {number}"), label));
+            lines.push(Line::from(spans));
+        }
+        lines.push(Line::from(Span::styled(letters, label)));
+        lines
+    }
+}
+
+impl Game for Pente {
+    /// The point to play.
+    type Move = [usize; 2];
+
+    fn dimensions(&self) -> (usize, usize) {
+        (SIZE, SIZE)
+    }
+
+    fn legal_moves(&self) -> Vec<[usize; 2]> {
+        if self.outcome.is_some() || self.turn != Player::Human {
+            return Vec::new();
+        }
+        (0..SIZE)
+            .flat_map(|row| (0..SIZE).map(move |col| [row, col]))
+            .filter(|&[row, col]| self.position.board[row][col].is_none())
+            .collect()
+    }
+
+    fn apply(&mut self, cell: [usize; 2]) -> bool {
+        if !self.legal_moves().contains(&cell) {
+            return false;
+        }
+        self.last_bot_move = None;
+        self.play(cell);
+        true
+    }
+
+    fn status(&self) -> Status {
+        match self.outcome {
+            None => Status::Playing,
+            Some(Outcome::Winner(Player::Human)) => Status::Won,
+            Some(Outcome::Winner(Player::Bot)) => Status::Lost,
+            Some(Outcome::Draw) => Status::Draw,
+        }
+    }
+
+    fn opponent(&self) -> Option<bot::Opponent> {
+        Some(self.opponent)
+    }
+
+    /// A point of the board with the grid lines leading off it, [`POINT_WIDTH`] columns wide;
+    /// the cursor is drawn as brackets around it and stones just captured as crosses.
+    fn render_cell(&self, cell: [usize; 2]) -> (String, Style) {
+        let [row, col] = cell;
+        let mut style = Style::default()
+            .bg(tailwind::AMBER.c600)
+            .fg(tailwind::AMBER.c950);
+        let glyph = match self.position.board[row][col] {
+            Some(stone) => {
+                style = style.fg(self.stone_color(stone));
+                if self.winning_row.contains(&cell) {
+                    style = style.bg(tailwind::EMERALD.c500);
+                } else if self.last_bot_move == Some(cell) {
+                    style = style.bg(tailwind::AMBER.c500);
+                }
+                '●'
+            }
+            None if self.captured.contains(&cell) => {
+                // the captured stones belonged to whoever did not just move
+                let victim = match self.outcome {
+                    Some(_) => self.turn.other(),
+                    None => self.turn,
+                };
+                style = style.fg(self.stone_color(victim));
+                '×'
+            }
+            None => match (row, col) {
+                (0, 0) => '┌',
+                (0, c) if c == SIZE - 1 => '┐',
+                (r, 0) if r == SIZE - 1 => '└',
+                (r, c) if r == SIZE - 1 && c == SIZE - 1 => '┘',
+                (0, _) => '┬',
+                (r, _) if r == SIZE - 1 => '┴',
+                (_, 0) => '├',
+                (_, c) if c == SIZE - 1 => '┤',
+                _ => '┼',
+            },
+        };
+        // the lines stop at the edges of the board
+        let line = |edge: bool| if edge { ' ' } else { '─' };
+        let (left, right) = (line(col == 0), line(col == SIZE - 1));
+        let text = match cell == self.cursor.cell() && self.outcome.is_none() {
+            true => format!("[{glyph}]"),
+            false => format!("{left}{glyph}{right}"),
+        };
+        (text, style.add_modifier(Modifier::BOLD))
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char('r') => self.rematch(),
+            KeyCode::Char('b') => self.opponent = self.opponent.next(),
+            // the board is frozen once the game is decided
+            _ if self.outcome.is_some() || self.turn != Player::Human => {}
+            KeyCode::Char(' ') | KeyCode::Enter => {
+                self.apply(self.cursor.cell());
+            }
+            code => {
+                self.cursor.move_with(code);
+            }
+        }
+    }
+
+    /// Weigh the bot's answer to the human's stone, and the pairs it captured, and place it
+    /// once chosen.
+    fn tick(&mut self) -> bool {
+        if self.outcome.is_some() || self.turn != Player::Bot {
+            return false;
+        }
+        let (position, opponent) = (self.position.clone(), self.opponent);
+        let thinking = self.thinking.get_or_insert_with(|| {
+            bot::Thinking::start(move || choose_move(&position, Player::Bot, opponent))
+        });
+        let answer = match thinking.answer() {
+            bot::Answer::Pending => return false,
+            bot::Answer::Ready(answer) => answer,
+            bot::Answer::Failed => choose_move(&self.position, Player::Bot, bot::Opponent::Random),
+        };
+        self.thinking = None;
+        if let Some(cell) = answer {
+            self.bot_move(cell);
+        }
+        true
+    }
+
+    fn apply_settings(&mut self, settings: &Settings) {
+        self.colors = settings.colors();
+        self.opponent = settings.opponent(self.opponent);
+    }
+
+    fn restart(&mut self) {
+        self.rematch();
+    }
+
+    fn help(&self) -> Help {
+        Help {
+            rules: "Place stones on the points of the board; five or more in a row wins. \
+                    Bracketing exactly two stones of the other side captures them, and capturing \
+                    five pairs wins as well."
+                .into(),
+            keys: vec![
+                ("←↑↓→/wasd", "move the cursor"),
+                ("Space/Enter", "place a stone"),
+                ("b", "switch bot"),
+                ("r", "rematch"),
+            ],
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let area = frame.area();
+        // the title, the help and the coordinates above and below the board
+        let chrome_height = 4;
+        let view_rows = SIZE.min(usize::from(area.height.saturating_sub(chrome_height)));
+        let view_cols =
+            SIZE.min(usize::from(area.width).saturating_sub(2 * LABEL_WIDTH) / POINT_WIDTH);
+        let min_width = ((2 * LABEL_WIDTH + MIN_VISIBLE_POINTS * POINT_WIDTH) as u16).max(60);
+        let min_height = MIN_VISIBLE_POINTS as u16 + chrome_height;
+        if area.width < min_width
+            || view_rows < MIN_VISIBLE_POINTS
+            || view_cols < MIN_VISIBLE_POINTS
+        {
+            grid::render_too_small(frame, area, min_width, min_height);
+            return;
+        }
+
+        // show as much of the board as fits and scroll over the rest
+        let viewport = &mut self.viewport;
+        viewport.rows = view_rows;
+        viewport.cols = view_cols;
+        viewport.row = Viewport::follow(viewport.row, view_rows, SIZE, self.cursor.row);
+        viewport.col = Viewport::follow(viewport.col, view_cols, SIZE, self.cursor.col);
+
+        let board_width = (2 * LABEL_WIDTH + view_cols * POINT_WIDTH) as u16;
+        let board_height = view_rows as u16 + 2;
+        let area = center(
+            area,
+            Constraint::Fill(1),
+            Constraint::Length(board_height + 2),
+        );
+        let [title_area, board_area, help_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(board_height),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+        frame.render_widget(Paragraph::new(self.render_title()).centered(), title_area);
+        let [board_area] = Layout::horizontal([Constraint::Length(board_width)])
+            .flex(Flex::Center)
+            .areas(board_area);
+        frame.render_widget(Paragraph::new(self.render_board()), board_area);
+        let help = "←↑↓→/wasd move · Space play · b switch bot · r rematch";
+        frame.render_widget(Paragraph::new(help).centered().dark_gray(), help_area);
+    }
+}
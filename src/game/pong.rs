@@ -0,0 +1,459 @@
+//! Pong for two players on one keyboard: `W`/`S` move the left paddle and the arrow keys the
+//! right one, and the first to 11 points wins.
+//!
+//! The ball moves in real time on a fixed step, so its speed doesn't depend on how often the
+//! screen is drawn. Where the terminal reports keys being let go, both players can hold their
+//! keys at once; elsewhere a paddle keeps moving for a moment after each press, which the
+//! terminal repeats while only one key is held.
+
+use std::time::{Duration, Instant};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout},
+    style::{Style, Stylize, palette::tailwind},
+    text::Line,
+    widgets::{Block, Paragraph},
+};
+
+use crate::{
+    game::{Game, Help, Status},
+    grid,
+    settings::Settings,
+    ui::{
+        layout::center,
+        palette::{PALETTES, TableColors},
+    },
+};
+
+/// Rows and columns of the field.
+const FIELD_ROWS: usize = 22;
+const FIELD_COLS: usize = 72;
+
+/// Rows a paddle covers.
+const PADDLE_HEIGHT: f32 = 5.0;
+
+/// How fast a paddle moves, in rows per second.
+const PADDLE_SPEED: f32 = 30.0;
+
+/// How fast the ball crosses the field when served, in columns per second.
+const SERVE_SPEED: f32 = 36.0;
+
+/// How much faster the ball gets with every return, and the fastest it goes.
+const SPEEDUP: f32 = 1.06;
+const MAX_SPEED: f32 = 90.0;
+
+/// The steepest the ball leaves a paddle, in rows per second, when it hits the paddle's end.
+const MAX_SPIN: f32 = 24.0;
+
+/// Points needed to win.
+const WINNING_SCORE: u32 = 11;
+
+/// The time advanced by each step of the ball and paddles.
+const STEP: Duration = Duration::from_micros(16_667);
+
+/// How long the ball waits in the middle before each serve.
+const SERVE_DELAY: Duration = Duration::from_secs(1);
+
+/// How long a paddle keeps moving after a press, in terminals that don't report keys being
+/// let go; about the time a held key takes to repeat.
+const HOLD_TIME: Duration = Duration::from_millis(80);
+
+/// One of the two sides of the field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+impl Side {
+    const fn index(self) -> usize {
+        match self {
+            Self::Left => 0,
+            Self::Right => 1,
+        }
+    }
+
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Left => "Left",
+            Self::Right => "Right",
+        }
+    }
+}
+
+/// A paddle and the keys moving it.
+#[derive(Debug, Clone, Copy)]
+struct Paddle {
+    /// The row of its top end.
+    top: f32,
+    /// When the keys moving it up and down were last pressed, while they are held.
+    up: Option<Instant>,
+    down: Option<Instant>,
+}
+
+/// A game of Pong.
+pub struct Pong {
+    paddles: [Paddle; 2],
+    /// The ball's position in rows and columns, and its velocity in them per second.
+    ball: (f32, f32),
+    velocity: (f32, f32),
+    scores: [u32; 2],
+    /// When the ball is served next, while it waits in the middle.
+    serve_at: Option<Instant>,
+    /// The side the next serve heads towards.
+    serve_to: Side,
+    /// When the last step was taken, while play goes on.
+    last_step: Option<Instant>,
+    paused: bool,
+    /// Whether the terminal has reported a key being let go, so held keys can be trusted
+    /// until it does.
+    releases: bool,
+    colors: TableColors,
+}
+
+impl Pong {
+    pub fn new() -> Self {
+        let middle = (FIELD_ROWS as f32 - PADDLE_HEIGHT) / 2.0;
+        let paddle = Paddle {
+            top: middle,
+            up: None,
+            down: None,
+        };
+        let serve_to = match rand::random_bool(0.5) {
+            true => Side::Left,
+            false => Side::Right,
+        };
+        let mut pong = Self {
+            paddles: [paddle; 2],
+            ball: (0.0, 0.0),
+            velocity: (0.0, 0.0),
+            scores: [0; 2],
+            serve_at: None,
+            serve_to,
+            last_step: None,
+            paused: false,
+            releases: false,
+            colors: TableColors::new(&PALETTES[0]),
+        };
+        pong.reset_ball();
+        pong
+    }
+
+    /// Start a new match, keeping what is known about the terminal.
+    fn restart(&mut self) {
+        *self = Self {
+            colors: self.colors,
+            releases: self.releases,
+            ..Self::new()
+        };
+    }
+
+    /// Put the ball back in the middle to wait for the next serve.
+    fn reset_ball(&mut self) {
+        self.ball = (FIELD_ROWS as f32 / 2.0, FIELD_COLS as f32 / 2.0);
+        self.velocity = (0.0, 0.0);
+        self.serve_at = Some(Instant::now() + SERVE_DELAY);
+    }
+
+    fn serve(&mut self) {
+        let across = match self.serve_to {
+            Side::Left => -SERVE_SPEED,
+            Side::Right => SERVE_SPEED,
+        };
+        let slope = rand::random_range(-0.4..=0.4);
+        self.velocity = (across * slope, across);
+        self.serve_at = None;
+    }
+
+    fn winner(&self) -> Option<Side> {
+        [Side::Left, Side::Right]
+            .into_iter()
+            .find(|side| self.scores[side.index()] >= WINNING_SCORE)
+    }
+
+    /// Whether a key pressed at `at` is still held down.
+    fn held(&self, at: Option<Instant>) -> bool {
+        at.is_some_and(|at| self.releases || at.elapsed() < HOLD_TIME)
+    }
+
+    /// The paddle and direction `code` moves, if it moves one.
+    fn paddle_key(code: KeyCode) -> Option<(Side, bool)> {
+        match code {
+            KeyCode::Char('w' | 'W') => Some((Side::Left, true)),
+            KeyCode::Char('s' | 'S') => Some((Side::Left, false)),
+            KeyCode::Up => Some((Side::Right, true)),
+            KeyCode::Down => Some((Side::Right, false)),
+            _ => None,
+        }
+    }
+
+    /// Move the paddles and the ball on by one [`STEP`], scoring a point when the ball gets
+    /// past a paddle.
+    fn step(&mut self) {
+        let dt = STEP.as_secs_f32();
+        for index in 0..2 {
+            let paddle = self.paddles[index];
+            let direction = match (self.held(paddle.up), self.held(paddle.down)) {
+                (true, false) => -1.0,
+                (false, true) => 1.0,
+                _ => 0.0,
+            };
+            let top = paddle.top + direction * PADDLE_SPEED * dt;
+            self.paddles[index].top = top.clamp(0.0, FIELD_ROWS as f32 - PADDLE_HEIGHT);
+        }
+
+        if let Some(serve_at) = self.serve_at {
+            if Instant::now() >= serve_at {
+                self.serve();
+            }
+            return;
+        }
+        let (mut row, mut col) = self.ball;
+        row += self.velocity.0 * dt;
+        col += self.velocity.1 * dt;
+        // the ball bounces off the top and bottom walls
+        let bottom = FIELD_ROWS as f32 - 1.0;
+        if row < 0.0 {
+            row = -row;
+            self.velocity.0 = -self.velocity.0;
+        } else if row > bottom {
+            row = 2.0 * bottom - row;
+            self.velocity.0 = -self.velocity.0;
+        }
+        // each paddle fills the first or last column, which the ball bounces back from
+        let right = FIELD_COLS as f32 - 1.0;
+        let (side, face) = match self.velocity.1 < 0.0 {
+            true => (Side::Left, 1.0),
+            false => (Side::Right, right - 1.0),
+        };
+        let crossed = match side {
+            Side::Left => self.ball.1 >= face && col < face,
+            Side::Right => self.ball.1 <= face && col > face,
+        };
+        let paddle = self.paddles[side.index()];
+        let centre = row + 0.5;
+        if crossed && (paddle.top..=paddle.top + PADDLE_HEIGHT).contains(&centre) {
+            col = 2.0 * face - col;
+            let speed = (self.velocity.1.abs() * SPEEDUP).min(MAX_SPEED);
+            self.velocity.1 = match side {
+                Side::Left => speed,
+                Side::Right => -speed,
+            };
+            // hitting the ball off centre sends it off at an angle
+            let offset = (centre - paddle.top) / PADDLE_HEIGHT * 2.0 - 1.0;
+            self.velocity.0 = offset * MAX_SPIN;
+        }
+        self.ball = (row, col);
+
+        if col < -1.0 || col > right + 1.0 {
+            let scorer = match side {
+                Side::Left => Side::Right,
+                Side::Right => Side::Left,
+            };
+            self.scores[scorer.index()] += 1;
+            self.serve_to = side;
+            self.reset_ball();
+        }
+    }
+
+    fn render_title(&self) -> Line<'static> {
+        let [left, right] = self.scores;
+        let status = match (self.winner(), self.paused) {
+            (Some(side), _) => {
+                format!("{} wins! (r: rematch)", side.name()).fg(self.colors.selected_cell_style_fg)
+            }
+            (None, true) => "Paused (p: resume)".fg(self.colors.row_fg),
+            (None, false) => format!("First to {WINNING_SCORE}").fg(self.colors.row_fg),
+        };
+        Line::from(vec![
+            "Pong · ".into(),
+            left.to_string().fg(tailwind::BLUE.c400).bold(),
+            " : ".into(),
+            right.to_string().fg(tailwind::RED.c400).bold(),
+            " · ".dark_gray(),
+            status,
+        ])
+    }
+}
+
+impl Game for Pong {
+    /// A paddle and whether it moves up.
+    type Move = (Side, bool);
+
+    fn dimensions(&self) -> (usize, usize) {
+        (FIELD_ROWS, FIELD_COLS)
+    }
+
+    fn legal_moves(&self) -> Vec<(Side, bool)> {
+        if self.winner().is_some() {
+            return Vec::new();
+        }
+        [Side::Left, Side::Right]
+            .into_iter()
+            .flat_map(|side| [(side, true), (side, false)])
+            .collect()
+    }
+
+    /// Nudge a paddle one row, as a single tap of its key does.
+    fn apply(&mut self, (side, up): (Side, bool)) -> bool {
+        if self.winner().is_some() {
+            return false;
+        }
+        let paddle = &mut self.paddles[side.index()];
+        let step = if up { -1.0 } else { 1.0 };
+        paddle.top = (paddle.top + step).clamp(0.0, FIELD_ROWS as f32 - PADDLE_HEIGHT);
+        true
+    }
+
+    /// How the match stands for the left player.
+    fn status(&self) -> Status {
+        match self.winner() {
+            None => Status::Playing,
+            Some(Side::Left) => Status::Won,
+            Some(Side::Right) => Status::Lost,
+        }
+    }
+
+    fn render_cell(&self, [row, col]: [usize; 2]) -> (String, Style) {
+        let style = Style::default().bg(self.colors.buffer_bg);
+        let on_paddle = |side: Side| {
+            let top = self.paddles[side.index()].top.round() as usize;
+            (top..top + PADDLE_HEIGHT as usize).contains(&row)
+        };
+        if col == 0 && on_paddle(Side::Left) {
+            return (String::new(), style.bg(tailwind::BLUE.c400));
+        }
+        if col == FIELD_COLS - 1 && on_paddle(Side::Right) {
+            return (String::new(), style.bg(tailwind::RED.c400));
+        }
+        let (ball_row, ball_col) = self.ball;
+        if row == ball_row.round() as usize && col == ball_col.floor() as usize {
+            return ("●".into(), style.fg(tailwind::SLATE.c50));
+        }
+        if col == FIELD_COLS / 2 && row % 2 == 0 {
+            return ("┊".into(), style.fg(tailwind::SLATE.c600));
+        }
+        (String::new(), style)
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char('r') => self.restart(),
+            _ if self.paused || self.winner().is_some() => {}
+            code => {
+                let Some((side, up)) = Self::paddle_key(code) else {
+                    return;
+                };
+                // a fresh press moves the paddle at once, so even a quick tap counts
+                if key.kind == KeyEventKind::Press {
+                    self.apply((side, up));
+                }
+                let paddle = &mut self.paddles[side.index()];
+                let held = match up {
+                    true => &mut paddle.up,
+                    false => &mut paddle.down,
+                };
+                *held = Some(Instant::now());
+            }
+        }
+    }
+
+    fn wants_key_releases(&self) -> bool {
+        true
+    }
+
+    fn handle_key_release(&mut self, key: KeyEvent) {
+        self.releases = true;
+        if let Some((side, up)) = Self::paddle_key(key.code) {
+            let paddle = &mut self.paddles[side.index()];
+            match up {
+                true => paddle.up = None,
+                false => paddle.down = None,
+            }
+        }
+    }
+
+    /// Take as many fixed steps as have fallen due since the last one.
+    fn tick(&mut self) -> bool {
+        if self.paused || self.winner().is_some() {
+            self.last_step = None;
+            return false;
+        }
+        let now = Instant::now();
+        let mut last = *self.last_step.get_or_insert(now);
+        let mut stepped = false;
+        while now.duration_since(last) >= STEP {
+            self.step();
+            last += STEP;
+            stepped = true;
+            if self.winner().is_some() {
+                break;
+            }
+        }
+        self.last_step = Some(last);
+        stepped
+    }
+
+    /// The field is redrawn at the rate it steps.
+    fn tick_rate(&self) -> Duration {
+        STEP
+    }
+
+    fn apply_settings(&mut self, settings: &Settings) {
+        self.colors = settings.colors();
+    }
+
+    fn pause(&mut self) {
+        self.paused = self.winner().is_none();
+    }
+
+    fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    fn restart(&mut self) {
+        self.restart();
+    }
+
+    fn help(&self) -> Help {
+        Help {
+            rules: "Two players on one keyboard: each keeps the ball from getting past their \
+                    paddle. The first to 11 points wins."
+                .into(),
+            keys: vec![
+                ("W/S", "move the left paddle"),
+                ("↑/↓", "move the right paddle"),
+                ("r", "restart"),
+            ],
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let area = frame.area();
+        let width = FIELD_COLS as u16 + 2;
+        let height = FIELD_ROWS as u16 + 4;
+        if area.width < width || area.height < height {
+            grid::render_too_small(frame, area, width, height);
+            return;
+        }
+
+        let area = center(area, Constraint::Length(width), Constraint::Length(height));
+        let [title_area, field_area, help_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(FIELD_ROWS as u16 + 2),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+        frame.render_widget(Paragraph::new(self.render_title()).centered(), title_area);
+        let block = Block::bordered().border_style(Style::default().fg(self.colors.row_fg));
+        let inner = block.inner(field_area);
+        frame.render_widget(block, field_area);
+        grid::render_pixels(frame, inner, self.dimensions(), 1, |cell| {
+            self.render_cell(cell)
+        });
+        let help = "W/S left paddle · ↑/↓ right paddle · p pause · r restart";
+        frame.render_widget(Paragraph::new(help).centered().dark_gray(), help_area);
+    }
+}
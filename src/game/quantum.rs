@@ -0,0 +1,179 @@
+//! Rules of quantum tic-tac-toe on a 3x3 board.
+//!
+//! Every move places a pair of entangled "spooky" marks in two different cells. Once the
+//! entanglements form a cycle the marks involved collapse into classical ones, with the
+//! opponent of whoever closed the cycle choosing which of its two cells the closing move ends
+//! up in.
+
+use crate::game::board::{Outcome, Player};
+
+/// Cells along one side of the board.
+const SIDE: usize = 3;
+
+/// Every line of three winning the board, as `[row, column]` pairs.
+const LINES: [[[usize; 2]; 3]; 8] = [
+    [[0, 0], [0, 1], [0, 2]],
+    [[1, 0], [1, 1], [1, 2]],
+    [[2, 0], [2, 1], [2, 2]],
+    [[0, 0], [1, 0], [2, 0]],
+    [[0, 1], [1, 1], [2, 1]],
+    [[0, 2], [1, 2], [2, 2]],
+    [[0, 0], [1, 1], [2, 2]],
+    [[0, 2], [1, 1], [2, 0]],
+];
+
+/// A move of quantum tic-tac-toe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct QuantumMove {
+    player: Player,
+    /// The two cells the move is superposed over; both are the same for a classical move.
+    cells: [[usize; 2]; 2],
+    /// The cell the move ended up in, once it has collapsed.
+    collapsed: Option<[usize; 2]>,
+}
+
+impl QuantumMove {
+    /// The cell of the move other than `cell`.
+    fn other_cell(&self, cell: [usize; 2]) -> [usize; 2] {
+        if self.cells[0] == cell {
+            self.cells[1]
+        } else {
+            self.cells[0]
+        }
+    }
+}
+
+/// The state of a game of quantum tic-tac-toe.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Board {
+    /// Every move played so far; a mark's subscript is its index here plus one.
+    moves: Vec<QuantumMove>,
+    /// The move whose cycle is waiting to be collapsed, if any.
+    pending: Option<usize>,
+}
+
+impl Board {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The player and subscript of the classical mark on `cell`, if it holds one.
+    pub fn classical(&self, cell: [usize; 2]) -> Option<(Player, usize)> {
+        self.moves
+            .iter()
+            .position(|mv| mv.collapsed == Some(cell))
+            .map(|i| (self.moves[i].player, i + 1))
+    }
+
+    /// The player and subscript of every spooky mark still superposed on `cell`.
+    pub fn spooky(&self, cell: [usize; 2]) -> Vec<(Player, usize)> {
+        self.moves
+            .iter()
+            .enumerate()
+            .filter(|(_, mv)| mv.collapsed.is_none() && mv.cells.contains(&cell))
+            .map(|(i, mv)| (mv.player, i + 1))
+            .collect()
+    }
+
+    /// The cells not yet holding a classical mark.
+    pub fn free_cells(&self) -> Vec<[usize; 2]> {
+        (0..SIDE)
+            .flat_map(|row| (0..SIDE).map(move |col| [row, col]))
+            .filter(|&cell| self.classical(cell).is_none())
+            .collect()
+    }
+
+    /// The two cells the move closing a cycle may collapse into, while that choice is pending.
+    pub fn collapse_options(&self) -> Option<[[usize; 2]; 2]> {
+        self.pending.map(|i| self.moves[i].cells)
+    }
+
+    /// Whether `from` and `to` are already linked through a chain of spooky marks.
+    fn entangled(&self, from: [usize; 2], to: [usize; 2]) -> bool {
+        let mut seen = vec![from];
+        let mut queue = vec![from];
+        while let Some(cell) = queue.pop() {
+            if cell == to {
+                return true;
+            }
+            for mv in self.moves.iter().filter(|mv| mv.collapsed.is_none()) {
+                if mv.cells.contains(&cell) {
+                    let next = mv.other_cell(cell);
+                    if !seen.contains(&next) {
+                        seen.push(next);
+                        queue.push(next);
+                    }
+                }
+            }
+        }
+        false
+    }
+
+    /// Play a move for `player` superposed over `cells`.
+    ///
+    /// When only one free cell is left the move is classical and both cells must be that one.
+    /// A move closing a cycle leaves its collapse pending, to be settled with
+    /// [`Board::collapse`] by the other player.
+    pub fn play(&mut self, player: Player, cells: [[usize; 2]; 2]) {
+        let [first, second] = cells;
+        let closes_cycle = first != second && self.entangled(first, second);
+        self.moves.push(QuantumMove {
+            player,
+            cells,
+            collapsed: (first == second).then_some(first),
+        });
+        if closes_cycle {
+            self.pending = Some(self.moves.len() - 1);
+        }
+    }
+
+    /// Collapse the pending cycle by sending the move that closed it to `cell`.
+    ///
+    /// Every spooky mark sharing a cell with a mark that has become classical is pushed into its
+    /// other cell, until the whole cycle and everything hanging off it is classical.
+    pub fn collapse(&mut self, cell: [usize; 2]) {
+        let Some(closing) = self.pending.take() else {
+            return;
+        };
+        let mut queue = vec![(closing, cell)];
+        while let Some((i, cell)) = queue.pop() {
+            if self.moves[i].collapsed.is_some() {
+                continue;
+            }
+            self.moves[i].collapsed = Some(cell);
+            for (j, mv) in self.moves.iter().enumerate() {
+                if j != i && mv.collapsed.is_none() && mv.cells.contains(&cell) {
+                    queue.push((j, mv.other_cell(cell)));
+                }
+            }
+        }
+    }
+
+    /// Determine whether the game has been decided.
+    ///
+    /// A collapse can complete lines for both players at once; the line whose newest mark was
+    /// played first then takes the game. Returns `None` while the game is still open.
+    pub fn outcome(&self) -> Option<Outcome> {
+        let completed = |player: Player| {
+            LINES
+                .iter()
+                .filter_map(|line| {
+                    line.iter()
+                        .map(|&cell| match self.classical(cell) {
+                            Some((owner, subscript)) if owner == player => Some(subscript),
+                            _ => None,
+                        })
+                        .collect::<Option<Vec<_>>>()
+                        .and_then(|subscripts| subscripts.into_iter().max())
+                })
+                .min()
+        };
+        match (completed(Player::Human), completed(Player::Bot)) {
+            (Some(human), Some(bot)) if human < bot => Some(Outcome::Winner(Player::Human)),
+            (Some(_), Some(_)) => Some(Outcome::Winner(Player::Bot)),
+            (Some(_), None) => Some(Outcome::Winner(Player::Human)),
+            (None, Some(_)) => Some(Outcome::Winner(Player::Bot)),
+            (None, None) => self.free_cells().is_empty().then_some(Outcome::Draw),
+        }
+    }
+}
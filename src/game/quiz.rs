@@ -0,0 +1,648 @@
+//! A trivia quiz: answer multiple-choice questions against the clock, scored by category.
+//!
+//! Questions come in packs, from a bundled one or from a TOML or JSON file given with
+//! `--pack`. A pack has a `title` and a list of `questions`, each with a `question`, its
+//! `choices`, the `answer` among them and optionally a `category`:
+//!
+//! ```toml
+//! title = "Capitals"
+//!
+//! [[questions]]
+//! category = "Europe"
+//! question = "What is the capital of Portugal?"
+//! choices = ["Lisbon", "Porto", "Madrid"]
+//! answer = "Lisbon"
+//! ```
+//!
+//! A JSON pack is the same as an object, with `questions` an array of objects.
+
+use std::{
+    collections::BTreeMap,
+    time::{Duration, Instant},
+};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use rand::seq::SliceRandom;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style, Stylize, palette::tailwind},
+    text::{Line, Span},
+    widgets::{LineGauge, Paragraph, Wrap},
+};
+use serde::Deserialize;
+
+use crate::{
+    game::{Game, Help, Status},
+    grid,
+    settings::Settings,
+    ui::{
+        layout::center,
+        palette::{PALETTES, TableColors},
+    },
+};
+
+/// The pack played when none is given.
+const BUNDLED_PACK: &str = include_str!("quiz.toml");
+
+/// The most questions asked in one quiz.
+const QUIZ_LENGTH: usize = 10;
+
+/// The fewest and most choices a question may offer, one for each number key.
+const CHOICES: std::ops::RangeInclusive<usize> = 2..=6;
+
+/// How long each question may take to answer.
+const TIME_LIMIT: Duration = Duration::from_secs(20);
+
+/// The category of questions that don't name one.
+const DEFAULT_CATEGORY: &str = "General";
+
+/// A multiple-choice question.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "QuestionFile")]
+pub struct Question {
+    category: String,
+    prompt: String,
+    choices: Vec<String>,
+    /// The right choice, as an index into `choices`.
+    answer: usize,
+}
+
+/// A set of questions to be quizzed on.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "PackFile")]
+pub struct Pack {
+    title: String,
+    questions: Vec<Question>,
+}
+
+/// A pack as a TOML or JSON file holds it, before it is checked.
+#[derive(Deserialize)]
+struct PackFile {
+    title: Option<String>,
+    questions: Vec<Question>,
+}
+
+/// A question as a pack file holds it, with the answer written out among the choices.
+#[derive(Deserialize)]
+struct QuestionFile {
+    category: Option<String>,
+    question: String,
+    choices: Vec<String>,
+    answer: String,
+}
+
+impl TryFrom<PackFile> for Pack {
+    type Error = String;
+
+    fn try_from(file: PackFile) -> Result<Self, String> {
+        if file.questions.is_empty() {
+            return Err("no questions found".into());
+        }
+        Ok(Self {
+            title: file.title.unwrap_or_else(|| "Quiz".into()),
+            questions: file.questions,
+        })
+    }
+}
+
+impl TryFrom<QuestionFile> for Question {
+    type Error = String;
+
+    fn try_from(file: QuestionFile) -> Result<Self, String> {
+        let filled = |key: &str, text: String| match text.trim() {
+            "" => Err(format!("`{key}` is empty")),
+            text => Ok(text.to_string()),
+        };
+        let category = match file.category {
+            Some(category) => filled("category", category)?,
+            None => DEFAULT_CATEGORY.into(),
+        };
+        let prompt = filled("question", file.question)?;
+        let choices: Vec<String> = file
+            .choices
+            .iter()
+            .map(|choice| choice.trim().to_string())
+            .collect();
+        if !CHOICES.contains(&choices.len()) {
+            return Err(format!(
+                "{} choices, expected {} to {}",
+                choices.len(),
+                CHOICES.start(),
+                CHOICES.end()
+            ));
+        }
+        let answer = filled("answer", file.answer)?;
+        let answer = choices
+            .iter()
+            .position(|choice| *choice == answer)
+            .ok_or_else(|| format!("the answer `{answer}` is not one of the choices"))?;
+        Ok(Self {
+            category,
+            prompt,
+            choices,
+            answer,
+        })
+    }
+}
+
+/// Read a pack from the text of a TOML file.
+pub fn parse_toml_pack(text: &str) -> Result<Pack, String> {
+    toml::from_str(text).map_err(|err| match err.span() {
+        Some(span) => format!("{}: {}", position(text, span.start), err.message()),
+        None => err.message().to_string(),
+    })
+}
+
+/// Read a pack from the text of a JSON file.
+pub fn parse_json_pack(text: &str) -> Result<Pack, String> {
+    // serde_json already says where in the text it went wrong
+    serde_json::from_str(text).map_err(|err| err.to_string())
+}
+
+/// The bundled pack.
+pub fn bundled_pack() -> Pack {
+    parse_toml_pack(BUNDLED_PACK).expect("the bundled pack is valid")
+}
+
+/// Where byte `at` of `text` is, as a line and column to report errors at.
+fn position(text: &str, at: usize) -> String {
+    let before = &text[..at];
+    let line = before.matches('\n').count() + 1;
+    let column = before.chars().rev().take_while(|&c| c != '\n').count() + 1;
+    format!("line {line}, column {column}")
+}
+
+/// How a question was answered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Answer {
+    /// The choice picked, as shown.
+    Chosen(usize),
+    /// The time ran out first.
+    TimedOut,
+}
+
+/// A quiz on the questions of a pack.
+pub struct Quiz {
+    pack: Pack,
+    /// The questions of this quiz, as indices into the pack's.
+    asked: Vec<usize>,
+    /// The question being asked, as an index into `asked`; past the end once all have been.
+    current: usize,
+    /// The choices of the current question in the order shown, as indices into its own.
+    shown: Vec<usize>,
+    /// How the current question was answered, once it has been.
+    answer: Option<Answer>,
+    /// Whether each question asked so far was answered right.
+    right: Vec<bool>,
+    /// When the current question was asked, and answered once it has been.
+    asked_at: Instant,
+    answered_at: Option<Instant>,
+    /// The choice picked to answer with, as shown.
+    selected: usize,
+    colors: TableColors,
+}
+
+impl Quiz {
+    /// A quiz on up to [`QUIZ_LENGTH`] random questions of `pack`.
+    pub fn new(pack: Pack) -> Self {
+        let mut asked: Vec<usize> = (0..pack.questions.len()).collect();
+        asked.shuffle(&mut rand::rng());
+        asked.truncate(QUIZ_LENGTH);
+        let mut quiz = Self {
+            pack,
+            asked,
+            current: 0,
+            shown: Vec::new(),
+            answer: None,
+            right: Vec::new(),
+            asked_at: Instant::now(),
+            answered_at: None,
+            selected: 0,
+            colors: TableColors::new(&PALETTES[0]),
+        };
+        quiz.ask();
+        quiz
+    }
+
+    /// Start a new quiz on the same pack.
+    fn restart(&mut self) {
+        let pack = self.pack.clone();
+        *self = Self {
+            colors: self.colors,
+            ..Self::new(pack)
+        };
+    }
+
+    /// Ask the current question, shuffling its choices.
+    fn ask(&mut self) {
+        let Some(question) = self.question() else {
+            return;
+        };
+        let mut shown: Vec<usize> = (0..question.choices.len()).collect();
+        shown.shuffle(&mut rand::rng());
+        self.shown = shown;
+        self.answer = None;
+        self.selected = 0;
+        self.asked_at = Instant::now();
+        self.answered_at = None;
+    }
+
+    /// Move on to the next question once this one is answered.
+    fn next_question(&mut self) {
+        if self.answer.is_some() {
+            self.current += 1;
+            self.ask();
+        }
+    }
+
+    fn question(&self) -> Option<&Question> {
+        let &index = self.asked.get(self.current)?;
+        Some(&self.pack.questions[index])
+    }
+
+    /// Record how the current question was answered.
+    fn answer_with(&mut self, answer: Answer) {
+        let Some(question) = self.question() else {
+            return;
+        };
+        let right = answer == Answer::Chosen(self.shown_answer(question));
+        self.right.push(right);
+        self.answer = Some(answer);
+        self.answered_at = Some(Instant::now());
+    }
+
+    /// Where the right choice of `question` is shown.
+    fn shown_answer(&self, question: &Question) -> usize {
+        self.shown
+            .iter()
+            .position(|&choice| choice == question.answer)
+            .unwrap_or(0)
+    }
+
+    /// The time left to answer the current question, or that was left when it was answered.
+    fn time_left(&self) -> Duration {
+        let now = self.answered_at.unwrap_or_else(Instant::now);
+        TIME_LIMIT.saturating_sub(now - self.asked_at)
+    }
+
+    fn score(&self) -> usize {
+        self.right.iter().filter(|&&right| right).count()
+    }
+
+    /// How many of the questions in each category were asked and answered right.
+    fn category_scores(&self) -> BTreeMap<&str, (usize, usize)> {
+        let mut scores: BTreeMap<&str, (usize, usize)> = BTreeMap::new();
+        for (&index, &right) in self.asked.iter().zip(&self.right) {
+            let score = scores
+                .entry(&self.pack.questions[index].category)
+                .or_default();
+            score.0 += usize::from(right);
+            score.1 += 1;
+        }
+        scores
+    }
+
+    fn render_title(&self) -> Line<'static> {
+        let progress = match self.current < self.asked.len() {
+            true => format!("Question {}/{}", self.current + 1, self.asked.len()),
+            false => format!("{} questions", self.asked.len()),
+        };
+        let status = match (self.status(), self.answer) {
+            (Status::Playing, None) => "Pick an answer".fg(self.colors.selected_cell_style_fg),
+            (Status::Playing, Some(_)) => "Space: next".fg(self.colors.selected_cell_style_fg),
+            (Status::Won, _) => "Well done! (r: new quiz)".fg(self.colors.selected_cell_style_fg),
+            (Status::Lost | Status::Draw, _) => {
+                "Better luck next time (r: new quiz)".fg(Color::Red)
+            }
+        };
+        Line::from(vec![
+            format!(
+                "Quiz · {} · {progress} · Score {}",
+                self.pack.title,
+                self.score()
+            )
+            .into(),
+            " · ".dark_gray(),
+            status,
+        ])
+    }
+
+    /// What became of the answer to the current question.
+    fn render_verdict(&self, question: &Question) -> Line<'static> {
+        let answer = question.choices[question.answer].clone();
+        match self.answer {
+            None => Line::default(),
+            Some(Answer::Chosen(_)) if self.right.last() == Some(&true) => {
+                Line::from("Right!".green().bold())
+            }
+            Some(Answer::Chosen(_)) => Line::from(vec![
+                "Wrong · ".red().bold(),
+                "the answer is ".into(),
+                answer.bold(),
+            ]),
+            Some(Answer::TimedOut) => Line::from(vec![
+                "Out of time · ".red().bold(),
+                "the answer is ".into(),
+                answer.bold(),
+            ]),
+        }
+    }
+
+    /// The score of each category once the quiz is over, with a bar for its share right.
+    fn render_results(&self) -> Vec<Line<'static>> {
+        let mut lines = vec![
+            Line::from(
+                format!("{} of {} right", self.score(), self.asked.len())
+                    .fg(self.colors.selected_cell_style_fg)
+                    .bold(),
+            ),
+            Line::default(),
+        ];
+        let scores = self.category_scores();
+        let width = scores
+            .keys()
+            .map(|category| category.len())
+            .max()
+            .unwrap_or(0);
+        for (category, (right, asked)) in scores {
+            let bar = 20 * right / asked;
+            lines.push(Line::from(vec![
+                format!("{category:<width$}  ").into(),
+                "■".repeat(bar).green(),
+                "■".repeat(20 - bar).fg(tailwind::SLATE.c700),
+                format!("  {right}/{asked}").into(),
+            ]));
+        }
+        lines
+    }
+}
+
+impl Game for Quiz {
+    /// A choice, as shown.
+    type Move = usize;
+
+    /// The choices of the current question, one per row.
+    fn dimensions(&self) -> (usize, usize) {
+        (self.shown.len(), 1)
+    }
+
+    fn legal_moves(&self) -> Vec<usize> {
+        match (self.question(), self.answer) {
+            (Some(_), None) => (0..self.shown.len()).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn apply(&mut self, choice: usize) -> bool {
+        if !self.legal_moves().contains(&choice) {
+            return false;
+        }
+        self.selected = choice;
+        self.answer_with(Answer::Chosen(choice));
+        true
+    }
+
+    /// The quiz is won with at least half of its questions answered right.
+    fn status(&self) -> Status {
+        if self.current < self.asked.len() {
+            Status::Playing
+        } else if 2 * self.score() >= self.asked.len() {
+            Status::Won
+        } else {
+            Status::Lost
+        }
+    }
+
+    /// A choice with its key, marked right or wrong once the question is answered.
+    fn render_cell(&self, [row, _]: [usize; 2]) -> (String, Style) {
+        let Some(question) = self.question() else {
+            return (String::new(), Style::default());
+        };
+        let text = format!(" {}  {} ", row + 1, question.choices[self.shown[row]]);
+        let right = row == self.shown_answer(question);
+        let style = match self.answer {
+            None if row == self.selected => Style::default()
+                .fg(self.colors.selected_cell_style_fg)
+                .add_modifier(Modifier::REVERSED),
+            None => Style::default().fg(self.colors.row_fg),
+            Some(_) if right => Style::default().fg(Color::Green).bold(),
+            Some(Answer::Chosen(chosen)) if chosen == row => Style::default()
+                .fg(Color::Red)
+                .add_modifier(Modifier::CROSSED_OUT),
+            Some(_) => Style::default().fg(tailwind::SLATE.c500),
+        };
+        (text, style)
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        let choices = self.shown.len();
+        match key.code {
+            KeyCode::Char('r') => self.restart(),
+            KeyCode::Char(digit @ '1'..='9') => {
+                self.apply(digit as usize - '1' as usize);
+            }
+            KeyCode::Up | KeyCode::Char('w') if choices > 0 => {
+                self.selected = (self.selected + choices - 1) % choices;
+            }
+            KeyCode::Down | KeyCode::Char('s') if choices > 0 => {
+                self.selected = (self.selected + 1) % choices;
+            }
+            KeyCode::Char(' ') | KeyCode::Enter => match self.answer {
+                None => {
+                    self.apply(self.selected);
+                }
+                Some(_) => self.next_question(),
+            },
+            _ => {}
+        }
+    }
+
+    /// Run the clock down on an unanswered question, which is missed once it runs out.
+    fn tick(&mut self) -> bool {
+        if self.question().is_none() || self.answer.is_some() {
+            return false;
+        }
+        if self.time_left().is_zero() {
+            self.answer_with(Answer::TimedOut);
+        }
+        true
+    }
+
+    fn apply_settings(&mut self, settings: &Settings) {
+        self.colors = settings.colors();
+    }
+
+    fn restart(&mut self) {
+        self.restart();
+    }
+
+    fn help(&self) -> Help {
+        Help {
+            rules: "Answer each multiple-choice question before its time runs out. Scores are \
+                    kept by category."
+                .into(),
+            keys: vec![
+                ("↑/↓", "choose an answer"),
+                ("1-6", "answer at once"),
+                ("Space/Enter", "answer, or go on to the next question"),
+                ("r", "new quiz"),
+            ],
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let area = frame.area();
+        let width = 80;
+        let height = 18;
+        if area.width < width || area.height < height {
+            grid::render_too_small(frame, area, width, height);
+            return;
+        }
+
+        let area = center(area, Constraint::Length(width), Constraint::Length(height));
+        let [title_area, _, body_area, help_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+        frame.render_widget(Paragraph::new(self.render_title()).centered(), title_area);
+        let help = "1-6 or ↑/↓ and Space answer · Space next · r new quiz";
+        frame.render_widget(Paragraph::new(help).centered().dark_gray(), help_area);
+
+        let Some(question) = self.question() else {
+            frame.render_widget(Paragraph::new(self.render_results()), body_area);
+            return;
+        };
+        let [
+            timer_area,
+            category_area,
+            prompt_area,
+            choices_area,
+            verdict_area,
+        ] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(2),
+            Constraint::Length(3),
+            Constraint::Length(CHOICES.end().to_owned() as u16),
+            Constraint::Length(1),
+        ])
+        .areas(body_area);
+        let left = self.time_left();
+        let timer_color = match left.as_secs() {
+            0..5 => Color::Red,
+            5..10 => Color::Yellow,
+            _ => Color::Green,
+        };
+        let ratio = left.as_secs_f64() / TIME_LIMIT.as_secs_f64();
+        let timer = LineGauge::default()
+            .filled_style(Style::default().fg(timer_color))
+            .unfilled_style(Style::default().fg(tailwind::SLATE.c700))
+            .label(format!("{:>2}s ", left.as_secs_f64().ceil()))
+            .ratio(ratio.clamp(0.0, 1.0));
+        frame.render_widget(timer, timer_area);
+        frame.render_widget(
+            Paragraph::new(question.category.clone().fg(tailwind::SLATE.c500).italic()),
+            category_area,
+        );
+        frame.render_widget(
+            Paragraph::new(question.prompt.clone().bold()).wrap(Wrap { trim: true }),
+            prompt_area,
+        );
+        let choices: Vec<Line> = (0..self.shown.len())
+            .map(|row| {
+                let (text, style) = self.render_cell([row, 0]);
+                Line::from(Span::styled(text, style))
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(choices), choices_area);
+        frame.render_widget(Paragraph::new(self.render_verdict(question)), verdict_area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOML_PACK: &str = r#"
+title = "Capitals"
+
+[[questions]]
+category = "Europe"
+question = "What is the capital of Portugal?"
+choices = ["Lisbon", "Porto", "Madrid"]
+answer = "Lisbon"
+
+[[questions]]
+question = "What is the capital of Peru?"
+choices = [" Cusco ", "Lima"]
+answer = "Lima"
+"#;
+
+    const JSON_PACK: &str = r#"{
+  "title": "Capitals",
+  "questions": [
+    {
+      "category": "Europe",
+      "question": "What is the capital of Portugal?",
+      "choices": ["Lisbon", "Porto", "Madrid"],
+      "answer": "Lisbon"
+    },
+    {
+      "question": "What is the capital of Peru?",
+      "choices": [" Cusco ", "Lima"],
+      "answer": "Lima"
+    }
+  ]
+}"#;
+
+    #[test]
+    fn toml_and_json_packs_read_the_same() {
+        let pack = parse_toml_pack(TOML_PACK).unwrap();
+        assert_eq!(parse_json_pack(JSON_PACK).unwrap(), pack);
+        assert_eq!(pack.title, "Capitals");
+        assert_eq!(pack.questions[0].answer, 0);
+        assert_eq!(pack.questions[1].category, DEFAULT_CATEGORY);
+        assert_eq!(pack.questions[1].choices, ["Cusco", "Lima"]);
+    }
+
+    #[test]
+    fn bundled_pack_is_valid() {
+        assert!(!bundled_pack().questions.is_empty());
+    }
+
+    #[test]
+    fn answer_must_be_a_choice() {
+        let json = r#"{"questions": [{"question": "Q?", "choices": ["a", "b"], "answer": "c"}]}"#;
+        let err = parse_json_pack(json).unwrap_err().to_string();
+        assert!(
+            err.contains("the answer `c` is not one of the choices"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn too_few_choices_are_rejected() {
+        let toml = "[[questions]]\nquestion = \"Q?\"\nchoices = [\"a\"]\nanswer = \"a\"\n";
+        let err = parse_toml_pack(toml).unwrap_err().to_string();
+        assert!(err.contains("1 choices, expected 2 to 6"), "{err}");
+    }
+
+    #[test]
+    fn empty_packs_are_rejected() {
+        let err = parse_json_pack(r#"{"questions": []}"#)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("no questions found"), "{err}");
+    }
+
+    #[test]
+    fn json_errors_say_where() {
+        let err = parse_json_pack("{\n  \"questions\": [,]\n}")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("line 2"), "{err}");
+    }
+}
@@ -0,0 +1,477 @@
+//! Reversi, or Othello, on an 8x8 board against the bot.
+//!
+//! Each disc placed must outflank a line of the other side's discs, which flip over to the
+//! mover's. A side with no such move passes; once neither has one, the side with more discs
+//! wins.
+
+use std::time::{Duration, Instant};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use rand::seq::IndexedRandom;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout},
+    style::{Modifier, Style, Stylize, palette::tailwind},
+    text::Line,
+    widgets::{Paragraph, TableState},
+};
+
+use crate::{
+    Outcome, Player, bot,
+    game::{Game, Help, Status},
+    grid::{self, Cursor, Viewport},
+    settings::Settings,
+    ui::{
+        layout::{calculate_layout, cell_size, table_extent},
+        palette::{PALETTES, TableColors},
+    },
+};
+
+/// Cells along one side of the board.
+const SIDE: usize = 8;
+
+/// How many moves ahead the hard bot searches.
+const SEARCH_DEPTH: usize = 4;
+
+/// A score beyond any positional count, for finished games.
+const WIN: i32 = 100_000;
+
+/// How much each cell is worth holding: corners can never be flipped back, while the cells
+/// next to them hand the corner to the other side.
+const WEIGHTS: [[i32; SIDE]; SIDE] = [
+    [100, -20, 10, 5, 5, 10, -20, 100],
+    [-20, -50, -2, -2, -2, -2, -50, -20],
+    [10, -2, -1, -1, -1, -1, -2, 10],
+    [5, -2, -1, -1, -1, -1, -2, 5],
+    [5, -2, -1, -1, -1, -1, -2, 5],
+    [10, -2, -1, -1, -1, -1, -2, 10],
+    [-20, -50, -2, -2, -2, -2, -50, -20],
+    [100, -20, 10, 5, 5, 10, -20, 100],
+];
+
+/// How long a disc takes to flip over.
+const FLIP_DURATION: Duration = Duration::from_millis(450);
+
+const DIRECTIONS: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+type Board = [[Option<Player>; SIDE]; SIDE];
+
+/// The four discs in the middle of the board at the start of a game.
+fn initial_board() -> Board {
+    let mut board = [[None; SIDE]; SIDE];
+    let mid = SIDE / 2;
+    board[mid - 1][mid - 1] = Some(Player::Bot);
+    board[mid][mid] = Some(Player::Bot);
+    board[mid - 1][mid] = Some(Player::Human);
+    board[mid][mid - 1] = Some(Player::Human);
+    board
+}
+
+/// The discs of the other side that `player` placing at `cell` would flip.
+fn flips(board: &Board, player: Player, [row, col]: [usize; 2]) -> Vec<[usize; 2]> {
+    if board[row][col].is_some() {
+        return Vec::new();
+    }
+    let mut flipped = Vec::new();
+    for (dr, dc) in DIRECTIONS {
+        let mut line = Vec::new();
+        let (mut r, mut c) = (row as isize + dr, col as isize + dc);
+        while (0..SIDE as isize).contains(&r) && (0..SIDE as isize).contains(&c) {
+            match board[r as usize][c as usize] {
+                Some(owner) if owner != player => line.push([r as usize, c as usize]),
+                // the line is outflanked only when it ends on one of the mover's discs
+                Some(_) => {
+                    flipped.append(&mut line);
+                    break;
+                }
+                None => break,
+            }
+            r += dr;
+            c += dc;
+        }
+    }
+    flipped
+}
+
+/// Every cell `player` may place a disc on.
+fn moves(board: &Board, player: Player) -> Vec<[usize; 2]> {
+    (0..SIDE)
+        .flat_map(|row| (0..SIDE).map(move |col| [row, col]))
+        .filter(|&cell| !flips(board, player, cell).is_empty())
+        .collect()
+}
+
+/// Place `player`'s disc at `cell` and flip what it outflanks, returning the flipped discs.
+fn place(board: &mut Board, player: Player, cell: [usize; 2]) -> Vec<[usize; 2]> {
+    let flipped = flips(board, player, cell);
+    for &[row, col] in flipped.iter().chain([&cell]) {
+        board[row][col] = Some(player);
+    }
+    flipped
+}
+
+/// How many discs each side holds, the human's first.
+fn count(board: &Board) -> (usize, usize) {
+    let discs = board.iter().flatten().flatten();
+    let human = discs
+        .clone()
+        .filter(|&&owner| owner == Player::Human)
+        .count();
+    (human, discs.count() - human)
+}
+
+/// The positional score of the board for `player`: the weights of the cells they hold, less
+/// those the other side holds.
+fn evaluate(board: &Board, player: Player) -> i32 {
+    let mut score = 0;
+    for (row, cells) in board.iter().enumerate() {
+        for (col, owner) in cells.iter().enumerate() {
+            match owner {
+                Some(owner) if *owner == player => score += WEIGHTS[row][col],
+                Some(_) => score -= WEIGHTS[row][col],
+                None => {}
+            }
+        }
+    }
+    score
+}
+
+/// Negamax with alpha-beta pruning, scoring the board for `player` to move.
+fn negamax(board: &Board, player: Player, depth: usize, mut alpha: i32, beta: i32) -> i32 {
+    let options = moves(board, player);
+    if options.is_empty() {
+        if moves(board, player.other()).is_empty() {
+            let (human, bot) = count(board);
+            let lead = match player {
+                Player::Human => human as i32 - bot as i32,
+                Player::Bot => bot as i32 - human as i32,
+            };
+            return lead.signum() * WIN + lead;
+        }
+        // passing uses up a move of the search too, so it always ends
+        return match depth {
+            0 => evaluate(board, player),
+            _ => -negamax(board, player.other(), depth - 1, -beta, -alpha),
+        };
+    }
+    if depth == 0 {
+        return evaluate(board, player);
+    }
+    let mut best = -i32::MAX;
+    for cell in options {
+        let mut next = *board;
+        place(&mut next, player, cell);
+        let score = -negamax(&next, player.other(), depth - 1, -beta, -alpha);
+        best = best.max(score);
+        alpha = alpha.max(score);
+        if alpha >= beta {
+            break;
+        }
+    }
+    best
+}
+
+/// The cell `opponent` places `player`'s disc on, if they have a move.
+fn choose_move(board: &Board, player: Player, opponent: bot::Opponent) -> Option<[usize; 2]> {
+    let options = moves(board, player);
+    match opponent {
+        bot::Opponent::Random => options.choose(&mut rand::rng()).copied(),
+        bot::Opponent::Heuristic | bot::Opponent::Minimax => {
+            let mut best = None;
+            let mut alpha = -i32::MAX;
+            for cell in options {
+                let mut next = *board;
+                place(&mut next, player, cell);
+                let score = -negamax(&next, player.other(), SEARCH_DEPTH - 1, -i32::MAX, -alpha);
+                if best.is_none() || score > alpha {
+                    alpha = score;
+                    best = Some(cell);
+                }
+            }
+            best
+        }
+    }
+}
+
+/// A game of Reversi against the bot.
+pub struct Reversi {
+    board: Board,
+    turn: Player,
+    /// Who moved first this game; the sides take turns opening.
+    starter: Player,
+    opponent: bot::Opponent,
+    outcome: Option<Outcome>,
+    /// The discs flipped by the last move and when, for the flip animation.
+    flipped: Vec<[usize; 2]>,
+    flipped_at: Instant,
+    last_bot_move: Option<[usize; 2]>,
+    /// The bot's search for its next disc.
+    thinking: Option<bot::Thinking<Option<[usize; 2]>>>,
+    /// Who had to pass last, or why the last pick was rejected.
+    notice: Option<&'static str>,
+    cursor: Cursor,
+    state: TableState,
+    colors: TableColors,
+}
+
+impl Reversi {
+    pub fn new() -> Self {
+        Self {
+            board: initial_board(),
+            turn: Player::Human,
+            starter: Player::Human,
+            opponent: bot::Opponent::Minimax,
+            outcome: None,
+            flipped: Vec::new(),
+            flipped_at: Instant::now(),
+            last_bot_move: None,
+            thinking: None,
+            notice: None,
+            cursor: Cursor::new(SIDE, SIDE),
+            state: TableState::default(),
+            colors: TableColors::new(&PALETTES[0]),
+        }
+    }
+
+    /// Start a new game, with the other side opening.
+    fn rematch(&mut self) {
+        let starter = self.starter.other();
+        *self = Self {
+            colors: self.colors,
+            turn: starter,
+            starter,
+            opponent: self.opponent,
+            ..Self::new()
+        };
+    }
+
+    /// Place a disc for the side to move and hand the turn over; a side left without a move
+    /// passes, and the game ends once neither has one.
+    fn play(&mut self, cell: [usize; 2]) {
+        self.flipped = place(&mut self.board, self.turn, cell);
+        self.flipped_at = Instant::now();
+        self.turn = self.turn.other();
+        if !moves(&self.board, self.turn).is_empty() {
+            return;
+        }
+        if moves(&self.board, self.turn.other()).is_empty() {
+            let (human, bot) = count(&self.board);
+            self.outcome = Some(match human.cmp(&bot) {
+                std::cmp::Ordering::Greater => Outcome::Winner(Player::Human),
+                std::cmp::Ordering::Less => Outcome::Winner(Player::Bot),
+                std::cmp::Ordering::Equal => Outcome::Draw,
+            });
+            return;
+        }
+        self.notice = Some(match self.turn {
+            Player::Human => "You have no move and pass",
+            Player::Bot => "The bot has no move and passes",
+        });
+        self.turn = self.turn.other();
+    }
+
+    /// Place the disc the bot settled on.
+    fn bot_move(&mut self, cell: [usize; 2]) {
+        self.notice = None;
+        self.play(cell);
+        self.last_bot_move = Some(cell);
+    }
+
+    fn render_title(&self) -> Line<'static> {
+        let (human, bot) = count(&self.board);
+        let versus = format!(
+            "Reversi · You ● {human} : {bot} ○ Bot [{}]",
+            self.opponent.label()
+        );
+        let status = match (self.outcome, self.turn) {
+            (Some(outcome), _) => format!("{} (r: rematch)", outcome.message()).into(),
+            (None, Player::Bot) => "Bot thinking…".fg(self.colors.row_fg),
+            (None, Player::Human) if let Some(notice) = self.notice => {
+                notice.fg(self.colors.selected_cell_style_fg)
+            }
+            (None, Player::Human) => "Your turn".fg(self.colors.selected_cell_style_fg),
+        };
+        Line::from(vec![versus.into(), " · ".dark_gray(), status])
+    }
+}
+
+impl Game for Reversi {
+    type Move = [usize; 2];
+
+    fn dimensions(&self) -> (usize, usize) {
+        (SIDE, SIDE)
+    }
+
+    fn legal_moves(&self) -> Vec<[usize; 2]> {
+        if self.outcome.is_some() || self.turn != Player::Human {
+            return Vec::new();
+        }
+        moves(&self.board, Player::Human)
+    }
+
+    fn apply(&mut self, cell: [usize; 2]) -> bool {
+        if !self.legal_moves().contains(&cell) {
+            return false;
+        }
+        self.last_bot_move = None;
+        self.play(cell);
+        true
+    }
+
+    fn status(&self) -> Status {
+        match self.outcome {
+            None => Status::Playing,
+            Some(Outcome::Winner(Player::Human)) => Status::Won,
+            Some(Outcome::Winner(Player::Bot)) => Status::Lost,
+            Some(Outcome::Draw) => Status::Draw,
+        }
+    }
+
+    fn opponent(&self) -> Option<bot::Opponent> {
+        Some(self.opponent)
+    }
+
+    /// A cell of the board: a disc, turning over for a moment after it is flipped, or a dot
+    /// where the human may place one.
+    fn render_cell(&self, cell: [usize; 2]) -> (String, Style) {
+        let [row, col] = cell;
+        let bg = match self.last_bot_move == Some(cell) {
+            true => tailwind::GREEN.c700,
+            false => tailwind::GREEN.c900,
+        };
+        let style = Style::default().bg(bg).add_modifier(Modifier::BOLD);
+        let disc = |owner: Player| match owner {
+            Player::Human => tailwind::SLATE.c950,
+            Player::Bot => tailwind::SLATE.c50,
+        };
+        let Some(owner) = self.board[row][col] else {
+            return match self.legal_moves().contains(&cell) {
+                true => ("·".into(), style.fg(self.colors.selected_cell_style_fg)),
+                false => (String::new(), style),
+            };
+        };
+        // a flipped disc shows its old side, then its edge, before settling on its new side
+        let elapsed = self.flipped_at.elapsed().as_millis() * 3 / FLIP_DURATION.as_millis();
+        match (self.flipped.contains(&cell), elapsed) {
+            (true, 0) => ("●".into(), style.fg(disc(owner.other()))),
+            (true, 1) => ("┃".into(), style.fg(tailwind::SLATE.c500)),
+            _ => ("●".into(), style.fg(disc(owner))),
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char('r') => self.rematch(),
+            KeyCode::Char('b') => self.opponent = self.opponent.next(),
+            // the board is frozen once the game is decided
+            _ if self.outcome.is_some() || self.turn != Player::Human => {}
+            KeyCode::Char(' ') | KeyCode::Enter => {
+                self.notice = None;
+                if !self.apply(self.cursor.cell()) {
+                    self.notice = Some("That doesn't outflank any disc!");
+                }
+            }
+            code => {
+                self.cursor.move_with(code);
+            }
+        }
+    }
+
+    /// Search for the bot's disc once the discs the human flipped have turned over, and
+    /// place it when found.
+    fn tick(&mut self) -> bool {
+        if self.outcome.is_some()
+            || self.turn != Player::Bot
+            || self.flipped_at.elapsed() < FLIP_DURATION
+        {
+            return false;
+        }
+        let (board, opponent) = (self.board, self.opponent);
+        let thinking = self.thinking.get_or_insert_with(|| {
+            bot::Thinking::start(move || choose_move(&board, Player::Bot, opponent))
+        });
+        let answer = match thinking.answer() {
+            bot::Answer::Pending => return false,
+            bot::Answer::Ready(answer) => answer,
+            bot::Answer::Failed => choose_move(&self.board, Player::Bot, bot::Opponent::Random),
+        };
+        self.thinking = None;
+        if let Some(cell) = answer {
+            self.bot_move(cell);
+        }
+        true
+    }
+
+    fn apply_settings(&mut self, settings: &Settings) {
+        self.colors = settings.colors();
+        self.opponent = settings.opponent(self.opponent);
+    }
+
+    fn restart(&mut self) {
+        self.rematch();
+    }
+
+    fn help(&self) -> Help {
+        Help {
+            rules: "Each disc placed must outflank a line of the bot's discs, which flip over to \
+                    yours. A side with no such move passes; once neither has one, more discs \
+                    wins."
+                .into(),
+            keys: vec![
+                ("←↑↓→/wasd", "move the cursor"),
+                ("Space/Enter", "place a disc"),
+                ("b", "switch bot"),
+                ("r", "rematch"),
+            ],
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [area, help_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(frame.area());
+        let help = match self.outcome {
+            Some(_) => "r rematch · b switch bot",
+            None => "←↑↓→/wasd move · Space place · b switch bot · r rematch",
+        };
+        frame.render_widget(Paragraph::new(help).centered().dark_gray(), help_area);
+        // tall cells when the whole board fits, otherwise the compact ones of a large board
+        let (cell_width, cell_height) = match area.height > table_extent(SIDE, 3, 0) {
+            true => cell_size(5),
+            false => cell_size(SIDE),
+        };
+        let table_width = table_extent(SIDE, cell_width, 1);
+        let table_height = table_extent(SIDE, cell_height, 0);
+        let min_width = table_width.max(60);
+        if area.width < min_width || area.height < table_height + 1 {
+            grid::render_too_small(frame, area, min_width, table_height + 1);
+            return;
+        }
+
+        let layout = calculate_layout(area, false, table_width, table_height);
+        let viewport = Viewport {
+            row: 0,
+            col: 0,
+            rows: SIDE,
+            cols: SIDE,
+        };
+        self.state.select(Some(self.cursor.row));
+        self.state.select_column(Some(self.cursor.col));
+        frame.render_widget(Paragraph::new(self.render_title()).centered(), layout.title);
+        let cursor_style = match (self.outcome, self.turn) {
+            (None, Player::Human) => Style::default().add_modifier(Modifier::REVERSED),
+            _ => Style::default(),
+        };
+        let table = grid::table(self, viewport, cell_width, cell_height, &self.colors)
+            .cell_highlight_style(cursor_style);
+        frame.render_stateful_widget(table, layout.table, &mut self.state);
+    }
+}
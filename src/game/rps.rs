@@ -0,0 +1,475 @@
+//! Rock Paper Scissors against the bot, over a best-of series.
+//!
+//! The hard bot learns how you play: it looks back for the throws you made after the same
+//! run of throws as your latest, guesses you'll do what you did most often then, and plays
+//! what beats it.
+
+use std::time::{Duration, Instant};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use rand::seq::IndexedRandom;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout},
+    style::{Modifier, Style, Stylize, palette::tailwind},
+    text::{Line, Span, Text},
+    widgets::Paragraph,
+};
+
+use crate::{
+    Outcome, Player, bot,
+    game::{Game, Help, Status},
+    grid,
+    settings::Settings,
+    ui::{
+        layout::center,
+        palette::{PALETTES, TableColors},
+    },
+};
+
+/// The series lengths `s` steps through, in rounds won by whoever takes most of them.
+const SERIES: [usize; 4] = [3, 5, 7, 9];
+
+/// How many of your latest throws the hard bot matches against the earlier ones, at most.
+const MEMORY: usize = 3;
+
+/// How long each word of "Rock, Paper, Scissors" is shown before the throws are revealed.
+const BEAT: Duration = Duration::from_millis(400);
+
+/// The words counted out before the throws are revealed.
+const COUNT: [&str; 3] = ["Rock…", "Paper…", "Scissors…"];
+
+/// One of the three throws.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Throw {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+impl Throw {
+    const ALL: [Self; 3] = [Self::Rock, Self::Paper, Self::Scissors];
+
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Rock => "Rock",
+            Self::Paper => "Paper",
+            Self::Scissors => "Scissors",
+        }
+    }
+
+    /// The throw beating this one.
+    const fn beaten_by(self) -> Self {
+        match self {
+            Self::Rock => Self::Paper,
+            Self::Paper => Self::Scissors,
+            Self::Scissors => Self::Rock,
+        }
+    }
+
+    /// The hand making the throw, facing right.
+    const fn art(self) -> [&'static str; 6] {
+        match self {
+            Self::Rock => [
+                "    _______       ",
+                "---'   ____)      ",
+                "      (_____)     ",
+                "      (_____)     ",
+                "      (____)      ",
+                "---.__(___)       ",
+            ],
+            Self::Paper => [
+                "    _______       ",
+                "---'   ____)____  ",
+                "          ______) ",
+                "          _______)",
+                "         _______) ",
+                "---.__________)   ",
+            ],
+            Self::Scissors => [
+                "    _______       ",
+                "---'   ____)____  ",
+                "          ______) ",
+                "       __________)",
+                "      (____)      ",
+                "---.__(___)       ",
+            ],
+        }
+    }
+}
+
+/// The hand as `art` draws it facing right, turned to face left.
+fn mirrored(art: [&str; 6]) -> [String; 6] {
+    art.map(|line| {
+        line.chars()
+            .rev()
+            .map(|c| match c {
+                '(' => ')',
+                ')' => '(',
+                '/' => '\\',
+                '\\' => '/',
+                c => c,
+            })
+            .collect()
+    })
+}
+
+/// Who wins a round of `human` against `bot`.
+fn round_outcome(human: Throw, bot: Throw) -> Outcome {
+    if human == bot {
+        Outcome::Draw
+    } else if human.beaten_by() == bot {
+        Outcome::Winner(Player::Bot)
+    } else {
+        Outcome::Winner(Player::Human)
+    }
+}
+
+/// The throw the human is guessed to make next from the throws so far: what most often came
+/// after the longest run of latest throws seen before, if any was.
+fn predict(history: &[Throw]) -> Option<Throw> {
+    (1..=MEMORY.min(history.len())).rev().find_map(|order| {
+        let context = &history[history.len() - order..];
+        let mut counts = [0; 3];
+        for window in history.windows(order + 1) {
+            if &window[..order] == context {
+                let next = Throw::ALL.iter().position(|&t| t == window[order]);
+                counts[next.unwrap_or(0)] += 1;
+            }
+        }
+        let most = *counts.iter().max()?;
+        let likeliest: Vec<Throw> = Throw::ALL
+            .into_iter()
+            .zip(counts)
+            .filter(|&(_, count)| count == most && count > 0)
+            .map(|(throw, _)| throw)
+            .collect();
+        likeliest.choose(&mut rand::rng()).copied()
+    })
+}
+
+/// A round played: both throws, and the throw the bot guessed the human would make.
+#[derive(Debug, Clone, Copy)]
+struct Round {
+    human: Throw,
+    bot: Throw,
+    guess: Option<Throw>,
+}
+
+/// A series of Rock Paper Scissors against the bot.
+pub struct RockPaperScissors {
+    /// Rounds in the series, as an index into [`SERIES`].
+    series: usize,
+    rounds: Vec<Round>,
+    /// Every throw the human has made this session, which the hard bot learns from.
+    history: Vec<Throw>,
+    /// When the throws of the last round were made, while they are being counted out.
+    thrown_at: Option<Instant>,
+    opponent: bot::Opponent,
+    /// The throw picked to make next.
+    selected: usize,
+    colors: TableColors,
+}
+
+impl RockPaperScissors {
+    pub fn new() -> Self {
+        Self {
+            series: 1,
+            rounds: Vec::new(),
+            history: Vec::new(),
+            thrown_at: None,
+            opponent: bot::Opponent::Minimax,
+            selected: 0,
+            colors: TableColors::new(&PALETTES[0]),
+        }
+    }
+
+    /// Start a new series, keeping what the bot has learned.
+    fn rematch(&mut self) {
+        self.rounds.clear();
+        self.thrown_at = None;
+    }
+
+    /// The rounds whose throws have been revealed.
+    fn revealed(&self) -> &[Round] {
+        match self.counting() {
+            Some(_) => &self.rounds[..self.rounds.len() - 1],
+            None => &self.rounds,
+        }
+    }
+
+    fn wins(&self) -> (usize, usize) {
+        let count = |player| {
+            self.revealed()
+                .iter()
+                .filter(|round| round_outcome(round.human, round.bot) == Outcome::Winner(player))
+                .count()
+        };
+        (count(Player::Human), count(Player::Bot))
+    }
+
+    /// The winner of the series, once one side has taken most of its rounds.
+    fn winner(&self) -> Option<Player> {
+        let needed = SERIES[self.series] / 2 + 1;
+        match self.wins() {
+            (human, _) if human >= needed => Some(Player::Human),
+            (_, bot) if bot >= needed => Some(Player::Bot),
+            _ => None,
+        }
+    }
+
+    /// Which word of the count is showing, while the last throws are counted out.
+    fn counting(&self) -> Option<usize> {
+        let elapsed = self.thrown_at?.elapsed();
+        let beat = (elapsed.as_millis() / BEAT.as_millis()) as usize;
+        (beat < COUNT.len()).then_some(beat)
+    }
+
+    fn render_title(&self) -> Line<'static> {
+        let (human, bot) = self.wins();
+        let versus = format!(
+            "Rock Paper Scissors · You {human} : {bot} Bot [{}] · Best of {}",
+            self.opponent.label(),
+            SERIES[self.series]
+        );
+        let status = match (self.counting(), self.winner()) {
+            (Some(_), _) => "Shaking…".fg(self.colors.row_fg),
+            (None, Some(winner)) => {
+                format!("{} (r: rematch)", Outcome::Winner(winner).message()).into()
+            }
+            (None, None) => "Your throw".fg(self.colors.selected_cell_style_fg),
+        };
+        Line::from(vec![versus.into(), " · ".dark_gray(), status])
+    }
+
+    /// Both hands side by side, shaking as they are counted out and then showing their
+    /// throws.
+    fn render_hands(&self) -> Text<'static> {
+        let counting = self.counting();
+        let (human, bot) = match (self.rounds.last(), counting) {
+            (Some(round), None) => (round.human, round.bot),
+            _ => (Throw::Rock, Throw::Rock),
+        };
+        // the fists bob up and down with each word of the count
+        let raised = counting.is_some_and(|beat| beat % 2 == 0);
+        let blank = " ".repeat(Throw::Rock.art()[0].len());
+        let mut left: Vec<String> = human.art().map(String::from).into();
+        let mut right: Vec<String> = mirrored(bot.art()).into();
+        match raised {
+            true => {
+                left.push(blank.clone());
+                right.push(blank);
+            }
+            false => {
+                left.insert(0, blank.clone());
+                right.insert(0, blank);
+            }
+        }
+        let hand = Style::default().fg(self.colors.row_fg);
+        let mut lines: Vec<Line> = left
+            .into_iter()
+            .zip(right)
+            .map(|(left, right)| {
+                Line::from(vec![
+                    Span::styled(left, hand),
+                    "      ".into(),
+                    Span::styled(right, hand),
+                ])
+            })
+            .collect();
+        lines.push(Line::default());
+        lines.push(match (counting, self.rounds.last()) {
+            (Some(beat), _) => Line::from(COUNT[beat].bold()),
+            (None, Some(round)) => {
+                let verdict = match round_outcome(round.human, round.bot) {
+                    Outcome::Winner(Player::Human) => "You take the round!".green(),
+                    Outcome::Winner(Player::Bot) => "The bot takes the round".red(),
+                    Outcome::Draw => "A tie".fg(self.colors.row_fg),
+                };
+                Line::from(vec![
+                    format!("{} against {} · ", round.human.name(), round.bot.name()).into(),
+                    verdict.bold(),
+                ])
+            }
+            (None, None) => Line::from("Pick a throw".fg(self.colors.row_fg)),
+        });
+        // the hard bot's read of the last round, to show what it learned
+        let guess = self
+            .rounds
+            .last()
+            .filter(|_| counting.is_none())
+            .and_then(|round| round.guess);
+        lines.push(match guess {
+            Some(guess) => Line::from(
+                format!("The bot expected {}", guess.name())
+                    .fg(tailwind::SLATE.c500)
+                    .italic(),
+            ),
+            None => Line::default(),
+        });
+        Text::from(lines).centered()
+    }
+
+    /// A mark for each round of the series: won, lost or tied.
+    fn render_rounds(&self) -> Line<'static> {
+        let mut spans: Vec<Span> = self
+            .revealed()
+            .iter()
+            .map(|round| match round_outcome(round.human, round.bot) {
+                Outcome::Winner(Player::Human) => "● ".green(),
+                Outcome::Winner(Player::Bot) => "● ".red(),
+                Outcome::Draw => "○ ".fg(self.colors.row_fg),
+            })
+            .collect();
+        spans.insert(0, "Rounds ".dark_gray());
+        Line::from(spans).centered()
+    }
+}
+
+impl Game for RockPaperScissors {
+    type Move = Throw;
+
+    /// One row of the three throws to pick from.
+    fn dimensions(&self) -> (usize, usize) {
+        (1, Throw::ALL.len())
+    }
+
+    fn legal_moves(&self) -> Vec<Throw> {
+        if self.winner().is_some() || self.counting().is_some() {
+            return Vec::new();
+        }
+        Throw::ALL.to_vec()
+    }
+
+    /// Throw, with the bot throwing at the same time from what it knew before.
+    fn apply(&mut self, throw: Throw) -> bool {
+        if !self.legal_moves().contains(&throw) {
+            return false;
+        }
+        let (bot, guess) = match self.opponent {
+            bot::Opponent::Random => (*Throw::ALL.choose(&mut rand::rng()).unwrap_or(&throw), None),
+            bot::Opponent::Heuristic | bot::Opponent::Minimax => match predict(&self.history) {
+                Some(guess) => (guess.beaten_by(), Some(guess)),
+                None => (*Throw::ALL.choose(&mut rand::rng()).unwrap_or(&throw), None),
+            },
+        };
+        self.history.push(throw);
+        self.rounds.push(Round {
+            human: throw,
+            bot,
+            guess,
+        });
+        self.thrown_at = Some(Instant::now());
+        true
+    }
+
+    fn status(&self) -> Status {
+        match self.winner() {
+            None => Status::Playing,
+            Some(Player::Human) => Status::Won,
+            Some(Player::Bot) => Status::Lost,
+        }
+    }
+
+    fn opponent(&self) -> Option<bot::Opponent> {
+        Some(self.opponent)
+    }
+
+    /// A throw to pick, numbered by its key.
+    fn render_cell(&self, [_, col]: [usize; 2]) -> (String, Style) {
+        let throw = Throw::ALL[col];
+        let text = format!(" {} {} ", col + 1, throw.name());
+        match col == self.selected && self.legal_moves().contains(&throw) {
+            true => (
+                text,
+                Style::default()
+                    .fg(self.colors.selected_cell_style_fg)
+                    .add_modifier(Modifier::REVERSED | Modifier::BOLD),
+            ),
+            false => (text, Style::default().fg(self.colors.row_fg)),
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char('r') => self.rematch(),
+            KeyCode::Char('b') => self.opponent = self.opponent.next(),
+            KeyCode::Char('s') => {
+                self.series = (self.series + 1) % SERIES.len();
+                self.rematch();
+            }
+            KeyCode::Char(digit @ '1'..='3') => {
+                self.selected = digit as usize - '1' as usize;
+                self.apply(Throw::ALL[self.selected]);
+            }
+            KeyCode::Left | KeyCode::Char('a') => self.selected = (self.selected + 2) % 3,
+            KeyCode::Right | KeyCode::Char('d') => self.selected = (self.selected + 1) % 3,
+            KeyCode::Char(' ') | KeyCode::Enter => {
+                self.apply(Throw::ALL[self.selected]);
+            }
+            _ => {}
+        }
+    }
+
+    /// Redraw as each word of the count comes up, and once the throws are revealed.
+    fn tick(&mut self) -> bool {
+        self.counting().is_some()
+    }
+
+    fn apply_settings(&mut self, settings: &Settings) {
+        self.colors = settings.colors();
+        self.opponent = settings.opponent(self.opponent);
+    }
+
+    fn restart(&mut self) {
+        self.rematch();
+    }
+
+    fn help(&self) -> Help {
+        Help {
+            rules: "Rock blunts scissors, scissors cut paper and paper wraps rock. Win the most \
+                    throws of a best-of series against a bot that learns your habits."
+                .into(),
+            keys: vec![
+                ("←/→", "choose a throw"),
+                ("Space/Enter", "throw it"),
+                ("1-3", "throw rock, paper or scissors"),
+                ("s", "series length"),
+                ("b", "switch bot"),
+                ("r", "rematch"),
+            ],
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let area = frame.area();
+        let width = 80;
+        let height = 18;
+        if area.width < width || area.height < height {
+            grid::render_too_small(frame, area, width, height);
+            return;
+        }
+
+        let area = center(area, Constraint::Length(width), Constraint::Length(height));
+        let [title_area, hands_area, picker_area, rounds_area, help_area] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Length(11),
+            Constraint::Length(2),
+            Constraint::Length(2),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+        frame.render_widget(Paragraph::new(self.render_title()).centered(), title_area);
+        frame.render_widget(Paragraph::new(self.render_hands()), hands_area);
+        let picker: Vec<Span> = (0..Throw::ALL.len())
+            .flat_map(|col| {
+                let (text, style) = self.render_cell([0, col]);
+                [Span::styled(text, style), "   ".into()]
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(Line::from(picker)).centered(), picker_area);
+        frame.render_widget(Paragraph::new(self.render_rounds()), rounds_area);
+        let help = "1-3 or ←/→ and Space throw · s series length · b switch bot · r rematch";
+        frame.render_widget(Paragraph::new(help).centered().dark_gray(), help_area);
+    }
+}
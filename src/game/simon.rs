@@ -0,0 +1,397 @@
+//! Simon: watch the pads light up one after another, then press them back in the same
+//! order. Each round the sequence grows by one more pad, played a little faster as it gets
+//! longer, until a pad is pressed wrong or too late.
+//!
+//! The longest sequence repeated is the streak, and the best streak is kept between runs.
+
+use std::time::{Duration, Instant};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style, Stylize, palette::tailwind},
+    text::Line,
+    widgets::{Block, BorderType, Paragraph},
+};
+
+use crate::{
+    game::{Game, Help, Status, TICK_RATE},
+    grid::{self, Cursor},
+    records,
+    settings::Settings,
+    ui::{
+        layout::center,
+        palette::{PALETTES, TableColors},
+    },
+};
+
+/// The pads, as laid out on the board: each with its name and its colors when dark and lit.
+const PADS: [[(&str, Color, Color); 2]; 2] = [
+    [
+        ("Green", tailwind::GREEN.c900, tailwind::GREEN.c400),
+        ("Red", tailwind::RED.c900, tailwind::RED.c400),
+    ],
+    [
+        ("Yellow", tailwind::YELLOW.c800, tailwind::YELLOW.c300),
+        ("Blue", tailwind::BLUE.c900, tailwind::BLUE.c400),
+    ],
+];
+
+/// The streak that wins the game, the longest sequence the original toy plays.
+const GOAL: usize = 31;
+
+/// How long each pad of the sequence stays lit: shorter from the 6th, 14th and 22nd on.
+const FLASHES: [(usize, Duration); 4] = [
+    (1, Duration::from_millis(420)),
+    (6, Duration::from_millis(320)),
+    (14, Duration::from_millis(220)),
+    (22, Duration::from_millis(170)),
+];
+
+/// How long the pads stay dark between two of the sequence.
+const GAP: Duration = Duration::from_millis(80);
+
+/// The pause before the sequence is played.
+const LEAD: Duration = Duration::from_millis(800);
+
+/// How long a pressed pad stays lit.
+const PRESS_FLASH: Duration = Duration::from_millis(200);
+
+/// How long each press of the sequence may take before it's too late.
+const PRESS_TIME: Duration = Duration::from_secs(3);
+
+/// How often the pads are redrawn while they flash.
+const FRAME_TIME: Duration = Duration::from_millis(20);
+
+/// The record the best streak is kept under between runs.
+const BEST_STREAK_RECORD: &str = "simon-best";
+
+/// How far into the game it is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Phase {
+    /// Playing the sequence back, after a pause from `since`.
+    Showing { since: Instant },
+    /// Waiting for the press of pad `next` of the sequence, until `deadline`.
+    Repeating { next: usize, deadline: Instant },
+    /// Pad `missed` should have been pressed next, and wasn't in time if `late`; the miss
+    /// was at `since`.
+    Missed {
+        missed: usize,
+        late: bool,
+        since: Instant,
+    },
+    /// The whole of the longest sequence was repeated.
+    Won,
+}
+
+/// A game of Simon.
+pub struct Simon {
+    /// The pads to press, as indices into [`PADS`] read row by row.
+    sequence: Vec<usize>,
+    phase: Phase,
+    /// The longest sequence repeated so far.
+    streak: usize,
+    best: usize,
+    /// The pad last pressed, and when.
+    pressed: Option<([usize; 2], Instant)>,
+    cursor: Cursor,
+    colors: TableColors,
+}
+
+impl Simon {
+    pub fn new() -> Self {
+        let mut game = Self {
+            sequence: Vec::new(),
+            phase: Phase::Won,
+            streak: 0,
+            best: records::load(BEST_STREAK_RECORD).unwrap_or(0),
+            pressed: None,
+            cursor: Cursor::new(2, 2),
+            colors: TableColors::new(&PALETTES[0]),
+        };
+        game.extend();
+        game
+    }
+
+    /// Start over with a sequence of one pad, keeping the best streak.
+    fn restart(&mut self) {
+        *self = Self {
+            colors: self.colors,
+            ..Self::new()
+        };
+    }
+
+    /// Add a random pad to the sequence and play it back.
+    fn extend(&mut self) {
+        self.sequence.push(rand::random_range(0..4));
+        self.phase = Phase::Showing {
+            since: Instant::now(),
+        };
+    }
+
+    /// How long each pad of the current sequence is lit while it plays.
+    fn flash(&self) -> Duration {
+        FLASHES
+            .iter()
+            .rev()
+            .find(|&&(from, _)| self.sequence.len() >= from)
+            .map_or(FLASHES[0].1, |&(_, flash)| flash)
+    }
+
+    /// The pad of the sequence being played and whether it is lit, or `None` once all of it
+    /// has been.
+    fn playing(&self, since: Instant) -> Option<(usize, bool)> {
+        let Some(elapsed) = since.elapsed().checked_sub(LEAD) else {
+            return Some((0, false));
+        };
+        let step = self.flash() + GAP;
+        let index = (elapsed.as_millis() / step.as_millis()) as usize;
+        let lit = elapsed.as_millis() % step.as_millis() < self.flash().as_millis();
+        (index < self.sequence.len()).then_some((index, lit))
+    }
+
+    /// The pad lit up now, if any.
+    fn lit(&self) -> Option<usize> {
+        match self.phase {
+            Phase::Showing { since } => match self.playing(since) {
+                Some((index, true)) => Some(self.sequence[index]),
+                _ => None,
+            },
+            // the pad that should have been pressed blinks
+            Phase::Missed { missed, since, .. } => {
+                (since.elapsed().as_millis() / 400 % 2 == 0).then_some(missed)
+            }
+            Phase::Repeating { .. } | Phase::Won => self
+                .pressed
+                .filter(|(_, at)| at.elapsed() < PRESS_FLASH)
+                .map(|([row, col], _)| row * 2 + col),
+        }
+    }
+
+    fn render_title(&self) -> Line<'static> {
+        let status = match self.phase {
+            Phase::Showing { .. } => "Watch…".fg(self.colors.row_fg),
+            Phase::Repeating { next, .. } => format!("Your turn {next}/{}", self.sequence.len())
+                .fg(self.colors.selected_cell_style_fg),
+            Phase::Missed { late: true, .. } => "Too slow! (r: new game)".fg(Color::Red),
+            Phase::Missed { late: false, .. } => "Wrong pad! (r: new game)".fg(Color::Red),
+            Phase::Won => "You win! (r: new game)".fg(self.colors.selected_cell_style_fg),
+        };
+        Line::from(vec![
+            format!("Simon · Streak {} · Best {}", self.streak, self.best).into(),
+            " · ".dark_gray(),
+            status,
+        ])
+    }
+
+    /// Draw the pad at `cell` into `area`, framed when it is the one selected.
+    fn render_pad(&self, frame: &mut Frame, area: Rect, cell: [usize; 2]) {
+        let (label, style) = self.render_cell(cell);
+        let selected = cell == self.cursor.cell() && matches!(self.phase, Phase::Repeating { .. });
+        let block = match selected {
+            true => Block::bordered()
+                .border_type(BorderType::Thick)
+                .border_style(Style::default().fg(Color::White)),
+            false => Block::bordered()
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(style.bg.unwrap_or_default())),
+        }
+        .style(style);
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+        let inner = center(inner, Constraint::Percentage(100), Constraint::Length(1));
+        frame.render_widget(Paragraph::new(label).centered().style(style), inner);
+    }
+}
+
+impl Game for Simon {
+    type Move = [usize; 2];
+
+    fn dimensions(&self) -> (usize, usize) {
+        (2, 2)
+    }
+
+    /// Any pad may be pressed while the sequence is being repeated.
+    fn legal_moves(&self) -> Vec<[usize; 2]> {
+        match self.phase {
+            Phase::Repeating { .. } => vec![[0, 0], [0, 1], [1, 0], [1, 1]],
+            _ => Vec::new(),
+        }
+    }
+
+    fn apply(&mut self, [row, col]: [usize; 2]) -> bool {
+        let Phase::Repeating { next, .. } = self.phase else {
+            return false;
+        };
+        self.pressed = Some(([row, col], Instant::now()));
+        if self.sequence[next] != row * 2 + col {
+            self.phase = Phase::Missed {
+                missed: self.sequence[next],
+                late: false,
+                since: Instant::now(),
+            };
+            return true;
+        }
+        if next + 1 < self.sequence.len() {
+            self.phase = Phase::Repeating {
+                next: next + 1,
+                deadline: Instant::now() + PRESS_TIME,
+            };
+            return true;
+        }
+        self.streak = self.sequence.len();
+        if self.streak > self.best {
+            self.best = self.streak;
+            records::save(BEST_STREAK_RECORD, self.best);
+        }
+        match self.streak >= GOAL {
+            true => self.phase = Phase::Won,
+            false => self.extend(),
+        }
+        true
+    }
+
+    fn status(&self) -> Status {
+        match self.phase {
+            Phase::Showing { .. } | Phase::Repeating { .. } => Status::Playing,
+            Phase::Missed { .. } => Status::Lost,
+            Phase::Won => Status::Won,
+        }
+    }
+
+    /// The pad's name on its color, bright while it is lit.
+    fn render_cell(&self, [row, col]: [usize; 2]) -> (String, Style) {
+        let (name, dark, bright) = PADS[row][col];
+        let label = format!("{} {name}", row * 2 + col + 1);
+        match self.lit() == Some(row * 2 + col) {
+            true => (
+                label,
+                Style::default()
+                    .bg(bright)
+                    .fg(Color::Black)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            false => (label, Style::default().bg(dark).fg(bright)),
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char('r') => self.restart(),
+            KeyCode::Char(digit @ '1'..='4') => {
+                let pad = digit as usize - '1' as usize;
+                self.apply([pad / 2, pad % 2]);
+            }
+            KeyCode::Char(' ') | KeyCode::Enter => {
+                self.apply(self.cursor.cell());
+            }
+            code => {
+                self.cursor.move_with(code);
+            }
+        }
+    }
+
+    /// Play the sequence back, then hand over to the player once it has been, and end the
+    /// game when a press comes too late.
+    fn tick(&mut self) -> bool {
+        match self.phase {
+            Phase::Showing { since } if self.playing(since).is_none() => {
+                self.phase = Phase::Repeating {
+                    next: 0,
+                    deadline: Instant::now() + PRESS_TIME,
+                };
+            }
+            Phase::Repeating { next, deadline } if Instant::now() >= deadline => {
+                self.phase = Phase::Missed {
+                    missed: self.sequence[next],
+                    late: true,
+                    since: Instant::now(),
+                };
+            }
+            _ => {}
+        }
+        true
+    }
+
+    fn tick_rate(&self) -> Duration {
+        match self.phase {
+            Phase::Showing { .. } | Phase::Repeating { .. } => FRAME_TIME,
+            Phase::Missed { .. } | Phase::Won => TICK_RATE,
+        }
+    }
+
+    fn apply_settings(&mut self, settings: &Settings) {
+        self.colors = settings.colors();
+    }
+
+    fn restart(&mut self) {
+        self.restart();
+    }
+
+    fn help(&self) -> Help {
+        Help {
+            rules: "Watch the pads light up, then press them back in the same order. Each round \
+                    adds one more pad, played a little faster, until one is pressed wrong or too \
+                    late."
+                .into(),
+            keys: vec![
+                ("←↑↓→/wasd", "move the cursor"),
+                ("Space/Enter", "press a pad"),
+                ("1-4", "press that pad"),
+                ("r", "new game"),
+            ],
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let area = frame.area();
+        let pad_width = 24;
+        let pad_height = 7;
+        let width = 2 * pad_width + 2;
+        let height = 2 * pad_height + 5;
+        if area.width < width.max(60) || area.height < height {
+            grid::render_too_small(frame, area, width.max(60), height);
+            return;
+        }
+
+        let area = center(
+            area,
+            Constraint::Percentage(100),
+            Constraint::Length(height),
+        );
+        let [title_area, _, board_area, _, help_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(2 * pad_height + 1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+        frame.render_widget(Paragraph::new(self.render_title()).centered(), title_area);
+        let board_area = center(
+            board_area,
+            Constraint::Length(width),
+            Constraint::Percentage(100),
+        );
+        let rows = Layout::vertical([
+            Constraint::Length(pad_height),
+            Constraint::Length(1),
+            Constraint::Length(pad_height),
+        ])
+        .split(board_area);
+        for (row, &row_area) in [rows[0], rows[2]].iter().enumerate() {
+            let cols = Layout::horizontal([
+                Constraint::Length(pad_width),
+                Constraint::Length(2),
+                Constraint::Length(pad_width),
+            ])
+            .split(row_area);
+            for (col, &pad_area) in [cols[0], cols[2]].iter().enumerate() {
+                self.render_pad(frame, pad_area, [row, col]);
+            }
+        }
+        let help = "1-4 or arrows and Space press a pad · r new game";
+        frame.render_widget(Paragraph::new(help).centered().dark_gray(), help_area);
+    }
+}
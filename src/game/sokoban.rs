@@ -0,0 +1,470 @@
+//! Sokoban: push every box onto a goal, one at a time and never pulling.
+//!
+//! A pack of levels comes bundled, and others can be loaded from files in the XSB format
+//! most level collections use.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Flex, Layout},
+    style::{Style, Stylize, palette::tailwind},
+    text::Line,
+    widgets::Paragraph,
+};
+
+use crate::{
+    game::{Game, Help, Status},
+    grid,
+    settings::Settings,
+    ui::palette::{PALETTES, TableColors},
+};
+
+/// The levels played when no level file is given, in the XSB format.
+const BUNDLED_LEVELS: &str = include_str!("sokoban.xsb");
+
+/// Columns of the terminal each square takes, so squares come out roughly square.
+const CELL_WIDTH: u16 = 2;
+
+/// A direction the warehouse keeper walks in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    const ALL: [Self; 4] = [Self::Up, Self::Down, Self::Left, Self::Right];
+
+    /// The square next to `[row, col]` this way, if it is on the level.
+    fn next(self, [row, col]: [usize; 2], rows: usize, cols: usize) -> Option<[usize; 2]> {
+        let [row, col] = match self {
+            Self::Up => [row.checked_sub(1)?, col],
+            Self::Down => [row + 1, col],
+            Self::Left => [row, col.checked_sub(1)?],
+            Self::Right => [row, col + 1],
+        };
+        (row < rows && col < cols).then_some([row, col])
+    }
+}
+
+/// A level as it starts, before any box is pushed.
+#[derive(Debug, Clone)]
+pub struct Level {
+    /// The name given on a `Title:` line after the level, if there is one.
+    name: Option<String>,
+    rows: usize,
+    cols: usize,
+    walls: Vec<Vec<bool>>,
+    goals: Vec<Vec<bool>>,
+    boxes: Vec<Vec<bool>>,
+    keeper: [usize; 2],
+    /// The floor within the walls, which the keeper could walk all of with no boxes about.
+    inside: Vec<Vec<bool>>,
+}
+
+impl Level {
+    /// Read the level drawn by `lines`: `#` walls, `.` goals, `$` boxes, `*` boxes on goals,
+    /// `@` the keeper, `+` the keeper on a goal, and spaces, `-` or `_` bare floor.
+    fn parse(lines: &[&str]) -> Result<Self, String> {
+        let rows = lines.len();
+        let cols = lines
+            .iter()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0);
+        let mut walls = vec![vec![false; cols]; rows];
+        let mut goals = vec![vec![false; cols]; rows];
+        let mut boxes = vec![vec![false; cols]; rows];
+        let mut keepers = Vec::new();
+        for (row, line) in lines.iter().enumerate() {
+            for (col, c) in line.chars().enumerate() {
+                walls[row][col] = c == '#';
+                goals[row][col] = matches!(c, '.' | '*' | '+');
+                boxes[row][col] = matches!(c, '$' | '*');
+                if matches!(c, '@' | '+') {
+                    keepers.push([row, col]);
+                }
+            }
+        }
+        let [keeper] = keepers[..] else {
+            return Err(format!("expected one keeper, found {}", keepers.len()));
+        };
+        let count = |squares: &[Vec<bool>]| squares.iter().flatten().filter(|&&on| on).count();
+        let (box_count, goal_count) = (count(&boxes), count(&goals));
+        if box_count == 0 || box_count != goal_count {
+            return Err(format!(
+                "expected as many boxes as goals, found {box_count} boxes and {goal_count} goals"
+            ));
+        }
+
+        let mut inside = vec![vec![false; cols]; rows];
+        inside[keeper[0]][keeper[1]] = true;
+        let mut reached = vec![keeper];
+        while let Some(square) = reached.pop() {
+            for direction in Direction::ALL {
+                if let Some([row, col]) = direction.next(square, rows, cols)
+                    && !walls[row][col]
+                    && !inside[row][col]
+                {
+                    inside[row][col] = true;
+                    reached.push([row, col]);
+                }
+            }
+        }
+        Ok(Self {
+            name: None,
+            rows,
+            cols,
+            walls,
+            goals,
+            boxes,
+            keeper,
+            inside,
+        })
+    }
+}
+
+/// Read the levels of an XSB file: each level is a block of lines drawn with the characters
+/// [`Level::parse`] reads, and any other line, blank or not, lies between levels.
+///
+/// A `Title:` line after a level names it; comments and other lines are skipped.
+pub fn parse_levels(text: &str) -> Result<Vec<Level>, String> {
+    let is_level_line =
+        |line: &str| line.contains('#') && line.chars().all(|c| "#.$*@+ -_".contains(c));
+    let mut levels: Vec<Level> = Vec::new();
+    let mut lines = Vec::new();
+    for line in text.lines().map(str::trim_end).chain([""]) {
+        if is_level_line(line) {
+            lines.push(line);
+            continue;
+        }
+        if !lines.is_empty() {
+            let level =
+                Level::parse(&lines).map_err(|err| format!("level {}: {err}", levels.len() + 1))?;
+            levels.push(level);
+            lines.clear();
+        }
+        if let Some(title) = line.strip_prefix("Title:")
+            && let Some(level) = levels.last_mut()
+        {
+            level.name = Some(title.trim().to_string());
+        }
+    }
+    if levels.is_empty() {
+        return Err("no levels found".into());
+    }
+    Ok(levels)
+}
+
+/// The bundled levels.
+pub fn bundled_levels() -> Vec<Level> {
+    parse_levels(BUNDLED_LEVELS).expect("the bundled levels are valid")
+}
+
+/// A move made, as it is undone.
+#[derive(Debug, Clone, Copy)]
+struct Undo {
+    /// Where the keeper stood before it.
+    keeper: [usize; 2],
+    /// Where the box it pushed stood and where it ended up, if it pushed one.
+    pushed: Option<([usize; 2], [usize; 2])>,
+}
+
+/// A game of Sokoban, played through a pack of levels.
+pub struct Sokoban {
+    levels: Vec<Level>,
+    /// The level being played, as an index into `levels`.
+    current: usize,
+    boxes: Vec<Vec<bool>>,
+    keeper: [usize; 2],
+    history: Vec<Undo>,
+    /// The level was solved and a move has been taken back since.
+    reopened: bool,
+    moves: usize,
+    pushes: usize,
+    colors: TableColors,
+}
+
+impl Sokoban {
+    /// Play through `levels` from the first.
+    pub fn new(levels: Vec<Level>) -> Self {
+        let level = &levels[0];
+        Self {
+            boxes: level.boxes.clone(),
+            keeper: level.keeper,
+            levels,
+            current: 0,
+            history: Vec::new(),
+            reopened: false,
+            moves: 0,
+            pushes: 0,
+            colors: TableColors::new(&PALETTES[0]),
+        }
+    }
+
+    fn level(&self) -> &Level {
+        &self.levels[self.current]
+    }
+
+    /// Start level `index` from the beginning.
+    fn load(&mut self, index: usize) {
+        self.current = index;
+        let level = &self.levels[index];
+        self.boxes = level.boxes.clone();
+        self.keeper = level.keeper;
+        self.history.clear();
+        self.reopened = false;
+        self.moves = 0;
+        self.pushes = 0;
+    }
+
+    fn undo(&mut self) {
+        let solved = self.status() == Status::Won;
+        let Some(undo) = self.history.pop() else {
+            return;
+        };
+        self.reopened |= solved;
+        self.keeper = undo.keeper;
+        self.moves -= 1;
+        if let Some(([from_row, from_col], [to_row, to_col])) = undo.pushed {
+            self.boxes[to_row][to_col] = false;
+            self.boxes[from_row][from_col] = true;
+            self.pushes -= 1;
+        }
+    }
+
+    /// How many boxes are on goals.
+    fn boxes_home(&self) -> usize {
+        let level = self.level();
+        (0..level.rows)
+            .flat_map(|row| (0..level.cols).map(move |col| [row, col]))
+            .filter(|&[row, col]| self.boxes[row][col] && level.goals[row][col])
+            .count()
+    }
+
+    fn goal_count(&self) -> usize {
+        self.level()
+            .goals
+            .iter()
+            .flatten()
+            .filter(|&&goal| goal)
+            .count()
+    }
+
+    fn render_title(&self) -> Line<'static> {
+        let level = self.level();
+        let name = match &level.name {
+            Some(name) => format!("{} {name}", self.current + 1),
+            None => format!("Level {}", self.current + 1),
+        };
+        let status = match self.status() {
+            Status::Won if self.current + 1 == self.levels.len() => {
+                "Every level solved!".fg(self.colors.selected_cell_style_fg)
+            }
+            Status::Won => "Solved! (n: next level)".fg(self.colors.selected_cell_style_fg),
+            _ => format!("Boxes home {}/{}", self.boxes_home(), self.goal_count())
+                .fg(self.colors.row_fg),
+        };
+        Line::from(vec![
+            format!(
+                "Sokoban · {name} of {} · Moves {} · Pushes {}",
+                self.levels.len(),
+                self.moves,
+                self.pushes
+            )
+            .into(),
+            " · ".dark_gray(),
+            status,
+        ])
+    }
+}
+
+impl Game for Sokoban {
+    type Move = Direction;
+
+    fn dimensions(&self) -> (usize, usize) {
+        (self.level().rows, self.level().cols)
+    }
+
+    /// The directions the keeper can walk in, pushing a box if there is one with free floor
+    /// behind it.
+    fn legal_moves(&self) -> Vec<Direction> {
+        if self.status() != Status::Playing {
+            return Vec::new();
+        }
+        let level = self.level();
+        let free = |[row, col]: [usize; 2]| !level.walls[row][col] && !self.boxes[row][col];
+        Direction::ALL
+            .into_iter()
+            .filter(|&direction| {
+                let Some(next) = direction.next(self.keeper, level.rows, level.cols) else {
+                    return false;
+                };
+                match self.boxes[next[0]][next[1]] {
+                    true => direction
+                        .next(next, level.rows, level.cols)
+                        .is_some_and(free),
+                    false => !level.walls[next[0]][next[1]],
+                }
+            })
+            .collect()
+    }
+
+    fn apply(&mut self, direction: Direction) -> bool {
+        if !self.legal_moves().contains(&direction) {
+            return false;
+        }
+        let (rows, cols) = self.dimensions();
+        let Some(next) = direction.next(self.keeper, rows, cols) else {
+            return false;
+        };
+        let mut undo = Undo {
+            keeper: self.keeper,
+            pushed: None,
+        };
+        if self.boxes[next[0]][next[1]]
+            && let Some(beyond) = direction.next(next, rows, cols)
+        {
+            self.boxes[next[0]][next[1]] = false;
+            self.boxes[beyond[0]][beyond[1]] = true;
+            undo.pushed = Some((next, beyond));
+            self.pushes += 1;
+        }
+        self.keeper = next;
+        self.moves += 1;
+        self.history.push(undo);
+        true
+    }
+
+    /// A level is won once every box is on a goal.
+    fn status(&self) -> Status {
+        match self.boxes_home() == self.goal_count() {
+            true => Status::Won,
+            false => Status::Playing,
+        }
+    }
+
+    fn render_cell(&self, [row, col]: [usize; 2]) -> (String, Style) {
+        let level = self.level();
+        if level.walls[row][col] {
+            return (String::new(), Style::default().bg(tailwind::SLATE.c500));
+        }
+        if !level.inside[row][col] {
+            return (String::new(), Style::default().bg(self.colors.buffer_bg));
+        }
+        let floor = Style::default().bg(tailwind::SLATE.c900);
+        let goal = level.goals[row][col];
+        if [row, col] == self.keeper {
+            ("●".into(), floor.fg(tailwind::SKY.c300).bold())
+        } else if self.boxes[row][col] {
+            let bg = match goal {
+                true => tailwind::EMERALD.c600,
+                false => tailwind::AMBER.c700,
+            };
+            ("[]".into(), floor.bg(bg).fg(tailwind::SLATE.c100))
+        } else if goal {
+            ("•".into(), floor.fg(tailwind::AMBER.c300))
+        } else {
+            (String::new(), floor)
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        let last = self.levels.len() - 1;
+        let direction = match key.code {
+            KeyCode::Char('u' | 'z') | KeyCode::Backspace => return self.undo(),
+            KeyCode::Char('r') => return self.load(self.current),
+            KeyCode::Char('n') if self.current < last => return self.load(self.current + 1),
+            KeyCode::Char('p') if self.current > 0 => return self.load(self.current - 1),
+            KeyCode::Char(' ') | KeyCode::Enter
+                if self.status() == Status::Won && self.current < last =>
+            {
+                return self.load(self.current + 1);
+            }
+            KeyCode::Char('w') | KeyCode::Up => Direction::Up,
+            KeyCode::Char('s') | KeyCode::Down => Direction::Down,
+            KeyCode::Char('a') | KeyCode::Left => Direction::Left,
+            KeyCode::Char('d') | KeyCode::Right => Direction::Right,
+            _ => return,
+        };
+        self.apply(direction);
+    }
+
+    /// `p` goes back a level rather than opening the pause menu.
+    fn uses_key(&self, code: KeyCode) -> bool {
+        code == KeyCode::Char('p')
+    }
+
+    fn apply_settings(&mut self, settings: &Settings) {
+        self.colors = settings.colors();
+    }
+
+    fn restart(&mut self) {
+        self.load(self.current);
+    }
+
+    fn reopened(&self) -> bool {
+        self.reopened
+    }
+
+    fn help(&self) -> Help {
+        Help {
+            rules: "Push every box onto a goal. Boxes can only be pushed, one at a time, and \
+                    never pulled."
+                .into(),
+            keys: vec![
+                ("←↑↓→/wasd", "walk and push"),
+                ("u/z/Backspace", "undo"),
+                ("r", "restart the level"),
+                ("n/p", "next or previous level"),
+            ],
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let area = frame.area();
+        let min_width = 72;
+        let min_height = 12;
+        if area.width < min_width || area.height < min_height {
+            grid::render_too_small(frame, area, min_width, min_height);
+            return;
+        }
+
+        let [title_area, level_area, help_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+        let (rows, cols) = self.dimensions();
+        let view_rows = rows.min(usize::from(level_area.height));
+        let view_cols = cols.min(usize::from(level_area.width / CELL_WIDTH));
+        // levels too large for the screen scroll to keep the keeper in the middle of the view
+        let [keeper_row, keeper_col] = self.keeper;
+        let first_row = keeper_row
+            .saturating_sub(view_rows / 2)
+            .min(rows - view_rows);
+        let first_col = keeper_col
+            .saturating_sub(view_cols / 2)
+            .min(cols - view_cols);
+
+        frame.render_widget(Paragraph::new(self.render_title()).centered(), title_area);
+        let [level_area] = Layout::horizontal([Constraint::Length(view_cols as u16 * CELL_WIDTH)])
+            .flex(Flex::Center)
+            .areas(level_area);
+        let [level_area] = Layout::vertical([Constraint::Length(view_rows as u16)])
+            .flex(Flex::Center)
+            .areas(level_area);
+        grid::render_pixels(
+            frame,
+            level_area,
+            (view_rows, view_cols),
+            CELL_WIDTH,
+            |[row, col]| self.render_cell([first_row + row, first_col + col]),
+        );
+        let help = "←↑↓→/wasd move · u undo · r restart level · n/p next/previous level";
+        frame.render_widget(Paragraph::new(help).centered().dark_gray(), help_area);
+    }
+}
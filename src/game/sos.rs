@@ -0,0 +1,478 @@
+//! SOS: take turns writing an S or an O in an empty square; spelling S-O-S in a line across,
+//! down or diagonally scores and earns another turn, and whoever spells more of them wins.
+//!
+//! The hard bot spells as many as it can, then writes where the other side can't spell one
+//! after it, and once nowhere is safe it leaves the fewest to be spelled.
+
+use std::ops::RangeInclusive;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use rand::seq::IndexedRandom;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout},
+    style::{Modifier, Style, Stylize, palette::tailwind},
+    text::Line,
+    widgets::{Paragraph, TableState},
+};
+
+use crate::{
+    Outcome, Player, bot,
+    game::{Game, Help, Status},
+    grid::{self, Cursor, Viewport},
+    settings::Settings,
+    ui::{
+        layout::{cell_size, center, table_extent},
+        palette::{PALETTES, TableColors},
+    },
+};
+
+/// The smallest and largest number of rows or columns of the board.
+pub const GRID_SIZES: RangeInclusive<usize> = 3..=12;
+
+/// The board played when none is given.
+pub const DEFAULT_GRID: (usize, usize) = (6, 6);
+
+/// The eight directions a line can run in from a square.
+const DIRECTIONS: [[isize; 2]; 8] = [
+    [-1, -1],
+    [-1, 0],
+    [-1, 1],
+    [0, -1],
+    [0, 1],
+    [1, -1],
+    [1, 0],
+    [1, 1],
+];
+
+/// What can be written in a square.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Letter {
+    S,
+    O,
+}
+
+impl Letter {
+    const fn symbol(self) -> &'static str {
+        match self {
+            Self::S => "S",
+            Self::O => "O",
+        }
+    }
+}
+
+/// Three squares in a line spelling S-O-S.
+type Word = [[usize; 2]; 3];
+
+/// A letter and the square it is written in.
+type Play = ([usize; 2], Letter);
+
+/// The squares of a board and the letters written in them.
+#[derive(Debug, Clone)]
+struct Board {
+    squares: Vec<Vec<Option<Letter>>>,
+}
+
+impl Board {
+    fn rows(&self) -> usize {
+        self.squares.len()
+    }
+
+    fn cols(&self) -> usize {
+        self.squares[0].len()
+    }
+
+    fn letter(&self, [row, col]: [usize; 2]) -> Option<Letter> {
+        self.squares.get(row)?.get(col).copied().flatten()
+    }
+
+    /// The square `steps` squares from `cell` in `direction`, if that is still on the board.
+    fn step(
+        &self,
+        [row, col]: [usize; 2],
+        [down, across]: [isize; 2],
+        steps: isize,
+    ) -> Option<[usize; 2]> {
+        let row = row.checked_add_signed(down * steps)?;
+        let col = col.checked_add_signed(across * steps)?;
+        (row < self.rows() && col < self.cols()).then_some([row, col])
+    }
+
+    /// The empty squares.
+    fn empty(&self) -> Vec<[usize; 2]> {
+        (0..self.rows())
+            .flat_map(|row| (0..self.cols()).map(move |col| [row, col]))
+            .filter(|&cell| self.letter(cell).is_none())
+            .collect()
+    }
+
+    /// Every move left: either letter in any empty square.
+    fn moves(&self) -> Vec<([usize; 2], Letter)> {
+        self.empty()
+            .into_iter()
+            .flat_map(|cell| [(cell, Letter::S), (cell, Letter::O)])
+            .collect()
+    }
+
+    /// The S-O-S lines writing `letter` in `cell` would spell.
+    fn spelled(&self, cell: [usize; 2], letter: Letter) -> Vec<Word> {
+        let is = |cell, letter| self.letter(cell) == Some(letter);
+        match letter {
+            // an S starts a line running out from it
+            Letter::S => DIRECTIONS
+                .iter()
+                .filter_map(|&direction| {
+                    let middle = self.step(cell, direction, 1)?;
+                    let end = self.step(cell, direction, 2)?;
+                    (is(middle, Letter::O) && is(end, Letter::S)).then_some([cell, middle, end])
+                })
+                .collect(),
+            // an O sits between two S's; half the directions cover each line once
+            Letter::O => DIRECTIONS[..4]
+                .iter()
+                .filter_map(|&direction| {
+                    let start = self.step(cell, direction, 1)?;
+                    let end = self.step(cell, direction, -1)?;
+                    (is(start, Letter::S) && is(end, Letter::S)).then_some([start, cell, end])
+                })
+                .collect(),
+        }
+    }
+
+    /// How many lines the other side could spell with its next letter after `letter` is
+    /// written in `cell`.
+    fn openings(&self, cell: [usize; 2], letter: Letter) -> usize {
+        let mut board = self.clone();
+        board.squares[cell[0]][cell[1]] = Some(letter);
+        board
+            .moves()
+            .into_iter()
+            .map(|(cell, letter)| board.spelled(cell, letter).len())
+            .sum()
+    }
+}
+
+/// The letter and square `opponent` writes on `board`, if any are left.
+fn choose_move(board: &Board, opponent: bot::Opponent) -> Option<Play> {
+    let moves = board.moves();
+    let mut rng = rand::rng();
+    if opponent == bot::Opponent::Random {
+        return moves.choose(&mut rng).copied();
+    }
+    // spell as much as possible; the extra turn lets the next line be taken after it
+    let spelled = |&(cell, letter): &([usize; 2], Letter)| board.spelled(cell, letter).len();
+    let most = moves.iter().map(spelled).max()?;
+    if most > 0 {
+        let best: Vec<_> = moves
+            .iter()
+            .copied()
+            .filter(|m| spelled(m) == most)
+            .collect();
+        return best.choose(&mut rng).copied();
+    }
+    let openings: Vec<_> = moves
+        .iter()
+        .map(|&(cell, letter)| board.openings(cell, letter))
+        .collect();
+    let fewest = *openings.iter().min()?;
+    let best: Vec<_> = moves
+        .into_iter()
+        .zip(openings)
+        .filter(|&(_, openings)| openings == fewest)
+        .map(|(m, _)| m)
+        .collect();
+    best.choose(&mut rng).copied()
+}
+
+/// A game of SOS against the bot.
+pub struct Sos {
+    board: Board,
+    /// The lines spelled so far and who spelled each.
+    spelled: Vec<(Word, Player)>,
+    turn: Player,
+    /// Who moved first this game; the sides take turns opening.
+    starter: Player,
+    opponent: bot::Opponent,
+    outcome: Option<Outcome>,
+    /// Whether the side to move spelled a line with its last letter.
+    extra_turn: bool,
+    last_bot_move: Option<[usize; 2]>,
+    /// The bot's search for the letter it writes next, and where.
+    thinking: Option<bot::Thinking<Option<Play>>>,
+    cursor: Cursor,
+    state: TableState,
+    colors: TableColors,
+}
+
+impl Sos {
+    /// A game on an empty board of `rows`×`cols` squares.
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Self {
+            board: Board {
+                squares: vec![vec![None; cols]; rows],
+            },
+            spelled: Vec::new(),
+            turn: Player::Human,
+            starter: Player::Human,
+            opponent: bot::Opponent::Minimax,
+            outcome: None,
+            extra_turn: false,
+            last_bot_move: None,
+            thinking: None,
+            cursor: Cursor::new(rows, cols),
+            state: TableState::default(),
+            colors: TableColors::new(&PALETTES[0]),
+        }
+    }
+
+    /// Start a new game on a board of the same size, with the other side opening.
+    fn rematch(&mut self) {
+        let starter = self.starter.other();
+        *self = Self {
+            colors: self.colors,
+            turn: starter,
+            starter,
+            opponent: self.opponent,
+            ..Self::new(self.board.rows(), self.board.cols())
+        };
+    }
+
+    /// How many lines each side has spelled, the human's first.
+    fn score(&self) -> (usize, usize) {
+        let human = self
+            .spelled
+            .iter()
+            .filter(|&&(_, player)| player == Player::Human)
+            .count();
+        (human, self.spelled.len() - human)
+    }
+
+    /// Write `letter` in `cell` for the side to move, which keeps the turn when it spells a
+    /// line.
+    fn play(&mut self, cell: [usize; 2], letter: Letter) {
+        let spelled = self.board.spelled(cell, letter);
+        self.board.squares[cell[0]][cell[1]] = Some(letter);
+        self.extra_turn = !spelled.is_empty();
+        self.spelled
+            .extend(spelled.into_iter().map(|word| (word, self.turn)));
+        if self.board.empty().is_empty() {
+            let (human, bot) = self.score();
+            self.outcome = Some(match human.cmp(&bot) {
+                std::cmp::Ordering::Greater => Outcome::Winner(Player::Human),
+                std::cmp::Ordering::Less => Outcome::Winner(Player::Bot),
+                std::cmp::Ordering::Equal => Outcome::Draw,
+            });
+        } else if !self.extra_turn {
+            self.turn = self.turn.other();
+        }
+    }
+
+    /// Write the letter the bot settled on.
+    fn bot_move(&mut self, cell: [usize; 2], letter: Letter) {
+        self.play(cell, letter);
+        self.last_bot_move = Some(cell);
+    }
+
+    /// Who spelled the latest line through `cell`, if any runs through it.
+    fn owner(&self, cell: [usize; 2]) -> Option<Player> {
+        self.spelled
+            .iter()
+            .rev()
+            .find(|(word, _)| word.contains(&cell))
+            .map(|&(_, player)| player)
+    }
+
+    fn render_title(&self) -> Line<'static> {
+        let (human, bot) = self.score();
+        let versus = format!("SOS · You {human} : {bot} Bot [{}]", self.opponent.label());
+        let status = match (self.outcome, self.turn) {
+            (Some(outcome), _) => format!("{} (r: rematch)", outcome.message()).into(),
+            (None, Player::Bot) => "Bot thinking…".fg(self.colors.row_fg),
+            (None, Player::Human) if self.extra_turn => {
+                "SOS! Go again".fg(self.colors.selected_cell_style_fg)
+            }
+            (None, Player::Human) => "Your turn".fg(self.colors.selected_cell_style_fg),
+        };
+        Line::from(vec![versus.into(), " · ".dark_gray(), status])
+    }
+}
+
+impl Game for Sos {
+    /// A square and the letter written in it.
+    type Move = ([usize; 2], Letter);
+
+    fn dimensions(&self) -> (usize, usize) {
+        (self.board.rows(), self.board.cols())
+    }
+
+    fn legal_moves(&self) -> Vec<([usize; 2], Letter)> {
+        if self.outcome.is_some() || self.turn != Player::Human {
+            return Vec::new();
+        }
+        self.board.moves()
+    }
+
+    fn apply(&mut self, (cell, letter): ([usize; 2], Letter)) -> bool {
+        if !self.legal_moves().contains(&(cell, letter)) {
+            return false;
+        }
+        self.last_bot_move = None;
+        self.play(cell, letter);
+        true
+    }
+
+    fn status(&self) -> Status {
+        match self.outcome {
+            None => Status::Playing,
+            Some(Outcome::Winner(Player::Human)) => Status::Won,
+            Some(Outcome::Winner(Player::Bot)) => Status::Lost,
+            Some(Outcome::Draw) => Status::Draw,
+        }
+    }
+
+    fn opponent(&self) -> Option<bot::Opponent> {
+        Some(self.opponent)
+    }
+
+    /// A letter, shaded in the color of whoever spelled the latest line through it.
+    fn render_cell(&self, cell: [usize; 2]) -> (String, Style) {
+        let Some(letter) = self.board.letter(cell) else {
+            return (String::new(), Style::default());
+        };
+        let style = match self.owner(cell) {
+            Some(Player::Human) => Style::default().bg(tailwind::BLUE.c700),
+            Some(Player::Bot) => Style::default().bg(tailwind::ROSE.c800),
+            None => Style::default(),
+        };
+        let style = match self.last_bot_move == Some(cell) {
+            true => style
+                .fg(self.colors.last_move_style_fg)
+                .add_modifier(Modifier::BOLD),
+            false => style.fg(self.colors.row_fg),
+        };
+        (letter.symbol().into(), style)
+    }
+
+    /// `o` writes an O rather than opening the settings.
+    fn uses_key(&self, code: KeyCode) -> bool {
+        code == KeyCode::Char('o')
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char('r') => self.rematch(),
+            KeyCode::Char('b') => self.opponent = self.opponent.next(),
+            // the board is frozen once the game is decided
+            _ if self.outcome.is_some() || self.turn != Player::Human => {}
+            // s writes rather than moves down here, so the cursor keeps to the arrows and a, d, w
+            KeyCode::Char('s' | 'S') => {
+                self.apply((self.cursor.cell(), Letter::S));
+            }
+            KeyCode::Char('o' | 'O') => {
+                self.apply((self.cursor.cell(), Letter::O));
+            }
+            code => {
+                self.cursor.move_with(code);
+            }
+        }
+    }
+
+    /// Search for the bot's next letter and write it when found; a bot spelling lines goes
+    /// on a letter at a time, each searched for anew, so every one it writes can be seen.
+    fn tick(&mut self) -> bool {
+        if self.outcome.is_some() || self.turn != Player::Bot {
+            return false;
+        }
+        let (board, opponent) = (self.board.clone(), self.opponent);
+        let thinking = self
+            .thinking
+            .get_or_insert_with(|| bot::Thinking::start(move || choose_move(&board, opponent)));
+        let answer = match thinking.answer() {
+            bot::Answer::Pending => return false,
+            bot::Answer::Ready(answer) => answer,
+            bot::Answer::Failed => choose_move(&self.board, bot::Opponent::Random),
+        };
+        self.thinking = None;
+        if let Some((cell, letter)) = answer {
+            self.bot_move(cell, letter);
+        }
+        true
+    }
+
+    fn apply_settings(&mut self, settings: &Settings) {
+        self.colors = settings.colors();
+        self.opponent = settings.opponent(self.opponent);
+    }
+
+    fn restart(&mut self) {
+        self.rematch();
+    }
+
+    fn help(&self) -> Help {
+        Help {
+            rules: "Take turns writing an S or an O in an empty square. Spelling S-O-S across, \
+                    down or diagonally scores and earns another turn, and whoever spells more \
+                    wins."
+                .into(),
+            keys: vec![
+                ("←↑↓→/a/d/w", "move the cursor"),
+                ("s", "write an S"),
+                ("o", "write an O"),
+                ("b", "switch bot"),
+                ("r", "rematch"),
+            ],
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let area = frame.area();
+        let (rows, cols) = self.dimensions();
+        let (cell_width, cell_height) = cell_size(rows.max(cols));
+        let table_width = table_extent(cols, cell_width, 1);
+        let table_height = table_extent(rows, cell_height, 0);
+        let width = table_width.max(60);
+        let height = table_height + 4;
+        if area.width < width || area.height < height {
+            grid::render_too_small(frame, area, width, height);
+            return;
+        }
+
+        let area = center(
+            area,
+            Constraint::Percentage(100),
+            Constraint::Length(height),
+        );
+        let [title_area, table_area, help_area] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Length(table_height + 1),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+        frame.render_widget(Paragraph::new(self.render_title()).centered(), title_area);
+        let table_area = center(
+            table_area,
+            Constraint::Length(table_width),
+            Constraint::Percentage(100),
+        );
+        let viewport = Viewport {
+            row: 0,
+            col: 0,
+            rows,
+            cols,
+        };
+        self.state.select(Some(self.cursor.row));
+        self.state.select_column(Some(self.cursor.col));
+        // the cursor is hidden while the bot moves and once the game is decided
+        let selected_cell_style = match self.outcome.is_none() && self.turn == Player::Human {
+            true => Style::default()
+                .add_modifier(Modifier::REVERSED)
+                .fg(self.colors.selected_cell_style_fg),
+            false => Style::default(),
+        };
+        let table = grid::table(self, viewport, cell_width, cell_height, &self.colors)
+            .cell_highlight_style(selected_cell_style);
+        frame.render_stateful_widget(table, table_area, &mut self.state);
+        let help = "Arrows move · s/o write S or O · b bot · r rematch";
+        frame.render_widget(Paragraph::new(help).centered().dark_gray(), help_area);
+    }
+}
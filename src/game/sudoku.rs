@@ -0,0 +1,571 @@
+//! Sudoku: fill the 9x9 grid so every row, column and 3x3 box holds each digit once.
+//!
+//! Puzzles are generated by filling a grid at random and then clearing cells for as long as
+//! the puzzle keeps a single solution.
+
+use std::str::FromStr;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use rand::seq::SliceRandom;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style, Stylize, palette::tailwind},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+use crate::{
+    error::Error,
+    game::{Game, Help, Status},
+    grid::{self, Cursor},
+    settings::Settings,
+    ui::{
+        layout::center,
+        palette::{PALETTES, TableColors},
+    },
+};
+
+/// Cells along one side of the grid.
+const SIDE: usize = 9;
+
+/// Cells along one side of a box.
+const BOX: usize = 3;
+
+/// How many clues a puzzle keeps at most, by difficulty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl Difficulty {
+    /// The number of given digits the generator stops clearing at.
+    const fn clues(self) -> usize {
+        match self {
+            Self::Easy => 40,
+            Self::Medium => 32,
+            Self::Hard => 24,
+        }
+    }
+
+    const fn next(self) -> Self {
+        match self {
+            Self::Easy => Self::Medium,
+            Self::Medium => Self::Hard,
+            Self::Hard => Self::Easy,
+        }
+    }
+
+    const fn label(self) -> &'static str {
+        match self {
+            Self::Easy => "easy",
+            Self::Medium => "medium",
+            Self::Hard => "hard",
+        }
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        match s {
+            "easy" => Ok(Self::Easy),
+            "medium" => Ok(Self::Medium),
+            "hard" => Ok(Self::Hard),
+            _ => Err(Error::invalid(
+                "difficulty",
+                s,
+                "`easy`, `medium` or `hard`",
+            )),
+        }
+    }
+}
+
+/// The digits of a grid, with 0 for an empty cell.
+type Digits = [[u8; SIDE]; SIDE];
+
+/// The cells sharing a row, column or box with `[row, col]`, without the cell itself.
+fn peers([row, col]: [usize; 2]) -> impl Iterator<Item = [usize; 2]> {
+    let (box_row, box_col) = (row / BOX * BOX, col / BOX * BOX);
+    (0..SIDE)
+        .map(move |i| [row, i])
+        .chain((0..SIDE).map(move |i| [i, col]))
+        .chain((0..SIDE).map(move |i| [box_row + i / BOX, box_col + i % BOX]))
+        .filter(move |&cell| cell != [row, col])
+}
+
+/// Whether `digit` may go in `cell` without repeating in its row, column or box.
+fn fits(digits: &Digits, cell: [usize; 2], digit: u8) -> bool {
+    peers(cell).all(|[row, col]| digits[row][col] != digit)
+}
+
+/// Count the ways to complete `digits`, stopping once `limit` is reached. With `shuffle` the
+/// digits are tried in random order, so the first completion left in `digits` is a random one.
+fn solve(digits: &mut Digits, limit: usize, shuffle: bool) -> usize {
+    let empty = (0..SIDE)
+        .flat_map(|row| (0..SIDE).map(move |col| [row, col]))
+        .find(|&[row, col]| digits[row][col] == 0);
+    let Some([row, col]) = empty else {
+        return 1;
+    };
+    let mut candidates: Vec<u8> = (1..=9).collect();
+    if shuffle {
+        candidates.shuffle(&mut rand::rng());
+    }
+    let mut found = 0;
+    for digit in candidates {
+        if fits(digits, [row, col], digit) {
+            digits[row][col] = digit;
+            found += solve(digits, limit - found, shuffle);
+            if found >= limit {
+                return found;
+            }
+        }
+    }
+    digits[row][col] = 0;
+    found
+}
+
+/// A puzzle of `difficulty` with a single solution.
+fn generate(difficulty: Difficulty) -> Digits {
+    let mut solution = [[0; SIDE]; SIDE];
+    solve(&mut solution, 1, true);
+
+    let mut puzzle = solution;
+    let mut cells: Vec<_> = (0..SIDE)
+        .flat_map(|row| (0..SIDE).map(move |col| [row, col]))
+        .collect();
+    cells.shuffle(&mut rand::rng());
+    let mut clues = SIDE * SIDE;
+    for [row, col] in cells {
+        if clues <= difficulty.clues() {
+            break;
+        }
+        let digit = puzzle[row][col];
+        puzzle[row][col] = 0;
+        if solve(&mut puzzle.clone(), 2, false) == 1 {
+            clues -= 1;
+        } else {
+            puzzle[row][col] = digit;
+        }
+    }
+    puzzle
+}
+
+/// A digit written into a cell, or the cell cleared with `None`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Entry {
+    pub cell: [usize; 2],
+    pub digit: Option<u8>,
+}
+
+/// A game of Sudoku.
+pub struct Sudoku {
+    difficulty: Difficulty,
+    digits: Digits,
+    /// The cells given by the puzzle, which cannot be changed.
+    given: [[bool; SIDE]; SIDE],
+    /// The candidates noted in each cell, one bit per digit.
+    pencil: [[u16; SIDE]; SIDE],
+    /// Digits typed go into the pencil marks instead of the cell.
+    pencil_mode: bool,
+    cursor: Cursor,
+    colors: TableColors,
+}
+
+impl Sudoku {
+    /// A new puzzle of `difficulty`.
+    pub fn new(difficulty: Difficulty) -> Self {
+        let digits = generate(difficulty);
+        Self {
+            difficulty,
+            digits,
+            given: digits.map(|row| row.map(|digit| digit != 0)),
+            pencil: [[0; SIDE]; SIDE],
+            pencil_mode: false,
+            cursor: Cursor::new(SIDE, SIDE),
+            colors: TableColors::new(&PALETTES[0]),
+        }
+    }
+
+    /// Start over on a new puzzle of `difficulty`, in the same colors.
+    fn new_puzzle(&mut self, difficulty: Difficulty) {
+        *self = Self {
+            colors: self.colors,
+            ..Self::new(difficulty)
+        };
+    }
+
+    /// Whether the digit in `cell` repeats within its row, column or box.
+    fn conflicts(&self, cell: [usize; 2]) -> bool {
+        let [row, col] = cell;
+        let digit = self.digits[row][col];
+        digit != 0 && !fits(&self.digits, cell, digit)
+    }
+
+    /// Note or drop `digit` as a candidate for the cell under the cursor.
+    fn toggle_pencil(&mut self, digit: u8) {
+        let [row, col] = self.cursor.cell();
+        if self.digits[row][col] == 0 {
+            self.pencil[row][col] ^= 1 << digit;
+        }
+    }
+
+    fn render_title(&self) -> Line<'static> {
+        let status = match self.status() {
+            Status::Won => "Solved! (r: new puzzle)".fg(self.colors.selected_cell_style_fg),
+            _ if self.pencil_mode => "1-9: pencil · p: pen".fg(self.colors.selected_cell_style_fg),
+            _ => "1-9: pen · p: pencil".fg(self.colors.selected_cell_style_fg),
+        };
+        Line::from(vec![
+            format!("Sudoku [{}]", self.difficulty.label()).into(),
+            " · ".dark_gray(),
+            status,
+            " · l: level".dark_gray(),
+        ])
+    }
+
+    /// Draw the grid with heavier lines around each box, cells `cell_width`×`cell_height` large.
+    fn render_grid(&self, frame: &mut Frame, area: Rect, cell_width: usize, cell_height: usize) {
+        let border = Style::default().fg(self.colors.selected_column_style_fg);
+        let rule = |left: &str, middle: &str, right: &str| {
+            let segment = "━".repeat((cell_width + 1) * BOX - 1);
+            let text = format!("{left}{segment}{middle}{segment}{middle}{segment}{right}");
+            Line::from(Span::styled(text, border))
+        };
+
+        let mut lines = vec![rule("┏", "┳", "┓")];
+        for row in 0..SIDE {
+            let cells: Vec<_> = (0..SIDE)
+                .map(|col| {
+                    let (text, style) = self.render_cell([row, col]);
+                    // shade every other cell so the rows of tall cells stay apart
+                    let bg = match (row + col) % 2 {
+                        0 => self.colors.normal_row_color,
+                        _ => self.colors.alt_row_color,
+                    };
+                    let style = Style::default().bg(bg).patch(style);
+                    let style = match self.cursor.cell() == [row, col] {
+                        true => style.add_modifier(Modifier::REVERSED),
+                        false => style,
+                    };
+                    (text, style)
+                })
+                .collect();
+            for line in 0..cell_height {
+                let mut spans = vec![Span::styled("┃", border)];
+                for (col, (text, style)) in cells.iter().enumerate() {
+                    // a single digit sits on the middle line; pencil marks fill every line
+                    let text_lines: Vec<_> = text.lines().collect();
+                    let padding = cell_height.saturating_sub(text_lines.len()) / 2;
+                    let content = match text_lines.len() {
+                        n if n > cell_height => "·",
+                        _ => line
+                            .checked_sub(padding)
+                            .and_then(|i| text_lines.get(i))
+                            .copied()
+                            .unwrap_or(""),
+                    };
+                    spans.push(Span::styled(format!("{content:^cell_width$}"), *style));
+                    let separator = match col % BOX {
+                        2 => "┃",
+                        _ => "│",
+                    };
+                    spans.push(Span::styled(separator, border));
+                }
+                lines.push(Line::from(spans));
+            }
+            match row {
+                2 | 5 => lines.push(rule("┣", "╋", "┫")),
+                8 => lines.push(rule("┗", "┻", "┛")),
+                _ => {}
+            }
+        }
+        frame.render_widget(Paragraph::new(lines), area);
+    }
+}
+
+impl Game for Sudoku {
+    type Move = Entry;
+
+    fn dimensions(&self) -> (usize, usize) {
+        (SIDE, SIDE)
+    }
+
+    /// Every digit, or clearing, for every cell not given by the puzzle.
+    fn legal_moves(&self) -> Vec<Entry> {
+        (0..SIDE)
+            .flat_map(|row| (0..SIDE).map(move |col| [row, col]))
+            .filter(|&[row, col]| !self.given[row][col])
+            .flat_map(|cell| {
+                (1..=9)
+                    .map(Some)
+                    .chain([None])
+                    .map(move |digit| Entry { cell, digit })
+            })
+            .collect()
+    }
+
+    /// Write the digit of `entry` into its cell, dropping it from the pencil marks of the
+    /// cell's peers. Given cells cannot be changed.
+    fn apply(&mut self, Entry { cell, digit }: Entry) -> bool {
+        let [row, col] = cell;
+        if self.given[row][col] || digit.is_some_and(|digit| !(1..=9).contains(&digit)) {
+            return false;
+        }
+        self.digits[row][col] = digit.unwrap_or(0);
+        self.pencil[row][col] = 0;
+        if let Some(digit) = digit {
+            for [row, col] in peers(cell) {
+                self.pencil[row][col] &= !(1 << digit);
+            }
+        }
+        true
+    }
+
+    fn status(&self) -> Status {
+        let full = self.digits.iter().flatten().all(|&digit| digit != 0);
+        let valid = (0..SIDE).all(|row| (0..SIDE).all(|col| !self.conflicts([row, col])));
+        match full && valid {
+            true => Status::Won,
+            false => Status::Playing,
+        }
+    }
+
+    /// A digit, or the pencil marks laid out three to a line; repeated digits are shown in red.
+    fn render_cell(&self, [row, col]: [usize; 2]) -> (String, Style) {
+        let digit = self.digits[row][col];
+        let style = if self.conflicts([row, col]) {
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+        } else if self.given[row][col] {
+            Style::default()
+                .fg(tailwind::SLATE.c50)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(self.colors.selected_row_style_fg)
+        };
+        if digit != 0 {
+            return (digit.to_string(), style);
+        }
+        let marks = self.pencil[row][col];
+        if marks == 0 {
+            return (String::new(), style);
+        }
+        let lines: Vec<_> = (0..3)
+            .map(|line| {
+                (1..=3)
+                    .map(|i| {
+                        let digit = line * 3 + i;
+                        match marks & (1 << digit) {
+                            0 => " ".to_string(),
+                            _ => digit.to_string(),
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect();
+        (lines.join("\n"), Style::default().fg(tailwind::SLATE.c400))
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char('r') => self.new_puzzle(self.difficulty),
+            KeyCode::Char('l') => self.new_puzzle(self.difficulty.next()),
+            KeyCode::Char('p') => self.pencil_mode = !self.pencil_mode,
+            // the grid is frozen once it is solved
+            _ if self.status() == Status::Won => {}
+            KeyCode::Char(c @ '1'..='9') => {
+                let digit = c as u8 - b'0';
+                if self.pencil_mode {
+                    self.toggle_pencil(digit);
+                } else {
+                    let cell = self.cursor.cell();
+                    self.apply(Entry {
+                        cell,
+                        digit: Some(digit),
+                    });
+                }
+            }
+            KeyCode::Char('0' | 'x') | KeyCode::Backspace | KeyCode::Delete => {
+                let cell = self.cursor.cell();
+                self.apply(Entry { cell, digit: None });
+            }
+            code => {
+                self.cursor.move_with(code);
+            }
+        }
+    }
+
+    /// `l` changes the difficulty rather than moving the cursor, and `p` turns pencil marks on
+    /// or off rather than opening the pause menu.
+    fn uses_key(&self, code: KeyCode) -> bool {
+        matches!(code, KeyCode::Char('l' | 'p'))
+    }
+
+    fn apply_settings(&mut self, settings: &Settings) {
+        self.colors = settings.colors();
+    }
+
+    fn restart(&mut self) {
+        self.new_puzzle(self.difficulty);
+    }
+
+    fn help(&self) -> Help {
+        Help {
+            rules: "Fill the grid so every row, column and 3x3 box holds each digit from 1 to 9 \
+                    once."
+                .into(),
+            keys: vec![
+                ("←↑↓→/wasd", "move the cursor"),
+                ("1-9", "write a digit"),
+                ("0/x/Backspace", "clear the cell"),
+                ("p", "pencil marks on or off"),
+                ("l", "next difficulty"),
+                ("r", "new puzzle"),
+            ],
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [area, help_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(frame.area());
+        let help = match self.status() {
+            Status::Won => "r new puzzle · l next level",
+            _ => "←↑↓→/wasd move · 1-9 write · 0/x clear · p pencil · r new puzzle",
+        };
+        frame.render_widget(Paragraph::new(help).centered().dark_gray(), help_area);
+        // pencil marks need cells three lines high; smaller terminals get one-line cells
+        let (cell_width, cell_height) = match area.height >= (SIDE * 3 + 5) as u16 {
+            true => (7, 3),
+            false => (3, 1),
+        };
+        let width = (SIDE * (cell_width + 1) + 1) as u16;
+        let height = (SIDE * cell_height + 4) as u16;
+        if area.width < width.max(40) || area.height < height + 1 {
+            grid::render_too_small(frame, area, width.max(40), height + 1);
+            return;
+        }
+
+        let area = center(
+            area,
+            Constraint::Percentage(100),
+            Constraint::Length(height + 1),
+        );
+        let [title_area, grid_area] =
+            Layout::vertical([Constraint::Length(1), Constraint::Length(height)]).areas(area);
+        frame.render_widget(Paragraph::new(self.render_title()).centered(), title_area);
+        let grid_area = center(
+            grid_area,
+            Constraint::Length(width),
+            Constraint::Length(height),
+        );
+        self.render_grid(frame, grid_area, cell_width, cell_height);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The empty cells of `digits`.
+    fn empty(digits: &Digits) -> Vec<[usize; 2]> {
+        (0..SIDE)
+            .flat_map(|row| (0..SIDE).map(move |col| [row, col]))
+            .filter(|&[row, col]| digits[row][col] == 0)
+            .collect()
+    }
+
+    #[test]
+    fn generated_puzzles_have_exactly_one_solution() {
+        for difficulty in [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard] {
+            let puzzle = generate(difficulty);
+            let clues = SIDE * SIDE - empty(&puzzle).len();
+            assert!(
+                clues >= difficulty.clues(),
+                "{difficulty:?} kept {clues} clues"
+            );
+            assert_eq!(solve(&mut puzzle.clone(), 2, false), 1, "{difficulty:?}");
+        }
+    }
+
+    #[test]
+    fn filling_in_the_solution_solves_the_puzzle() {
+        let mut sudoku = Sudoku::new(Difficulty::Easy);
+        let mut solution = sudoku.digits;
+        solve(&mut solution, 1, false);
+        for [row, col] in empty(&sudoku.digits) {
+            assert_eq!(sudoku.status(), Status::Playing);
+            let digit = Some(solution[row][col]);
+            assert!(sudoku.apply(Entry {
+                cell: [row, col],
+                digit
+            }));
+        }
+        assert_eq!(sudoku.status(), Status::Won);
+    }
+
+    #[test]
+    fn a_digit_repeated_in_a_row_column_or_box_is_a_conflict() {
+        let mut sudoku = Sudoku::new(Difficulty::Easy);
+        let mut solution = sudoku.digits;
+        solve(&mut solution, 1, false);
+        let cell @ [row, col] = empty(&sudoku.digits)[0];
+        for peer @ [r, c] in peers(cell) {
+            sudoku.digits = solution;
+            sudoku.digits[row][col] = solution[r][c];
+            assert!(sudoku.conflicts(cell), "{cell:?} against {peer:?}");
+            assert!(sudoku.conflicts(peer));
+            assert_eq!(sudoku.status(), Status::Playing);
+        }
+        sudoku.digits = solution;
+        assert!(!sudoku.conflicts(cell));
+        assert_eq!(sudoku.status(), Status::Won);
+    }
+
+    #[test]
+    fn given_cells_and_digits_out_of_range_are_turned_down() {
+        let mut sudoku = Sudoku::new(Difficulty::Easy);
+        let given = (0..SIDE)
+            .flat_map(|row| (0..SIDE).map(move |col| [row, col]))
+            .find(|&[row, col]| sudoku.given[row][col])
+            .unwrap();
+        assert!(!sudoku.apply(Entry {
+            cell: given,
+            digit: None
+        }));
+        assert_ne!(sudoku.digits[given[0]][given[1]], 0);
+
+        let cell @ [row, col] = empty(&sudoku.digits)[0];
+        assert!(!sudoku.apply(Entry {
+            cell,
+            digit: Some(0)
+        }));
+        assert!(!sudoku.apply(Entry {
+            cell,
+            digit: Some(10)
+        }));
+        assert_eq!(sudoku.digits[row][col], 0);
+    }
+
+    #[test]
+    fn writing_a_digit_drops_it_from_the_pencil_marks_of_its_peers() {
+        let mut sudoku = Sudoku::new(Difficulty::Easy);
+        let cell = empty(&sudoku.digits)[0];
+        let [peer, ..] = peers(cell)
+            .filter(|&[row, col]| !sudoku.given[row][col])
+            .collect::<Vec<_>>()[..]
+        else {
+            panic!("no empty cell shares a row, column or box with {cell:?}");
+        };
+        sudoku.pencil[peer[0]][peer[1]] = 1 << 4 | 1 << 7;
+        assert!(sudoku.apply(Entry {
+            cell,
+            digit: Some(4)
+        }));
+        assert_eq!(sudoku.pencil[peer[0]][peer[1]], 1 << 7);
+    }
+}
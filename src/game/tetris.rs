@@ -0,0 +1,555 @@
+//! Tetris: steer the falling pieces into full rows, which clear, before the stack reaches the
+//! top.
+//!
+//! Every ten cleared rows raise the level, and each level makes the pieces fall faster. The
+//! best score is kept between runs.
+
+use std::time::{Duration, Instant};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use rand::seq::SliceRandom;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style, Stylize, palette::tailwind},
+    text::Line,
+    widgets::{Block, Paragraph},
+};
+
+use crate::{
+    game::{Game, Help, Status},
+    grid, records,
+    settings::Settings,
+    ui::{
+        layout::center,
+        palette::{PALETTES, TableColors},
+    },
+};
+
+/// The rows and columns of the well.
+const ROWS: usize = 20;
+const COLS: usize = 10;
+
+/// Columns of the terminal each cell takes, so cells come out roughly square.
+const CELL_WIDTH: u16 = 2;
+
+/// How long a piece takes to fall one row on the first level.
+const FIRST_FALL: Duration = Duration::from_millis(800);
+
+/// The quickest a piece ever falls one row.
+const FASTEST_FALL: Duration = Duration::from_millis(50);
+
+/// How many rows must be cleared to reach the next level.
+const ROWS_PER_LEVEL: usize = 10;
+
+/// Points for clearing one to four rows at once, multiplied by the level.
+const CLEAR_POINTS: [usize; 4] = [100, 300, 500, 800];
+
+/// How long to wait for a key before letting the piece fall, short enough that it falls on
+/// time even on the fastest levels.
+const FRAME_TIME: Duration = Duration::from_millis(20);
+
+/// The record the best score is kept under between runs.
+const BEST_SCORE_RECORD: &str = "tetris-best";
+
+/// The seven shapes of four blocks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Shape {
+    I,
+    O,
+    T,
+    S,
+    Z,
+    J,
+    L,
+}
+
+impl Shape {
+    const ALL: [Self; 7] = [
+        Self::I,
+        Self::O,
+        Self::T,
+        Self::S,
+        Self::Z,
+        Self::J,
+        Self::L,
+    ];
+
+    /// The blocks of the shape lying flat, within a square box of [`Self::size`] cells.
+    const fn blocks(self) -> [[usize; 2]; 4] {
+        match self {
+            Self::I => [[1, 0], [1, 1], [1, 2], [1, 3]],
+            Self::O => [[0, 0], [0, 1], [1, 0], [1, 1]],
+            Self::T => [[0, 1], [1, 0], [1, 1], [1, 2]],
+            Self::S => [[0, 1], [0, 2], [1, 0], [1, 1]],
+            Self::Z => [[0, 0], [0, 1], [1, 1], [1, 2]],
+            Self::J => [[0, 0], [1, 0], [1, 1], [1, 2]],
+            Self::L => [[0, 2], [1, 0], [1, 1], [1, 2]],
+        }
+    }
+
+    /// The side of the box the shape turns in.
+    const fn size(self) -> usize {
+        match self {
+            Self::I => 4,
+            Self::O => 2,
+            _ => 3,
+        }
+    }
+
+    const fn color(self) -> Color {
+        match self {
+            Self::I => tailwind::CYAN.c400,
+            Self::O => tailwind::YELLOW.c400,
+            Self::T => tailwind::PURPLE.c400,
+            Self::S => tailwind::GREEN.c400,
+            Self::Z => tailwind::RED.c400,
+            Self::J => tailwind::BLUE.c400,
+            Self::L => tailwind::ORANGE.c400,
+        }
+    }
+}
+
+/// A piece in the well: its shape, how far it has turned and where its box is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Piece {
+    shape: Shape,
+    /// Quarter turns clockwise from lying flat.
+    rotation: usize,
+    row: isize,
+    col: isize,
+}
+
+impl Piece {
+    /// A piece of `shape` entering at the top of the well, in the middle.
+    const fn spawn(shape: Shape) -> Self {
+        Self {
+            shape,
+            rotation: 0,
+            row: 0,
+            col: ((COLS - shape.size()) / 2) as isize,
+        }
+    }
+
+    /// The cells the piece covers, which may lie outside the well.
+    fn cells(self) -> [[isize; 2]; 4] {
+        let size = self.shape.size();
+        self.shape.blocks().map(|[mut row, mut col]| {
+            for _ in 0..self.rotation {
+                (row, col) = (col, size - 1 - row);
+            }
+            [self.row + row as isize, self.col + col as isize]
+        })
+    }
+
+    const fn shifted(self, rows: isize, cols: isize) -> Self {
+        Self {
+            row: self.row + rows,
+            col: self.col + cols,
+            ..self
+        }
+    }
+}
+
+/// What the player does with the falling piece.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Left,
+    Right,
+    RotateClockwise,
+    RotateCounterClockwise,
+    /// Move down one row.
+    SoftDrop,
+    /// Drop all the way down and lock in place.
+    HardDrop,
+}
+
+impl Action {
+    const ALL: [Self; 6] = [
+        Self::Left,
+        Self::Right,
+        Self::RotateClockwise,
+        Self::RotateCounterClockwise,
+        Self::SoftDrop,
+        Self::HardDrop,
+    ];
+}
+
+/// The well, each cell holding the shape whose block landed there.
+type Well = [[Option<Shape>; COLS]; ROWS];
+
+/// A game of Tetris.
+pub struct Tetris {
+    well: Well,
+    piece: Piece,
+    /// The shapes coming next, the first one shown as a preview.
+    queue: Vec<Shape>,
+    score: usize,
+    best: usize,
+    /// Rows cleared this game.
+    cleared: usize,
+    /// When the piece next falls a row.
+    next_fall: Instant,
+    /// When the game was paused, to push back the next fall by as long on resuming.
+    paused: Option<Instant>,
+    over: bool,
+    colors: TableColors,
+}
+
+impl Tetris {
+    pub fn new() -> Self {
+        let mut game = Self {
+            well: [[None; COLS]; ROWS],
+            piece: Piece::spawn(Shape::T),
+            queue: Vec::new(),
+            score: 0,
+            best: records::load(BEST_SCORE_RECORD).unwrap_or(0),
+            cleared: 0,
+            next_fall: Instant::now() + FIRST_FALL,
+            paused: None,
+            over: false,
+            colors: TableColors::new(&PALETTES[0]),
+        };
+        game.piece = Piece::spawn(game.next_shape());
+        game
+    }
+
+    /// Start over on an empty well, keeping the best score.
+    fn restart(&mut self) {
+        *self = Self {
+            colors: self.colors,
+            ..Self::new()
+        };
+    }
+
+    /// Take the next shape from the queue, refilling it a shuffled set of all seven at a time
+    /// so no shape is ever long in coming.
+    fn next_shape(&mut self) -> Shape {
+        if self.queue.len() < 2 {
+            let mut bag = Shape::ALL;
+            bag.shuffle(&mut rand::rng());
+            self.queue.extend(bag);
+        }
+        self.queue.remove(0)
+    }
+
+    fn level(&self) -> usize {
+        1 + self.cleared / ROWS_PER_LEVEL
+    }
+
+    /// How long a piece takes to fall a row on the current level, a fifth quicker each level.
+    fn fall_interval(&self) -> Duration {
+        let exponent = (self.level() - 1).min(30) as i32;
+        FIRST_FALL.mul_f64(0.8_f64.powi(exponent)).max(FASTEST_FALL)
+    }
+
+    /// Whether `piece` lies wholly within the well without overlapping a landed block.
+    fn fits(&self, piece: Piece) -> bool {
+        piece.cells().iter().all(|&[row, col]| {
+            (0..ROWS as isize).contains(&row)
+                && (0..COLS as isize).contains(&col)
+                && self.well[row as usize][col as usize].is_none()
+        })
+    }
+
+    /// Where the falling piece would land if dropped now.
+    fn landing(&self) -> Piece {
+        let mut piece = self.piece;
+        while self.fits(piece.shifted(1, 0)) {
+            piece = piece.shifted(1, 0);
+        }
+        piece
+    }
+
+    /// The falling piece turned a quarter clockwise or back, nudged sideways or up when it
+    /// would otherwise hit a wall or the stack.
+    fn rotated(&self, clockwise: bool) -> Option<Piece> {
+        let turn = if clockwise { 1 } else { 3 };
+        let piece = Piece {
+            rotation: (self.piece.rotation + turn) % 4,
+            ..self.piece
+        };
+        [(0, 0), (0, -1), (0, 1), (0, -2), (0, 2), (-1, 0)]
+            .into_iter()
+            .map(|(rows, cols)| piece.shifted(rows, cols))
+            .find(|&piece| self.fits(piece))
+    }
+
+    /// The falling piece after `action`, if it can be taken; a hard drop gives where it lands.
+    fn moved(&self, action: Action) -> Option<Piece> {
+        let piece = match action {
+            Action::Left => self.piece.shifted(0, -1),
+            Action::Right => self.piece.shifted(0, 1),
+            Action::SoftDrop => self.piece.shifted(1, 0),
+            Action::RotateClockwise => return self.rotated(true),
+            Action::RotateCounterClockwise => return self.rotated(false),
+            Action::HardDrop => return Some(self.landing()),
+        };
+        self.fits(piece).then_some(piece)
+    }
+
+    /// Fix the falling piece in the well, clear any full rows and bring in the next piece;
+    /// the game is over when it has no room to enter.
+    fn lock(&mut self) {
+        for [row, col] in self.piece.cells() {
+            self.well[row as usize][col as usize] = Some(self.piece.shape);
+        }
+        let remaining: Vec<_> = self
+            .well
+            .iter()
+            .filter(|row| row.iter().any(Option::is_none))
+            .copied()
+            .collect();
+        let full = ROWS - remaining.len();
+        if full > 0 {
+            self.score += CLEAR_POINTS[full - 1] * self.level();
+            self.cleared += full;
+            let mut well = [[None; COLS]; ROWS];
+            well[full..].copy_from_slice(&remaining);
+            self.well = well;
+        }
+
+        self.piece = Piece::spawn(self.next_shape());
+        self.next_fall = Instant::now() + self.fall_interval();
+        if !self.fits(self.piece) {
+            self.over = true;
+        }
+        if self.score > self.best {
+            self.best = self.score;
+            records::save(BEST_SCORE_RECORD, self.best);
+        }
+    }
+
+    fn toggle_pause(&mut self) {
+        match self.paused.take() {
+            Some(paused) => self.next_fall += paused.elapsed(),
+            None => self.paused = Some(Instant::now()),
+        }
+    }
+
+    fn render_title(&self) -> Line<'static> {
+        let status = match (self.over, self.paused) {
+            (true, _) => "Game over! (r: new game)".fg(Color::Red),
+            (false, Some(_)) => "Paused (p: resume)".fg(self.colors.selected_cell_style_fg),
+            (false, None) => "p: pause".fg(self.colors.selected_cell_style_fg),
+        };
+        Line::from(vec![
+            format!("Tetris · Score {} · Best {}", self.score, self.best).into(),
+            " · ".dark_gray(),
+            status,
+        ])
+    }
+
+    /// The next piece, the level and the keys, beside the well.
+    fn render_panel(&self, frame: &mut Frame, area: Rect) {
+        let [next_label, preview, _, stats, _, keys] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(2),
+            Constraint::Length(1),
+            Constraint::Length(2),
+            Constraint::Length(1),
+            Constraint::Fill(1),
+        ])
+        .areas(area);
+        frame.render_widget(Paragraph::new("Next".bold()), next_label);
+        if let Some(&shape) = self.queue.first() {
+            let blocks = shape.blocks();
+            // the top two rows of the box hold every shape lying flat, but the I sits in its second
+            let offset = usize::from(shape == Shape::I);
+            grid::render_pixels(
+                frame,
+                preview,
+                (2, 4),
+                CELL_WIDTH,
+                |[row, col]| match blocks.contains(&[row + offset, col]) {
+                    true => (String::new(), Style::default().bg(shape.color())),
+                    false => (String::new(), Style::default()),
+                },
+            );
+        }
+        let stats_text = format!("Level {}\nLines {}", self.level(), self.cleared);
+        frame.render_widget(Paragraph::new(stats_text), stats);
+        let keys_text = "←/→ move\n↑ rotate\nz rotate back\n↓ soft drop\nSpace drop";
+        frame.render_widget(Paragraph::new(keys_text).dark_gray(), keys);
+    }
+}
+
+impl Game for Tetris {
+    type Move = Action;
+
+    fn dimensions(&self) -> (usize, usize) {
+        (ROWS, COLS)
+    }
+
+    /// The actions the falling piece has room for.
+    fn legal_moves(&self) -> Vec<Action> {
+        if self.over || self.paused.is_some() {
+            return Vec::new();
+        }
+        Action::ALL
+            .into_iter()
+            .filter(|&action| self.moved(action).is_some())
+            .collect()
+    }
+
+    fn apply(&mut self, action: Action) -> bool {
+        if self.over || self.paused.is_some() {
+            return false;
+        }
+        let Some(piece) = self.moved(action) else {
+            return false;
+        };
+        // dropping scores a point for each row soft-dropped, two for each hard-dropped
+        match action {
+            Action::SoftDrop => self.score += 1,
+            Action::HardDrop => self.score += 2 * (piece.row - self.piece.row) as usize,
+            _ => {}
+        }
+        self.piece = piece;
+        if action == Action::HardDrop {
+            self.lock();
+        }
+        true
+    }
+
+    fn status(&self) -> Status {
+        match self.over {
+            true => Status::Lost,
+            false => Status::Playing,
+        }
+    }
+
+    /// A cell of the well: a landed block, the falling piece, or the faint outline of where it
+    /// will land.
+    fn render_cell(&self, [row, col]: [usize; 2]) -> (String, Style) {
+        let cell = [row as isize, col as isize];
+        if let Some(shape) = self.well[row][col] {
+            return (String::new(), Style::default().bg(shape.color()));
+        }
+        if !self.over && self.piece.cells().contains(&cell) {
+            return (String::new(), Style::default().bg(self.piece.shape.color()));
+        }
+        if !self.over && self.landing().cells().contains(&cell) {
+            return ("░░".into(), Style::default().fg(self.piece.shape.color()));
+        }
+        (String::new(), Style::default())
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        let action = match key.code {
+            KeyCode::Char('r') => return self.restart(),
+            KeyCode::Char('a') | KeyCode::Left => Action::Left,
+            KeyCode::Char('d') | KeyCode::Right => Action::Right,
+            KeyCode::Char('w') | KeyCode::Up => Action::RotateClockwise,
+            KeyCode::Char('z') => Action::RotateCounterClockwise,
+            KeyCode::Char('s') | KeyCode::Down => Action::SoftDrop,
+            KeyCode::Char(' ') | KeyCode::Enter => Action::HardDrop,
+            _ => return,
+        };
+        self.apply(action);
+    }
+
+    /// Let the piece fall a row when its time comes, locking it once it can fall no further.
+    fn tick(&mut self) -> bool {
+        if self.over || self.paused.is_some() || Instant::now() < self.next_fall {
+            return false;
+        }
+        match self.moved(Action::SoftDrop) {
+            Some(piece) => {
+                self.piece = piece;
+                self.next_fall += self.fall_interval();
+            }
+            None => self.lock(),
+        }
+        true
+    }
+
+    fn tick_rate(&self) -> Duration {
+        FRAME_TIME
+    }
+
+    fn apply_settings(&mut self, settings: &Settings) {
+        self.colors = settings.colors();
+    }
+
+    fn pause(&mut self) {
+        if !self.over && self.paused.is_none() {
+            self.toggle_pause();
+        }
+    }
+
+    fn resume(&mut self) {
+        if self.paused.is_some() {
+            self.toggle_pause();
+        }
+    }
+
+    fn restart(&mut self) {
+        self.restart();
+    }
+
+    fn help(&self) -> Help {
+        Help {
+            rules: "Steer the falling pieces into full rows, which clear, before the stack \
+                    reaches the top. Every ten rows raise the level, and the pieces fall faster."
+                .into(),
+            keys: vec![
+                ("←/→", "move the piece"),
+                ("↑/w", "rotate clockwise"),
+                ("z", "rotate counter-clockwise"),
+                ("↓/s", "drop faster"),
+                ("Space/Enter", "drop at once"),
+                ("r", "new game"),
+            ],
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [area, help_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(frame.area());
+        let help = match (self.over, self.paused) {
+            (true, _) => "r new game",
+            (false, Some(_)) => "p resume · r new game",
+            (false, None) => "←/→ move · ↑ rotate · ↓ soft drop · Space hard drop · p pause",
+        };
+        frame.render_widget(Paragraph::new(help).centered().dark_gray(), help_area);
+        // the well with its border, and the panel beside it
+        let well_width = COLS as u16 * CELL_WIDTH + 2;
+        let well_height = ROWS as u16 + 2;
+        let panel_width = 15;
+        let width = (well_width + 2 + panel_width).max(50);
+        let height = well_height + 1;
+        if area.width < width || area.height < height {
+            grid::render_too_small(frame, area, width, height);
+            return;
+        }
+
+        let area = center(
+            area,
+            Constraint::Percentage(100),
+            Constraint::Length(height),
+        );
+        let [title_area, main_area] =
+            Layout::vertical([Constraint::Length(1), Constraint::Length(well_height)]).areas(area);
+        frame.render_widget(Paragraph::new(self.render_title()).centered(), title_area);
+        let main_area = center(
+            main_area,
+            Constraint::Length(well_width + 2 + panel_width),
+            Constraint::Percentage(100),
+        );
+        let [well_area, _, panel_area] = Layout::horizontal([
+            Constraint::Length(well_width),
+            Constraint::Length(2),
+            Constraint::Length(panel_width),
+        ])
+        .areas(main_area);
+
+        let block = Block::bordered().border_style(Style::default().fg(self.colors.row_fg));
+        let inner = block.inner(well_area);
+        frame.render_widget(block, well_area);
+        grid::render_pixels(frame, inner, (ROWS, COLS), CELL_WIDTH, |cell| {
+            self.render_cell(cell)
+        });
+        self.render_panel(frame, panel_area);
+    }
+}
@@ -0,0 +1,507 @@
+//! A typing test: type the words shown for as long as the test lasts, then see how fast and
+//! how accurately they were typed.
+//!
+//! Speed is in words per minute, a word being five characters. The net speed counts only the
+//! characters left right when the time runs out; the raw speed counts all of them. Accuracy
+//! is the share of keystrokes that were right when made, so fixing a typo still costs some.
+//!
+//! Words come from a bundled list of common words, or from a file of the player's own given
+//! with `--words`. The best net speed of each test length is kept between runs.
+
+use std::{
+    collections::HashMap,
+    ops::Range,
+    time::{Duration, Instant},
+};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use rand::seq::IndexedRandom;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style, Stylize, palette::tailwind},
+    text::{Line, Span},
+    widgets::{Block, Paragraph},
+};
+
+use crate::{
+    game::{Game, Help, Status},
+    grid, records,
+    settings::Settings,
+    ui::{
+        layout::center,
+        palette::{PALETTES, TableColors},
+    },
+};
+
+/// The words typed when no word file is given, separated by whitespace.
+const BUNDLED_WORDS: &str = include_str!("typing.txt");
+
+/// The lengths of test ←/→ choose between.
+const DURATIONS: [Duration; 4] = [
+    Duration::from_secs(15),
+    Duration::from_secs(30),
+    Duration::from_secs(60),
+    Duration::from_secs(120),
+];
+
+/// The test length when the game starts, as an index into [`DURATIONS`].
+const DEFAULT_DURATION: usize = 1;
+
+/// Characters on a line of the text.
+const LINE_WIDTH: usize = 60;
+
+/// Lines of the text shown at once: the one being typed and those after it.
+const LINES_SHOWN: usize = 3;
+
+/// How many characters of text are kept ahead of the typing.
+const LOOKAHEAD: usize = 400;
+
+/// The words of `text` that can be typed: anything between whitespace made of printable
+/// characters no longer than a line.
+pub fn parse_words(text: &str) -> Vec<String> {
+    text.split_whitespace()
+        .filter(|word| word.chars().count() < LINE_WIDTH && word.chars().all(|c| !c.is_control()))
+        .map(String::from)
+        .collect()
+}
+
+/// The bundled words.
+pub fn bundled_words() -> Vec<String> {
+    parse_words(BUNDLED_WORDS)
+}
+
+/// Split `text` into lines at most [`LINE_WIDTH`] long, breaking after the spaces between
+/// words. The last line runs to the end of the text.
+fn wrap(text: &[char]) -> Vec<Range<usize>> {
+    let mut lines = Vec::new();
+    let mut start = 0;
+    while text.len() - start > LINE_WIDTH {
+        let end = text[start..start + LINE_WIDTH]
+            .iter()
+            .rposition(|&c| c == ' ')
+            .map_or(start + LINE_WIDTH, |space| start + space + 1);
+        lines.push(start..end);
+        start = end;
+    }
+    lines.push(start..text.len());
+    lines
+}
+
+/// Words per minute for `chars` characters typed over `elapsed`.
+fn words_per_minute(chars: usize, elapsed: Duration) -> f64 {
+    let minutes = elapsed.as_secs_f64() / 60.0;
+    if minutes > 0.0 {
+        chars as f64 / 5.0 / minutes
+    } else {
+        0.0
+    }
+}
+
+/// A key typed during the test.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Keystroke {
+    Char(char),
+    Backspace,
+}
+
+/// A typing test.
+pub struct Typing {
+    /// The words the text is made of.
+    words: Vec<String>,
+    /// The text to type, extended with more words as the typing gets near its end.
+    text: Vec<char>,
+    /// The characters typed so far, one for each character of the text from its start.
+    typed: Vec<char>,
+    /// Every character typed, counting those later deleted.
+    keystrokes: usize,
+    /// The characters typed wrong, counting those later fixed, by the character that should
+    /// have been typed.
+    misses: HashMap<char, usize>,
+    /// The test length, as an index into [`DURATIONS`].
+    duration: usize,
+    /// When the first key was typed.
+    started: Option<Instant>,
+    /// Whether the time has run out.
+    finished: bool,
+    /// The best net speed of this test length, including this test's once finished.
+    best: Option<f64>,
+    /// Whether this test beat the best speed from before.
+    new_best: bool,
+    colors: TableColors,
+}
+
+impl Typing {
+    /// A test on a text of `words`, which must not be empty.
+    pub fn new(words: Vec<String>) -> Self {
+        Self::with_duration(words, DEFAULT_DURATION)
+    }
+
+    fn with_duration(words: Vec<String>, duration: usize) -> Self {
+        let mut game = Self {
+            words,
+            text: Vec::new(),
+            typed: Vec::new(),
+            keystrokes: 0,
+            misses: HashMap::new(),
+            duration,
+            started: None,
+            finished: false,
+            best: records::load(&Self::record(duration)),
+            new_best: false,
+            colors: TableColors::new(&PALETTES[0]),
+        };
+        game.extend();
+        game
+    }
+
+    /// Start over on a new text, keeping the test length.
+    fn restart(&mut self) {
+        *self = Self {
+            colors: self.colors,
+            ..Self::with_duration(std::mem::take(&mut self.words), self.duration)
+        };
+    }
+
+    /// Switch to the next or previous test length, starting over.
+    fn change_duration(&mut self, step: isize) {
+        self.duration = self
+            .duration
+            .saturating_add_signed(step)
+            .min(DURATIONS.len() - 1);
+        self.restart();
+    }
+
+    /// The record keeping the best speed of tests `duration` long.
+    fn record(duration: usize) -> String {
+        format!("typing-best-{}s", DURATIONS[duration].as_secs())
+    }
+
+    /// Add random words to the text until there is plenty left to type.
+    fn extend(&mut self) {
+        while self.text.len() < self.typed.len() + LOOKAHEAD {
+            let Some(word) = self.words.choose(&mut rand::rng()) else {
+                return;
+            };
+            if !self.text.is_empty() {
+                self.text.push(' ');
+            }
+            self.text.extend(word.chars());
+        }
+    }
+
+    /// How long the test has been going, up to its length.
+    fn elapsed(&self) -> Duration {
+        let length = DURATIONS[self.duration];
+        match self.started {
+            Some(started) if !self.finished => started.elapsed().min(length),
+            Some(_) => length,
+            None => Duration::ZERO,
+        }
+    }
+
+    /// The characters typed that match the text.
+    fn correct(&self) -> usize {
+        self.typed
+            .iter()
+            .zip(&self.text)
+            .filter(|(typed, expected)| typed == expected)
+            .count()
+    }
+
+    /// Net words per minute so far, steadied over the first second or it would leap about.
+    fn speed(&self) -> f64 {
+        words_per_minute(self.correct(), self.elapsed().max(Duration::from_secs(1)))
+    }
+
+    /// The share of keystrokes right when made, as a percentage.
+    fn accuracy(&self) -> f64 {
+        let missed: usize = self.misses.values().sum();
+        match self.keystrokes {
+            0 => 100.0,
+            keystrokes => (keystrokes - missed) as f64 * 100.0 / keystrokes as f64,
+        }
+    }
+
+    /// End the test, saving its speed if it is the best of its length.
+    fn finish(&mut self) {
+        self.finished = true;
+        let speed = self.speed();
+        if self.best.is_none_or(|best| speed > best) && speed > 0.0 {
+            self.new_best = self.best.is_some();
+            self.best = Some(speed);
+            records::save(&Self::record(self.duration), format!("{speed:.1}"));
+        }
+    }
+
+    fn render_title(&self) -> Line<'static> {
+        let length = DURATIONS[self.duration];
+        let best = self
+            .best
+            .map_or("–".into(), |best| format!("{best:.0} wpm"));
+        let status = match (self.started, self.finished) {
+            (_, true) => "Time! (Tab: new test)".fg(self.colors.selected_cell_style_fg),
+            (None, false) => "Start typing".fg(self.colors.selected_cell_style_fg),
+            (Some(_), false) => {
+                let left = length.saturating_sub(self.elapsed()).as_secs_f64().ceil();
+                format!("{left:.0}s · {:.0} wpm", self.speed()).fg(self.colors.row_fg)
+            }
+        };
+        Line::from(vec![
+            format!("Typing · {}s · Best {best}", length.as_secs()).into(),
+            " · ".dark_gray(),
+            status,
+        ])
+    }
+
+    /// The results of a finished test.
+    fn render_results(&self) -> Vec<Line<'static>> {
+        let elapsed = self.elapsed();
+        let correct = self.correct();
+        let wrong = self.typed.len() - correct;
+        let mut missed: Vec<(char, usize)> = self.misses.iter().map(|(&c, &n)| (c, n)).collect();
+        missed.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        let missed = match missed.is_empty() {
+            true => "nothing".into(),
+            false => missed
+                .iter()
+                .take(5)
+                .map(|&(c, n)| match c {
+                    ' ' => format!("space ×{n}"),
+                    c => format!("{c} ×{n}"),
+                })
+                .collect::<Vec<_>>()
+                .join("  "),
+        };
+        let speed = format!("{:.0} wpm", self.speed());
+        let mut lines = vec![
+            Line::from(vec![
+                "Speed      ".dark_gray(),
+                speed.fg(self.colors.selected_cell_style_fg).bold(),
+            ]),
+            Line::from(vec![
+                "Raw        ".dark_gray(),
+                format!("{:.0} wpm", words_per_minute(self.typed.len(), elapsed)).into(),
+            ]),
+            Line::from(vec![
+                "Accuracy   ".dark_gray(),
+                format!("{:.1}%", self.accuracy()).into(),
+            ]),
+            Line::from(vec![
+                "Characters ".dark_gray(),
+                format!("{correct} right").green(),
+                " · ".dark_gray(),
+                format!("{wrong} wrong").red(),
+            ]),
+            Line::from(vec!["Missed     ".dark_gray(), missed.into()]),
+        ];
+        if self.new_best {
+            lines.push(Line::default());
+            lines.push(Line::from(
+                "New best!".fg(self.colors.selected_cell_style_fg).bold(),
+            ));
+        }
+        lines
+    }
+}
+
+impl Game for Typing {
+    type Move = Keystroke;
+
+    /// The text as wrapped into lines.
+    fn dimensions(&self) -> (usize, usize) {
+        (wrap(&self.text).len(), LINE_WIDTH)
+    }
+
+    /// The right next character, and deleting the last one once something is typed.
+    fn legal_moves(&self) -> Vec<Keystroke> {
+        if self.finished {
+            return Vec::new();
+        }
+        let mut moves = vec![Keystroke::Char(self.text[self.typed.len()])];
+        if !self.typed.is_empty() {
+            moves.push(Keystroke::Backspace);
+        }
+        moves
+    }
+
+    fn apply(&mut self, keystroke: Keystroke) -> bool {
+        if self.finished {
+            return false;
+        }
+        match keystroke {
+            Keystroke::Char(c) => {
+                self.started.get_or_insert_with(Instant::now);
+                let expected = self.text[self.typed.len()];
+                if c != expected {
+                    *self.misses.entry(expected).or_default() += 1;
+                }
+                self.keystrokes += 1;
+                self.typed.push(c);
+                self.extend();
+            }
+            Keystroke::Backspace => return self.typed.pop().is_some(),
+        }
+        true
+    }
+
+    /// A finished test counts as won: it can't be failed, only done slowly.
+    fn status(&self) -> Status {
+        match self.finished {
+            true => Status::Won,
+            false => Status::Playing,
+        }
+    }
+
+    /// A character of the text: dim until typed, then bright if typed right or red if not,
+    /// and reversed where the next one goes. Cells past the end of a line are blank.
+    fn render_cell(&self, [row, col]: [usize; 2]) -> (String, Style) {
+        let Some(index) = wrap(&self.text)
+            .get(row)
+            .and_then(|line| (line.start + col < line.end).then_some(line.start + col))
+        else {
+            return (String::new(), Style::default());
+        };
+        let expected = self.text[index];
+        match self.typed.get(index) {
+            Some(&typed) if typed == expected => (
+                expected.to_string(),
+                Style::default().fg(self.colors.row_fg),
+            ),
+            // a wrong space shows the character typed instead, or it couldn't be seen
+            Some(&typed) => (
+                match expected {
+                    ' ' => typed.to_string(),
+                    expected => expected.to_string(),
+                },
+                Style::default()
+                    .fg(Color::Red)
+                    .add_modifier(Modifier::UNDERLINED),
+            ),
+            None if index == self.typed.len() && !self.finished => (
+                expected.to_string(),
+                Style::default()
+                    .fg(tailwind::SLATE.c500)
+                    .add_modifier(Modifier::REVERSED),
+            ),
+            None => (
+                expected.to_string(),
+                Style::default().fg(tailwind::SLATE.c500),
+            ),
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Tab => self.restart(),
+            KeyCode::Left => self.change_duration(-1),
+            KeyCode::Right => self.change_duration(1),
+            KeyCode::Backspace => {
+                self.apply(Keystroke::Backspace);
+            }
+            KeyCode::Char(c) if self.takes_letters() => {
+                self.apply(Keystroke::Char(c));
+            }
+            KeyCode::Char('r') | KeyCode::Enter => self.restart(),
+            _ => {}
+        }
+    }
+
+    /// Every key is typed into the text until the time runs out.
+    fn takes_letters(&self) -> bool {
+        !self.finished
+    }
+
+    /// Count the time down, ending the test when it runs out.
+    fn tick(&mut self) -> bool {
+        if self.finished || self.started.is_none() {
+            return false;
+        }
+        if self.elapsed() >= DURATIONS[self.duration] {
+            self.finish();
+        }
+        true
+    }
+
+    fn apply_settings(&mut self, settings: &Settings) {
+        self.colors = settings.colors();
+    }
+
+    fn restart(&mut self) {
+        self.restart();
+    }
+
+    fn help(&self) -> Help {
+        Help {
+            rules: "Type the words shown for as long as the test lasts. Speed is in words per \
+                    minute of five characters, and accuracy counts every keystroke as it was \
+                    made."
+                .into(),
+            keys: vec![
+                ("letters", "type the words"),
+                ("Backspace", "fix a typo"),
+                ("←/→", "test length"),
+                ("Tab", "new test"),
+                ("r/Enter", "new test once over"),
+            ],
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let area = frame.area();
+        let width = LINE_WIDTH as u16 + 4;
+        let height = LINES_SHOWN as u16 + 10;
+        if area.width < width || area.height < height {
+            grid::render_too_small(frame, area, width, height);
+            return;
+        }
+
+        let area = center(area, Constraint::Length(width), Constraint::Length(height));
+        let [title_area, _, text_area, _, help_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(LINES_SHOWN as u16 + 6),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+        frame.render_widget(Paragraph::new(self.render_title()).centered(), title_area);
+        let block = Block::bordered().border_style(Style::default().fg(self.colors.row_fg));
+        let inner = block.inner(text_area);
+        frame.render_widget(block, text_area);
+        let inner = center(
+            inner,
+            Constraint::Length(LINE_WIDTH as u16),
+            Constraint::Percentage(100),
+        );
+        if self.finished {
+            frame.render_widget(Paragraph::new(self.render_results()), inner);
+        } else {
+            // the line being typed stays at the top, a blank line between each
+            let lines = wrap(&self.text);
+            let current = lines
+                .iter()
+                .position(|line| line.contains(&self.typed.len()))
+                .unwrap_or(lines.len() - 1);
+            let text: Vec<Line> = (current..current + LINES_SHOWN)
+                .flat_map(|row| {
+                    let line: Vec<Span> = (0..LINE_WIDTH)
+                        .map(|col| {
+                            let (text, style) = self.render_cell([row, col]);
+                            Span::styled(text, style)
+                        })
+                        .collect();
+                    [Line::from(line), Line::default()]
+                })
+                .collect();
+            let inner = center(
+                inner,
+                Constraint::Percentage(100),
+                Constraint::Length(2 * LINES_SHOWN as u16 - 1),
+            );
+            frame.render_widget(Paragraph::new(text), inner);
+        }
+        let help = "Type the words · ←/→ test length · Tab new test · Esc quit";
+        frame.render_widget(Paragraph::new(help).centered().dark_gray(), help_area);
+    }
+}
@@ -0,0 +1,117 @@
+//! Rules of ultimate tic-tac-toe, played on a 3x3 grid of 3x3 sub-boards.
+//!
+//! The board is stored as a plain 9x9 grid; sub-board `[row, col]` covers the cells from
+//! `[row * 3, col * 3]` to `[row * 3 + 2, col * 3 + 2]`.
+
+use crate::game::board::{CellState, Outcome, Player};
+
+/// Cells along one side of a sub-board, and sub-boards along one side of the board.
+const SIDE: usize = 3;
+
+/// Every line of three winning a 3x3 grid, as `[row, column]` pairs.
+const LINES: [[[usize; 2]; 3]; 8] = [
+    [[0, 0], [0, 1], [0, 2]],
+    [[1, 0], [1, 1], [1, 2]],
+    [[2, 0], [2, 1], [2, 2]],
+    [[0, 0], [1, 0], [2, 0]],
+    [[0, 1], [1, 1], [2, 1]],
+    [[0, 2], [1, 2], [2, 2]],
+    [[0, 0], [1, 1], [2, 2]],
+    [[0, 2], [1, 1], [2, 0]],
+];
+
+/// Decide a 3x3 grid given the owner of each of its cells.
+fn grid_outcome(owner: impl Fn([usize; 2]) -> Option<Player>) -> Option<Outcome> {
+    let winner = LINES.iter().find_map(|line| {
+        let player = owner(line[0])?;
+        line.iter()
+            .all(|&cell| owner(cell) == Some(player))
+            .then_some(player)
+    });
+    if let Some(player) = winner {
+        return Some(Outcome::Winner(player));
+    }
+    let full = (0..SIDE).all(|row| (0..SIDE).all(|col| owner([row, col]).is_some()));
+    full.then_some(Outcome::Draw)
+}
+
+/// The sub-board containing the cell `[row, col]`.
+pub const fn sub_board_of([row, col]: [usize; 2]) -> [usize; 2] {
+    [row / SIDE, col / SIDE]
+}
+
+/// The cells of sub-board `[board_row, board_col]`.
+fn cells_of([board_row, board_col]: [usize; 2]) -> impl Iterator<Item = [usize; 2]> {
+    (0..SIDE).flat_map(move |row| {
+        (0..SIDE).map(move |col| [board_row * SIDE + row, board_col * SIDE + col])
+    })
+}
+
+/// Whether sub-board `board` has been won by someone or filled up.
+pub fn sub_board_outcome(
+    items: &[Vec<CellState>],
+    [board_row, board_col]: [usize; 2],
+) -> Option<Outcome> {
+    grid_outcome(|[row, col]| items[board_row * SIDE + row][board_col * SIDE + col].owner())
+}
+
+/// The sub-board the next move must be played in, given the `last` move played.
+///
+/// The cell played within its sub-board sends the opponent to the matching sub-board. When
+/// that sub-board is already decided, or nothing has been played yet, any open sub-board may
+/// be chosen and `None` is returned.
+pub fn forced_sub_board(items: &[Vec<CellState>], last: Option<[usize; 2]>) -> Option<[usize; 2]> {
+    let [row, col] = last?;
+    let target = [row % SIDE, col % SIDE];
+    sub_board_outcome(items, target).is_none().then_some(target)
+}
+
+/// Every cell the player to move may play on, as `[row, column]` pairs.
+pub fn legal_moves(items: &[Vec<CellState>], last: Option<[usize; 2]>) -> Vec<[usize; 2]> {
+    if check_winner(items).is_some() {
+        return Vec::new();
+    }
+    let forced = forced_sub_board(items, last);
+    (0..SIDE)
+        .flat_map(|row| (0..SIDE).map(move |col| [row, col]))
+        .filter(|&board| {
+            forced.is_none_or(|forced| forced == board) && sub_board_outcome(items, board).is_none()
+        })
+        .flat_map(cells_of)
+        .filter(|&[row, col]| items[row][col].is_empty())
+        .collect()
+}
+
+/// Determine whether the whole game has been decided.
+///
+/// Someone wins by taking three sub-boards in a row; once every sub-board is decided without
+/// that, the game is drawn.
+pub fn check_winner(items: &[Vec<CellState>]) -> Option<Outcome> {
+    // a drawn sub-board is decided but belongs to nobody
+    let owner = |board| match sub_board_outcome(items, board) {
+        Some(Outcome::Winner(player)) => Some(player),
+        _ => None,
+    };
+    if let Some(Outcome::Winner(player)) = grid_outcome(owner) {
+        return Some(Outcome::Winner(player));
+    }
+    let all_decided =
+        (0..SIDE).all(|row| (0..SIDE).all(|col| sub_board_outcome(items, [row, col]).is_some()));
+    all_decided.then_some(Outcome::Draw)
+}
+
+/// The cells of the three sub-boards forming the winning line, together with their owner.
+pub fn winning_line(items: &[Vec<CellState>]) -> Option<(Player, Vec<[usize; 2]>)> {
+    LINES.iter().find_map(|line| {
+        let Some(Outcome::Winner(player)) = sub_board_outcome(items, line[0]) else {
+            return None;
+        };
+        if !line
+            .iter()
+            .all(|&board| sub_board_outcome(items, board) == Some(Outcome::Winner(player)))
+        {
+            return None;
+        }
+        Some((player, line.iter().copied().flat_map(cells_of).collect()))
+    })
+}
@@ -0,0 +1,784 @@
+//! Yahtzee against the bot: each turn, roll five dice up to three times, holding any between
+//! rolls, then score them in one of the thirteen boxes of the scorecard. Thirteen rounds
+//! fill the card, and the higher total wins.
+//!
+//! Upper boxes adding up to 63 or more earn a bonus of 35. A Yahtzee rolled once the
+//! Yahtzee box scored 50 earns another 100, and once the Yahtzee box is filled a Yahtzee
+//! also counts as a joker, scoring in full as a full house or either straight.
+//!
+//! The hard bot holds whichever dice give the best expected score after the next roll,
+//! taking each box's score over what it usually scores.
+
+use std::time::{Duration, Instant};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Flex, Layout, Rect},
+    style::{Modifier, Style, Stylize, palette::tailwind},
+    text::{Line, Span},
+    widgets::{Cell, Paragraph, Row, Table},
+};
+
+use crate::{
+    Outcome, Player, bot,
+    game::{Game, Help, Status},
+    grid,
+    settings::Settings,
+    ui::{
+        layout::center,
+        palette::{PALETTES, TableColors},
+    },
+};
+
+/// Dice rolled each turn.
+const DICE: usize = 5;
+
+/// Rolls allowed each turn.
+const ROLLS: usize = 3;
+
+/// How long the dice tumble before they land.
+const ROLL_TIME: Duration = Duration::from_millis(600);
+
+/// The upper total earning the bonus, and the bonus it earns.
+const UPPER_BONUS: (u32, u32) = (63, 35);
+
+/// What each Yahtzee after one scored 50 earns.
+const YAHTZEE_BONUS: u32 = 100;
+
+/// A box of the scorecard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Category {
+    Ones,
+    Twos,
+    Threes,
+    Fours,
+    Fives,
+    Sixes,
+    ThreeOfAKind,
+    FourOfAKind,
+    FullHouse,
+    SmallStraight,
+    LargeStraight,
+    Yahtzee,
+    Chance,
+}
+
+impl Category {
+    const ALL: [Self; 13] = [
+        Self::Ones,
+        Self::Twos,
+        Self::Threes,
+        Self::Fours,
+        Self::Fives,
+        Self::Sixes,
+        Self::ThreeOfAKind,
+        Self::FourOfAKind,
+        Self::FullHouse,
+        Self::SmallStraight,
+        Self::LargeStraight,
+        Self::Yahtzee,
+        Self::Chance,
+    ];
+
+    /// How many of [`Category::ALL`] are in the upper section.
+    const UPPER: usize = 6;
+
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Ones => "Ones",
+            Self::Twos => "Twos",
+            Self::Threes => "Threes",
+            Self::Fours => "Fours",
+            Self::Fives => "Fives",
+            Self::Sixes => "Sixes",
+            Self::ThreeOfAKind => "3 of a kind",
+            Self::FourOfAKind => "4 of a kind",
+            Self::FullHouse => "Full house",
+            Self::SmallStraight => "Sm. straight",
+            Self::LargeStraight => "Lg. straight",
+            Self::Yahtzee => "Yahtzee",
+            Self::Chance => "Chance",
+        }
+    }
+
+    /// Roughly what the box scores in a game played well, which a score in it is weighed
+    /// against.
+    const fn average(self) -> f64 {
+        match self {
+            Self::Ones => 2.1,
+            Self::Twos => 5.3,
+            Self::Threes => 8.6,
+            Self::Fours => 12.2,
+            Self::Fives => 15.7,
+            Self::Sixes => 19.2,
+            Self::ThreeOfAKind => 21.7,
+            Self::FourOfAKind => 13.1,
+            Self::FullHouse => 22.6,
+            Self::SmallStraight => 29.5,
+            Self::LargeStraight => 32.7,
+            Self::Yahtzee => 16.9,
+            Self::Chance => 22.0,
+        }
+    }
+
+    fn index(self) -> usize {
+        Self::ALL
+            .iter()
+            .position(|&category| category == self)
+            .unwrap_or(0)
+    }
+}
+
+type Dice = [u8; DICE];
+
+/// How many of each face `dice` show, indexed by face.
+fn counts(dice: &Dice) -> [usize; 7] {
+    let mut counts = [0; 7];
+    for &die in dice {
+        counts[usize::from(die)] += 1;
+    }
+    counts
+}
+
+/// Whether `dice` hold a run of `length` faces.
+fn has_run(counts: &[usize; 7], length: usize) -> bool {
+    counts[1..]
+        .windows(length)
+        .any(|run| run.iter().all(|&count| count > 0))
+}
+
+/// What `dice` score in `category`, as a joker if `joker`.
+fn score(category: Category, dice: &Dice, joker: bool) -> u32 {
+    let counts = counts(dice);
+    let sum = dice.iter().map(|&die| u32::from(die)).sum();
+    let most = counts.iter().max().copied().unwrap_or(0);
+    match category {
+        Category::Ones
+        | Category::Twos
+        | Category::Threes
+        | Category::Fours
+        | Category::Fives
+        | Category::Sixes => {
+            let face = category.index() + 1;
+            (counts[face] * face) as u32
+        }
+        Category::ThreeOfAKind if most >= 3 => sum,
+        Category::FourOfAKind if most >= 4 => sum,
+        Category::FullHouse if joker || (counts.contains(&3) && counts.contains(&2)) => 25,
+        Category::SmallStraight if joker || has_run(&counts, 4) => 30,
+        Category::LargeStraight if joker || has_run(&counts, 5) => 40,
+        Category::Yahtzee if most == DICE => 50,
+        Category::Chance => sum,
+        _ => 0,
+    }
+}
+
+/// A player's scores.
+#[derive(Debug, Clone, Copy, Default)]
+struct Scorecard {
+    /// The score in each box of [`Category::ALL`], once it is filled.
+    boxes: [Option<u32>; 13],
+    /// What extra Yahtzees have earned.
+    yahtzee_bonus: u32,
+}
+
+impl Scorecard {
+    fn is_open(&self, category: Category) -> bool {
+        self.boxes[category.index()].is_none()
+    }
+
+    fn open(&self) -> impl Iterator<Item = Category> + '_ {
+        Category::ALL
+            .into_iter()
+            .filter(|&category| self.is_open(category))
+    }
+
+    fn is_full(&self) -> bool {
+        self.open().next().is_none()
+    }
+
+    fn upper(&self) -> u32 {
+        self.boxes[..Category::UPPER].iter().flatten().sum()
+    }
+
+    fn upper_bonus(&self) -> u32 {
+        match self.upper() >= UPPER_BONUS.0 {
+            true => UPPER_BONUS.1,
+            false => 0,
+        }
+    }
+
+    fn total(&self) -> u32 {
+        self.boxes.iter().flatten().sum::<u32>() + self.upper_bonus() + self.yahtzee_bonus
+    }
+
+    /// Whether `dice` are a Yahtzee rolled once the Yahtzee box is filled.
+    fn is_joker(&self, dice: &Dice) -> bool {
+        counts(dice).contains(&DICE) && !self.is_open(Category::Yahtzee)
+    }
+
+    /// What scoring `dice` in `category` earns, counting any Yahtzee bonus.
+    fn gain(&self, category: Category, dice: &Dice) -> u32 {
+        let bonus = match self.is_joker(dice) && self.boxes[Category::Yahtzee.index()] == Some(50) {
+            true => YAHTZEE_BONUS,
+            false => 0,
+        };
+        score(category, dice, self.is_joker(dice)) + bonus
+    }
+
+    /// Score `dice` in `category`.
+    fn fill(&mut self, category: Category, dice: &Dice) {
+        let score = score(category, dice, self.is_joker(dice));
+        self.yahtzee_bonus += self.gain(category, dice) - score;
+        self.boxes[category.index()] = Some(score);
+    }
+
+    /// How good filling `category` with `dice` is: what it scores over the box's average,
+    /// and over what the upper boxes need on average to earn their bonus.
+    fn worth(&self, category: Category, dice: &Dice) -> f64 {
+        let gain = f64::from(self.gain(category, dice)) - category.average();
+        match category.index() {
+            face if face < Category::UPPER && self.upper() < UPPER_BONUS.0 => {
+                let par = 3.0 * (face + 1) as f64;
+                gain + (f64::from(score(category, dice, false)) - par) * 0.5
+            }
+            _ => gain,
+        }
+    }
+
+    /// The box `dice` are best scored in.
+    fn best_box(&self, dice: &Dice) -> Option<Category> {
+        self.open()
+            .max_by(|&a, &b| self.worth(a, dice).total_cmp(&self.worth(b, dice)))
+    }
+
+    /// How good `dice` are to score with: the worth of their best box.
+    fn value(&self, dice: &Dice) -> f64 {
+        self.open()
+            .map(|category| self.worth(category, dice))
+            .fold(f64::MIN, f64::max)
+    }
+
+    /// The dice to hold from `dice` for the best expected value after one more roll, or
+    /// `None` when holding them all, so scoring now, is best.
+    fn best_hold(&self, dice: &Dice) -> Option<[bool; DICE]> {
+        let mut best = (self.value(dice), None);
+        for mask in 0..(1 << DICE) - 1 {
+            let held: [bool; DICE] = std::array::from_fn(|i| mask & (1 << i) != 0);
+            let free: Vec<usize> = (0..DICE).filter(|&i| !held[i]).collect();
+            let outcomes = 6_usize.pow(free.len() as u32);
+            let mut total = 0.0;
+            for outcome in 0..outcomes {
+                let mut rolled = *dice;
+                let mut rest = outcome;
+                for &i in &free {
+                    rolled[i] = (rest % 6) as u8 + 1;
+                    rest /= 6;
+                }
+                total += self.value(&rolled);
+            }
+            let expected = total / outcomes as f64;
+            if expected > best.0 + 1e-9 {
+                best = (expected, Some(held));
+            }
+        }
+        best.1
+    }
+}
+
+/// A step of a turn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    /// Roll the dice not held.
+    Roll,
+    /// Hold or release a die.
+    Hold(usize),
+    /// Score the dice in a box, ending the turn.
+    Score(Category),
+}
+
+/// A game of Yahtzee against the bot.
+pub struct Yahtzee {
+    cards: [Scorecard; 2],
+    dice: Dice,
+    held: [bool; DICE],
+    /// Rolls left this turn.
+    rolls_left: usize,
+    /// When the dice last rolled land, while they tumble.
+    rolling: Option<Instant>,
+    turn: Player,
+    /// Who moved first this game; the sides take turns opening.
+    starter: Player,
+    opponent: bot::Opponent,
+    outcome: Option<Outcome>,
+    /// What the bot scored last, and in which box.
+    last_bot_move: Option<(Category, u32)>,
+    /// When the bot takes its next step, while it plays its turn.
+    bot_step_at: Option<Instant>,
+    /// The box under the cursor, as an index into [`Category::ALL`].
+    cursor: usize,
+    colors: TableColors,
+}
+
+impl Yahtzee {
+    pub fn new() -> Self {
+        Self {
+            cards: [Scorecard::default(); 2],
+            dice: [1; DICE],
+            held: [false; DICE],
+            rolls_left: ROLLS,
+            rolling: None,
+            turn: Player::Human,
+            starter: Player::Human,
+            opponent: bot::Opponent::Minimax,
+            outcome: None,
+            last_bot_move: None,
+            bot_step_at: None,
+            cursor: 0,
+            colors: TableColors::new(&PALETTES[0]),
+        }
+    }
+
+    /// Start a new game, with the other side opening.
+    fn rematch(&mut self) {
+        let starter = self.starter.other();
+        *self = Self {
+            colors: self.colors,
+            turn: starter,
+            starter,
+            opponent: self.opponent,
+            ..Self::new()
+        };
+    }
+
+    const fn card(&self, player: Player) -> &Scorecard {
+        match player {
+            Player::Human => &self.cards[0],
+            Player::Bot => &self.cards[1],
+        }
+    }
+
+    const fn card_mut(&mut self, player: Player) -> &mut Scorecard {
+        match player {
+            Player::Human => &mut self.cards[0],
+            Player::Bot => &mut self.cards[1],
+        }
+    }
+
+    /// The steps the side to move may take.
+    fn actions(&self) -> Vec<Action> {
+        if self.outcome.is_some() || self.rolling.is_some() {
+            return Vec::new();
+        }
+        let mut actions = Vec::new();
+        if self.rolls_left > 0 && self.held.contains(&false) {
+            actions.push(Action::Roll);
+        }
+        if self.rolls_left < ROLLS {
+            actions.extend((0..DICE).map(Action::Hold));
+            actions.extend(self.card(self.turn).open().map(Action::Score));
+        }
+        actions
+    }
+
+    /// Take `action` for the side to move.
+    fn play(&mut self, action: Action) {
+        match action {
+            Action::Roll => {
+                for (die, held) in self.dice.iter_mut().zip(self.held) {
+                    if !held {
+                        *die = rand::random_range(1..=6);
+                    }
+                }
+                self.rolls_left -= 1;
+                self.rolling = Some(Instant::now() + ROLL_TIME);
+            }
+            Action::Hold(die) => self.held[die] = !self.held[die],
+            Action::Score(category) => {
+                let dice = self.dice;
+                let card = self.card_mut(self.turn);
+                card.fill(category, &dice);
+                if self.turn == Player::Bot {
+                    let score = self.card(Player::Bot).boxes[category.index()].unwrap_or(0);
+                    self.last_bot_move = Some((category, score));
+                }
+                self.held = [false; DICE];
+                self.rolls_left = ROLLS;
+                self.turn = self.turn.other();
+                if self.cards.iter().all(Scorecard::is_full) {
+                    let (human, bot) = (self.cards[0].total(), self.cards[1].total());
+                    self.outcome = Some(match human.cmp(&bot) {
+                        std::cmp::Ordering::Greater => Outcome::Winner(Player::Human),
+                        std::cmp::Ordering::Less => Outcome::Winner(Player::Bot),
+                        std::cmp::Ordering::Equal => Outcome::Draw,
+                    });
+                }
+            }
+        }
+    }
+
+    /// The bot's next step: roll first, then hold what it likes and roll again, or score
+    /// once rolling more isn't worth it.
+    fn bot_move(&mut self) {
+        let card = *self.card(Player::Bot);
+        if self.rolls_left == ROLLS {
+            self.play(Action::Roll);
+            return;
+        }
+        let hold = match self.opponent {
+            // the easy bot keeps its most common face and rolls on at random
+            bot::Opponent::Random => {
+                let counts = counts(&self.dice);
+                let common = (1..=6).max_by_key(|&face| counts[face]).unwrap_or(6) as u8;
+                rand::random_bool(0.6).then(|| self.dice.map(|die| die == common))
+            }
+            bot::Opponent::Heuristic | bot::Opponent::Minimax => card.best_hold(&self.dice),
+        };
+        if let Some(hold) = hold.filter(|_| self.rolls_left > 0)
+            && hold.contains(&false)
+        {
+            if let Some(die) = (0..DICE).find(|&die| self.held[die] != hold[die]) {
+                // dice are held one at a time, so each can be seen
+                self.play(Action::Hold(die));
+            } else {
+                self.play(Action::Roll);
+            }
+            return;
+        }
+        let category = match self.opponent {
+            bot::Opponent::Random => card
+                .open()
+                .max_by_key(|&category| card.gain(category, &self.dice)),
+            bot::Opponent::Heuristic | bot::Opponent::Minimax => card.best_box(&self.dice),
+        };
+        if let Some(category) = category {
+            self.play(Action::Score(category));
+        }
+    }
+
+    /// The faces shown: tumbling dice show random ones until they land.
+    fn shown_dice(&self) -> Dice {
+        match self.rolling {
+            Some(_) => std::array::from_fn(|i| match self.held[i] {
+                true => self.dice[i],
+                false => rand::random_range(1..=6),
+            }),
+            None => self.dice,
+        }
+    }
+
+    fn render_title(&self) -> Line<'static> {
+        let versus = format!(
+            "Yahtzee · You {} : {} Bot [{}]",
+            self.cards[0].total(),
+            self.cards[1].total(),
+            self.opponent.label()
+        );
+        let highlight = self.colors.selected_cell_style_fg;
+        let status = match (self.outcome, self.turn) {
+            (Some(outcome), _) => format!("{} (r: rematch)", outcome.message()).into(),
+            _ if self.rolling.is_some() => "Rolling…".fg(self.colors.row_fg),
+            (None, Player::Bot) => "Bot's turn…".fg(self.colors.row_fg),
+            (None, Player::Human) => match (self.rolls_left, self.last_bot_move) {
+                (ROLLS, Some((category, score))) => format!(
+                    "Bot scored {score} in {} · Your roll",
+                    category.name().to_lowercase()
+                )
+                .fg(highlight),
+                (ROLLS, None) => "Your roll".fg(highlight),
+                (0, _) => "Pick a box to score".fg(highlight),
+                (left, _) => format!("{left} rolls left").fg(highlight),
+            },
+        };
+        Line::from(vec![versus.into(), " · ".dark_gray(), status])
+    }
+
+    /// A die showing `face` as three rows of pips in a frame, or blank before the first roll.
+    fn render_die(&self, face: Option<u8>, held: bool) -> [Line<'static>; 5] {
+        let pips: [[bool; 3]; 3] = match face {
+            Some(1) => [[false; 3], [false, true, false], [false; 3]],
+            Some(2) => [[true, false, false], [false; 3], [false, false, true]],
+            Some(3) => [
+                [true, false, false],
+                [false, true, false],
+                [false, false, true],
+            ],
+            Some(4) => [[true, false, true], [false; 3], [true, false, true]],
+            Some(5) => [
+                [true, false, true],
+                [false, true, false],
+                [true, false, true],
+            ],
+            Some(6) => [[true, false, true]; 3],
+            _ => [[false; 3]; 3],
+        };
+        let style = match held {
+            true => Style::default()
+                .fg(self.colors.selected_cell_style_fg)
+                .add_modifier(Modifier::BOLD),
+            false => Style::default().fg(self.colors.row_fg),
+        };
+        let row = |pips: [bool; 3]| {
+            let pips = pips.map(|pip| if pip { "●" } else { " " });
+            Line::from(Span::styled(
+                format!("│ {} {} {} │", pips[0], pips[1], pips[2]),
+                style,
+            ))
+        };
+        [
+            Line::from(Span::styled("┌───────┐", style)),
+            row(pips[0]),
+            row(pips[1]),
+            row(pips[2]),
+            Line::from(Span::styled("└───────┘", style)),
+        ]
+    }
+
+    fn render_dice(&self, frame: &mut Frame, area: Rect) {
+        let rolled = self.rolls_left < ROLLS || self.rolling.is_some();
+        let dice = self.shown_dice();
+        let areas = Layout::horizontal([Constraint::Length(9); DICE])
+            .spacing(2)
+            .flex(Flex::Center)
+            .split(area);
+        for (i, &die_area) in areas.iter().enumerate() {
+            let held = self.held[i];
+            let mut lines: Vec<Line> = self.render_die(rolled.then_some(dice[i]), held).into();
+            let label = match held {
+                true => format!("{} held", i + 1).fg(self.colors.selected_cell_style_fg),
+                false => format!("{}", i + 1).dark_gray(),
+            };
+            lines.push(Line::from(label));
+            frame.render_widget(Paragraph::new(lines).centered(), die_area);
+        }
+    }
+
+    /// A section of the scorecard: `categories` and the `extra` rows totalling them.
+    fn render_section(
+        &self,
+        categories: &[Category],
+        extra: Vec<(&'static str, u32, u32)>,
+    ) -> Table<'static> {
+        let header = Row::new(["", "You", "Bot"]).style(Style::default().bold());
+        let mut rows: Vec<Row> = categories
+            .iter()
+            .map(|&category| {
+                let (you, you_style) = self.render_cell([category.index(), 0]);
+                let bot = self.card(Player::Bot).boxes[category.index()]
+                    .map_or(String::new(), |score| score.to_string());
+                let name_style = match category.index() == self.cursor {
+                    true => Style::default()
+                        .fg(self.colors.selected_cell_style_fg)
+                        .add_modifier(Modifier::REVERSED),
+                    false => Style::default().fg(self.colors.row_fg),
+                };
+                Row::new([
+                    Cell::from(category.name()).style(name_style),
+                    Cell::from(Line::from(you).right_aligned()).style(you_style),
+                    Cell::from(Line::from(bot).right_aligned()),
+                ])
+            })
+            .collect();
+        rows.extend(extra.into_iter().map(|(name, you, bot)| {
+            Row::new([
+                Cell::from(name),
+                Cell::from(Line::from(you.to_string()).right_aligned()),
+                Cell::from(Line::from(bot.to_string()).right_aligned()),
+            ])
+            .style(Style::default().fg(tailwind::SLATE.c400).italic())
+        }));
+        Table::new(
+            rows,
+            [
+                Constraint::Length(13),
+                Constraint::Length(5),
+                Constraint::Length(5),
+            ],
+        )
+        .header(header)
+    }
+}
+
+impl Game for Yahtzee {
+    type Move = Action;
+
+    /// The boxes of the scorecard, one per row.
+    fn dimensions(&self) -> (usize, usize) {
+        (Category::ALL.len(), 1)
+    }
+
+    fn legal_moves(&self) -> Vec<Action> {
+        match self.turn {
+            Player::Human => self.actions(),
+            Player::Bot => Vec::new(),
+        }
+    }
+
+    fn apply(&mut self, action: Action) -> bool {
+        if !self.legal_moves().contains(&action) {
+            return false;
+        }
+        self.play(action);
+        true
+    }
+
+    fn status(&self) -> Status {
+        match self.outcome {
+            None => Status::Playing,
+            Some(Outcome::Winner(Player::Human)) => Status::Won,
+            Some(Outcome::Winner(Player::Bot)) => Status::Lost,
+            Some(Outcome::Draw) => Status::Draw,
+        }
+    }
+
+    fn opponent(&self) -> Option<bot::Opponent> {
+        Some(self.opponent)
+    }
+
+    /// The human's score in a box, or what the dice would score there while it is open.
+    fn render_cell(&self, [row, _]: [usize; 2]) -> (String, Style) {
+        let category = Category::ALL[row];
+        let card = self.card(Player::Human);
+        match card.boxes[row] {
+            Some(score) => (score.to_string(), Style::default()),
+            None if self.turn == Player::Human
+                && self.rolls_left < ROLLS
+                && self.rolling.is_none() =>
+            {
+                (
+                    card.gain(category, &self.dice).to_string(),
+                    Style::default()
+                        .fg(tailwind::SLATE.c500)
+                        .add_modifier(Modifier::ITALIC),
+                )
+            }
+            None => (String::new(), Style::default()),
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        let boxes = Category::ALL.len();
+        match key.code {
+            KeyCode::Char('r') => self.rematch(),
+            KeyCode::Char('b') => self.opponent = self.opponent.next(),
+            KeyCode::Char(digit @ '1'..='5') => {
+                self.apply(Action::Hold(digit as usize - '1' as usize));
+            }
+            KeyCode::Char(' ') => {
+                self.apply(Action::Roll);
+            }
+            KeyCode::Enter => {
+                self.apply(Action::Score(Category::ALL[self.cursor]));
+            }
+            KeyCode::Up | KeyCode::Char('w') => self.cursor = (self.cursor + boxes - 1) % boxes,
+            KeyCode::Down | KeyCode::Char('s') => self.cursor = (self.cursor + 1) % boxes,
+            // across to the box at the same height in the other section
+            KeyCode::Left | KeyCode::Right | KeyCode::Char('a') | KeyCode::Char('d') => {
+                self.cursor = match self.cursor < Category::UPPER {
+                    true => self.cursor + Category::UPPER,
+                    false => (self.cursor - Category::UPPER).min(Category::UPPER - 1),
+                };
+            }
+            _ => {}
+        }
+    }
+
+    /// Let tumbling dice land, or give the bot its next step.
+    fn tick(&mut self) -> bool {
+        if let Some(lands) = self.rolling {
+            if Instant::now() >= lands {
+                self.rolling = None;
+            }
+            return true;
+        }
+        if self.outcome.is_some() || self.turn != Player::Bot {
+            self.bot_step_at = None;
+            return false;
+        }
+        let step_at = *self
+            .bot_step_at
+            .get_or_insert_with(|| Instant::now() + bot::BOT_DELAY / 2);
+        if Instant::now() < step_at {
+            return false;
+        }
+        self.bot_step_at = None;
+        self.bot_move();
+        true
+    }
+
+    fn apply_settings(&mut self, settings: &Settings) {
+        self.colors = settings.colors();
+        self.opponent = settings.opponent(self.opponent);
+    }
+
+    fn restart(&mut self) {
+        self.rematch();
+    }
+
+    fn help(&self) -> Help {
+        Help {
+            rules: "Each turn, roll five dice up to three times, holding any between rolls, then \
+                    score them in an empty box of the scorecard. Upper boxes adding up to 63 earn \
+                    a bonus of 35; after thirteen rounds the higher total wins."
+                .into(),
+            keys: vec![
+                ("Space", "roll"),
+                ("1-5", "hold or release a die"),
+                ("←↑↓→/wasd", "choose a box"),
+                ("Enter", "score in it"),
+                ("b", "switch bot"),
+                ("r", "rematch"),
+            ],
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let area = frame.area();
+        let width = 66;
+        let height = 22;
+        if area.width < width || area.height < height {
+            grid::render_too_small(frame, area, width, height);
+            return;
+        }
+
+        let area = center(area, Constraint::Length(width), Constraint::Length(height));
+        let [title_area, _, dice_area, _, card_area, help_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(6),
+            Constraint::Length(1),
+            Constraint::Length(11),
+            Constraint::Length(2),
+        ])
+        .areas(area);
+        frame.render_widget(Paragraph::new(self.render_title()).centered(), title_area);
+        self.render_dice(frame, dice_area);
+
+        let [upper_area, lower_area] =
+            Layout::horizontal([Constraint::Length(27), Constraint::Length(27)])
+                .spacing(4)
+                .flex(Flex::Center)
+                .areas(card_area);
+        let (you, bot) = (self.card(Player::Human), self.card(Player::Bot));
+        let upper = self.render_section(
+            &Category::ALL[..Category::UPPER],
+            vec![
+                ("Bonus", you.upper_bonus(), bot.upper_bonus()),
+                ("Upper", you.upper(), bot.upper()),
+            ],
+        );
+        let lower = self.render_section(
+            &Category::ALL[Category::UPPER..],
+            vec![
+                ("Yahtzee bonus", you.yahtzee_bonus, bot.yahtzee_bonus),
+                ("Total", you.total(), bot.total()),
+            ],
+        );
+        frame.render_widget(upper, upper_area);
+        frame.render_widget(lower, lower_area);
+        let help =
+            "Space roll · 1-5 hold · arrows pick a box · Enter score\nb switch bot · r rematch";
+        frame.render_widget(Paragraph::new(help).centered().dark_gray(), help_area);
+    }
+}
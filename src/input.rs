@@ -0,0 +1,37 @@
+//! The keys the player moves with, as the games see them.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// The keys moving the cursor, besides the arrow keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Keys {
+    Wasd,
+    Hjkl,
+}
+
+impl Keys {
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Wasd => "Arrows or WASD",
+            Self::Hjkl => "Arrows or HJKL",
+        }
+    }
+
+    /// `key` as the games see it: with HJKL, those letters are turned into the arrow keys,
+    /// unless held with `Ctrl` or `Alt`.
+    pub fn translate(self, mut key: KeyEvent) -> KeyEvent {
+        let held = key
+            .modifiers
+            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT);
+        if self == Self::Hjkl && !held {
+            key.code = match key.code {
+                KeyCode::Char('h') => KeyCode::Left,
+                KeyCode::Char('j') => KeyCode::Down,
+                KeyCode::Char('k') => KeyCode::Up,
+                KeyCode::Char('l') => KeyCode::Right,
+                code => code,
+            };
+        }
+        key
+    }
+}
@@ -0,0 +1,100 @@
+//! Games played in the terminal against a bot, chosen from a menu: tic-tac-toe and its
+//! variants, and a few dozen others.
+//!
+//! The rules of the games of placing marks in a row are in [`game::board`] and the bots playing
+//! them in [`bot`], where they can be used without a terminal.
+
+pub mod bot;
+pub mod config;
+pub mod engine;
+pub mod error;
+pub mod game;
+pub mod input;
+mod ui;
+
+mod app;
+mod options;
+
+use std::env;
+
+use config::{records, save, settings, stats, theme};
+use error::Error;
+use game::board::{Outcome, Player};
+use settings::Settings;
+use ui::{banner, color, grid, menu, screen::Screens, splash, toast};
+
+pub use options::GameMode;
+
+use app::Symbols;
+use options::Options;
+
+/// Play from the command line `env::args` gives, until the player quits the menu.
+///
+/// Trouble along the way is shown in a dialog and play goes on; only what stops the program
+/// from starting, or the terminal from working, is returned.
+pub async fn run() -> Result<(), Error> {
+    let options = Options::parse(env::args().skip(1))?;
+    let (themes, theme_error) = theme::load(options.themes.as_deref());
+    let (mut settings, config_error) = Settings::load(themes);
+    settings.color = options.color.unwrap_or_else(color::Mode::detect);
+    // marks given on the command line take the place of the saved ones for this run
+    if options.symbols != Symbols::default() {
+        settings.given_symbols = Some(options.symbols.clone());
+    }
+    // report invalid options for the chosen game before taking over the terminal
+    if let Some(game) = options.game {
+        options.session(game, &settings)?;
+    }
+
+    let mut terminal = ratatui::init();
+    if settings.color != color::Mode::None {
+        settings.light_terminal = color::light_background().unwrap_or(false);
+    }
+    let errors = theme_error.into_iter().chain(config_error).collect();
+    let result = Screens::new(options, errors)
+        .run(&mut terminal, &mut settings)
+        .await;
+    ratatui::restore();
+    result
+}
+
+/// Evaluate `$body` with `$game` bound to the boxed game `$session` holds, whichever kind it
+/// is.
+macro_rules! with_game {
+    ($session:expr, $game:ident => $body:expr) => {
+        match $session {
+            $crate::options::Session::Board($game) => $body,
+            $crate::options::Session::Minesweeper($game) => $body,
+            $crate::options::Session::TwentyFortyEight($game) => $body,
+            $crate::options::Session::Sudoku($game) => $body,
+            $crate::options::Session::Checkers($game) => $body,
+            $crate::options::Session::Chess($game) => $body,
+            $crate::options::Session::Battleship($game) => $body,
+            $crate::options::Session::Memory($game) => $body,
+            $crate::options::Session::Hangman($game) => $body,
+            $crate::options::Session::Tetris($game) => $body,
+            $crate::options::Session::Life($game) => $body,
+            $crate::options::Session::Reversi($game) => $body,
+            $crate::options::Session::Nim($game) => $body,
+            $crate::options::Session::Dots($game) => $body,
+            $crate::options::Session::LightsOut($game) => $body,
+            $crate::options::Session::Blackjack($game) => $body,
+            $crate::options::Session::Mancala($game) => $body,
+            $crate::options::Session::Pong($game) => $body,
+            $crate::options::Session::Maze($game) => $body,
+            $crate::options::Session::Sokoban($game) => $body,
+            $crate::options::Session::Nonogram($game) => $body,
+            $crate::options::Session::Hex($game) => $body,
+            $crate::options::Session::Go($game) => $body,
+            $crate::options::Session::Rps($game) => $body,
+            $crate::options::Session::Simon($game) => $body,
+            $crate::options::Session::Typing($game) => $body,
+            $crate::options::Session::Quiz($game) => $body,
+            $crate::options::Session::Yahtzee($game) => $body,
+            $crate::options::Session::Hanoi($game) => $body,
+            $crate::options::Session::Sos($game) => $body,
+            $crate::options::Session::Pente($game) => $body,
+        }
+    };
+}
+pub(crate) use with_game;
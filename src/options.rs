@@ -0,0 +1,1151 @@
+//! The command line: the options it takes, the games they choose among and the game they set
+//! up.
+
+use std::{fs, ops::RangeInclusive, path::PathBuf, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{
+    app::{App, FirstMove, SavedBoard, Symbols},
+    error::{Error, Result},
+    game::{
+        battleship::Battleship,
+        blackjack::Blackjack,
+        board::{Player, Rules},
+        checkers::Checkers,
+        chess::Chess,
+        dots::{self, Dots},
+        game2048::Game2048,
+        go::Go,
+        hangman::{self, Hangman},
+        hanoi::Hanoi,
+        hex::Hex,
+        life::{self, Life, Pattern},
+        lights::{self, LightsOut},
+        mancala::Mancala,
+        maze::{self, Maze},
+        memory::{self, Memory},
+        minesweeper::{self, Minesweeper},
+        nim::{self, Nim},
+        nonogram::{self, Nonogram},
+        pente::Pente,
+        pong::Pong,
+        quiz::{self, Quiz},
+        reversi::Reversi,
+        rps::RockPaperScissors,
+        simon::Simon,
+        sokoban::{self, Sokoban},
+        sos::{self, Sos},
+        sudoku::{Difficulty, Sudoku},
+        tetris::Tetris,
+        typing::{self, Typing},
+        yahtzee::Yahtzee,
+    },
+    settings::Settings,
+    ui::color,
+};
+
+/// A game set up and ready to be played.
+pub enum Session {
+    /// One of the games of placing marks in a row against the bot.
+    Board(Box<App>),
+    Minesweeper(Box<Minesweeper>),
+    TwentyFortyEight(Box<Game2048>),
+    Sudoku(Box<Sudoku>),
+    Checkers(Box<Checkers>),
+    Chess(Box<Chess>),
+    Battleship(Box<Battleship>),
+    Memory(Box<Memory>),
+    Hangman(Box<Hangman>),
+    Tetris(Box<Tetris>),
+    Life(Box<Life>),
+    Reversi(Box<Reversi>),
+    Nim(Box<Nim>),
+    Dots(Box<Dots>),
+    LightsOut(Box<LightsOut>),
+    Blackjack(Box<Blackjack>),
+    Mancala(Box<Mancala>),
+    Pong(Box<Pong>),
+    Maze(Box<Maze>),
+    Sokoban(Box<Sokoban>),
+    Nonogram(Box<Nonogram>),
+    Hex(Box<Hex>),
+    Go(Box<Go>),
+    Rps(Box<RockPaperScissors>),
+    Simon(Box<Simon>),
+    Typing(Box<Typing>),
+    Quiz(Box<Quiz>),
+    Yahtzee(Box<Yahtzee>),
+    Hanoi(Box<Hanoi>),
+    Sos(Box<Sos>),
+    Pente(Box<Pente>),
+}
+
+/// The options given on the command line.
+#[derive(Debug)]
+pub struct Options {
+    /// The game selected in the menu's list of games when it opens.
+    pub game: Option<GameMode>,
+    first_move: FirstMove,
+    pub symbols: Symbols,
+    size: Option<usize>,
+    win_length: Option<usize>,
+    misere: bool,
+    wild: bool,
+    wrap: bool,
+    swap: bool,
+    boards: Option<usize>,
+    order: Option<Player>,
+    /// The rows and columns of a Minesweeper, Memory, Life, Dots and Boxes, Lights Out, maze or
+    /// SOS grid.
+    grid: Option<(usize, usize)>,
+    /// The share of a Minesweeper grid hiding mines, in percent.
+    density: Option<usize>,
+    /// How hard a Sudoku puzzle is.
+    difficulty: Option<Difficulty>,
+    /// A file of words to play Hangman or take a typing test with in place of the bundled
+    /// ones.
+    words: Option<PathBuf>,
+    /// An RLE file of a pattern to start Life with.
+    pattern: Option<PathBuf>,
+    /// An XSB file of levels to play Sokoban with in place of the bundled ones.
+    levels: Option<PathBuf>,
+    /// A file of pictures to play nonograms with in place of the bundled ones.
+    puzzles: Option<PathBuf>,
+    /// A TOML or JSON file of quiz questions to play in place of the bundled ones.
+    pack: Option<PathBuf>,
+    /// A TOML file of themes to offer besides the built-in ones, in place of the one in the
+    /// config directory.
+    pub themes: Option<PathBuf>,
+    /// The colors to draw in, in place of those the terminal seems to show.
+    pub color: Option<color::Mode>,
+    /// The stones in each heap of a Nim game.
+    heaps: Option<Vec<usize>>,
+}
+
+impl Options {
+    /// Parse the command-line arguments `args`, without the program name.
+    pub fn parse(args: impl Iterator<Item = String>) -> Result<Self> {
+        let mut options = Self {
+            game: None,
+            first_move: FirstMove::Alternate,
+            symbols: Symbols::default(),
+            size: None,
+            win_length: None,
+            misere: false,
+            wild: false,
+            wrap: false,
+            swap: false,
+            boards: None,
+            order: None,
+            grid: None,
+            density: None,
+            difficulty: None,
+            words: None,
+            pattern: None,
+            levels: None,
+            puzzles: None,
+            pack: None,
+            themes: None,
+            color: None,
+            heaps: None,
+        };
+        for arg in args {
+            if let Some(value) = arg.strip_prefix("--game=") {
+                options.game = Some(value.parse()?);
+            } else if let Some(value) = arg.strip_prefix("--size=") {
+                options.size = Some(parse_board_size(value)?);
+            } else if let Some(value) = arg.strip_prefix("--win=") {
+                let win_length = value.parse();
+                options.win_length = Some(win_length.map_err(|_| {
+                    Error::invalid("win length", value, "a number from 3 to the board size")
+                })?);
+            } else if let Some(value) = arg.strip_prefix("--boards=") {
+                options.boards = Some(parse_board_count(value)?);
+            } else if let Some(value) = arg.strip_prefix("--role=") {
+                options.order = Some(match value {
+                    "order" => Player::Human,
+                    "chaos" => Player::Bot,
+                    _ => return Err(Error::invalid("role", value, "`order` or `chaos`")),
+                });
+            } else if let Some(value) = arg.strip_prefix("--grid=") {
+                options.grid = Some(parse_grid(value)?);
+            } else if let Some(value) = arg.strip_prefix("--mines=") {
+                options.density = Some(parse_mine_density(value)?);
+            } else if let Some(value) = arg.strip_prefix("--difficulty=") {
+                options.difficulty = Some(value.parse()?);
+            } else if let Some(value) = arg.strip_prefix("--words=") {
+                options.words = Some(PathBuf::from(value));
+            } else if let Some(value) = arg.strip_prefix("--pattern=") {
+                options.pattern = Some(PathBuf::from(value));
+            } else if let Some(value) = arg.strip_prefix("--levels=") {
+                options.levels = Some(PathBuf::from(value));
+            } else if let Some(value) = arg.strip_prefix("--puzzles=") {
+                options.puzzles = Some(PathBuf::from(value));
+            } else if let Some(value) = arg.strip_prefix("--pack=") {
+                options.pack = Some(PathBuf::from(value));
+            } else if let Some(value) = arg.strip_prefix("--themes=") {
+                options.themes = Some(PathBuf::from(value));
+            } else if let Some(value) = arg.strip_prefix("--color=") {
+                options.color = Some(color::Mode::parse(value)?);
+            } else if let Some(value) = arg.strip_prefix("--heaps=") {
+                options.heaps = Some(parse_heaps(value)?);
+            } else if arg == "--misere" {
+                options.misere = true;
+            } else if arg == "--wild" {
+                options.wild = true;
+            } else if arg == "--wrap" {
+                options.wrap = true;
+            } else if arg == "--swap" {
+                options.swap = true;
+            } else if let Some(value) = arg.strip_prefix("--first=") {
+                options.first_move = value.parse()?;
+            } else if let Some(value) = arg.strip_prefix("--me=") {
+                options.symbols.human = parse_symbol(value)?;
+            } else if let Some(value) = arg.strip_prefix("--bot=") {
+                options.symbols.bot = parse_symbol(value)?;
+            } else {
+                return Err(Error::UnknownArgument(arg));
+            }
+        }
+        if options.symbols.human == options.symbols.bot {
+            return Err(Error::SameMarks(options.symbols.human));
+        }
+        Ok(options)
+    }
+
+    /// Set up `game` as the options ask for, and as `settings` do where the options leave it
+    /// open, rejecting options that do not apply to it.
+    pub fn session(&self, game: GameMode, settings: &Settings) -> Result<Session> {
+        if !matches!(
+            game,
+            GameMode::Minesweeper
+                | GameMode::Memory
+                | GameMode::Life
+                | GameMode::Dots
+                | GameMode::LightsOut
+                | GameMode::Maze
+                | GameMode::Sos
+        ) && self.grid.is_some()
+        {
+            return Err(Error::OnlyFor {
+                option: "--grid",
+                games: "`--game=minesweeper`, `--game=memory`, `--game=life`, `--game=dots`, `--game=lights`, `--game=maze` or `--game=sos`",
+            });
+        }
+        if game != GameMode::Minesweeper && self.density.is_some() {
+            return Err(Error::OnlyFor {
+                option: "--mines",
+                games: "`--game=minesweeper`",
+            });
+        }
+        if game != GameMode::Sudoku && self.difficulty.is_some() {
+            return Err(Error::OnlyFor {
+                option: "--difficulty",
+                games: "`--game=sudoku`",
+            });
+        }
+        if !matches!(game, GameMode::Hangman | GameMode::Typing) && self.words.is_some() {
+            return Err(Error::OnlyFor {
+                option: "--words",
+                games: "`--game=hangman` or `--game=typing`",
+            });
+        }
+        if game != GameMode::Life && self.pattern.is_some() {
+            return Err(Error::OnlyFor {
+                option: "--pattern",
+                games: "`--game=life`",
+            });
+        }
+        if game != GameMode::Sokoban && self.levels.is_some() {
+            return Err(Error::OnlyFor {
+                option: "--levels",
+                games: "`--game=sokoban`",
+            });
+        }
+        if game != GameMode::Nonogram && self.puzzles.is_some() {
+            return Err(Error::OnlyFor {
+                option: "--puzzles",
+                games: "`--game=nonogram`",
+            });
+        }
+        if game != GameMode::Quiz && self.pack.is_some() {
+            return Err(Error::OnlyFor {
+                option: "--pack",
+                games: "`--game=quiz`",
+            });
+        }
+        if game != GameMode::Nim && self.heaps.is_some() {
+            return Err(Error::OnlyFor {
+                option: "--heaps",
+                games: "`--game=nim`",
+            });
+        }
+        let (rows, cols, mut rules) = match game {
+            GameMode::TicTacToe => {
+                let size = self.size.unwrap_or(settings.board_size);
+                let mut rules = Rules::new(size);
+                if let Some(win_length) = self.win_length {
+                    if !(3..=size).contains(&win_length) {
+                        return Err(Error::invalid(
+                            "win length",
+                            win_length.to_string(),
+                            format!("a number from 3 to the board size {size}"),
+                        ));
+                    }
+                    rules.win_length = win_length;
+                }
+                (size, size, rules)
+            }
+            GameMode::ConnectFour => {
+                if self.size.is_some() || self.win_length.is_some() {
+                    return Err(Error::Conflict {
+                        options: "`--size` and `--win`",
+                        with: "`--game=connect4`".into(),
+                    });
+                }
+                let rules = Rules {
+                    gravity: true,
+                    ..Rules::new(4)
+                };
+                (6, 7, rules)
+            }
+            GameMode::Gomoku => {
+                if self.size.is_some() || self.win_length.is_some() {
+                    return Err(Error::Conflict {
+                        options: "`--size` and `--win`",
+                        with: "`--game=gomoku`".into(),
+                    });
+                }
+                let rules = Rules::new(5);
+                (15, 15, rules)
+            }
+            GameMode::Ultimate => {
+                if self.size.is_some() || self.win_length.is_some() {
+                    return Err(Error::Conflict {
+                        options: "`--size` and `--win`",
+                        with: "`--game=ultimate`".into(),
+                    });
+                }
+                let rules = Rules {
+                    ultimate: true,
+                    ..Rules::new(3)
+                };
+                (9, 9, rules)
+            }
+            GameMode::Qubic => {
+                if self.size.is_some() || self.win_length.is_some() {
+                    return Err(Error::Conflict {
+                        options: "`--size` and `--win`",
+                        with: "`--game=qubic`".into(),
+                    });
+                }
+                let rules = Rules {
+                    layers: 4,
+                    ..Rules::new(4)
+                };
+                (4, 16, rules)
+            }
+            GameMode::Notakto => {
+                if self.size.is_some() || self.win_length.is_some() || self.misere || self.wild {
+                    return Err(Error::Conflict {
+                        options: "`--size`, `--win`, `--misere` and `--wild`",
+                        with: "`--game=notakto`".into(),
+                    });
+                }
+                let boards = self.boards.unwrap_or(1);
+                let rules = Rules {
+                    misere: true,
+                    notakto: true,
+                    boards,
+                    ..Rules::new(3)
+                };
+                (3, 3 * boards, rules)
+            }
+            GameMode::OrderAndChaos => {
+                if self.size.is_some() || self.win_length.is_some() || self.misere || self.wild {
+                    return Err(Error::Conflict {
+                        options: "`--size`, `--win`, `--misere` and `--wild`",
+                        with: "`--game=orderchaos`".into(),
+                    });
+                }
+                let rules = Rules {
+                    wild: true,
+                    order: Some(self.order.unwrap_or(Player::Human)),
+                    ..Rules::new(5)
+                };
+                (6, 6, rules)
+            }
+            GameMode::Quantum => {
+                if self.size.is_some() || self.win_length.is_some() || self.misere || self.wild {
+                    return Err(Error::Conflict {
+                        options: "`--size`, `--win`, `--misere` and `--wild`",
+                        with: "`--game=quantum`".into(),
+                    });
+                }
+                let rules = Rules {
+                    quantum: true,
+                    ..Rules::new(3)
+                };
+                (3, 3, rules)
+            }
+            GameMode::Minesweeper => {
+                return Ok(Session::Minesweeper(Box::new(self.minesweeper()?)));
+            }
+            GameMode::TwentyFortyEight => {
+                self.reject_rules("2048")?;
+                return Ok(Session::TwentyFortyEight(Box::new(Game2048::new())));
+            }
+            GameMode::Sudoku => {
+                self.reject_rules("sudoku")?;
+                let difficulty = self.difficulty.unwrap_or(Difficulty::Medium);
+                return Ok(Session::Sudoku(Box::new(Sudoku::new(difficulty))));
+            }
+            GameMode::Checkers => {
+                self.reject_rules("checkers")?;
+                return Ok(Session::Checkers(Box::new(Checkers::new())));
+            }
+            GameMode::Chess => {
+                self.reject_rules("chess")?;
+                return Ok(Session::Chess(Box::new(Chess::new())));
+            }
+            GameMode::Battleship => {
+                self.reject_rules("battleship")?;
+                return Ok(Session::Battleship(Box::new(Battleship::new())));
+            }
+            GameMode::Memory => return Ok(Session::Memory(Box::new(self.memory()?))),
+            GameMode::Hangman => return Ok(Session::Hangman(Box::new(self.hangman()?))),
+            GameMode::Life => return Ok(Session::Life(Box::new(self.life()?))),
+            GameMode::Reversi => {
+                self.reject_rules("reversi")?;
+                return Ok(Session::Reversi(Box::new(Reversi::new())));
+            }
+            GameMode::Nim => {
+                self.reject_rules("nim")?;
+                let heaps = self.heaps.clone().unwrap_or(nim::DEFAULT_HEAPS.to_vec());
+                return Ok(Session::Nim(Box::new(Nim::new(heaps))));
+            }
+            GameMode::Dots => return Ok(Session::Dots(Box::new(self.dots()?))),
+            GameMode::LightsOut => {
+                return Ok(Session::LightsOut(Box::new(self.lights_out()?)));
+            }
+            GameMode::Blackjack => {
+                self.reject_rules("blackjack")?;
+                return Ok(Session::Blackjack(Box::new(Blackjack::new())));
+            }
+            GameMode::Mancala => {
+                self.reject_rules("mancala")?;
+                return Ok(Session::Mancala(Box::new(Mancala::new())));
+            }
+            GameMode::Pong => {
+                self.reject_rules("pong")?;
+                return Ok(Session::Pong(Box::new(Pong::new())));
+            }
+            GameMode::Maze => return Ok(Session::Maze(Box::new(self.maze()?))),
+            GameMode::Sos => return Ok(Session::Sos(Box::new(self.sos()?))),
+            GameMode::Sokoban => return Ok(Session::Sokoban(Box::new(self.sokoban()?))),
+            GameMode::Nonogram => return Ok(Session::Nonogram(Box::new(self.nonogram()?))),
+            GameMode::Hex => {
+                self.reject_rules("hex")?;
+                return Ok(Session::Hex(Box::new(Hex::new())));
+            }
+            GameMode::Go => {
+                self.reject_rules("go")?;
+                return Ok(Session::Go(Box::new(Go::new())));
+            }
+            GameMode::Rps => {
+                self.reject_rules("rps")?;
+                return Ok(Session::Rps(Box::new(RockPaperScissors::new())));
+            }
+            GameMode::Simon => {
+                self.reject_rules("simon")?;
+                return Ok(Session::Simon(Box::new(Simon::new())));
+            }
+            GameMode::Typing => return Ok(Session::Typing(Box::new(self.typing()?))),
+            GameMode::Quiz => return Ok(Session::Quiz(Box::new(self.quiz()?))),
+            GameMode::Yahtzee => {
+                self.reject_rules("yahtzee")?;
+                return Ok(Session::Yahtzee(Box::new(Yahtzee::new())));
+            }
+            GameMode::Hanoi => {
+                self.reject_rules("hanoi")?;
+                return Ok(Session::Hanoi(Box::new(Hanoi::new())));
+            }
+            GameMode::Pente => {
+                self.reject_rules("pente")?;
+                return Ok(Session::Pente(Box::new(Pente::new())));
+            }
+            GameMode::Tetris => {
+                self.reject_rules("tetris")?;
+                return Ok(Session::Tetris(Box::new(Tetris::new())));
+            }
+        };
+        if self.boards.is_some() && !rules.notakto {
+            return Err(Error::OnlyFor {
+                option: "--boards",
+                games: "`--game=notakto`",
+            });
+        }
+        if self.order.is_some() && rules.order.is_none() {
+            return Err(Error::OnlyFor {
+                option: "--role",
+                games: "`--game=orderchaos`",
+            });
+        }
+        if self.wrap && (rules.ultimate || rules.quantum || rules.layers > 1) {
+            return Err(Error::Conflict {
+                options: "`--wrap`",
+                with: "`--game=ultimate`, `--game=qubic` or `--game=quantum`".into(),
+            });
+        }
+        rules.misere |= self.misere;
+        rules.wild |= self.wild;
+        rules.wrap = self.wrap;
+        if self.swap && (rules.wild || rules.notakto || rules.quantum || rules.order.is_some()) {
+            return Err(Error::Conflict {
+                options: "`--swap`",
+                with: "`--wild`, `--game=notakto`, `--game=orderchaos` or `--game=quantum`".into(),
+            });
+        }
+        rules.swap = self.swap;
+
+        let app = App::new()
+            .with_board_size(rows, cols)
+            .with_rules(rules)
+            .with_first_move(self.first_move)
+            .with_symbols(self.symbols.clone())
+            .with_name(game.name());
+        Ok(Session::Board(Box::new(app)))
+    }
+
+    /// Set up a game of Minesweeper.
+    fn minesweeper(&self) -> Result<Minesweeper> {
+        self.reject_rules("minesweeper")?;
+        let (rows, cols) = self
+            .grid(minesweeper::GRID_SIZES)?
+            .unwrap_or(minesweeper::DEFAULT_GRID);
+        let density = self.density.unwrap_or(minesweeper::DEFAULT_DENSITY);
+        Ok(Minesweeper::new(rows, cols, density))
+    }
+
+    fn memory(&self) -> Result<Memory> {
+        self.reject_rules("memory")?;
+        let (rows, cols) = self
+            .grid(memory::GRID_SIZES)?
+            .unwrap_or(memory::DEFAULT_GRID);
+        if rows * cols % 2 != 0 {
+            return Err(Error::invalid(
+                "grid",
+                format!("{rows}x{cols}"),
+                "an even number of cards to pair up",
+            ));
+        }
+        Ok(Memory::new(rows, cols))
+    }
+
+    fn hangman(&self) -> Result<Hangman> {
+        self.reject_rules("hangman")?;
+        let Some(path) = &self.words else {
+            return Ok(Hangman::new(hangman::bundled_words()));
+        };
+        let text = fs::read_to_string(path).map_err(|source| Error::ReadFile {
+            what: "word list",
+            path: path.clone(),
+            source,
+        })?;
+        let words = hangman::parse_words(&text);
+        if words.is_empty() {
+            return Err(Error::InvalidFile {
+                what: "word list",
+                path: path.clone(),
+                reason:
+                    "no words, expected words of at least three letters separated by whitespace"
+                        .into(),
+            });
+        }
+        Ok(Hangman::new(words))
+    }
+
+    fn typing(&self) -> Result<Typing> {
+        self.reject_rules("typing")?;
+        let Some(path) = &self.words else {
+            return Ok(Typing::new(typing::bundled_words()));
+        };
+        let text = fs::read_to_string(path).map_err(|source| Error::ReadFile {
+            what: "word list",
+            path: path.clone(),
+            source,
+        })?;
+        let words = typing::parse_words(&text);
+        if words.is_empty() {
+            return Err(Error::InvalidFile {
+                what: "word list",
+                path: path.clone(),
+                reason: "no words, expected words separated by whitespace".into(),
+            });
+        }
+        Ok(Typing::new(words))
+    }
+
+    fn quiz(&self) -> Result<Quiz> {
+        self.reject_rules("quiz")?;
+        let Some(path) = &self.pack else {
+            return Ok(Quiz::new(quiz::bundled_pack()));
+        };
+        let text = fs::read_to_string(path).map_err(|source| Error::ReadFile {
+            what: "quiz pack",
+            path: path.clone(),
+            source,
+        })?;
+        let pack = match path.extension().and_then(|extension| extension.to_str()) {
+            Some("json") => quiz::parse_json_pack(&text),
+            _ => quiz::parse_toml_pack(&text),
+        };
+        let pack = pack.map_err(|reason| Error::InvalidFile {
+            what: "quiz pack",
+            path: path.clone(),
+            reason,
+        })?;
+        Ok(Quiz::new(pack))
+    }
+
+    fn life(&self) -> Result<Life> {
+        self.reject_rules("life")?;
+        let (rows, cols) = self.grid(life::GRID_SIZES)?.unwrap_or(life::DEFAULT_GRID);
+        let Some(path) = &self.pattern else {
+            return Ok(Life::new(rows, cols, None));
+        };
+        let text = fs::read_to_string(path).map_err(|source| Error::ReadFile {
+            what: "pattern",
+            path: path.clone(),
+            source,
+        })?;
+        let pattern = Pattern::parse_rle(&text).map_err(|reason| Error::InvalidFile {
+            what: "pattern",
+            path: path.clone(),
+            reason,
+        })?;
+        if pattern.rows() > rows || pattern.cols() > cols {
+            return Err(Error::InvalidFile {
+                what: "pattern",
+                path: path.clone(),
+                reason: format!(
+                    "{}x{} cells, too large for the {rows}x{cols} grid",
+                    pattern.rows(),
+                    pattern.cols()
+                ),
+            });
+        }
+        Ok(Life::new(rows, cols, Some(&pattern)))
+    }
+
+    fn dots(&self) -> Result<Dots> {
+        self.reject_rules("dots")?;
+        let (rows, cols) = self.grid(dots::GRID_SIZES)?.unwrap_or(dots::DEFAULT_GRID);
+        Ok(Dots::new(rows, cols))
+    }
+
+    fn lights_out(&self) -> Result<LightsOut> {
+        self.reject_rules("lights")?;
+        let (rows, cols) = self
+            .grid(lights::GRID_SIZES)?
+            .unwrap_or(lights::DEFAULT_GRID);
+        Ok(LightsOut::new(rows, cols))
+    }
+
+    fn maze(&self) -> Result<Maze> {
+        self.reject_rules("maze")?;
+        let (rows, cols) = self.grid(maze::GRID_SIZES)?.unwrap_or(maze::DEFAULT_GRID);
+        Ok(Maze::new(rows, cols))
+    }
+
+    fn sos(&self) -> Result<Sos> {
+        self.reject_rules("sos")?;
+        let (rows, cols) = self.grid(sos::GRID_SIZES)?.unwrap_or(sos::DEFAULT_GRID);
+        Ok(Sos::new(rows, cols))
+    }
+
+    fn sokoban(&self) -> Result<Sokoban> {
+        self.reject_rules("sokoban")?;
+        let Some(path) = &self.levels else {
+            return Ok(Sokoban::new(sokoban::bundled_levels()));
+        };
+        let text = fs::read_to_string(path).map_err(|source| Error::ReadFile {
+            what: "levels",
+            path: path.clone(),
+            source,
+        })?;
+        let levels = sokoban::parse_levels(&text).map_err(|reason| Error::InvalidFile {
+            what: "levels",
+            path: path.clone(),
+            reason,
+        })?;
+        Ok(Sokoban::new(levels))
+    }
+
+    fn nonogram(&self) -> Result<Nonogram> {
+        self.reject_rules("nonogram")?;
+        let Some(path) = &self.puzzles else {
+            return Ok(Nonogram::new(nonogram::bundled_puzzles()));
+        };
+        let text = fs::read_to_string(path).map_err(|source| Error::ReadFile {
+            what: "puzzles",
+            path: path.clone(),
+            source,
+        })?;
+        let puzzles = nonogram::parse_puzzles(&text).map_err(|reason| Error::InvalidFile {
+            what: "puzzles",
+            path: path.clone(),
+            reason,
+        })?;
+        Ok(Nonogram::new(puzzles))
+    }
+
+    /// The `--grid` asked for, checking both of its sides are within `sizes`.
+    fn grid(&self, sizes: RangeInclusive<usize>) -> Result<Option<(usize, usize)>> {
+        match self.grid {
+            Some((rows, cols)) if !sizes.contains(&rows) || !sizes.contains(&cols) => {
+                Err(Error::invalid(
+                    "grid",
+                    format!("{rows}x{cols}"),
+                    format!("both sides from {} to {}", sizes.start(), sizes.end()),
+                ))
+            }
+            grid => Ok(grid),
+        }
+    }
+
+    /// Reject the rules of the games against the bot for `--game=<game>`, which takes none.
+    fn reject_rules(&self, game: &str) -> Result<()> {
+        if self.size.is_some()
+            || self.win_length.is_some()
+            || self.boards.is_some()
+            || self.order.is_some()
+            || self.misere
+            || self.wild
+            || self.wrap
+            || self.swap
+        {
+            return Err(Error::Conflict {
+                options: "`--size`, `--win`, `--boards`, `--role`, `--misere`, `--wild`, `--wrap` and `--swap`",
+                with: format!("`--game={game}`"),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Check that `value` is a single grapheme, such as a letter or an emoji.
+fn parse_symbol(value: &str) -> Result<String> {
+    if value.graphemes(true).count() != 1 {
+        return Err(Error::invalid("mark", value, "a single character or emoji"));
+    }
+    Ok(value.into())
+}
+
+/// Check that `value` is one of the [`NOTAKTO_BOARDS`].
+fn parse_board_count(value: &str) -> Result<usize> {
+    match value.parse() {
+        Ok(boards) if NOTAKTO_BOARDS.contains(&boards) => Ok(boards),
+        _ => Err(Error::invalid(
+            "number of boards",
+            value,
+            format!(
+                "a number from {} to {}",
+                NOTAKTO_BOARDS.start(),
+                NOTAKTO_BOARDS.end()
+            ),
+        )),
+    }
+}
+
+/// Check that `value` is one of the [`BOARD_SIZES`].
+fn parse_board_size(value: &str) -> Result<usize> {
+    match value.parse() {
+        Ok(size) if BOARD_SIZES.contains(&size) => Ok(size),
+        _ => Err(Error::invalid(
+            "board size",
+            value,
+            format!(
+                "a number from {} to {}",
+                BOARD_SIZES.start(),
+                BOARD_SIZES.end()
+            ),
+        )),
+    }
+}
+
+/// Parse a grid given as `<rows>x<cols>`; each game checks the sizes it allows.
+fn parse_grid(value: &str) -> Result<(usize, usize)> {
+    let parsed = value
+        .split_once('x')
+        .and_then(|(rows, cols)| Some((rows.parse().ok()?, cols.parse().ok()?)));
+    match parsed {
+        Some(grid) => Ok(grid),
+        None => Err(Error::invalid("grid", value, "`<rows>x<cols>`")),
+    }
+}
+
+/// Parse the heaps of a Nim game given as `<n>,<n>,…`, checking there are
+/// [`nim::HEAP_COUNTS`] of them with [`nim::HEAP_SIZES`] stones each.
+fn parse_heaps(value: &str) -> Result<Vec<usize>> {
+    let (counts, sizes) = (nim::HEAP_COUNTS, nim::HEAP_SIZES);
+    let heaps: Option<Vec<usize>> = value
+        .split(',')
+        .map(|heap| heap.trim().parse().ok().filter(|size| sizes.contains(size)))
+        .collect();
+    match heaps {
+        Some(heaps) if counts.contains(&heaps.len()) => Ok(heaps),
+        _ => Err(Error::invalid(
+            "heaps",
+            value,
+            format!(
+                "{} to {} comma-separated heaps of {} to {} stones",
+                counts.start(),
+                counts.end(),
+                sizes.start(),
+                sizes.end()
+            ),
+        )),
+    }
+}
+
+/// Check that `value` is one of the [`minesweeper::MINE_DENSITIES`], in percent.
+fn parse_mine_density(value: &str) -> Result<usize> {
+    let densities = minesweeper::MINE_DENSITIES;
+    match value.trim_end_matches('%').parse() {
+        Ok(density) if densities.contains(&density) => Ok(density),
+        _ => Err(Error::invalid(
+            "mine density",
+            value,
+            format!(
+                "a percentage from {} to {}",
+                densities.start(),
+                densities.end()
+            ),
+        )),
+    }
+}
+
+/// The smallest and largest supported board sizes, which a tic-tac-toe board can be set to.
+pub const BOARD_SIZES: RangeInclusive<usize> = 3..=9;
+
+/// How many boards a game of Notakto can be played on at once.
+const NOTAKTO_BOARDS: RangeInclusive<usize> = 1..=3;
+
+/// The games that can be played on the board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameMode {
+    TicTacToe,
+    ConnectFour,
+    Gomoku,
+    Ultimate,
+    Qubic,
+    Notakto,
+    OrderAndChaos,
+    Quantum,
+    Minesweeper,
+    TwentyFortyEight,
+    Sudoku,
+    Checkers,
+    Chess,
+    Battleship,
+    Memory,
+    Hangman,
+    Tetris,
+    Life,
+    Reversi,
+    Nim,
+    Dots,
+    LightsOut,
+    Blackjack,
+    Mancala,
+    Pong,
+    Maze,
+    Sokoban,
+    Nonogram,
+    Hex,
+    Go,
+    Rps,
+    Simon,
+    Typing,
+    Quiz,
+    Yahtzee,
+    Hanoi,
+    Sos,
+    Pente,
+}
+
+impl GameMode {
+    /// Every game, in the order the menu lists them.
+    pub const ALL: [Self; 38] = [
+        Self::TicTacToe,
+        Self::ConnectFour,
+        Self::Gomoku,
+        Self::Ultimate,
+        Self::Qubic,
+        Self::Notakto,
+        Self::OrderAndChaos,
+        Self::Quantum,
+        Self::Minesweeper,
+        Self::TwentyFortyEight,
+        Self::Sudoku,
+        Self::Checkers,
+        Self::Chess,
+        Self::Battleship,
+        Self::Memory,
+        Self::Hangman,
+        Self::Tetris,
+        Self::Life,
+        Self::Reversi,
+        Self::Nim,
+        Self::Dots,
+        Self::LightsOut,
+        Self::Blackjack,
+        Self::Mancala,
+        Self::Pong,
+        Self::Maze,
+        Self::Sokoban,
+        Self::Nonogram,
+        Self::Hex,
+        Self::Go,
+        Self::Rps,
+        Self::Simon,
+        Self::Typing,
+        Self::Quiz,
+        Self::Yahtzee,
+        Self::Hanoi,
+        Self::Sos,
+        Self::Pente,
+    ];
+
+    /// The names `--game` takes, each joined to the next by `separator`.
+    pub fn keys(separator: &str) -> String {
+        Self::ALL.map(Self::key).join(separator)
+    }
+
+    /// The name `--game` takes to play the game.
+    pub const fn key(self) -> &'static str {
+        match self {
+            Self::TicTacToe => "tictactoe",
+            Self::ConnectFour => "connect4",
+            Self::Gomoku => "gomoku",
+            Self::Ultimate => "ultimate",
+            Self::Qubic => "qubic",
+            Self::Notakto => "notakto",
+            Self::OrderAndChaos => "orderchaos",
+            Self::Quantum => "quantum",
+            Self::Minesweeper => "minesweeper",
+            Self::TwentyFortyEight => "2048",
+            Self::Sudoku => "sudoku",
+            Self::Checkers => "checkers",
+            Self::Chess => "chess",
+            Self::Battleship => "battleship",
+            Self::Memory => "memory",
+            Self::Hangman => "hangman",
+            Self::Tetris => "tetris",
+            Self::Life => "life",
+            Self::Reversi => "reversi",
+            Self::Nim => "nim",
+            Self::Dots => "dots",
+            Self::LightsOut => "lights",
+            Self::Blackjack => "blackjack",
+            Self::Mancala => "mancala",
+            Self::Pong => "pong",
+            Self::Maze => "maze",
+            Self::Sokoban => "sokoban",
+            Self::Nonogram => "nonogram",
+            Self::Hex => "hex",
+            Self::Go => "go",
+            Self::Rps => "rps",
+            Self::Simon => "simon",
+            Self::Typing => "typing",
+            Self::Quiz => "quiz",
+            Self::Yahtzee => "yahtzee",
+            Self::Hanoi => "hanoi",
+            Self::Sos => "sos",
+            Self::Pente => "pente",
+        }
+    }
+
+    /// The name the game is listed under.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::TicTacToe => "Tic-tac-toe",
+            Self::ConnectFour => "Connect Four",
+            Self::Gomoku => "Gomoku",
+            Self::Ultimate => "Ultimate tic-tac-toe",
+            Self::Qubic => "Qubic",
+            Self::Notakto => "Notakto",
+            Self::OrderAndChaos => "Order and Chaos",
+            Self::Quantum => "Quantum tic-tac-toe",
+            Self::Minesweeper => "Minesweeper",
+            Self::TwentyFortyEight => "2048",
+            Self::Sudoku => "Sudoku",
+            Self::Checkers => "Checkers",
+            Self::Chess => "Chess",
+            Self::Battleship => "Battleship",
+            Self::Memory => "Memory",
+            Self::Hangman => "Hangman",
+            Self::Tetris => "Tetris",
+            Self::Life => "Game of Life",
+            Self::Reversi => "Reversi",
+            Self::Nim => "Nim",
+            Self::Dots => "Dots and Boxes",
+            Self::LightsOut => "Lights Out",
+            Self::Blackjack => "Blackjack",
+            Self::Mancala => "Mancala",
+            Self::Pong => "Pong",
+            Self::Maze => "Maze",
+            Self::Sokoban => "Sokoban",
+            Self::Nonogram => "Nonogram",
+            Self::Hex => "Hex",
+            Self::Go => "Go",
+            Self::Rps => "Rock Paper Scissors",
+            Self::Simon => "Simon",
+            Self::Typing => "Typing Test",
+            Self::Quiz => "Trivia Quiz",
+            Self::Yahtzee => "Yahtzee",
+            Self::Hanoi => "Tower of Hanoi",
+            Self::Sos => "SOS",
+            Self::Pente => "Pente",
+        }
+    }
+
+    /// A one-line summary of how the game is played.
+    pub const fn description(self) -> &'static str {
+        match self {
+            Self::TicTacToe => "Three in a row on a 3x3 board, or more on a bigger one",
+            Self::ConnectFour => "Drop marks into a 6x7 grid and line up four",
+            Self::Gomoku => "Five in a row on a 15x15 board",
+            Self::Ultimate => "Nine boards in one; your move picks where the bot plays next",
+            Self::Qubic => "Four in a row through a 4x4x4 cube",
+            Self::Notakto => "Both sides play X; whoever completes a line loses",
+            Self::OrderAndChaos => "Order lines up five of either mark while Chaos blocks",
+            Self::Quantum => "Entangled marks that collapse into place once they form a cycle",
+            Self::Minesweeper => "Uncover every cell without setting off a mine",
+            Self::TwentyFortyEight => "Slide and merge numbered tiles up to 2048",
+            Self::Sudoku => "Fill the grid so no digit repeats in a row, column or box",
+            Self::Checkers => "Jump and capture every piece of the bot's; captures are forced",
+            Self::Chess => "Checkmate the bot's king, castling and en passant included",
+            Self::Battleship => "Place your fleet, then hunt down the bot's before it finds yours",
+            Self::Memory => "Turn over two cards at a time and find every pair",
+            Self::Hangman => "Guess the word a letter at a time before the gallows are built",
+            Self::Tetris => "Fit the falling pieces into full rows as they speed up",
+            Self::Life => "Draw cells and watch them live, die and multiply by Conway's rules",
+            Self::Reversi => "Outflank the bot's discs to flip them; most discs at the end wins",
+            Self::Nim => "Take stones from one heap at a time; whoever takes the last one wins",
+            Self::Dots => "Draw lines between dots and close more boxes than the bot",
+            Self::LightsOut => "Press lights to toggle them and their neighbours until all are off",
+            Self::Blackjack => {
+                "Bet chips against the dealer and get closer to 21 without going over"
+            }
+            Self::Mancala => "Sow seeds around the pits and gather more in your store than the bot",
+            Self::Pong => "Two players on one keyboard: W/S against ↑/↓, first to 11 points",
+            Self::Maze => "Find the way out of a dark maze before the clock runs out",
+            Self::Sokoban => "Push every box onto a goal in as few moves as you can",
+            Self::Nonogram => {
+                "Fill in cells to match the row and column clues and reveal a picture"
+            }
+            Self::Hex => "Join your two edges of the rhombus with a chain before the bot does",
+            Self::Go => "Surround more of a 9x9 board than the bot, capturing its stones",
+            Self::Rps => "Win a best-of series against a bot that learns your habits",
+            Self::Simon => "Repeat an ever longer sequence of flashing colored pads",
+            Self::Typing => "Type the words shown as fast and accurately as you can",
+            Self::Quiz => "Answer multiple-choice trivia questions before the time runs out",
+            Self::Yahtzee => "Roll five dice up to three times a turn and fill a scorecard",
+            Self::Hanoi => "Move a tower of discs to the last peg, never a disc on a smaller one",
+            Self::Sos => "Write S or O and spell more S-O-S lines than the bot",
+            Self::Pente => "Get five in a row or capture five pairs on a 19x19 board",
+        }
+    }
+}
+
+impl FromStr for GameMode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let Some(game) = Self::ALL.into_iter().find(|game| game.key() == s) else {
+            let keys = Self::keys("`, `");
+            // the last name is set apart with "or"
+            let expected = match keys.rsplit_once("`, `") {
+                Some((first, last)) => format!("`{first}` or `{last}`"),
+                None => format!("`{keys}`"),
+            };
+            return Err(Error::invalid("game", s, expected));
+        };
+        Ok(game)
+    }
+}
+
+/// Saved as its [key](GameMode::key).
+impl Serialize for GameMode {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.key())
+    }
+}
+
+impl<'de> Deserialize<'de> for GameMode {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let key = String::deserialize(deserializer)?;
+        key.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl Session {
+    /// The game as the save file holds it, or `None` if it can't be saved.
+    pub fn snapshot(&self, game: GameMode) -> Option<SavedBoard> {
+        match self {
+            Self::Board(app) => app.snapshot(game),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Options> {
+        Options::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn values_out_of_range_say_what_was_expected() {
+        let Err(Error::InvalidValue { what, value, .. }) = parse(&["--size=12"]) else {
+            panic!("a board size of 12 was taken");
+        };
+        assert_eq!((what, value.as_str()), ("board size", "12"));
+    }
+
+    #[test]
+    fn unknown_arguments_and_shared_marks_are_refused() {
+        let unknown = parse(&["--bogus"]);
+        assert!(matches!(&unknown, Err(Error::UnknownArgument(arg)) if arg == "--bogus"));
+        let message = unknown.unwrap_err().to_string();
+        assert!(message.contains(&format!("`--game={}`", GameMode::keys("|"))));
+        assert!(matches!(
+            parse(&["--me=A", "--bot=A"]),
+            Err(Error::SameMarks(mark)) if mark == "A"
+        ));
+    }
+
+    #[test]
+    fn options_for_other_games_are_refused() {
+        let settings = Settings::default();
+        let options = parse(&["--mines=20"]).unwrap();
+        assert!(matches!(
+            options.session(GameMode::Sudoku, &settings),
+            Err(Error::OnlyFor {
+                option: "--mines",
+                ..
+            })
+        ));
+        let options = parse(&["--wild"]).unwrap();
+        assert!(matches!(
+            options.session(GameMode::Chess, &settings),
+            Err(Error::Conflict { .. })
+        ));
+    }
+}
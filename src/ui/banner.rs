@@ -0,0 +1,49 @@
+//! Large letters two rows high, drawn with block characters, for titles.
+
+/// The two rows of each letter, and of a space.
+const FONT: [(char, [&str; 2]); 27] = [
+    ('A', ["▄▀█", "█▀█"]),
+    ('B', ["█▄▄", "█▄█"]),
+    ('C', ["█▀▀", "█▄▄"]),
+    ('D', ["█▀▄", "█▄▀"]),
+    ('E', ["█▀▀", "██▄"]),
+    ('F', ["█▀▀", "█▀ "]),
+    ('G', ["█▀▀", "█▄█"]),
+    ('H', ["█ █", "█▀█"]),
+    ('I', ["█", "█"]),
+    ('J', ["  █", "█▄█"]),
+    ('K', ["█▄▀", "█ █"]),
+    ('L', ["█  ", "█▄▄"]),
+    ('M', ["█▀▄▀█", "█ ▀ █"]),
+    ('N', ["█▄ █", "█ ▀█"]),
+    ('O', ["█▀█", "█▄█"]),
+    ('P', ["█▀█", "█▀▀"]),
+    ('Q', ["█▀█", "▀▀█"]),
+    ('R', ["█▀█", "█▀▄"]),
+    ('S', ["█▀▀", "▄▄█"]),
+    ('T', ["▀█▀", " █ "]),
+    ('U', ["█ █", "█▄█"]),
+    ('V', ["█ █", "▀▄▀"]),
+    ('W', ["█ █ █", "▀▄▀▄▀"]),
+    ('X', ["▀▄▀", "█ █"]),
+    ('Y', ["█▄█", " █ "]),
+    ('Z', ["▀▀█", "█▄▄"]),
+    (' ', ["  ", "  "]),
+];
+
+/// `text` in large letters, as the two rows they take up, or `None` if it holds anything but
+/// letters and spaces.
+pub fn lines(text: &str) -> Option<[String; 2]> {
+    let mut rows = [String::new(), String::new()];
+    for (i, c) in text.chars().enumerate() {
+        let c = c.to_ascii_uppercase();
+        let (_, glyph) = FONT.iter().find(|(letter, _)| *letter == c)?;
+        for (row, part) in rows.iter_mut().zip(glyph) {
+            if i > 0 {
+                row.push(' ');
+            }
+            row.push_str(part);
+        }
+    }
+    Some(rows)
+}
@@ -0,0 +1,214 @@
+//! Drawing on terminals that can't show the full colors the games are drawn in: with the
+//! sixteen ANSI colors instead, or with no colors at all and only bold, dim and reversed text
+//! to tell things apart.
+//!
+//! The games draw as always and each frame is brought down to what the terminal shows just
+//! before it is sent out. The terminal is also asked whether its background is light, for the
+//! themes to match it.
+
+use std::{
+    env,
+    io::{self, Write},
+    time::{Duration, Instant},
+};
+
+use crossterm::event::{self, Event, KeyCode, KeyModifiers};
+use ratatui::{buffer::Buffer, style::Color, style::Modifier};
+
+use crate::error::Error;
+
+/// The sixteen ANSI colors and how xterm shows them by default.
+const ANSI: [(Color, [u8; 3]); 16] = [
+    (Color::Black, [0, 0, 0]),
+    (Color::Red, [205, 0, 0]),
+    (Color::Green, [0, 205, 0]),
+    (Color::Yellow, [205, 205, 0]),
+    (Color::Blue, [0, 0, 238]),
+    (Color::Magenta, [205, 0, 205]),
+    (Color::Cyan, [0, 205, 205]),
+    (Color::Gray, [229, 229, 229]),
+    (Color::DarkGray, [127, 127, 127]),
+    (Color::LightRed, [255, 0, 0]),
+    (Color::LightGreen, [0, 255, 0]),
+    (Color::LightYellow, [255, 255, 0]),
+    (Color::LightBlue, [92, 92, 255]),
+    (Color::LightMagenta, [255, 0, 255]),
+    (Color::LightCyan, [0, 255, 255]),
+    (Color::White, [255, 255, 255]),
+];
+
+/// How bright a background must be to stand out from the terminal's own once it has no
+/// color, from 0 to 255.
+const LIGHT: u8 = 96;
+
+/// How dark gray text must be to be drawn dim once it has no color, from 0 to 255.
+const DARK: u8 = 160;
+
+/// How long the terminal gets to tell its background color.
+const QUERY_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// The colors a terminal can show.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Mode {
+    /// Any color, given as red, green and blue.
+    #[default]
+    Full,
+    /// The sixteen ANSI colors, which the terminal shows as it likes.
+    Ansi16,
+    /// No colors, only text attributes.
+    None,
+}
+
+impl Mode {
+    /// The colors the terminal is likely to show, going by its environment: none when
+    /// `NO_COLOR` is set or the terminal is dumb, all of them when `COLORTERM` says so or the
+    /// terminal has 256 colors, and the sixteen ANSI colors otherwise.
+    pub fn detect() -> Self {
+        let var = |name| env::var(name).unwrap_or_default();
+        let term = var("TERM");
+        if !var("NO_COLOR").is_empty() || term == "dumb" {
+            Self::None
+        } else if matches!(var("COLORTERM").as_str(), "truecolor" | "24bit")
+            || term.contains("256color")
+            || cfg!(windows)
+        {
+            Self::Full
+        } else {
+            Self::Ansi16
+        }
+    }
+
+    /// Parse the value of `--color`.
+    pub fn parse(value: &str) -> Result<Self, Error> {
+        match value {
+            "24bit" => Ok(Self::Full),
+            "16" => Ok(Self::Ansi16),
+            "none" => Ok(Self::None),
+            _ => Err(Error::invalid("color mode", value, "24bit, 16 or none")),
+        }
+    }
+
+    /// Bring the colors of everything drawn in `buffer` down to those of this mode.
+    pub fn apply(self, buffer: &mut Buffer) {
+        match self {
+            Self::Full => {}
+            Self::Ansi16 => {
+                for cell in &mut buffer.content {
+                    cell.fg = ansi(cell.fg);
+                    cell.bg = ansi(cell.bg);
+                }
+            }
+            Self::None => {
+                for cell in &mut buffer.content {
+                    // what stood out by its background now stands out reversed, and faint
+                    // text stays faint
+                    if brightness(cell.bg).is_some_and(|bright| bright >= LIGHT) {
+                        cell.modifier.toggle(Modifier::REVERSED);
+                    }
+                    if faint(cell.fg) {
+                        cell.modifier.insert(Modifier::DIM);
+                    }
+                    cell.fg = Color::Reset;
+                    cell.bg = Color::Reset;
+                }
+            }
+        }
+    }
+}
+
+/// Whether the terminal's background is light, going by its answer to an OSC 11 query, or
+/// `None` if it doesn't answer in time. The terminal must be in raw mode, so the answer
+/// isn't echoed.
+pub fn light_background() -> Option<bool> {
+    let mut stdout = io::stdout();
+    stdout.write_all(b"\x1b]11;?\x07").ok()?;
+    stdout.flush().ok()?;
+
+    // crossterm doesn't know the answer, and hands it over as the keys it's made of: Alt+],
+    // then `11;rgb:RRRR/GGGG/BBBB`, then Ctrl+G or Alt+\ to end it
+    let deadline = Instant::now() + QUERY_TIMEOUT;
+    let mut answer = None;
+    loop {
+        let left = deadline.checked_duration_since(Instant::now())?;
+        if !event::poll(left).ok()? {
+            return None;
+        }
+        let Event::Key(key) = event::read().ok()? else {
+            continue;
+        };
+        match (key.modifiers, key.code, &mut answer) {
+            (KeyModifiers::ALT, KeyCode::Char(']'), _) => answer = Some(String::new()),
+            (KeyModifiers::CONTROL, KeyCode::Char('g'), Some(_))
+            | (KeyModifiers::ALT, KeyCode::Char('\\'), Some(_)) => break,
+            (_, KeyCode::Char(c), Some(answer)) => answer.push(c),
+            _ => {}
+        }
+    }
+
+    // each channel is given in up to four hex digits, of which the first two are enough
+    let answer = answer?;
+    let channels = answer.strip_prefix("11;rgb:")?.split('/').map(|hex| {
+        let hex = hex.get(..2).unwrap_or(hex);
+        u8::from_str_radix(hex, 16).ok()
+    });
+    let [r, g, b] = channels.collect::<Option<Vec<_>>>()?[..] else {
+        return None;
+    };
+    Some(brightness(Color::Rgb(r, g, b))? >= 128)
+}
+
+/// The ANSI color closest to `color`.
+fn ansi(color: Color) -> Color {
+    let Some([r, g, b]) = rgb(color) else {
+        return color;
+    };
+    if matches!(color, Color::Indexed(..16)) || ANSI.iter().any(|&(ansi, _)| ansi == color) {
+        return color;
+    }
+    let distance = |[r2, g2, b2]: [u8; 3]| {
+        let d = |a: u8, b: u8| (i32::from(a) - i32::from(b)).pow(2);
+        d(r, r2) + d(g, g2) + d(b, b2)
+    };
+    ANSI.iter()
+        .min_by_key(|&&(_, rgb)| distance(rgb))
+        .map_or(color, |&(ansi, _)| ansi)
+}
+
+/// Whether `color` is a gray dark enough to mark text as less important, such as hints.
+fn faint(color: Color) -> bool {
+    let Some(rgb) = rgb(color) else {
+        return false;
+    };
+    let spread = rgb.iter().max().zip(rgb.iter().min());
+    spread.is_some_and(|(max, min)| max - min < 48) && brightness(color) < Some(DARK)
+}
+
+/// How bright `color` is, from 0 to 255, or `None` for the terminal's own colors.
+fn brightness(color: Color) -> Option<u8> {
+    let [r, g, b] = rgb(color)?;
+    let bright = (299 * u32::from(r) + 587 * u32::from(g) + 114 * u32::from(b)) / 1000;
+    Some(bright as u8)
+}
+
+/// The red, green and blue of `color`, or `None` for the terminal's own colors.
+fn rgb(color: Color) -> Option<[u8; 3]> {
+    match color {
+        Color::Reset => None,
+        Color::Rgb(r, g, b) => Some([r, g, b]),
+        Color::Indexed(index @ ..16) => Some(ANSI[usize::from(index)].1),
+        // a 6×6×6 cube of colors, then 24 grays
+        Color::Indexed(index @ ..232) => {
+            let level = |step: u8| match step {
+                0 => 0,
+                step => 55 + 40 * step,
+            };
+            let index = index - 16;
+            Some([level(index / 36), level(index / 6 % 6), level(index % 6)])
+        }
+        Color::Indexed(index) => Some([8 + 10 * (index - 232); 3]),
+        named => ANSI
+            .iter()
+            .find(|&&(ansi, _)| ansi == named)
+            .map(|&(_, rgb)| rgb),
+    }
+}
@@ -0,0 +1,194 @@
+//! Pieces shared by the games played on a grid of cells: the cursor moving over it and the
+//! table showing the part of it that fits on screen.
+
+use crossterm::event::KeyCode;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Position, Rect},
+    style::{Color, Style, Stylize},
+    text::Text,
+    widgets::{Cell, HighlightSpacing, Paragraph, Row, Table, Wrap},
+};
+
+use crate::{
+    game::Game,
+    ui::{layout::center, palette::TableColors},
+};
+
+/// The part of the board currently shown, in cells.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Viewport {
+    pub row: usize,
+    pub col: usize,
+    pub rows: usize,
+    pub cols: usize,
+}
+
+impl Viewport {
+    /// Shift the viewport along one axis so that `cursor` stays inside it.
+    ///
+    /// `offset` is the first visible index, `visible` the number of visible cells and `total`
+    /// the length of the board along that axis.
+    pub fn follow(offset: usize, visible: usize, total: usize, cursor: usize) -> usize {
+        let offset = if cursor < offset {
+            cursor
+        } else if cursor >= offset + visible {
+            cursor + 1 - visible
+        } else {
+            offset
+        };
+        offset.min(total - visible)
+    }
+}
+
+/// The selected cell of a board, moved with the arrow keys or `wasd`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cursor {
+    pub row: usize,
+    pub col: usize,
+    rows: usize,
+    cols: usize,
+}
+
+impl Cursor {
+    /// A cursor in the middle of a board of `rows`×`cols` cells.
+    pub const fn new(rows: usize, cols: usize) -> Self {
+        Self {
+            row: rows / 2,
+            col: cols / 2,
+            rows,
+            cols,
+        }
+    }
+
+    /// The selected cell, as a `[row, column]` pair.
+    pub const fn cell(&self) -> [usize; 2] {
+        [self.row, self.col]
+    }
+
+    /// Move one cell in the direction of `code`, wrapping around the edges of the board.
+    ///
+    /// Returns `false` when `code` is not a movement key.
+    pub fn move_with(&mut self, code: KeyCode) -> bool {
+        match code {
+            KeyCode::Char('s') | KeyCode::Down => self.row = (self.row + 1) % self.rows,
+            KeyCode::Char('w') | KeyCode::Up => self.row = (self.row + self.rows - 1) % self.rows,
+            KeyCode::Char('d') | KeyCode::Right => self.col = (self.col + 1) % self.cols,
+            KeyCode::Char('a') | KeyCode::Left => self.col = (self.col + self.cols - 1) % self.cols,
+            _ => return false,
+        }
+        true
+    }
+}
+
+/// Where a [table](table) of cells was drawn, to tell which cell of the board is under the
+/// mouse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hitbox {
+    /// The area of the screen the table was drawn in.
+    pub area: Rect,
+    /// The part of the board the table showed.
+    pub viewport: Viewport,
+    pub cell_width: u16,
+    pub cell_height: u16,
+    /// How far apart the columns were set.
+    pub spacing: u16,
+}
+
+impl Hitbox {
+    /// The cell of the board drawn at `column` and `row` of the screen, if any; the gaps
+    /// between columns belong to no cell.
+    pub fn cell_at(&self, column: u16, row: u16) -> Option<[usize; 2]> {
+        if !self.area.contains(Position::new(column, row)) {
+            return None;
+        }
+        // columns are set `spacing` apart, rows follow each other
+        let x = column - self.area.x;
+        let y = row - self.area.y;
+        let stride = self.cell_width + self.spacing;
+        if x % stride >= self.cell_width {
+            return None;
+        }
+        let col = usize::from(x / stride);
+        let row = usize::from(y / self.cell_height);
+        (row < self.viewport.rows && col < self.viewport.cols)
+            .then_some([self.viewport.row + row, self.viewport.col + col])
+    }
+}
+
+/// The visible part of `game`'s board as a table of cells `cell_width`×`cell_height` large,
+/// with every other row shaded.
+///
+/// Each cell shows the text and style of [`Game::render_cell`], centered vertically; the
+/// highlight styles of the selected row, column and cell are left to the caller.
+pub fn table<G: Game + ?Sized>(
+    game: &G,
+    viewport: Viewport,
+    cell_width: u16,
+    cell_height: u16,
+    colors: &TableColors,
+) -> Table<'static> {
+    let (rows, cols) = game.dimensions();
+    let rows = (viewport.row..rows).take(viewport.rows).map(|i| {
+        let color = match i % 2 {
+            0 => colors.normal_row_color,
+            _ => colors.alt_row_color,
+        };
+        (viewport.col..cols)
+            .take(viewport.cols)
+            .map(|j| {
+                let (text, style) = game.render_cell([i, j]);
+                // center the text vertically within the cell
+                let lines = text.lines().count().max(1);
+                let padding = usize::from(cell_height).saturating_sub(lines) / 2;
+                let text = format!("{}{text}", "\n".repeat(padding));
+                Cell::from(Text::from(text).centered()).style(style)
+            })
+            .collect::<Row>()
+            .style(Style::new().fg(colors.row_fg).bg(color))
+            .height(cell_height)
+    });
+    Table::new(rows, vec![Constraint::Length(cell_width); viewport.cols])
+        .bg(colors.buffer_bg)
+        .highlight_spacing(HighlightSpacing::Always)
+}
+
+/// Draw a board of `rows`×`cols` cells into `area` as solid blocks `cell_width` columns wide
+/// and one line high, with no gap between them, so each cell reads as a pixel.
+///
+/// Each block is filled with the style `cell` gives it, and shows its text if there is any.
+pub fn render_pixels(
+    frame: &mut Frame,
+    area: Rect,
+    (rows, cols): (usize, usize),
+    cell_width: u16,
+    cell: impl Fn([usize; 2]) -> (String, Style),
+) {
+    let buffer = frame.buffer_mut();
+    for row in 0..rows.min(usize::from(area.height)) {
+        for col in 0..cols.min(usize::from(area.width / cell_width)) {
+            let (text, style) = cell([row, col]);
+            let block = Rect {
+                x: area.x + col as u16 * cell_width,
+                y: area.y + row as u16,
+                width: cell_width,
+                height: 1,
+            };
+            buffer.set_style(block, style);
+            buffer.set_stringn(block.x, block.y, text, usize::from(cell_width), style);
+        }
+    }
+}
+
+/// Ask for a terminal of at least `min_width`×`min_height` cells in place of the board.
+pub fn render_too_small(frame: &mut Frame, area: Rect, min_width: u16, min_height: u16) {
+    let message = format!("Terminal size too small.\nMinimum size is {min_width}x{min_height}.");
+    let block = Paragraph::new(message)
+        .centered()
+        .wrap(Wrap { trim: true })
+        .style(Style::default().fg(Color::Red));
+    frame.render_widget(
+        block,
+        center(area, Constraint::Percentage(100), Constraint::Length(2)),
+    );
+}
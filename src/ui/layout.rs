@@ -0,0 +1,86 @@
+//! Where the title, the board and the panels around it go on the screen.
+
+use ratatui::layout::{Constraint, Flex, Layout, Rect};
+
+/// Width of the score panel, including its border.
+pub const SCORE_WIDTH: u16 = 12;
+
+/// The areas the game screen is split into.
+pub struct AppLayout {
+    pub title: Rect,
+    pub clocks: Option<Rect>,
+    pub table: Rect,
+    pub score: Option<Rect>,
+}
+
+/// The width and height of a single board cell on a board up to `size` cells across.
+pub const fn cell_size(size: usize) -> (u16, u16) {
+    match size {
+        ..=3 => (9, 3),
+        4 => (7, 3),
+        5 => (6, 3),
+        _ => (4, 1),
+    }
+}
+
+/// How many cells of length `cell` placed `spacing` apart fit in `length`.
+pub const fn cells_fitting(length: u16, cell: u16, spacing: u16) -> usize {
+    ((length + spacing) / (cell + spacing)) as usize
+}
+
+/// The length of `size` cells of length `cell` placed `spacing` apart.
+pub const fn table_extent(size: usize, cell: u16, spacing: u16) -> u16 {
+    let size = size as u16;
+    size * cell + (size - 1) * spacing
+}
+
+/// Split the screen into the title, the clocks when `show_clocks` is set, the table and, when
+/// there is room beside the table, the score panel.
+pub fn calculate_layout(
+    area: Rect,
+    show_clocks: bool,
+    table_width: u16,
+    table_height: u16,
+) -> AppLayout {
+    let clocks_height = if show_clocks { 1 } else { 0 };
+    let main_layout = Layout::vertical([
+        Constraint::Max(1),
+        Constraint::Length(clocks_height),
+        Constraint::Max(table_height),
+    ])
+    .flex(Flex::Center);
+    let [title_area, clocks_area, main_area] = main_layout.areas(area);
+    let table_area = center(
+        main_area,
+        Constraint::Length(table_width),
+        Constraint::Length(table_height),
+    );
+
+    // keep the table centered and only show the panel if it fits on its right
+    let score_area = (table_area.right() + 1 + SCORE_WIDTH <= area.right()).then(|| Rect {
+        x: table_area.right() + 1,
+        y: table_area.y,
+        width: SCORE_WIDTH,
+        height: 5,
+    });
+
+    AppLayout {
+        title: center(
+            title_area,
+            Constraint::Percentage(100),
+            Constraint::Length(1),
+        ),
+        clocks: show_clocks.then_some(clocks_area),
+        table: table_area,
+        score: score_area,
+    }
+}
+
+/// The part of `area` `horizontal` wide and `vertical` high in its middle.
+pub fn center(area: Rect, horizontal: Constraint, vertical: Constraint) -> Rect {
+    let [area] = Layout::horizontal([horizontal])
+        .flex(Flex::Center)
+        .areas(area);
+    let [area] = Layout::vertical([vertical]).flex(Flex::Center).areas(area);
+    area
+}
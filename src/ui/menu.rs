@@ -0,0 +1,385 @@
+//! The start screen, the list of games that can be played it leads to, and the stats of the
+//! games finished so far.
+
+use std::time::Duration;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style, Stylize, palette::tailwind},
+    text::{Line, Span},
+    widgets::{
+        Block, Cell, HighlightSpacing, List, ListItem, ListState, Padding, Paragraph, Row, Table,
+        TableState, Wrap,
+    },
+};
+
+use crate::{
+    GameMode, bot,
+    game::Status,
+    settings::Settings,
+    stats::{Stats, Tally},
+    ui::layout::center,
+};
+
+/// Width of the list of games, including its border.
+const MENU_WIDTH: u16 = 72;
+
+/// Width of the start screen, including its border.
+const START_WIDTH: u16 = 40;
+
+/// The pages of the menu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Page {
+    Start,
+    Games,
+}
+
+/// The entries of the start screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Entry {
+    NewGame,
+    Continue,
+    Settings,
+    Stats,
+    Quit,
+}
+
+impl Entry {
+    const ALL: [Self; 5] = [
+        Self::NewGame,
+        Self::Continue,
+        Self::Settings,
+        Self::Stats,
+        Self::Quit,
+    ];
+
+    const fn label(self) -> &'static str {
+        match self {
+            Self::NewGame => "New Game",
+            Self::Continue => "Continue",
+            Self::Settings => "Settings",
+            Self::Stats => "Stats",
+            Self::Quit => "Quit",
+        }
+    }
+}
+
+/// What the player chose to play from the menu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Choice {
+    /// A new game of the kind chosen from the list.
+    New(GameMode),
+    /// The game left unfinished last, or else the one saved.
+    Continue,
+}
+
+/// Where the player asked to go from the menu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Play(Choice),
+    Settings,
+    Stats,
+    Quit,
+}
+
+/// The menu, remembering the page shown and what was last chosen on each.
+pub struct Menu {
+    page: Page,
+    start: ListState,
+    games: ListState,
+    /// Why the last chosen game could not be started.
+    error: Option<String>,
+    /// The game last played and how it stood when the player left it.
+    last_result: Option<(GameMode, Status)>,
+    /// The game left unfinished, which Continue goes back to.
+    paused: Option<GameMode>,
+    /// The game saved with `Ctrl+S`, which Continue resumes when none was left unfinished.
+    saved: Option<GameMode>,
+}
+
+impl Menu {
+    /// A menu on the start screen, with `game` selected in the list of games if given.
+    pub fn new(game: Option<GameMode>) -> Self {
+        let selected = game.and_then(|game| GameMode::ALL.iter().position(|&g| g == game));
+        Self {
+            page: Page::Start,
+            start: ListState::default().with_selected(Some(0)),
+            games: ListState::default().with_selected(Some(selected.unwrap_or(0))),
+            error: None,
+            last_result: None,
+            paused: None,
+            saved: None,
+        }
+    }
+
+    /// Offer to resume the game of `game` saved to disk.
+    pub const fn show_saved(&mut self, game: GameMode) {
+        self.saved = Some(game);
+    }
+
+    /// Report that the chosen game could not be started, until another key is pressed.
+    pub fn show_error(&mut self, error: String) {
+        self.error = Some(error);
+    }
+
+    /// Remember how the game of `game` just left ended, to show it below the menu, and offer
+    /// to continue it if it was left unfinished.
+    pub fn show_result(&mut self, game: GameMode, status: Status) {
+        self.last_result = Some((game, status));
+        self.paused = (status == Status::Playing).then_some(game);
+    }
+
+    /// React to `key`, returning where the player asked to go: to a game chosen, to the
+    /// settings with `o` or the stats, or away with Quit, `q` or `Esc` on the start screen or
+    /// `Ctrl+C` on any page. `Shift+←/→` change the theme and `Ctrl+L` the background.
+    pub fn handle_key(&mut self, key: KeyEvent, settings: &mut Settings) -> Option<Action> {
+        let key = settings.keys.translate(key);
+        self.error = None;
+        let down = matches!(key.code, KeyCode::Char('s') | KeyCode::Down);
+        let up = matches!(key.code, KeyCode::Char('w') | KeyCode::Up);
+        let enter = matches!(key.code, KeyCode::Char(' ') | KeyCode::Enter);
+        match (self.page, key.modifiers, key.code) {
+            (_, KeyModifiers::CONTROL, KeyCode::Char('c'))
+            | (Page::Start, _, KeyCode::Char('q') | KeyCode::Esc) => return Some(Action::Quit),
+            // the list of games leads back to the start
+            (_, _, KeyCode::Char('q') | KeyCode::Esc) => self.page = Page::Start,
+            (_, _, KeyCode::Char('o')) => return Some(Action::Settings),
+            (_, KeyModifiers::SHIFT, KeyCode::Left | KeyCode::Right) => {
+                settings.cycle_theme(key.code == KeyCode::Left);
+            }
+            (_, KeyModifiers::CONTROL, KeyCode::Char('l')) => settings.toggle_background(),
+            (Page::Start, ..) if down => self.start.select_next(),
+            (Page::Start, ..) if up => self.start.select_previous(),
+            (Page::Start, ..) if enter => match self.entry() {
+                Entry::NewGame => self.page = Page::Games,
+                Entry::Continue if self.paused.or(self.saved).is_some() => {
+                    return Some(Action::Play(Choice::Continue));
+                }
+                Entry::Continue => self.error = Some("There is no game to continue".into()),
+                Entry::Settings => return Some(Action::Settings),
+                Entry::Stats => return Some(Action::Stats),
+                Entry::Quit => return Some(Action::Quit),
+            },
+            (Page::Games, ..) if down => self.games.select_next(),
+            (Page::Games, ..) if up => self.games.select_previous(),
+            (Page::Games, ..) if enter => {
+                let selected = self.games.selected().unwrap_or(0);
+                return GameMode::ALL
+                    .get(selected)
+                    .copied()
+                    .map(Choice::New)
+                    .map(Action::Play);
+            }
+            _ => {}
+        }
+        None
+    }
+
+    /// The entry selected on the start screen.
+    fn entry(&self) -> Entry {
+        Entry::ALL[self.start.selected().unwrap_or(0).min(Entry::ALL.len() - 1)]
+    }
+
+    /// Draw the page shown in the middle of the screen, with the footer below it.
+    pub fn draw(&mut self, frame: &mut Frame, settings: &Settings) {
+        let (width, height) = match self.page {
+            Page::Start => (START_WIDTH, Entry::ALL.len() as u16 + 4),
+            Page::Games => (MENU_WIDTH, GameMode::ALL.len() as u16 * 2 + 2),
+        };
+        let area = center(
+            frame.area(),
+            Constraint::Length(width),
+            Constraint::Length(height + 3),
+        );
+        let [screen_area, footer_area] =
+            Layout::vertical([Constraint::Length(height), Constraint::Length(3)]).areas(area);
+        match self.page {
+            Page::Start => self.render_start(frame, screen_area, highlight(settings)),
+            Page::Games => self.render_games(frame, screen_area, highlight(settings)),
+        }
+        self.render_footer(frame, footer_area);
+    }
+
+    fn render_start(&mut self, frame: &mut Frame, area: Rect, highlight: Style) {
+        let items = Entry::ALL.iter().map(|&entry| {
+            let line = match (entry, self.paused, self.saved) {
+                (Entry::Continue, Some(game), _) => Line::from(vec![
+                    entry.label().bold(),
+                    format!(" · {}", game.name()).fg(tailwind::SLATE.c400),
+                ]),
+                (Entry::Continue, None, Some(game)) => Line::from(vec![
+                    entry.label().bold(),
+                    format!(" · {} (saved)", game.name()).fg(tailwind::SLATE.c400),
+                ]),
+                // there is nothing to continue until a game is left unfinished or saved
+                (Entry::Continue, None, None) => Line::from(entry.label().dark_gray()),
+                _ => Line::from(entry.label().bold()),
+            };
+            ListItem::new(line)
+        });
+        let list = List::new(items)
+            .block(
+                Block::bordered()
+                    .title(Line::from(" rustgame ".bold()).centered())
+                    .padding(Padding::vertical(1)),
+            )
+            .highlight_style(highlight)
+            .highlight_symbol("▶ ")
+            .highlight_spacing(HighlightSpacing::Always);
+        frame.render_stateful_widget(list, area, &mut self.start);
+    }
+
+    fn render_games(&mut self, frame: &mut Frame, area: Rect, highlight: Style) {
+        let items = GameMode::ALL.iter().map(|game| {
+            ListItem::new(vec![
+                Line::from(game.name()).bold(),
+                Line::from(game.description()).fg(tailwind::SLATE.c400),
+            ])
+        });
+        let list = List::new(items)
+            .block(Block::bordered().title(Line::from(" Choose a game ").centered()))
+            .highlight_style(highlight)
+            .highlight_symbol("▶ ")
+            .highlight_spacing(HighlightSpacing::Always);
+        frame.render_stateful_widget(list, area, &mut self.games);
+    }
+
+    fn render_footer(&self, frame: &mut Frame, area: Rect) {
+        let footer = match &self.error {
+            Some(error) => Paragraph::new(error.as_str()).fg(Color::Red),
+            None => {
+                let result = self.last_result.map(|(game, status)| {
+                    let result = match status {
+                        Status::Playing => "left unfinished",
+                        Status::Won => "won",
+                        Status::Lost => "lost",
+                        Status::Draw => "drawn",
+                    };
+                    Line::from(format!("Last game of {}: {result}", game.name()))
+                });
+                let help = match self.page {
+                    Page::Start => {
+                        "↑/↓ choose · Enter select · o settings · Shift+←/→ theme · q quit"
+                    }
+                    Page::Games => "↑/↓ choose · Enter play · o settings · q back",
+                };
+                Paragraph::new(result.into_iter().chain([help.into()]).collect::<Vec<_>>())
+            }
+        };
+        frame.render_widget(footer.centered().wrap(Wrap { trim: true }), area);
+    }
+}
+
+/// The style of the selected line of a list.
+fn highlight(settings: &Settings) -> Style {
+    Style::default()
+        .bg(settings.colors().human_board_bg)
+        .add_modifier(Modifier::REVERSED)
+}
+
+/// Draw how many games of each kind and against each bot were won, lost and drawn, and how
+/// long was spent playing, in the middle of the screen, with the games scrolled to the row
+/// `state` selects and the help line below.
+pub fn draw_stats(frame: &mut Frame, settings: &Settings, stats: &Stats, state: &mut TableState) {
+    let games: Vec<_> = GameMode::ALL
+        .iter()
+        .map(|&game| (game.name(), stats.game(game)))
+        .filter(|(_, tally)| tally.played() > 0)
+        .collect();
+    let bots: Vec<_> = bot::Opponent::ALL
+        .iter()
+        .map(|&opponent| (opponent.label(), stats.difficulty(opponent)))
+        .filter(|(_, tally)| tally.played() > 0)
+        .collect();
+    let games_height = games.len().max(1) as u16 + 3;
+    // the bots are left out until a game against one is finished
+    let bots_height = match bots.is_empty() {
+        true => 0,
+        false => bots.len() as u16 + 4,
+    };
+    let area = center(
+        frame.area(),
+        Constraint::Length(MENU_WIDTH),
+        Constraint::Length(games_height + bots_height + 2),
+    );
+    let [games_area, bots_area, _, help_area] = Layout::vertical([
+        Constraint::Length(games_height),
+        Constraint::Length(bots_height),
+        Constraint::Length(1),
+        Constraint::Length(1),
+    ])
+    .areas(area);
+    let played = format!(" {} played ", play_time(stats.play_time()));
+    let block = Block::bordered()
+        .title(Line::from(" Stats ").centered())
+        .title_bottom(Line::from(played).centered());
+    if games.is_empty() {
+        let empty = Paragraph::new("No games finished yet")
+            .italic()
+            .centered()
+            .block(block);
+        frame.render_widget(empty, games_area);
+    } else {
+        let table = tally_table("Game", &games).block(block);
+        let table = table
+            .row_highlight_style(highlight(settings))
+            .highlight_symbol("▶ ")
+            .highlight_spacing(HighlightSpacing::Always);
+        frame.render_stateful_widget(table, games_area, state);
+    }
+    if !bots.is_empty() {
+        let [_, bots_area] =
+            Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(bots_area);
+        let block = Block::bordered().title(Line::from(" Against the bots ").centered());
+        // lined up with the games above, which leave room for the selection mark
+        let table = tally_table("Difficulty", &bots)
+            .block(block)
+            .highlight_symbol("▶ ")
+            .highlight_spacing(HighlightSpacing::Always);
+        frame.render_widget(table, bots_area);
+    }
+    let help = Paragraph::new("↑/↓ scroll · o settings · q back").centered();
+    frame.render_widget(help, help_area);
+}
+
+/// How long `time` is in hours and minutes, or minutes and seconds when under an hour.
+fn play_time(time: Duration) -> String {
+    let secs = time.as_secs();
+    match secs / 3600 {
+        0 => format!("{}m {:02}s", secs / 60, secs % 60),
+        hours => format!("{hours}h {:02}m", secs / 60 % 60),
+    }
+}
+
+/// A table of how many of the games `tallies` counts were won, lost and drawn, each under
+/// the name given with it, which the first column is headed by `heading`.
+fn tally_table<'a>(heading: &'a str, tallies: &[(&'a str, Tally)]) -> Table<'a> {
+    let rows = tallies.iter().map(|&(name, tally)| {
+        let share = 100 * tally.won / tally.played();
+        Row::new([
+            Cell::from(name),
+            Cell::from(tally.played().to_string()),
+            Cell::from(Span::from(tally.won.to_string()).fg(tailwind::EMERALD.c400)),
+            Cell::from(Span::from(tally.lost.to_string()).fg(tailwind::ROSE.c400)),
+            Cell::from(tally.drawn.to_string()),
+            Cell::from(format!("{share}%")),
+        ])
+    });
+    let header = Row::new([heading, "Played", "Won", "Lost", "Drawn", "Won %"])
+        .bold()
+        .fg(tailwind::SLATE.c400);
+    Table::new(
+        rows,
+        [
+            Constraint::Fill(1),
+            Constraint::Length(7),
+            Constraint::Length(5),
+            Constraint::Length(5),
+            Constraint::Length(6),
+            Constraint::Length(6),
+        ],
+    )
+    .header(header)
+}
@@ -0,0 +1,11 @@
+//! The screens shown around the games and the pieces they are drawn with.
+
+pub mod banner;
+pub mod color;
+pub mod grid;
+pub mod layout;
+pub mod menu;
+pub mod palette;
+pub mod screen;
+pub mod splash;
+pub mod toast;
@@ -0,0 +1,65 @@
+//! The colors the games and the panels around them are drawn in, as the themes give them.
+
+use ratatui::style::{Color, palette::tailwind};
+
+/// The palettes of the built-in themes, the first of which every game starts out in.
+pub const PALETTES: [tailwind::Palette; 4] = [
+    tailwind::BLUE,
+    tailwind::EMERALD,
+    tailwind::INDIGO,
+    tailwind::RED,
+];
+
+/// The colors a board and the panels around it are drawn in, as a theme gives them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TableColors {
+    pub buffer_bg: Color,
+    pub row_fg: Color,
+    pub selected_row_style_fg: Color,
+    pub selected_column_style_fg: Color,
+    pub selected_cell_style_fg: Color,
+    pub hint_cell_style_bg: Color,
+    pub last_move_style_fg: Color,
+    pub normal_row_color: Color,
+    pub alt_row_color: Color,
+    pub open_board_bg: Color,
+    pub human_board_bg: Color,
+    pub bot_board_bg: Color,
+}
+
+impl TableColors {
+    pub const fn new(color: &tailwind::Palette) -> Self {
+        Self {
+            buffer_bg: tailwind::SLATE.c950,
+            row_fg: tailwind::SLATE.c200,
+            selected_row_style_fg: color.c400,
+            selected_column_style_fg: color.c400,
+            selected_cell_style_fg: color.c600,
+            hint_cell_style_bg: color.c800,
+            last_move_style_fg: color.c300,
+            normal_row_color: tailwind::SLATE.c950,
+            alt_row_color: tailwind::SLATE.c900,
+            open_board_bg: color.c950,
+            human_board_bg: color.c900,
+            bot_board_bg: tailwind::SLATE.c700,
+        }
+    }
+
+    /// The colors of `color` on a light terminal background.
+    pub const fn light(color: &tailwind::Palette) -> Self {
+        Self {
+            buffer_bg: tailwind::SLATE.c50,
+            row_fg: tailwind::SLATE.c900,
+            selected_row_style_fg: color.c600,
+            selected_column_style_fg: color.c600,
+            selected_cell_style_fg: color.c500,
+            hint_cell_style_bg: color.c200,
+            last_move_style_fg: color.c700,
+            normal_row_color: tailwind::SLATE.c50,
+            alt_row_color: tailwind::SLATE.c200,
+            open_board_bg: color.c100,
+            human_board_bg: color.c300,
+            bot_board_bg: tailwind::SLATE.c300,
+        }
+    }
+}
@@ -0,0 +1,874 @@
+//! The screens the player moves between, from the splash through the menu to the games and
+//! back, each drawn and answering keys in its own way, and the loop showing them.
+
+use std::{
+    io, mem,
+    time::{Duration, Instant},
+};
+
+use crossterm::{
+    event::{
+        DisableMouseCapture, EnableMouseCapture, Event, EventStream, KeyCode, KeyEvent,
+        KeyEventKind, KeyModifiers, KeyboardEnhancementFlags, PopKeyboardEnhancementFlags,
+        PushKeyboardEnhancementFlags,
+    },
+    execute, terminal,
+};
+use futures::StreamExt;
+use ratatui::{
+    DefaultTerminal, Frame,
+    layout::Constraint,
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{
+        Block, Clear, HighlightSpacing, List, ListItem, ListState, Padding, Paragraph, TableState,
+    },
+};
+
+use crate::{
+    GameMode,
+    app::SavedBoard,
+    error::{Error, Result},
+    game::{Game, Help, Status, TICK_RATE},
+    menu::{self, Action, Choice, Menu},
+    options::{Options, Session},
+    save,
+    settings::{self, Panel, Settings},
+    splash,
+    stats::Stats,
+    toast::Toasts,
+    ui::layout::center,
+    with_game,
+};
+use tokio::time;
+
+/// Width of the help overlay, including its border.
+const HELP_WIDTH: u16 = 64;
+
+/// Width of the pause menu, including its border.
+const PAUSE_WIDTH: u16 = 24;
+
+/// Width of the popup asking whether to leave a game, including its border.
+const CONFIRM_WIDTH: u16 = 40;
+
+/// Width of the dialog telling the player what went wrong, including its border.
+const DIALOG_WIDTH: u16 = 52;
+
+/// The screen shown, each drawn and answering keys in its own way.
+enum AppScreen {
+    /// The logo, shown since the given time.
+    Splash(Instant),
+    /// The start screen or the list of games.
+    Menu,
+    /// A game under way.
+    Playing,
+    /// A game standing still behind a popup.
+    Paused(Overlay),
+    /// A game decided, which is left without asking.
+    GameOver,
+    /// The settings, changed on `panel` from how they were when opened over `back`, the
+    /// screen they lead back to.
+    Settings {
+        panel: Panel,
+        before: Box<Settings>,
+        back: Box<AppScreen>,
+    },
+    /// How many games of each kind and against each bot were won, lost and drawn, and how long
+    /// was spent playing.
+    Stats(TableState),
+}
+
+impl AppScreen {
+    /// Whether a game is on screen, even behind a popup.
+    const fn shows_game(&self) -> bool {
+        matches!(self, Self::Playing | Self::Paused(_) | Self::GameOver)
+    }
+
+    /// Whether a game is on screen and moving on by itself.
+    const fn runs_game(&self) -> bool {
+        matches!(self, Self::Playing | Self::GameOver)
+    }
+}
+
+/// What is shown over a game, which stands still meanwhile.
+#[derive(Debug)]
+enum Overlay {
+    Help,
+    Pause(ListState),
+    /// Asking whether to leave a game still under way.
+    ConfirmQuit,
+}
+
+/// The entries of the pause menu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PauseEntry {
+    Resume,
+    Restart,
+    Settings,
+    Quit,
+}
+
+impl PauseEntry {
+    const ALL: [Self; 4] = [Self::Resume, Self::Restart, Self::Settings, Self::Quit];
+
+    const fn label(self) -> &'static str {
+        match self {
+            Self::Resume => "Resume",
+            Self::Restart => "Restart",
+            Self::Settings => "Settings",
+            Self::Quit => "Quit to menu",
+        }
+    }
+}
+
+/// The screen shown, and what the others remember meanwhile.
+pub struct Screens {
+    screen: AppScreen,
+    menu: Menu,
+    options: Options,
+    /// The game shown, or the one left unfinished for Continue to go back to, and its kind.
+    game: Option<(GameMode, Session)>,
+    /// The messages shown over the game.
+    toasts: Toasts,
+    /// The terminal reports the mouse to the game shown.
+    mouse: bool,
+    /// The terminal reports keys let go to the game shown.
+    releases: bool,
+    /// What went wrong, shown one at a time over every screen until a key is pressed.
+    dialogs: Vec<Error>,
+    /// Everything played so far, in this run and earlier ones.
+    stats: Stats,
+    /// Since when the game shown has been played without the time being counted in the stats.
+    counted: Instant,
+    /// The game shown has been counted in the stats as decided, and isn't again until it
+    /// starts over.
+    recorded: bool,
+}
+
+impl Screens {
+    /// Start on the splash, with the menu set up from `options` and offering the game saved
+    /// if there is one; `errors`, such as why the theme file was left out, are shown in
+    /// dialogs until a key is pressed.
+    pub fn new(options: Options, errors: Vec<Error>) -> Self {
+        let mut menu = Menu::new(options.game);
+        let mut dialogs = errors;
+        let (stats, stats_error) = Stats::load();
+        dialogs.extend(stats_error);
+        match save::read::<SavedBoard>() {
+            Ok(Some(saved)) => menu.show_saved(saved.game),
+            Ok(None) => {}
+            Err(error) => dialogs.push(error),
+        }
+        Self {
+            screen: AppScreen::Splash(Instant::now()),
+            menu,
+            options,
+            game: None,
+            toasts: Toasts::default(),
+            mouse: false,
+            releases: false,
+            dialogs,
+            stats,
+            counted: Instant::now(),
+            recorded: false,
+        }
+    }
+
+    /// Show the screens until the player quits from the menu; a game left unfinished can be
+    /// continued from there until another one is started.
+    pub async fn run(
+        mut self,
+        terminal: &mut DefaultTerminal,
+        settings: &mut Settings,
+    ) -> Result<()> {
+        let result = self.show(terminal, settings).await;
+        self.release()?;
+        result
+    }
+
+    async fn show(
+        &mut self,
+        terminal: &mut DefaultTerminal,
+        settings: &mut Settings,
+    ) -> Result<()> {
+        let mut events = EventStream::new();
+        let mut next_tick = time::Instant::now();
+        loop {
+            self.advance();
+            terminal.draw(|frame| {
+                self.draw(frame, settings);
+                if let Some(error) = self.dialogs.first() {
+                    draw_dialog(frame, error);
+                }
+                settings.color.apply(frame.buffer_mut());
+            })?;
+            // input waiting is taken before the tick, so a game moving by itself never keeps
+            // the player waiting
+            tokio::select! {
+                biased;
+                event = events.next() => {
+                    // the terminal has no more input to give
+                    let Some(event) = event else {
+                        return Ok(());
+                    };
+                    if !self.handle_event(event?, settings) {
+                        return Ok(());
+                    }
+                }
+                () = time::sleep_until(next_tick) => {
+                    // a game that changed ticks again as soon as it has been drawn
+                    next_tick = match self.tick() {
+                        true => time::Instant::now(),
+                        false => time::Instant::now() + self.tick_rate(),
+                    };
+                }
+            }
+        }
+    }
+
+    /// Move on from what ends by itself: the splash once it has run its course, and the game
+    /// once the player asked from within it to leave, or when it is decided or starts over.
+    fn advance(&mut self) {
+        if let AppScreen::Splash(started) = self.screen
+            && started.elapsed() >= splash::SPLASH_DURATION
+        {
+            self.screen = AppScreen::Menu;
+        }
+        if !self.screen.runs_game() {
+            return;
+        }
+        let Some((_, session)) = &mut self.game else {
+            return;
+        };
+        if with_game!(session, game => game.wants_to_leave()) {
+            self.leave_game();
+            return;
+        }
+        for message in with_game!(session, game => game.toasts()) {
+            self.toasts.push(message);
+        }
+        let (status, opponent, reopened) = with_game!(session, game => {
+            (game.status(), game.opponent(), game.reopened())
+        });
+        // a game is counted in the stats once, as soon as it is decided; one taken back and
+        // decided again was counted already
+        match status {
+            Status::Playing => self.recorded &= reopened,
+            _ if self.recorded => {}
+            _ => {
+                if let Some((game, _)) = self.game {
+                    let played = self.uncounted();
+                    self.stats.record(game, status, opponent, played);
+                }
+                self.recorded = true;
+            }
+        }
+        self.screen = match status {
+            Status::Playing => AppScreen::Playing,
+            _ => AppScreen::GameOver,
+        };
+    }
+
+    /// How long the game shown has been played since that was last counted in the stats,
+    /// which from now on it is.
+    fn uncounted(&mut self) -> Duration {
+        mem::replace(&mut self.counted, Instant::now()).elapsed()
+    }
+
+    /// Let the game shown move on by itself, returning whether it changed.
+    fn tick(&mut self) -> bool {
+        match &mut self.game {
+            Some((_, session)) if self.screen.runs_game() => {
+                with_game!(session, game => game.tick())
+            }
+            _ => false,
+        }
+    }
+
+    /// How long until the screen ticks and is drawn again: the fixed [`TICK_RATE`], or sooner
+    /// for a game moving in real time.
+    fn tick_rate(&self) -> Duration {
+        match &self.game {
+            Some((_, session)) if self.screen.runs_game() => {
+                with_game!(session, game => game.tick_rate()).min(TICK_RATE)
+            }
+            _ => TICK_RATE,
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame, settings: &Settings) {
+        match &mut self.screen {
+            AppScreen::Splash(started) => splash::draw(frame, settings, started.elapsed()),
+            AppScreen::Menu => self.menu.draw(frame, settings),
+            AppScreen::Settings { panel, .. } => settings::draw(frame, panel, settings),
+            AppScreen::Stats(state) => menu::draw_stats(frame, settings, &self.stats, state),
+            screen => {
+                if let Some((_, session)) = &mut self.game {
+                    with_game!(session, game => {
+                        draw_game(game.as_mut(), frame, screen, &mut self.toasts);
+                    });
+                }
+            }
+        }
+    }
+
+    /// React to `event` on the screen shown, returning `false` once the player quits.
+    fn handle_event(&mut self, event: Event, settings: &mut Settings) -> bool {
+        let key = match event {
+            // a dialog takes the key that closes it
+            Event::Key(key) if !self.dialogs.is_empty() => {
+                if key.kind == KeyEventKind::Press {
+                    self.dialogs.remove(0);
+                }
+                return true;
+            }
+            Event::Key(key) => key,
+            Event::Mouse(mouse) => {
+                if let Some((_, session)) = &mut self.game
+                    && self.screen.runs_game()
+                {
+                    with_game!(session, game => game.handle_mouse(mouse));
+                }
+                return true;
+            }
+            _ => return true,
+        };
+        if self.screen.shows_game()
+            && key.kind == KeyEventKind::Press
+            && key.modifiers == KeyModifiers::CONTROL
+            && key.code == KeyCode::Char('s')
+        {
+            self.save_game();
+            return true;
+        }
+        match &mut self.screen {
+            // games that want them hear of keys let go
+            screen if screen.shows_game() => {
+                let Some((_, session)) = &mut self.game else {
+                    return true;
+                };
+                let left = with_game!(session, game => {
+                    handle_game_key(game.as_mut(), key, screen, settings, &mut self.toasts)
+                });
+                if left {
+                    self.leave_game();
+                }
+            }
+            _ if key.kind != KeyEventKind::Press => {}
+            AppScreen::Splash(_) => self.screen = AppScreen::Menu,
+            AppScreen::Menu => match self.menu.handle_key(key, settings) {
+                Some(Action::Play(choice)) => self.start_game(choice, settings),
+                Some(Action::Settings) => open_settings(&mut self.screen, settings),
+                Some(Action::Stats) => {
+                    self.screen = AppScreen::Stats(TableState::default().with_selected(Some(0)));
+                }
+                Some(Action::Quit) => return false,
+                None => {}
+            },
+            AppScreen::Settings { panel, .. } => {
+                let back = settings::handle_key(panel, key, settings);
+                if back {
+                    self.close_settings(settings);
+                }
+            }
+            AppScreen::Stats(state) => match settings.keys.translate(key) {
+                key if key.modifiers == KeyModifiers::CONTROL && key.code == KeyCode::Char('c') => {
+                    return false;
+                }
+                key => match (key.modifiers, key.code) {
+                    (_, KeyCode::Char('q') | KeyCode::Esc) => self.screen = AppScreen::Menu,
+                    (_, KeyCode::Char('o')) => open_settings(&mut self.screen, settings),
+                    (_, KeyCode::Char('s') | KeyCode::Down) => state.select_next(),
+                    (_, KeyCode::Char('w') | KeyCode::Up) => state.select_previous(),
+                    (KeyModifiers::SHIFT, KeyCode::Left | KeyCode::Right) => {
+                        settings.cycle_theme(key.code == KeyCode::Left);
+                    }
+                    (KeyModifiers::CONTROL, KeyCode::Char('l')) => settings.toggle_background(),
+                    _ => {}
+                },
+            },
+            _ => {}
+        }
+        true
+    }
+
+    /// Show `game`, new or continued. One saved once it was decided was counted in the stats
+    /// when it was.
+    fn show_game(&mut self, game: (GameMode, Session)) {
+        let (_, session) = &game;
+        self.recorded = with_game!(session, game => game.status()) != Status::Playing;
+        self.game = Some(game);
+    }
+
+    /// Start the game the player chose from the menu, or report why it cannot be.
+    fn start_game(&mut self, choice: Choice, settings: &Settings) {
+        // with no game left unfinished, Continue resumes the one saved
+        if choice == Choice::Continue && self.game.is_none() {
+            match save::read::<SavedBoard>() {
+                Ok(Some(saved)) => self.show_game(saved.restore()),
+                Ok(None) => return,
+                Err(error) => {
+                    self.dialogs.push(error);
+                    return;
+                }
+            }
+        }
+        if let Choice::New(game) = choice {
+            // options that do not fit the chosen game are reported in the menu
+            match self.options.session(game, settings) {
+                Ok(session) => self.show_game((game, session)),
+                Err(error) => {
+                    self.menu.show_error(error.to_string());
+                    return;
+                }
+            }
+        }
+        let Some((_, session)) = &mut self.game else {
+            return;
+        };
+        let captured = with_game!(session, game => {
+            game.apply_settings(settings);
+            capture(game.as_ref())
+        });
+        // the game is played without the mouse or keys let go if the terminal won't report them
+        (self.mouse, self.releases) = captured.unwrap_or_else(|error| {
+            self.dialogs.push(error);
+            (false, false)
+        });
+        self.toasts = Toasts::default();
+        self.counted = Instant::now();
+        self.screen = AppScreen::Playing;
+    }
+
+    /// Go back to the menu from the game shown, counting the time spent in it and keeping it
+    /// for Continue while it is unfinished.
+    fn leave_game(&mut self) {
+        if let Err(error) = self.release() {
+            self.dialogs.push(error);
+        }
+        self.screen = AppScreen::Menu;
+        let Some((game, session)) = &self.game else {
+            return;
+        };
+        let game = *game;
+        let status = with_game!(session, game => game.status());
+        let played = self.uncounted();
+        self.stats.add_play_time(played);
+        self.menu.show_result(game, status);
+        if status != Status::Playing {
+            self.game = None;
+        }
+    }
+
+    /// Save the game shown for Continue to resume, in this run or a later one, or tell the
+    /// player why it can't be.
+    fn save_game(&mut self) {
+        let Some((game, session)) = &self.game else {
+            return;
+        };
+        let game = *game;
+        match session.snapshot(game) {
+            Some(saved) => match save::write(saved) {
+                Ok(()) => {
+                    self.menu.show_saved(game);
+                    self.toasts.push("Game saved");
+                }
+                Err(error) => self.dialogs.push(error),
+            },
+            None => self.toasts.push("This game can't be saved"),
+        }
+    }
+
+    /// Go back from the settings to the screen they were opened over, which a game showing
+    /// there takes them on from, telling the player whether anything was saved.
+    fn close_settings(&mut self, settings: &Settings) {
+        let AppScreen::Settings { before, back, .. } =
+            mem::replace(&mut self.screen, AppScreen::Menu)
+        else {
+            return;
+        };
+        self.screen = *back;
+        if !self.screen.shows_game() {
+            return;
+        }
+        let Some((_, session)) = &mut self.game else {
+            return;
+        };
+        with_game!(session, game => {
+            game.apply_settings(settings);
+            if self.screen.runs_game() {
+                game.resume();
+            }
+        });
+        if *settings != *before {
+            self.toasts.push(match settings.saved() {
+                true => "Settings saved",
+                false => "Settings kept for this run only",
+            });
+        }
+    }
+
+    /// Stop the terminal reporting what only the game shown asked for.
+    fn release(&mut self) -> Result<()> {
+        if mem::take(&mut self.mouse) {
+            execute!(io::stdout(), DisableMouseCapture)?;
+        }
+        if mem::take(&mut self.releases) {
+            execute!(io::stdout(), PopKeyboardEnhancementFlags)?;
+        }
+        Ok(())
+    }
+}
+
+/// Have the terminal report to `game` what it asks for beyond key presses, returning whether it
+/// now reports the mouse and keys let go.
+fn capture(game: &impl Game) -> Result<(bool, bool)> {
+    let releases =
+        game.wants_key_releases() && terminal::supports_keyboard_enhancement().unwrap_or(false);
+    if releases {
+        let flags = KeyboardEnhancementFlags::REPORT_EVENT_TYPES;
+        execute!(io::stdout(), PushKeyboardEnhancementFlags(flags))?;
+    }
+    let mouse = game.wants_mouse();
+    if mouse {
+        execute!(io::stdout(), EnableMouseCapture)?;
+    }
+    Ok((mouse, releases))
+}
+
+/// Open the settings over `screen`, which they lead back to.
+fn open_settings(screen: &mut AppScreen, settings: &Settings) {
+    let back = mem::replace(screen, AppScreen::Menu);
+    *screen = AppScreen::Settings {
+        panel: Panel::default(),
+        before: Box::new(settings.clone()),
+        back: Box::new(back),
+    };
+}
+
+/// Draw `game` with the messages it has for the player, and the popup over it while it is
+/// paused.
+fn draw_game(game: &mut impl Game, frame: &mut Frame, screen: &mut AppScreen, toasts: &mut Toasts) {
+    game.draw(frame);
+    toasts.render(frame);
+    match screen {
+        AppScreen::Paused(Overlay::Help) => draw_help(frame, game),
+        AppScreen::Paused(Overlay::Pause(menu)) => draw_pause(frame, menu),
+        AppScreen::Paused(Overlay::ConfirmQuit) => draw_confirm_quit(frame),
+        _ => {}
+    }
+}
+
+/// React to `key` pressed on the screen of `game`, or on the popup over it, moving `screen` to
+/// where it leads; returns whether the player left the game.
+///
+/// The player leaves with `q` or `Esc`, confirmed while the game is under way, or with
+/// `Ctrl+C`; `o` opens the settings, `p` the pause menu, `?` the help, `Shift+←/→` change the
+/// theme and `Ctrl+L` switches between a dark and a light background. `q`, `o`, `p` and `?`
+/// are left to games that [take letters](Game::takes_letters).
+fn handle_game_key(
+    game: &mut impl Game,
+    key: KeyEvent,
+    screen: &mut AppScreen,
+    settings: &mut Settings,
+    toasts: &mut Toasts,
+) -> bool {
+    if let AppScreen::Paused(overlay) = screen {
+        if key.kind != KeyEventKind::Press {
+            return false;
+        }
+        let chosen = match overlay {
+            _ if key.modifiers == KeyModifiers::CONTROL && key.code == KeyCode::Char('c') => {
+                Some(PauseEntry::Quit)
+            }
+            // any key closes the help
+            Overlay::Help => Some(PauseEntry::Resume),
+            Overlay::ConfirmQuit if key.code == KeyCode::Char('y') => Some(PauseEntry::Quit),
+            Overlay::ConfirmQuit => Some(PauseEntry::Resume),
+            Overlay::Pause(menu) => match settings.keys.translate(key).code {
+                KeyCode::Char('s') | KeyCode::Down => {
+                    menu.select_next();
+                    None
+                }
+                KeyCode::Char('w') | KeyCode::Up => {
+                    menu.select_previous();
+                    None
+                }
+                KeyCode::Char(' ') | KeyCode::Enter => {
+                    let selected = menu.selected().unwrap_or(0);
+                    Some(PauseEntry::ALL[selected.min(PauseEntry::ALL.len() - 1)])
+                }
+                KeyCode::Char('p' | 'q') | KeyCode::Esc => Some(PauseEntry::Resume),
+                _ => None,
+            },
+        };
+        match chosen {
+            Some(PauseEntry::Resume) => {
+                *screen = AppScreen::Playing;
+                game.resume();
+            }
+            Some(PauseEntry::Restart) => {
+                game.restart();
+                // games start over as new, so they take the settings on again
+                game.apply_settings(settings);
+                *screen = AppScreen::Playing;
+                game.resume();
+            }
+            // the pause menu shows again once the settings are closed
+            Some(PauseEntry::Settings) => open_settings(screen, settings),
+            Some(PauseEntry::Quit) => return true,
+            None => {}
+        }
+        return false;
+    }
+
+    // letters the game takes as input are never turned into movement keys
+    let key = match game.takes_letters() || game.uses_key(key.code) {
+        true => key,
+        false => settings.keys.translate(key),
+    };
+    // keys held down repeat as presses; only games wanting releases get those at all
+    if key.kind == KeyEventKind::Release {
+        game.handle_key_release(key);
+        return false;
+    }
+    match (key.modifiers, key.code) {
+        (KeyModifiers::CONTROL, KeyCode::Char('c')) => return true,
+        // a game still under way is only left once the player says so
+        (_, KeyCode::Char('q') | KeyCode::Esc)
+            if !game.uses_key(key.code) && (key.code == KeyCode::Esc || !game.takes_letters()) =>
+        {
+            if game.status() != Status::Playing {
+                return true;
+            }
+            game.pause();
+            *screen = AppScreen::Paused(Overlay::ConfirmQuit);
+        }
+        (_, KeyCode::Char('o')) if !game.takes_letters() && !game.uses_key(key.code) => {
+            game.pause();
+            open_settings(screen, settings);
+        }
+        (_, KeyCode::Char('p')) if !game.takes_letters() && !game.uses_key(key.code) => {
+            game.pause();
+            *screen =
+                AppScreen::Paused(Overlay::Pause(ListState::default().with_selected(Some(0))));
+        }
+        (KeyModifiers::SHIFT, KeyCode::Left | KeyCode::Right) if !game.uses_key(key.code) => {
+            settings.cycle_theme(key.code == KeyCode::Left);
+            game.apply_settings(settings);
+            toasts.push(format!("Theme: {}", settings.theme));
+        }
+        (KeyModifiers::CONTROL, KeyCode::Char('l')) => {
+            settings.toggle_background();
+            game.apply_settings(settings);
+            toasts.push(match settings.light() {
+                true => "Light background",
+                false => "Dark background",
+            });
+        }
+        (_, KeyCode::Char('?')) if !game.takes_letters() && !game.uses_key(key.code) => {
+            game.pause();
+            *screen = AppScreen::Paused(Overlay::Help);
+        }
+        _ => game.handle_key(key),
+    }
+    false
+}
+
+/// Draw the help of `game` in a popup over the middle of its screen, dimming the rest.
+fn draw_help(frame: &mut Frame, game: &impl Game) {
+    let Help { rules, mut keys } = game.help();
+    if !game.takes_letters() && !game.uses_key(KeyCode::Char('o')) {
+        keys.push(("o", "settings"));
+    }
+    if !game.takes_letters() && !game.uses_key(KeyCode::Char('p')) {
+        keys.push(("p", "pause menu"));
+    }
+    if !game.uses_key(KeyCode::Left) {
+        keys.push(("Shift+←/→", "change the theme"));
+    }
+    keys.push(("Ctrl+L", "light or dark background"));
+    keys.push(("?", "close this help"));
+    keys.push(match game.takes_letters() {
+        true => ("Esc", "leave the game"),
+        false => ("q / Esc", "leave the game"),
+    });
+
+    let area = frame.area();
+    dim(frame);
+
+    let width = HELP_WIDTH.min(area.width);
+    // the border and the padding on either side
+    let text_width = usize::from(width.saturating_sub(4)).max(1);
+    let key_width = keys
+        .iter()
+        .map(|(key, _)| key.chars().count())
+        .max()
+        .unwrap_or(0);
+    let mut lines: Vec<Line> = wrap(&rules, text_width)
+        .into_iter()
+        .map(Line::from)
+        .collect();
+    lines.push(Line::default());
+    lines.extend(keys.into_iter().map(|(key, action)| {
+        Line::from(vec![
+            Span::from(format!("{key:>key_width$}")).bold(),
+            Span::from(format!("  {action}")),
+        ])
+    }));
+
+    let height = (lines.len() as u16 + 2).min(area.height);
+    let popup = center(area, Constraint::Length(width), Constraint::Length(height));
+    let block = Block::bordered()
+        .title(Line::from(" Help ").centered())
+        .title_bottom(Line::from(" any key closes ").centered())
+        .padding(Padding::horizontal(1));
+    frame.render_widget(Clear, popup);
+    frame.render_widget(Paragraph::new(lines).block(block), popup);
+}
+
+/// Draw the pause menu in a popup over the middle of the game's screen, dimming the rest.
+fn draw_pause(frame: &mut Frame, menu: &mut ListState) {
+    let area = frame.area();
+    dim(frame);
+    let height = PauseEntry::ALL.len() as u16 + 4;
+    let popup = center(
+        area,
+        Constraint::Length(PAUSE_WIDTH.min(area.width)),
+        Constraint::Length(height.min(area.height)),
+    );
+    let items = PauseEntry::ALL
+        .iter()
+        .map(|entry| ListItem::new(Line::from(entry.label().bold())));
+    let list = List::new(items)
+        .block(
+            Block::bordered()
+                .title(Line::from(" Paused ").centered())
+                .title_bottom(Line::from(" p resumes ").centered())
+                .padding(Padding::vertical(1)),
+        )
+        .highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("▶ ")
+        .highlight_spacing(HighlightSpacing::Always);
+    frame.render_widget(Clear, popup);
+    frame.render_stateful_widget(list, popup, menu);
+}
+
+/// Ask whether to leave the game in a popup over the middle of its screen, dimming the rest.
+fn draw_confirm_quit(frame: &mut Frame) {
+    let area = frame.area();
+    dim(frame);
+    let lines = vec![
+        Line::from("Leave the game?".bold()),
+        Line::from("It can be continued from the menu.".italic()),
+    ];
+    let popup = center(
+        area,
+        Constraint::Length(CONFIRM_WIDTH.min(area.width)),
+        Constraint::Length(6.min(area.height)),
+    );
+    let block = Block::bordered()
+        .title_bottom(Line::from(" y leave · any other key stays ").centered())
+        .padding(Padding::vertical(1));
+    frame.render_widget(Clear, popup);
+    frame.render_widget(Paragraph::new(lines).centered().block(block), popup);
+}
+
+/// Tell the player about `error` in a dialog over the whole screen.
+fn draw_dialog(frame: &mut Frame, error: &Error) {
+    let area = frame.area();
+    dim(frame);
+    let width = DIALOG_WIDTH.min(area.width);
+    let lines: Vec<Line> = wrap(&error.to_string(), usize::from(width.saturating_sub(4)))
+        .into_iter()
+        .map(Line::from)
+        .collect();
+    let popup = center(
+        area,
+        Constraint::Length(width),
+        Constraint::Length((lines.len() as u16 + 4).min(area.height)),
+    );
+    let block = Block::bordered()
+        .title(Line::from(format!(" {} ", error.title()).bold()).centered())
+        .title_bottom(Line::from(" any key closes ").centered())
+        .fg(Color::Red)
+        .padding(Padding::new(1, 1, 1, 1));
+    frame.render_widget(Clear, popup);
+    frame.render_widget(Paragraph::new(lines).centered().block(block), popup);
+}
+
+/// Dim the whole screen, for a popup to stand out from it.
+fn dim(frame: &mut Frame) {
+    let area = frame.area();
+    frame.buffer_mut().set_style(
+        area,
+        Style::new().fg(Color::DarkGray).add_modifier(Modifier::DIM),
+    );
+}
+
+/// `text` broken into lines of at most `width` characters, between words where it can be.
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        let needed = line.chars().count() + usize::from(!line.is_empty()) + word.chars().count();
+        if !line.is_empty() && needed > width {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::App;
+
+    /// The screens showing `game`, counting it in stats that aren't saved.
+    fn showing(game: App) -> Screens {
+        let options = Options::parse(std::iter::empty()).expect("no options are valid");
+        let mut screens = Screens {
+            screen: AppScreen::Playing,
+            menu: Menu::new(None),
+            options,
+            game: None,
+            toasts: Toasts::default(),
+            mouse: false,
+            releases: false,
+            dialogs: Vec::new(),
+            stats: Stats::unreadable(),
+            counted: Instant::now(),
+            recorded: false,
+        };
+        screens.show_game((GameMode::TicTacToe, Session::Board(Box::new(game))));
+        screens
+    }
+
+    #[test]
+    fn a_decided_game_saved_and_continued_is_counted_once() {
+        let mut screens = showing(App::new());
+        screens.advance();
+        if let Some((_, Session::Board(game))) = &mut screens.game {
+            game.resign();
+        }
+        screens.advance();
+        screens.advance();
+        assert_eq!(screens.stats.game(GameMode::TicTacToe).lost, 1);
+
+        let Some((mode, session)) = &screens.game else {
+            panic!("the game was left");
+        };
+        let saved = session.snapshot(*mode).expect("tic-tac-toe can be saved");
+        screens.game = None;
+        screens.show_game(saved.restore());
+        screens.advance();
+        assert_eq!(screens.stats.game(GameMode::TicTacToe).lost, 1);
+    }
+}
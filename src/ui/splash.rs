@@ -0,0 +1,55 @@
+//! The logo shown for a moment before the menu, drawn in from left to right.
+
+use std::time::Duration;
+
+use ratatui::{
+    Frame,
+    layout::Constraint,
+    style::{Style, Stylize},
+    text::Line,
+    widgets::Paragraph,
+};
+
+use crate::{banner, settings::Settings, ui::layout::center};
+
+/// How long the logo takes to be drawn in.
+const REVEAL_DURATION: Duration = Duration::from_millis(700);
+
+/// How long the splash shows in all, unless a key skips it.
+pub const SPLASH_DURATION: Duration = Duration::from_millis(1500);
+
+/// Draw the splash as it stands `elapsed` into it.
+pub fn draw(frame: &mut Frame, settings: &Settings, elapsed: Duration) {
+    let colors = settings.colors();
+    frame.render_widget(
+        Paragraph::default().style(Style::new().bg(colors.buffer_bg)),
+        frame.area(),
+    );
+    let Some(logo) = banner::lines("rustgame") else {
+        return;
+    };
+    let width = logo[0].chars().count();
+    // the columns of the logo drawn in so far
+    let shown = match elapsed >= REVEAL_DURATION {
+        true => width,
+        false => width * elapsed.as_millis() as usize / REVEAL_DURATION.as_millis() as usize,
+    };
+    let mut lines: Vec<Line> = logo
+        .iter()
+        .map(|row| {
+            let row: String = row.chars().take(shown).collect();
+            Line::from(format!("{row:<width$}").fg(colors.selected_row_style_fg))
+        })
+        .collect();
+    lines.push(Line::default());
+    let version = format!("v{}", env!("CARGO_PKG_VERSION"));
+    lines.push(Line::from(version.fg(colors.row_fg)));
+    lines.push(Line::from("any key to skip".dark_gray()));
+
+    let area = center(
+        frame.area(),
+        Constraint::Length(width as u16),
+        Constraint::Length(lines.len() as u16),
+    );
+    frame.render_widget(Paragraph::new(lines).centered(), area);
+}
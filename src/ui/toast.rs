@@ -0,0 +1,76 @@
+//! Short messages shown for a moment in the top right corner of the screen, such as that it
+//! is the player's turn, so the player hears about things without a screen of their own.
+
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Style, palette::tailwind},
+    widgets::{Clear, Paragraph},
+};
+use unicode_width::UnicodeWidthStr;
+
+/// How long a message stays on screen.
+const TOAST_DURATION: Duration = Duration::from_millis(2000);
+
+/// How many messages are shown at once; older ones make way for newer.
+const MAX_TOASTS: usize = 3;
+
+/// A message and when it goes away.
+#[derive(Debug, Clone)]
+struct Toast {
+    message: String,
+    until: Instant,
+}
+
+/// The messages on screen, oldest first.
+#[derive(Debug, Default)]
+pub struct Toasts {
+    shown: VecDeque<Toast>,
+}
+
+impl Toasts {
+    /// Show `message` for a moment, below those already shown.
+    pub fn push(&mut self, message: impl Into<String>) {
+        let message = message.into();
+        // the same message twice in a row is shown once, for longer
+        self.shown.retain(|toast| toast.message != message);
+        if self.shown.len() == MAX_TOASTS {
+            self.shown.pop_front();
+        }
+        self.shown.push_back(Toast {
+            message,
+            until: Instant::now() + TOAST_DURATION,
+        });
+    }
+
+    /// Draw the messages still due in the top right corner of the screen, one per line.
+    pub fn render(&mut self, frame: &mut Frame) {
+        let now = Instant::now();
+        self.shown.retain(|toast| toast.until > now);
+        let area = frame.area();
+        let style = Style::new()
+            .fg(tailwind::SLATE.c100)
+            .bg(tailwind::SLATE.c700);
+        for (row, toast) in self.shown.iter().enumerate() {
+            let text = format!(" {} ", toast.message);
+            let width = (text.width() as u16).min(area.width);
+            let row = row as u16;
+            if row >= area.height {
+                break;
+            }
+            let toast_area = Rect {
+                x: area.right() - width,
+                y: area.y + row,
+                width,
+                height: 1,
+            };
+            frame.render_widget(Clear, toast_area);
+            frame.render_widget(Paragraph::new(text).style(style), toast_area);
+        }
+    }
+}